@@ -22,11 +22,19 @@ fn main() -> Result<(), Box<dyn Error>> {
     #[cfg(feature = "embassy")]
     cfg_if::cfg_if! {
         if #[cfg(feature = "esp32")] {
-            assert_unique_used_features!("embassy-time-timg0");
+            assert_unique_used_features!("embassy-time-timg0", "embassy-time-any-timer");
         } else if #[cfg(feature = "esp32s2")] {
-            assert_unique_used_features!("embassy-time-systick-80mhz", "embassy-time-timg0");
+            assert_unique_used_features!(
+                "embassy-time-systick-80mhz",
+                "embassy-time-timg0",
+                "embassy-time-any-timer"
+            );
         } else {
-            assert_unique_used_features!("embassy-time-systick-16mhz", "embassy-time-timg0");
+            assert_unique_used_features!(
+                "embassy-time-systick-16mhz",
+                "embassy-time-timg0",
+                "embassy-time-any-timer"
+            );
         }
     }
 
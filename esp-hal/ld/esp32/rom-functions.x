@@ -1,5 +1,6 @@
 PROVIDE(ets_delay_us = 0x40008534);
 PROVIDE(ets_update_cpu_frequency_rom = 0x40008550);
+PROVIDE(rom_i2c_readReg_Mask = 0x400041c0);
 PROVIDE(rom_i2c_writeReg = 0x400041a4);
 PROVIDE(rom_i2c_writeReg_Mask = 0x400041fc);
 PROVIDE(rtc_get_reset_reason = 0x400081d4);
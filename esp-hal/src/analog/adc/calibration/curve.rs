@@ -3,6 +3,7 @@ use core::marker::PhantomData;
 use crate::analog::adc::{
     AdcCalEfuse,
     AdcCalLine,
+    AdcCalMillivolts,
     AdcCalScheme,
     AdcHasLineCal,
     Attenuation,
@@ -108,6 +109,11 @@ where
     }
 }
 
+impl<ADCI> AdcCalMillivolts<ADCI> for AdcCalCurve<ADCI> where
+    ADCI: AdcCalEfuse + AdcHasLineCal + AdcHasCurveCal + CalibrationAccess
+{
+}
+
 macro_rules! coeff_tables {
     ($($(#[$($meta:meta)*])* $name:ident [ $($att:ident => [ $($val:literal,)* ],)* ];)*) => {
         $(
@@ -3,6 +3,7 @@ use core::marker::PhantomData;
 use crate::analog::adc::{
     AdcCalBasic,
     AdcCalEfuse,
+    AdcCalMillivolts,
     AdcCalScheme,
     AdcCalSource,
     AdcConfig,
@@ -97,6 +98,11 @@ where
     }
 }
 
+impl<ADCI> AdcCalMillivolts<ADCI> for AdcCalLine<ADCI> where
+    ADCI: AdcCalEfuse + AdcHasLineCal + CalibrationAccess
+{
+}
+
 #[cfg(any(esp32c2, esp32c3, esp32c6, esp32s3))]
 impl AdcHasLineCal for crate::peripherals::ADC1 {}
 
@@ -1,5 +1,6 @@
 #[cfg(any(esp32c3, esp32c6, esp32s3))]
 pub use self::curve::{AdcCalCurve, AdcHasCurveCal};
+pub use self::nominal::AdcCalNominal;
 #[cfg(any(esp32c2, esp32c3, esp32c6, esp32s3))]
 pub use self::{
     basic::AdcCalBasic,
@@ -12,3 +13,4 @@ mod basic;
 mod curve;
 #[cfg(any(esp32c2, esp32c3, esp32c6, esp32s3))]
 mod line;
+mod nominal;
@@ -0,0 +1,42 @@
+use core::marker::PhantomData;
+
+use crate::analog::adc::{nominal_millivolts, AdcCalMillivolts, AdcCalScheme, Attenuation};
+
+/// The largest raw reading
+#[cfg(esp32s2)]
+const FULL_SCALE: u16 = 0x1fff;
+#[cfg(not(esp32s2))]
+const FULL_SCALE: u16 = 0xfff;
+
+/// Uncalibrated conversion to millivolts
+///
+/// This scheme doesn't use any calibration values, it maps the raw readings
+/// linearly onto the nominal input range of the attenuation, see the "ADC
+/// Characteristics" section of the datasheet. The readings can be off by 10%
+/// or more.
+///
+/// Use it on chips without a calibration scheme, i.e. the ESP32-S2 and
+/// ESP32-H2, otherwise prefer `AdcCalLine` or `AdcCalCurve`.
+#[derive(Clone, Copy)]
+pub struct AdcCalNominal<ADCI> {
+    attenuation: Attenuation,
+
+    _phantom: PhantomData<ADCI>,
+}
+
+impl<ADCI> crate::private::Sealed for AdcCalNominal<ADCI> {}
+
+impl<ADCI> AdcCalScheme<ADCI> for AdcCalNominal<ADCI> {
+    fn new_cal(atten: Attenuation) -> Self {
+        Self {
+            attenuation: atten,
+            _phantom: PhantomData,
+        }
+    }
+
+    fn adc_val(&self, val: u16) -> u16 {
+        nominal_millivolts(self.attenuation, val, FULL_SCALE)
+    }
+}
+
+impl<ADCI> AdcCalMillivolts<ADCI> for AdcCalNominal<ADCI> {}
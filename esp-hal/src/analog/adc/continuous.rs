@@ -0,0 +1,817 @@
+//! # ADC continuous mode
+//!
+//! ## Overview
+//! In continuous mode the digital controller of the ADC converts a pattern of
+//! up to [`MAX_PATTERN_LEN`] channels over and over, at a fixed sample rate,
+//! and a DMA channel moves the results into memory without involving the CPU.
+//!
+//! Every conversion results in one 4 byte word, [`samples`] decodes a
+//! received buffer into [`Sample`]s.
+//!
+//! Only ADC1 is supported, the DMA transfers of ADC2 don't work reliably on
+//! the ESP32-C3.
+//!
+//! ## Sample timing
+//! By default the internal timer of the ADC starts the conversions. The clock
+//! of the ADC is divided with a fractional divider to get as close to the
+//! requested sample rate as possible, [`AdcContinuous::sample_rate`] returns
+//! the rate actually generated. The samples are taken without any jitter from
+//! the CPU or interrupts, as needed for e.g. spectral analysis.
+//!
+//! On the ESP32-C6 and ESP32-H2 the conversions can be started by an ETM
+//! event instead,
+//! e.g. the alarm of a TIMG timer, see `Trigger::Etm` and the `etm` module.
+//!
+//! ## Example
+//! ```no_run
+//! let mut config = ContinuousConfig::new(20.kHz());
+//! let _pin2 = config
+//!     .enable_pin(io.pins.gpio2.into_analog(), Attenuation::Attenuation11dB)
+//!     .unwrap();
+//! let _pin3 = config
+//!     .enable_pin(io.pins.gpio3.into_analog(), Attenuation::Attenuation11dB)
+//!     .unwrap();
+//!
+//! let dma = Gdma::new(peripherals.DMA);
+//! let (_, mut rx_descriptors) = dma_descriptors!(0, 4 * 4092);
+//! let channel =
+//!     dma.channel0
+//!         .configure(false, &mut [], &mut rx_descriptors, DmaPriority::Priority0);
+//!
+//! let mut adc = AdcContinuous::new(peripherals.ADC1, config, channel, &clocks).unwrap();
+//!
+//! let buffer = dma_buffer!(4 * 4092);
+//! let mut transfer = adc.read_dma_circular(buffer).unwrap();
+//!
+//! let mut data = [0u8; 4092];
+//! loop {
+//!     let len = transfer.pop(&mut data).unwrap();
+//!     for sample in samples(&data[..len]) {
+//!         println!("channel {}: {}", sample.channel, sample.value);
+//!     }
+//! }
+//! ```
+
+use core::marker::PhantomData;
+
+use embedded_dma::WriteBuffer;
+use fugit::HertzU32;
+
+use super::{AdcChannel, AdcPin, Attenuation};
+use crate::{
+    clock::Clocks,
+    dma::{AdcPeripheral, Channel, ChannelTypes, DmaError, DmaPeripheral, DmaTransfer, RxPrivate},
+    peripheral::{Peripheral, PeripheralRef},
+    peripherals::APB_SARADC,
+    system::{Peripheral as PeripheralEnable, PeripheralClockControl},
+    Mode,
+};
+
+/// The most channels converted in one pattern
+pub const MAX_PATTERN_LEN: usize = 8;
+
+/// The size of one conversion result in the DMA buffer, in bytes
+pub const SAMPLE_SIZE: usize = 4;
+
+/// The smallest integral divider of the ADC controller clock, the one ESP-IDF
+/// uses
+const MIN_CLKM_DIV: u32 = 16;
+
+/// The largest integral divider of the ADC controller clock
+const MAX_CLKM_DIV: u32 = 256;
+
+/// The largest denominator (`div_a`) of the fractional part of the clock
+/// divider
+const MAX_CLKM_DIV_A: u32 = 63;
+
+/// The sample interval is a 12 bit number of controller clock cycles
+const MAX_INTERVAL: u32 = 0xfff;
+
+/// The number of conversions after which the ADC ends a DMA transfer
+const MAX_EOF_NUM: u32 = 0xffff;
+
+/// ADC continuous mode errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The sample rate can't be generated from the ADC clock
+    SampleRate,
+    /// More than [`MAX_PATTERN_LEN`] pins were enabled
+    TooManyChannels,
+    /// No pin was enabled
+    NoChannels,
+    /// The buffer is empty or its length isn't a multiple of [`SAMPLE_SIZE`]
+    BufferSize,
+    /// DMA error
+    Dma(DmaError),
+}
+
+impl From<DmaError> for Error {
+    fn from(error: DmaError) -> Self {
+        Error::Dma(error)
+    }
+}
+
+/// ADC units which can be sampled continuously
+pub trait ContinuousAccess: crate::private::Sealed {
+    #[doc(hidden)]
+    const UNIT: u8;
+}
+
+impl ContinuousAccess for crate::peripherals::ADC1 {
+    const UNIT: u8 = 0;
+}
+
+/// What starts the conversions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Trigger {
+    /// The internal timer of the ADC, at the configured sample rate
+    #[default]
+    Timer,
+    /// The [`AdcContinuous::sample_task`] ETM task, one conversion per task
+    #[cfg(soc_etm)]
+    Etm,
+}
+
+/// One conversion result
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Sample {
+    /// The channel the value was converted from
+    pub channel: u8,
+    /// The raw 12 bit conversion result
+    pub value: u16,
+}
+
+impl Sample {
+    /// Decode a sample from the 4 bytes the DMA wrote
+    pub fn from_bytes(bytes: [u8; SAMPLE_SIZE]) -> Self {
+        let raw = u32::from_le_bytes(bytes);
+
+        Sample {
+            channel: ((raw >> 13) & 0b111) as u8,
+            value: (raw & 0xfff) as u16,
+        }
+    }
+}
+
+/// Decode the samples in a buffer received from the ADC, trailing bytes of
+/// an incomplete sample are ignored
+pub fn samples(data: &[u8]) -> impl Iterator<Item = Sample> + '_ {
+    data.chunks_exact(SAMPLE_SIZE)
+        .map(|bytes| Sample::from_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+/// Configuration for the ADC in continuous mode
+pub struct ContinuousConfig<ADCI> {
+    sample_rate: HertzU32,
+    trigger: Trigger,
+    pattern: [u8; MAX_PATTERN_LEN],
+    pattern_len: usize,
+    _phantom: PhantomData<ADCI>,
+}
+
+impl<ADCI> ContinuousConfig<ADCI>
+where
+    ADCI: ContinuousAccess,
+{
+    /// Convert one channel of the pattern every `1 / sample_rate`.
+    ///
+    /// Each enabled pin is sampled at `sample_rate` divided by the number of
+    /// enabled pins.
+    pub fn new(sample_rate: HertzU32) -> Self {
+        Self {
+            sample_rate,
+            trigger: Trigger::Timer,
+            pattern: [0; MAX_PATTERN_LEN],
+            pattern_len: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Select what starts the conversions, the sample rate is ignored unless
+    /// the trigger is [`Trigger::Timer`]
+    pub fn set_trigger(&mut self, trigger: Trigger) {
+        self.trigger = trigger;
+    }
+
+    /// Append the pin with the given attenuation to the pattern.
+    ///
+    /// Pins are converted in the order they were enabled in. A pin can be
+    /// enabled multiple times to sample it more often.
+    pub fn enable_pin<PIN>(
+        &mut self,
+        pin: PIN,
+        attenuation: Attenuation,
+    ) -> Result<AdcPin<PIN, ADCI>, Error>
+    where
+        PIN: AdcChannel,
+    {
+        if self.pattern_len == MAX_PATTERN_LEN {
+            return Err(Error::TooManyChannels);
+        }
+
+        self.pattern[self.pattern_len] =
+            (ADCI::UNIT << 5) | (PIN::CHANNEL << 2) | attenuation as u8;
+        self.pattern_len += 1;
+
+        Ok(AdcPin {
+            pin,
+            cal_scheme: (),
+            _phantom: PhantomData,
+        })
+    }
+}
+
+/// ADC sampling continuously into DMA buffers
+pub struct AdcContinuous<'d, ADCI, CH, DmaMode>
+where
+    CH: ChannelTypes,
+    DmaMode: Mode,
+{
+    _adc: PeripheralRef<'d, ADCI>,
+    rx_channel: CH::Rx<'d>,
+    trigger: Trigger,
+    sample_rate: HertzU32,
+    achieved_rate_millihz: u64,
+    phantom: PhantomData<DmaMode>,
+}
+
+impl<'d, ADCI, CH, DmaMode> AdcContinuous<'d, ADCI, CH, DmaMode>
+where
+    ADCI: ContinuousAccess + 'd,
+    CH: ChannelTypes,
+    DmaMode: Mode,
+{
+    /// Configure the ADC for continuous sampling into buffers of the given
+    /// DMA channel
+    pub fn new(
+        adc_instance: impl Peripheral<P = ADCI> + 'd,
+        config: ContinuousConfig<ADCI>,
+        mut channel: Channel<'d, CH, DmaMode>,
+        clocks: &Clocks,
+    ) -> Result<Self, Error>
+    where
+        CH::P: AdcPeripheral,
+    {
+        if config.pattern_len == 0 {
+            return Err(Error::NoChannels);
+        }
+
+        #[cfg(esp32c3)]
+        let source_clock = clocks.apb_clock;
+        #[cfg(any(esp32c6, esp32h2))]
+        let source_clock = clocks.xtal_clock;
+
+        let clock_settings = match config.trigger {
+            Trigger::Timer => calculate_clock(source_clock.raw(), config.sample_rate.raw())
+                .ok_or(Error::SampleRate)?,
+            #[cfg(soc_etm)]
+            Trigger::Etm => ClockSettings::default(),
+        };
+
+        channel.rx.init_channel();
+        PeripheralClockControl::enable(PeripheralEnable::ApbSarAdc);
+
+        configure_clock(&clock_settings);
+
+        let sar_adc = unsafe { &*APB_SARADC::PTR };
+
+        sar_adc.ctrl().modify(|_, w| unsafe {
+            w.saradc_start_force()
+                .clear_bit()
+                .saradc_start()
+                .clear_bit()
+                .saradc_sar_clk_gated()
+                .set_bit()
+                .saradc_xpd_sar_force()
+                .bits(0b11)
+                .saradc_sar_patt_len()
+                .bits(config.pattern_len as u8 - 1)
+        });
+
+        // each table holds four 6 bit entries, the first one in the topmost
+        // bits
+        let mut tables = [0u32; 2];
+        for (i, entry) in config.pattern[..config.pattern_len].iter().enumerate() {
+            tables[i / 4] |= (*entry as u32) << (18 - (i % 4) * 6);
+        }
+        sar_adc
+            .sar_patt_tab1()
+            .write(|w| unsafe { w.saradc_sar_patt_tab1().bits(tables[0]) });
+        sar_adc
+            .sar_patt_tab2()
+            .write(|w| unsafe { w.saradc_sar_patt_tab2().bits(tables[1]) });
+
+        sar_adc.ctrl2().modify(|_, w| unsafe {
+            w.saradc_meas_num_limit()
+                .clear_bit()
+                .saradc_timer_target()
+                .bits(clock_settings.interval)
+                .saradc_timer_en()
+                .clear_bit()
+        });
+
+        Ok(Self {
+            _adc: adc_instance.into_ref(),
+            rx_channel: channel.rx,
+            trigger: config.trigger,
+            sample_rate: config.sample_rate,
+            achieved_rate_millihz: clock_settings.achieved_rate_millihz,
+            phantom: PhantomData,
+        })
+    }
+
+    /// The sample rate actually generated by the clock dividers.
+    ///
+    /// Not every sample rate can be derived exactly from the ADC source clock,
+    /// this is the closest the dividers get to the requested rate.
+    pub fn sample_rate(&self) -> HertzU32 {
+        HertzU32::Hz(((self.achieved_rate_millihz + 500) / 1000) as u32)
+    }
+
+    /// The deviation of the generated sample rate from the requested one, in
+    /// parts per million
+    pub fn sample_rate_error_ppm(&self) -> i32 {
+        let requested = self.sample_rate.raw() as i64 * 1000;
+        if requested == 0 {
+            return 0;
+        }
+
+        ((self.achieved_rate_millihz as i64 - requested) * 1_000_000 / requested) as i32
+    }
+
+    /// Sample until `words` is full.
+    ///
+    /// Returns [AdcReadDmaTransfer] which represents the in-progress DMA
+    /// transfer.
+    pub fn read_dma<'t, RXBUF>(
+        &'t mut self,
+        words: &'t mut RXBUF,
+    ) -> Result<AdcReadDmaTransfer<'t, 'd, ADCI, CH, DmaMode>, Error>
+    where
+        RXBUF: WriteBuffer<Word = u8>,
+    {
+        let (ptr, len) = unsafe { words.write_buffer() };
+        self.start_transfer(ptr, len, false)?;
+
+        Ok(AdcReadDmaTransfer { adc: self })
+    }
+
+    /// Sample continuously into the ring buffer `words`.
+    ///
+    /// Returns [AdcReadDmaTransfer] which represents the in-progress DMA
+    /// transfer, pop the samples from it before the DMA overwrites them.
+    pub fn read_dma_circular<'t, RXBUF>(
+        &'t mut self,
+        words: &'t mut RXBUF,
+    ) -> Result<AdcReadDmaTransfer<'t, 'd, ADCI, CH, DmaMode>, Error>
+    where
+        RXBUF: WriteBuffer<Word = u8>,
+    {
+        let (ptr, len) = unsafe { words.write_buffer() };
+        self.start_transfer(ptr, len, true)?;
+
+        Ok(AdcReadDmaTransfer { adc: self })
+    }
+
+    fn start_transfer(&mut self, ptr: *mut u8, len: usize, circular: bool) -> Result<(), Error> {
+        if len == 0 || len % SAMPLE_SIZE != 0 {
+            return Err(Error::BufferSize);
+        }
+
+        self.stop();
+
+        let sar_adc = unsafe { &*APB_SARADC::PTR };
+
+        // in circular mode the descriptors are handed back when they are full
+        let eof_num = if circular {
+            MAX_EOF_NUM
+        } else {
+            u32::min((len / SAMPLE_SIZE) as u32, MAX_EOF_NUM)
+        };
+
+        sar_adc
+            .dma_conf()
+            .modify(|_, w| w.apb_adc_reset_fsm().set_bit());
+        sar_adc.dma_conf().modify(|_, w| unsafe {
+            w.apb_adc_reset_fsm()
+                .clear_bit()
+                .apb_adc_eof_num()
+                .bits(eof_num as u16)
+                .apb_adc_trans()
+                .set_bit()
+        });
+
+        self.rx_channel
+            .prepare_transfer_without_start(circular, DmaPeripheral::Adc, ptr, len)
+            .and_then(|_| self.rx_channel.start_transfer())?;
+
+        // start the pattern from its first entry
+        sar_adc
+            .ctrl()
+            .modify(|_, w| w.saradc_sar_patt_p_clear().set_bit());
+        sar_adc
+            .ctrl()
+            .modify(|_, w| w.saradc_sar_patt_p_clear().clear_bit());
+
+        if self.trigger == Trigger::Timer {
+            sar_adc.ctrl2().modify(|_, w| w.saradc_timer_en().set_bit());
+        }
+
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        let sar_adc = unsafe { &*APB_SARADC::PTR };
+
+        sar_adc
+            .ctrl2()
+            .modify(|_, w| w.saradc_timer_en().clear_bit());
+        sar_adc
+            .dma_conf()
+            .modify(|_, w| w.apb_adc_trans().clear_bit());
+    }
+}
+
+/// The dividers of the ADC controller clock and the sample timer.
+///
+/// The sample rate is `source / (div_num + 1 + div_b / div_a) / 2 /
+/// interval`, the fractional part is `div_b / div_a` as in the TRM.
+struct ClockSettings {
+    div_num: u8,
+    div_a: u8,
+    div_b: u8,
+    interval: u16,
+    achieved_rate_millihz: u64,
+}
+
+impl Default for ClockSettings {
+    fn default() -> Self {
+        Self {
+            div_num: (MIN_CLKM_DIV - 1) as u8,
+            div_a: 1,
+            div_b: 0,
+            interval: 0,
+            achieved_rate_millihz: 0,
+        }
+    }
+}
+
+/// Find the dividers generating the sample rate closest to `sample_rate`
+fn calculate_clock(source: u32, sample_rate: u32) -> Option<ClockSettings> {
+    if sample_rate == 0 {
+        return None;
+    }
+
+    let source = source as u64;
+    let sample_rate = sample_rate as u64;
+    let requested_millihz = sample_rate * 1000;
+
+    // the sample timer intervals for which the integral divider is in range
+    let min_interval = u64::max(
+        1,
+        source.div_ceil(2 * sample_rate * (MAX_CLKM_DIV as u64 + 1)),
+    );
+    let max_interval = u64::min(
+        MAX_INTERVAL as u64,
+        source / (2 * sample_rate * MIN_CLKM_DIV as u64),
+    );
+
+    let mut best: Option<(u64, ClockSettings)> = None;
+    for interval in min_interval..=max_interval {
+        // the ideal divider is `source / den`, approximate its fractional part
+        let den = 2 * sample_rate * interval;
+        let (mut div, mut div_b, mut div_a) = (source / den, 0, 1);
+        let (num, denom) = closest_fraction(source % den, den, MAX_CLKM_DIV_A as u64);
+        if num == denom {
+            div += 1;
+        } else {
+            (div_b, div_a) = (num, denom);
+        }
+
+        if !(MIN_CLKM_DIV as u64..=MAX_CLKM_DIV as u64).contains(&div) {
+            continue;
+        }
+
+        let achieved_rate_millihz = source * 1000 * div_a / (2 * interval * (div * div_a + div_b));
+        let error = achieved_rate_millihz.abs_diff(requested_millihz);
+        if best
+            .as_ref()
+            .map_or(true, |(best_error, _)| error < *best_error)
+        {
+            let settings = ClockSettings {
+                div_num: (div - 1) as u8,
+                div_a: div_a as u8,
+                div_b: div_b as u8,
+                interval: interval as u16,
+                achieved_rate_millihz,
+            };
+            if error == 0 {
+                return Some(settings);
+            }
+            best = Some((error, settings));
+        }
+    }
+
+    best.map(|(_, settings)| settings)
+}
+
+/// The fraction with a denominator of at most `max_den` closest to `num /
+/// den`, from the convergents and semiconvergents of its continued fraction
+fn closest_fraction(num: u64, den: u64, max_den: u64) -> (u64, u64) {
+    // the two previous convergents
+    let (mut p0, mut q0, mut p1, mut q1) = (0, 1, 1, 0);
+    let (mut n, mut d) = (num, den);
+
+    while d != 0 {
+        let a = n / d;
+        let q2 = q0 + a * q1;
+        if q2 > max_den {
+            // the best semiconvergent within the bound, or the last convergent
+            let k = (max_den - q0) / q1;
+            let (ps, qs) = (p0 + k * p1, q0 + k * q1);
+            let error_s = (ps * den).abs_diff(num * qs) * q1;
+            let error_1 = (p1 * den).abs_diff(num * q1) * qs;
+            return if error_s < error_1 {
+                (ps, qs)
+            } else {
+                (p1, q1)
+            };
+        }
+
+        (p0, q0, p1, q1) = (p1, q1, p0 + a * p1, q2);
+        (n, d) = (d, n - a * d);
+    }
+
+    (p1, q1)
+}
+
+#[cfg(esp32c3)]
+fn configure_clock(settings: &ClockSettings) {
+    let sar_adc = unsafe { &*APB_SARADC::PTR };
+
+    // APB clock divided by div_num + 1 + div_b / div_a
+    sar_adc.clkm_conf().modify(|_, w| unsafe {
+        w.clkm_div_num()
+            .bits(settings.div_num)
+            .clkm_div_b()
+            .bits(settings.div_b)
+            .clkm_div_a()
+            .bits(settings.div_a)
+            .clk_sel()
+            .bits(2)
+            .clk_en()
+            .set_bit()
+    });
+}
+
+#[cfg(any(esp32c6, esp32h2))]
+fn configure_clock(settings: &ClockSettings) {
+    let pcr = unsafe { &*crate::peripherals::PCR::PTR };
+
+    // XTAL clock divided by div_num + 1 + div_b / div_a
+    pcr.saradc_clkm_conf().modify(|_, w| unsafe {
+        w.saradc_clkm_div_num()
+            .bits(settings.div_num)
+            .saradc_clkm_div_b()
+            .bits(settings.div_b)
+            .saradc_clkm_div_a()
+            .bits(settings.div_a)
+            .saradc_clkm_sel()
+            .bits(0)
+            .saradc_clkm_en()
+            .set_bit()
+    });
+}
+
+/// An in-progress DMA read transfer.
+#[must_use]
+pub struct AdcReadDmaTransfer<'t, 'd, ADCI, CH, DmaMode>
+where
+    ADCI: ContinuousAccess,
+    CH: ChannelTypes,
+    DmaMode: Mode,
+{
+    adc: &'t mut AdcContinuous<'d, ADCI, CH, DmaMode>,
+}
+
+impl<'t, 'd, ADCI, CH, DmaMode> AdcReadDmaTransfer<'t, 'd, ADCI, CH, DmaMode>
+where
+    ADCI: ContinuousAccess,
+    CH: ChannelTypes,
+    DmaMode: Mode,
+{
+    /// Amount of bytes which can be popped
+    pub fn available(&mut self) -> usize {
+        self.adc.rx_channel.available()
+    }
+
+    /// Pop the available bytes into `data`.
+    ///
+    /// Fails with [DmaError::Late] if the DMA overwrote data which wasn't
+    /// popped in time, the oldest data is dropped in that case.
+    pub fn pop(&mut self, data: &mut [u8]) -> Result<usize, Error> {
+        Ok(self.adc.rx_channel.pop(data)?)
+    }
+}
+
+impl<'t, 'd, ADCI, CH, DmaMode> DmaTransfer for AdcReadDmaTransfer<'t, 'd, ADCI, CH, DmaMode>
+where
+    ADCI: ContinuousAccess,
+    CH: ChannelTypes,
+    DmaMode: Mode,
+{
+    /// Wait for the DMA transfer to complete
+    fn wait(self) -> Result<(), DmaError> {
+        while !self.adc.rx_channel.is_done() {}
+
+        if self.adc.rx_channel.has_error() {
+            Err(DmaError::DescriptorError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check if the DMA transfer is complete
+    fn is_done(&self) -> bool {
+        self.adc.rx_channel.is_done()
+    }
+}
+
+impl<'t, 'd, ADCI, CH, DmaMode> Drop for AdcReadDmaTransfer<'t, 'd, ADCI, CH, DmaMode>
+where
+    ADCI: ContinuousAccess,
+    CH: ChannelTypes,
+    DmaMode: Mode,
+{
+    fn drop(&mut self) {
+        self.adc.stop();
+    }
+}
+
+#[cfg(soc_etm)]
+pub mod etm {
+    //! # ETM triggered ADC conversions
+    //!
+    //! ## Overview
+    //! With [`Trigger::Etm`](super::Trigger::Etm) the ADC converts the next
+    //! channel of its pattern each time the [`AdcContinuous::sample_task`]
+    //! task is triggered, e.g. by the alarm of a TIMG timer.
+    //!
+    //! ## Example
+    //! ```no_run
+    //! let mut config = ContinuousConfig::new(0.Hz());
+    //! config.set_trigger(Trigger::Etm);
+    //! let _pin2 = config
+    //!     .enable_pin(io.pins.gpio2.into_analog(), Attenuation::Attenuation11dB)
+    //!     .unwrap();
+    //!
+    //! let mut adc = AdcContinuous::new(peripherals.ADC1, config, channel, &clocks).unwrap();
+    //!
+    //! let etm = Etm::new(peripherals.SOC_ETM);
+    //! let _channel = etm.channel0.setup(&timer0.on_alarm(), &adc.sample_task());
+    //! ```
+
+    use super::{AdcContinuous, ContinuousAccess};
+    use crate::{dma::ChannelTypes, etm::EtmTask, private::Sealed, Mode};
+
+    /// ETM task id of the ADC sample task, `ADC_TASK_SAMPLE0` in ESP-IDF's
+    /// `components/soc/<chip>/include/soc/soc_etm_source.h`, the same on the
+    /// ESP32-C6 and ESP32-H2
+    const ADC_TASK_SAMPLE0: u8 = 117;
+
+    pub struct AdcEtmTask {
+        id: u8,
+    }
+
+    impl EtmTask for AdcEtmTask {
+        fn id(&self) -> u8 {
+            self.id
+        }
+    }
+
+    impl Sealed for AdcEtmTask {}
+
+    impl<'d, ADCI, CH, DmaMode> AdcContinuous<'d, ADCI, CH, DmaMode>
+    where
+        ADCI: ContinuousAccess + 'd,
+        CH: ChannelTypes,
+        DmaMode: Mode,
+    {
+        /// The task converting the next channel of the pattern, with
+        /// [`Trigger::Etm`](super::Trigger::Etm)
+        pub fn sample_task(&self) -> AdcEtmTask {
+            AdcEtmTask {
+                id: ADC_TASK_SAMPLE0,
+            }
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+pub mod asynch {
+    use embedded_dma::WriteBuffer;
+
+    use super::{AdcContinuous, ContinuousAccess, Error};
+    use crate::{
+        dma::{
+            asynch::{DmaRxDoneChFuture, DmaRxFuture},
+            ChannelTypes,
+            DmaError,
+            RxPrivate,
+        },
+        Async,
+    };
+
+    impl<'d, ADCI, CH> AdcContinuous<'d, ADCI, CH, Async>
+    where
+        ADCI: ContinuousAccess + 'd,
+        CH: ChannelTypes,
+    {
+        /// Sample until `words` is full
+        pub async fn read_dma_async(&mut self, words: &mut [u8]) -> Result<(), Error> {
+            let (ptr, len) = (words.as_mut_ptr(), words.len());
+
+            self.rx_channel.listen_eof();
+            self.start_transfer(ptr, len, false)?;
+
+            DmaRxFuture::new(&mut self.rx_channel).await;
+            self.stop();
+
+            if self.rx_channel.has_error() {
+                Err(Error::Dma(DmaError::DescriptorError))
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Sample continuously into the ring buffer `words`. Returns
+        /// [AdcReadDmaTransferAsync]
+        pub fn read_dma_circular_async<RXBUF>(
+            mut self,
+            mut words: RXBUF,
+        ) -> Result<AdcReadDmaTransferAsync<'d, ADCI, CH, RXBUF>, Error>
+        where
+            RXBUF: WriteBuffer<Word = u8>,
+        {
+            let (ptr, len) = unsafe { words.write_buffer() };
+            self.start_transfer(ptr, len, true)?;
+
+            Ok(AdcReadDmaTransferAsync {
+                adc: self,
+                _buffer: words,
+            })
+        }
+    }
+
+    /// An in-progress async circular DMA read transfer.
+    #[non_exhaustive]
+    pub struct AdcReadDmaTransferAsync<'d, ADCI, CH, BUFFER>
+    where
+        ADCI: ContinuousAccess,
+        CH: ChannelTypes,
+    {
+        adc: AdcContinuous<'d, ADCI, CH, Async>,
+        _buffer: BUFFER,
+    }
+
+    impl<'d, ADCI, CH, BUFFER> AdcReadDmaTransferAsync<'d, ADCI, CH, BUFFER>
+    where
+        ADCI: ContinuousAccess + 'd,
+        CH: ChannelTypes,
+    {
+        /// How many bytes can be popped from the DMA transaction.
+        /// Will wait for more than 0 bytes available.
+        pub async fn available(&mut self) -> usize {
+            loop {
+                let res = self.adc.rx_channel.available();
+
+                if res != 0 {
+                    break res;
+                }
+
+                let future = DmaRxDoneChFuture::new(&mut self.adc.rx_channel);
+                future.await;
+            }
+        }
+
+        /// Pop bytes from the DMA transaction.
+        pub async fn pop(&mut self, data: &mut [u8]) -> Result<usize, Error> {
+            let avail = self.available().await;
+            let to_rcv = usize::min(avail, data.len());
+            Ok(self.adc.rx_channel.pop(&mut data[..to_rcv])?)
+        }
+    }
+
+    impl<'d, ADCI, CH, BUFFER> Drop for AdcReadDmaTransferAsync<'d, ADCI, CH, BUFFER>
+    where
+        ADCI: ContinuousAccess,
+        CH: ChannelTypes,
+    {
+        fn drop(&mut self) {
+            self.adc.stop();
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use core::marker::PhantomData;
+
 use super::{AdcConfig, Attenuation};
 use crate::{
     peripheral::PeripheralRef,
@@ -198,6 +200,7 @@ pub struct ADC<'d, ADC> {
     attenuations: [Option<Attenuation>; NUM_ATTENS],
     #[allow(dead_code)] // FIXME
     active_channel: Option<u8>,
+    resolution: Resolution,
 }
 
 impl<'d, ADCI> ADC<'d, ADCI>
@@ -276,9 +279,69 @@ where
             _adc: adc_instance.into_ref(),
             attenuations: config.attenuations,
             active_channel: None,
+            resolution: config.resolution,
+        }
+    }
+
+    /// Enable a pin with the given attenuation after the driver was created
+    pub fn enable_pin<PIN>(
+        &mut self,
+        pin: PIN,
+        attenuation: Attenuation,
+    ) -> super::AdcPin<PIN, ADCI>
+    where
+        PIN: super::AdcChannel,
+    {
+        ADCI::set_attenuation(PIN::CHANNEL as usize, attenuation as u8);
+        self.attenuations[PIN::CHANNEL as usize] = Some(attenuation);
+
+        super::AdcPin {
+            pin,
+            cal_scheme: (),
+            _phantom: PhantomData,
         }
     }
 
+    /// Change the attenuation of an enabled pin, e.g. to widen the measured
+    /// range. A conversion in progress is finished first and its result
+    /// discarded.
+    pub fn set_attenuation<PIN>(
+        &mut self,
+        _pin: &mut super::AdcPin<PIN, ADCI>,
+        attenuation: Attenuation,
+    ) where
+        PIN: super::AdcChannel,
+    {
+        if self.active_channel.is_some() {
+            self.finish_conversion();
+        }
+
+        ADCI::set_attenuation(PIN::CHANNEL as usize, attenuation as u8);
+        self.attenuations[PIN::CHANNEL as usize] = Some(attenuation);
+    }
+
+    /// Disable a pin, returning the underlying GPIO
+    pub fn disable_pin<PIN>(&mut self, pin: super::AdcPin<PIN, ADCI>) -> PIN
+    where
+        PIN: super::AdcChannel,
+    {
+        if self.active_channel == Some(PIN::CHANNEL) {
+            self.finish_conversion();
+        }
+
+        self.attenuations[PIN::CHANNEL as usize] = None;
+        pin.pin
+    }
+
+    /// Wait for the conversion in progress to end, the SAR of the ESP32 can't
+    /// be reset. Afterwards it's idle, ready for the next `read_oneshot`.
+    fn finish_conversion(&mut self) {
+        while !ADCI::read_done_sar() {}
+        ADCI::clear_start_sar();
+
+        self.active_channel = None;
+    }
+
     /// Request that the ADC begin a conversion on the specified pin
     ///
     /// This method takes an [AdcPin](super::AdcPin) reference, as it is
@@ -323,6 +386,25 @@ where
 
         Ok(converted_value)
     }
+
+    /// Read the voltage of the pin in millivolts.
+    ///
+    /// The ESP32 has no calibration scheme, the reading is mapped onto the
+    /// nominal input range of the pin's attenuation, see the "ADC
+    /// Characteristics" section of the datasheet. The result is uncalibrated
+    /// and can be off by 10% or more.
+    pub fn read_mv<PIN>(&mut self, pin: &mut super::AdcPin<PIN, ADCI>) -> nb::Result<u16, ()>
+    where
+        PIN: super::AdcChannel,
+    {
+        let raw = self.read_oneshot(pin)?;
+
+        // read_oneshot panics for pins which aren't enabled
+        let attenuation = self.attenuations[PIN::CHANNEL as usize].unwrap();
+        let full_scale = (1 << (9 + self.resolution as u16)) - 1;
+
+        Ok(super::nominal_millivolts(attenuation, raw, full_scale))
+    }
 }
 
 impl<'d, ADC1> ADC<'d, ADC1> {
@@ -341,6 +423,104 @@ impl<'d, ADC1> ADC<'d, ADC1> {
     }
 }
 
+/// Let the digital controller continuously convert `channel` of ADC1 and
+/// pass the 12 bit results to I2S0.
+///
+/// The conversions are triggered by the I2S0 receiver.
+pub(crate) fn enable_i2s_mode(channel: u8, attenuation: Attenuation) {
+    let sensors = unsafe { &*SENS::ptr() };
+    let syscon = unsafe { &*crate::peripherals::APB_CTRL::ptr() };
+
+    ADC1::set_bit_width(Resolution::Resolution12Bit as u8);
+    ADC1::set_sample_bit(Resolution::Resolution12Bit as u8);
+    ADC1::set_attenuation(channel as usize, attenuation as u8);
+
+    // Set controller to DIG
+    sensors
+        .sar_read_ctrl()
+        .modify(|_, w| w.sar1_dig_force().set_bit());
+    sensors.sar_meas_start1().modify(|_, w| {
+        w.meas1_start_force()
+            .clear_bit()
+            .sar1_en_pad_force()
+            .clear_bit()
+    });
+    sensors
+        .sar_touch_ctrl1()
+        .modify(|_, w| w.xpd_hall_force().set_bit().hall_phase_force().set_bit());
+
+    // Set power to SW power on
+    sensors
+        .sar_meas_wait2()
+        .modify(|_, w| unsafe { w.force_xpd_sar().bits(0b11) });
+
+    // a single pattern table entry: channel, bit width and attenuation
+    let pattern =
+        (channel as u32) << 4 | (Resolution::Resolution12Bit as u32) << 2 | attenuation as u32;
+    syscon
+        .saradc_sar1_patt_tab1()
+        .write(|w| unsafe { w.bits(pattern << 24) });
+
+    syscon.saradc_ctrl().modify(|_, w| unsafe {
+        w.sar1_patt_len()
+            .bits(0)
+            .sar1_patt_p_clear()
+            .set_bit()
+            .work_mode()
+            .bits(0)
+            .sar_sel()
+            .clear_bit()
+            .sar_clk_gated()
+            .set_bit()
+            .sar_clk_div()
+            .bits(4)
+            .data_sar_sel()
+            .clear_bit()
+            .data_to_i2s()
+            .set_bit()
+    });
+    syscon
+        .saradc_ctrl()
+        .modify(|_, w| w.sar1_patt_p_clear().clear_bit());
+
+    // convert continuously, the results of ADC1 are inverted otherwise
+    syscon
+        .saradc_ctrl2()
+        .modify(|_, w| w.meas_num_limit().clear_bit().sar1_inv().set_bit());
+}
+
+/// Hand `channel` of ADC1 over to the ULP coprocessor, which converts it with
+/// its `ADC` instruction.
+pub(crate) fn enable_ulp_mode(channel: u8, attenuation: Attenuation) {
+    let sensors = unsafe { &*SENS::ptr() };
+
+    ADC1::set_bit_width(Resolution::Resolution12Bit as u8);
+    ADC1::set_sample_bit(Resolution::Resolution12Bit as u8);
+    ADC1::set_attenuation(channel as usize, attenuation as u8);
+
+    // Set controller to ULP
+    sensors
+        .sar_read_ctrl()
+        .modify(|_, w| w.sar1_dig_force().clear_bit());
+    sensors.sar_meas_start1().modify(|_, w| {
+        w.meas1_start_force()
+            .clear_bit()
+            .sar1_en_pad_force()
+            .clear_bit()
+    });
+    sensors.sar_touch_ctrl1().modify(|_, w| {
+        w.xpd_hall_force()
+            .clear_bit()
+            .hall_phase_force()
+            .clear_bit()
+    });
+
+    // Set power to FSM, so the ADC is only powered up while converting
+    sensors
+        .sar_meas_wait2()
+        .modify(|_, w| unsafe { w.force_xpd_sar().bits(0b00).force_xpd_amp().bits(0b10) });
+}
+
 #[cfg(feature = "embedded-hal-02")]
 impl<'d, ADCI, PIN> embedded_hal_02::adc::OneShot<ADCI, u16, super::AdcPin<PIN, ADCI>>
     for ADC<'d, ADCI>
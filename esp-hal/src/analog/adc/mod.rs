@@ -20,16 +20,92 @@
 //!     delay.delay_ms(1500u32);
 //! }
 //! ```
+//!
+//! ## Calibration
+//!
+//! Raw readings vary from chip to chip. On the ESP32-C2, ESP32-C3, ESP32-C6
+//! and ESP32-S3 the calibration values stored in eFuse during manufacturing
+//! correct this. Enable the pin with the `AdcCalLine` (line fitting) or
+//! `AdcCalCurve` (line and curve fitting, not on the ESP32-C2) scheme and read
+//! it in millivolts:
+//!
+//! ```no_run
+//! type AdcCal = adc::AdcCalCurve<ADC1>;
+//!
+//! let mut adc1_config = AdcConfig::new();
+//! let mut pin = adc1_config.enable_pin_with_cal::<_, AdcCal>(
+//!     io.pins.gpio2.into_analog(),
+//!     Attenuation::Attenuation11dB,
+//! );
+//! let mut adc1 = ADC::<ADC1>::new(peripherals.ADC1, adc1_config);
+//!
+//! let millivolts: u16 = nb::block!(adc1.read_mv(&mut pin)).unwrap();
+//! ```
+//!
+//! The ESP32-S2 and ESP32-H2 have no such scheme yet, the `AdcCalNominal`
+//! scheme converts their readings with the nominal input range of the
+//! attenuation instead. On the ESP32 `ADC::read_mv` always does this. These
+//! readings are uncalibrated and can be off by 10% or more.
+//!
+//! ## Scan sequences
+//!
+//! Sensors polled together can be converted with a single call, in the order
+//! of a [`ScanSequence`]:
+//!
+//! ```no_run
+//! let mut adc1_config = AdcConfig::new();
+//! let mut pin2 =
+//!     adc1_config.enable_pin(io.pins.gpio2.into_analog(), Attenuation::Attenuation11dB);
+//! let mut pin3 = adc1_config.enable_pin(io.pins.gpio3.into_analog(), Attenuation::Attenuation0dB);
+//! let mut adc1 = ADC::<ADC1>::new(peripherals.ADC1, adc1_config);
+//!
+//! let mut scan = ScanSequence::new([&mut pin2, &mut pin3]);
+//! loop {
+//!     let [value2, value3] = nb::block!(adc1.read_scan(&mut scan)).unwrap();
+//!     println!("PIN2 = {value2}, PIN3 = {value3}");
+//! }
+//! ```
+//!
+//! ## Runtime configuration
+//!
+//! Pins can also be enabled and disabled after the driver was created, and the
+//! attenuation of an enabled pin can be changed at any time:
+//!
+//! ```no_run
+//! let mut adc1 = ADC::<ADC1>::new(peripherals.ADC1, AdcConfig::new());
+//! let mut battery = adc1.enable_pin(io.pins.gpio2.into_analog(), Attenuation::Attenuation0dB);
+//!
+//! let value: u16 = nb::block!(adc1.read_oneshot(&mut battery)).unwrap();
+//! if value > 4000 {
+//!     // close to the top of the range, measure up to higher voltages
+//!     adc1.set_attenuation(&mut battery, Attenuation::Attenuation11dB);
+//! }
+//! ```
+//!
+//! ## Sampling in deep sleep
+//!
+//! On the ESP32, the ULP coprocessor can sample an ADC1 pin while the chip is
+//! in deep sleep and wake it up when a sample crosses a threshold, see the
+//! `ulp` module.
 
 use core::marker::PhantomData;
 
-pub use self::implementation::*;
+pub use self::{
+    implementation::*,
+    scan::{ScanPin, ScanSequence},
+};
 
 #[cfg_attr(esp32, path = "esp32.rs")]
 #[cfg_attr(riscv, path = "riscv.rs")]
 #[cfg_attr(any(esp32s2, esp32s3), path = "xtensa.rs")]
 mod implementation;
 
+#[cfg(any(esp32c3, esp32c6, esp32h2))]
+pub mod continuous;
+mod scan;
+#[cfg(esp32)]
+pub mod ulp;
+
 /// The attenuation of the ADC pin.
 ///
 /// The effective measurement range for a given attuenation is dependent on the
@@ -176,12 +252,53 @@ pub trait AdcCalScheme<ADCI>: Sized + crate::private::Sealed {
     }
 }
 
+/// Calibration schemes converting readings to millivolts.
+///
+/// Pins enabled with one of these schemes can be read with `ADC::read_mv`.
+#[cfg(not(esp32))]
+pub trait AdcCalMillivolts<ADCI>: AdcCalScheme<ADCI> {}
+
 impl crate::private::Sealed for () {}
 
 impl<ADCI> AdcCalScheme<ADCI> for () {
     fn new_cal(_atten: Attenuation) -> Self {}
 }
 
+/// The approximate input range of an attenuation in millivolts, see the "ADC
+/// Characteristics" section of the datasheet
+fn nominal_range_mv(attenuation: Attenuation) -> (u32, u32) {
+    match attenuation {
+        #[cfg(esp32)]
+        Attenuation::Attenuation0dB => (100, 950),
+        #[cfg(esp32)]
+        Attenuation::Attenuation2p5dB => (100, 1250),
+        #[cfg(esp32)]
+        Attenuation::Attenuation6dB => (150, 1750),
+        #[cfg(esp32)]
+        Attenuation::Attenuation11dB => (150, 2450),
+
+        #[cfg(not(esp32))]
+        Attenuation::Attenuation0dB => (0, 750),
+        #[cfg(not(any(esp32, esp32c2)))]
+        Attenuation::Attenuation2p5dB => (0, 1050),
+        #[cfg(not(any(esp32, esp32c2)))]
+        Attenuation::Attenuation6dB => (0, 1300),
+        #[cfg(esp32c2)]
+        Attenuation::Attenuation11dB => (0, 2800),
+        #[cfg(not(any(esp32, esp32c2)))]
+        Attenuation::Attenuation11dB => (0, 2500),
+    }
+}
+
+/// Convert a raw reading, `full_scale` being the largest one, to millivolts
+/// with the nominal input range of `attenuation`. The result is uncalibrated.
+fn nominal_millivolts(attenuation: Attenuation, raw: u16, full_scale: u16) -> u16 {
+    let (low, high) = nominal_range_mv(attenuation);
+    let raw = u16::min(raw, full_scale) as u32;
+
+    (low + raw * (high - low) / full_scale as u32) as u16
+}
+
 /// A helper trait to get access to ADC calibration efuses.
 #[cfg(not(esp32h2))]
 trait AdcCalEfuse {
@@ -0,0 +1,98 @@
+use super::{AdcChannel, AdcPin, RegisterAccess, ADC};
+
+/// A pin which can be part of a [`ScanSequence`]
+pub trait ScanPin<ADCI>: crate::private::Sealed {
+    #[doc(hidden)]
+    fn read<'d>(&mut self, adc: &mut ADC<'d, ADCI>) -> nb::Result<u16, ()>
+    where
+        ADCI: 'd;
+}
+
+impl<PIN, ADCI, CS> crate::private::Sealed for AdcPin<PIN, ADCI, CS> {}
+
+#[cfg(not(esp32))]
+impl<PIN, ADCI, CS> ScanPin<ADCI> for AdcPin<PIN, ADCI, CS>
+where
+    PIN: AdcChannel,
+    ADCI: RegisterAccess,
+    CS: super::AdcCalScheme<ADCI>,
+{
+    fn read<'d>(&mut self, adc: &mut ADC<'d, ADCI>) -> nb::Result<u16, ()>
+    where
+        ADCI: 'd,
+    {
+        adc.read_oneshot(self)
+    }
+}
+
+#[cfg(esp32)]
+impl<PIN, ADCI> ScanPin<ADCI> for AdcPin<PIN, ADCI>
+where
+    PIN: AdcChannel,
+    ADCI: RegisterAccess,
+{
+    fn read<'d>(&mut self, adc: &mut ADC<'d, ADCI>) -> nb::Result<u16, ()>
+    where
+        ADCI: 'd,
+    {
+        adc.read_oneshot(self)
+    }
+}
+
+/// An ordered list of pins converted one after another by
+/// [`ADC::read_scan`].
+///
+/// Each pin is converted with the attenuation and calibration scheme it was
+/// enabled with, a pin can appear more than once.
+pub struct ScanSequence<'a, ADCI, const N: usize> {
+    pins: [&'a mut dyn ScanPin<ADCI>; N],
+    results: [u16; N],
+    next: usize,
+}
+
+impl<'a, ADCI, const N: usize> ScanSequence<'a, ADCI, N> {
+    /// Create a sequence converting `pins` in the given order
+    pub fn new(pins: [&'a mut dyn ScanPin<ADCI>; N]) -> Self {
+        Self {
+            pins,
+            results: [0; N],
+            next: 0,
+        }
+    }
+
+    /// Return the pins of the sequence
+    pub fn free(self) -> [&'a mut dyn ScanPin<ADCI>; N] {
+        self.pins
+    }
+}
+
+impl<'d, ADCI> ADC<'d, ADCI>
+where
+    ADCI: RegisterAccess + 'd,
+{
+    /// Convert all pins of the sequence, returning the results in the order
+    /// of the sequence.
+    ///
+    /// The conversions already done are kept while this returns
+    /// [`nb::Error::WouldBlock`], so the next call continues with the pin
+    /// after them.
+    pub fn read_scan<const N: usize>(
+        &mut self,
+        scan: &mut ScanSequence<'_, ADCI, N>,
+    ) -> nb::Result<[u16; N], ()> {
+        while scan.next < N {
+            scan.results[scan.next] = match scan.pins[scan.next].read(self) {
+                Ok(value) => value,
+                Err(nb::Error::WouldBlock) => return Err(nb::Error::WouldBlock),
+                Err(nb::Error::Other(error)) => {
+                    scan.next = 0;
+                    return Err(nb::Error::Other(error));
+                }
+            };
+            scan.next += 1;
+        }
+        scan.next = 0;
+
+        Ok(scan.results)
+    }
+}
@@ -0,0 +1,384 @@
+//! # ADC sampling by the ULP coprocessor
+//!
+//! ## Overview
+//! The `ulp` module programs the ULP FSM coprocessor of the ESP32 to sample
+//! one ADC1 channel periodically, also while the chip is in deep sleep. The
+//! samples are stored in a ring buffer in RTC slow memory. When a sample is
+//! below the low or at or above the high threshold, the ULP wakes up the chip,
+//! see [`UlpWakeupSource`](crate::rtc_cntl::sleep::UlpWakeupSource).
+//!
+//! The program and the samples live in a buffer placed in RTC slow memory by
+//! the application, so they survive deep sleep. After waking up, the samples
+//! are read from the same buffer with [`UlpAdcSamples`], without setting up the
+//! ULP again.
+//!
+//! The ULP-RISC-V of the ESP32-S2 and ESP32-S3 runs a separately built
+//! program instead, see the `ulp_core` module.
+//!
+//! ## Example
+//! ```no_run
+//! #[ram(rtc_slow, uninitialized)]
+//! static mut ULP_MEMORY: [u32; 64] = [0; 64];
+//!
+//! if get_wakeup_cause() == SleepSource::Ulp {
+//!     let samples = UlpAdcSamples::new(unsafe { &ULP_MEMORY });
+//!     for sample in samples.iter() {
+//!         println!("{sample}");
+//!     }
+//! }
+//!
+//! let pin = io.pins.gpio36.into_analog();
+//! let _sampler = UlpAdcSampler::new(
+//!     peripherals.ADC1,
+//!     pin,
+//!     Attenuation::Attenuation11dB,
+//!     unsafe { &mut ULP_MEMORY },
+//!     UlpAdcConfig {
+//!         period: 100.millis(),
+//!         low_threshold: 1000,
+//!         high_threshold: 3000,
+//!     },
+//! )
+//! .unwrap();
+//!
+//! let ulp = UlpWakeupSource::new();
+//! rtc.sleep_deep(&[&ulp], &mut delay);
+//! ```
+
+use core::marker::PhantomData;
+
+use fugit::MicrosDurationU64;
+
+use super::{AdcChannel, Attenuation};
+use crate::{
+    peripheral::{Peripheral, PeripheralRef},
+    peripherals::{ADC1, RTC_CNTL, SENS},
+    rtc_cntl::{Clock, RtcClock},
+};
+
+/// The start of RTC slow memory, ULP addresses count 32-bit words from here
+const RTC_SLOW_MEM: usize = 0x5000_0000;
+
+/// The size of RTC slow memory in 32-bit words
+const RTC_SLOW_MEM_WORDS: usize = 8 * 1024 / 4;
+
+/// Offsets of the data words from the end of the program
+const COUNT: u32 = 0;
+const INDEX: u32 = 1;
+const LAST: u32 = 2;
+const BUFFER: u32 = 3;
+
+/// The number of words in front of the ring buffer
+const HEADER_LEN: usize = PROGRAM_LEN + BUFFER as usize;
+
+const PROGRAM_LEN: usize = 17;
+
+/// ULP sampler errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The memory isn't in RTC slow memory, or has no room for a sample
+    Memory,
+    /// The sample period is zero or too long
+    Period,
+}
+
+/// Configuration of the ULP sampler
+#[derive(Debug, Clone, Copy)]
+pub struct UlpAdcConfig {
+    /// The time between two samples
+    pub period: MicrosDurationU64,
+    /// Samples below this value wake up the chip, 0 disables this
+    pub low_threshold: u16,
+    /// Samples at or above this value wake up the chip, anything above 4095
+    /// disables this
+    pub high_threshold: u16,
+}
+
+/// An ADC1 channel sampled by the ULP coprocessor
+pub struct UlpAdcSampler<'d, PIN> {
+    _adc: PeripheralRef<'d, ADC1>,
+    _pin: PhantomData<PIN>,
+}
+
+impl<'d, PIN> UlpAdcSampler<'d, PIN>
+where
+    PIN: AdcChannel,
+{
+    /// Load the sampling program into `memory` and start the ULP.
+    ///
+    /// `memory` has to be in RTC slow memory, e.g. a `#[ram(rtc_slow)]`
+    /// static. All of it after the program and a few words of state is used
+    /// for the ring buffer, whose length is rounded down to a power of two.
+    ///
+    /// The ULP keeps sampling when the sampler is dropped, call
+    /// [`UlpAdcSampler::stop`] to stop it.
+    pub fn new(
+        adc: impl Peripheral<P = ADC1> + 'd,
+        _pin: PIN,
+        attenuation: Attenuation,
+        memory: &'static mut [u32],
+        config: UlpAdcConfig,
+    ) -> Result<Self, Error> {
+        crate::into_ref!(adc);
+
+        let start = (memory.as_ptr() as usize)
+            .checked_sub(RTC_SLOW_MEM)
+            .map(|offset| offset / 4)
+            .filter(|start| start + memory.len() <= RTC_SLOW_MEM_WORDS)
+            .ok_or(Error::Memory)?;
+        let capacity = buffer_capacity(memory.len()).ok_or(Error::Memory)?;
+
+        let slow_clock = RtcClock::get_slow_freq().frequency().to_Hz() as u64;
+        let cycles = config.period.ticks() * slow_clock / 1_000_000;
+        if cycles == 0 || cycles > u32::MAX as u64 {
+            return Err(Error::Period);
+        }
+
+        ulp_stop();
+
+        let data = (start + PROGRAM_LEN) as u32;
+        let program = program(
+            data,
+            PIN::CHANNEL,
+            capacity as u32,
+            config.low_threshold,
+            config.high_threshold,
+        );
+        memory[..PROGRAM_LEN].copy_from_slice(&program);
+        memory[PROGRAM_LEN..].fill(0);
+
+        super::enable_ulp_mode(PIN::CHANNEL, attenuation);
+
+        let sensors = unsafe { &*SENS::ptr() };
+        sensors
+            .ulp_cp_sleep_cyc0()
+            .write(|w| unsafe { w.bits(cycles as u32) });
+
+        ulp_run(start as u16);
+
+        Ok(Self {
+            _adc: adc,
+            _pin: PhantomData,
+        })
+    }
+
+    /// Stop the ULP
+    pub fn stop(self) {
+        ulp_stop();
+    }
+}
+
+/// The samples taken by the ULP, read from its memory
+pub struct UlpAdcSamples<'a> {
+    memory: &'a [u32],
+}
+
+impl<'a> UlpAdcSamples<'a> {
+    /// Read the samples from the memory the sampler was created with
+    pub fn new(memory: &'a [u32]) -> Self {
+        Self { memory }
+    }
+
+    /// The number of samples taken, saturating at `u16::MAX`
+    pub fn count(&self) -> u16 {
+        self.word(COUNT)
+    }
+
+    /// The latest sample
+    pub fn last(&self) -> Option<u16> {
+        (self.count() > 0).then(|| self.word(LAST))
+    }
+
+    /// The samples in the ring buffer, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = u16> + '_ {
+        let buffer = match buffer_capacity(self.memory.len()) {
+            Some(capacity) => &self.memory[HEADER_LEN..][..capacity],
+            None => &[],
+        };
+        let stored = usize::min(self.count() as usize, buffer.len());
+        let index = self.word(INDEX) as usize;
+        // before the buffer wrapped, the samples start at its beginning
+        let oldest = if stored < buffer.len() { 0 } else { index };
+
+        (0..stored).map(move |i| (buffer[(oldest + i) % buffer.len()] & 0xffff) as u16)
+    }
+
+    fn word(&self, offset: u32) -> u16 {
+        self.memory
+            .get(PROGRAM_LEN + offset as usize)
+            .map_or(0, |word| (*word & 0xffff) as u16)
+    }
+}
+
+/// The largest power of two number of samples fitting into the memory
+fn buffer_capacity(memory_len: usize) -> Option<usize> {
+    let available = memory_len.checked_sub(HEADER_LEN)?;
+    (available > 0).then(|| 1 << available.ilog2())
+}
+
+/// The sampling program, one run per ULP timer period.
+///
+/// R3 points to the data words, R1 holds the ring buffer index and R0 the
+/// sample.
+fn program(data: u32, channel: u8, capacity: u32, low: u16, high: u16) -> [u32; PROGRAM_LEN] {
+    [
+        movi(3, data),
+        // saturating sample count
+        ld(0, 3, COUNT),
+        jumpr(2, 0xffff, JUMPR_GE),
+        addi(0, 0, 1),
+        st(0, 3, COUNT),
+        // take the sample and store it
+        ld(1, 3, INDEX),
+        adc(0, 0, channel + 1),
+        st(0, 3, LAST),
+        addr(2, 3, 1),
+        st(0, 2, BUFFER),
+        addi(1, 1, 1),
+        andi(1, 1, capacity - 1),
+        st(1, 3, INDEX),
+        // compare against the thresholds
+        jumpr(2, low, JUMPR_LT),
+        jumpr(2, high, JUMPR_LT),
+        wake(),
+        halt(),
+    ]
+}
+
+// ULP FSM instruction encodings, see ESP-IDF's `ulp.h`
+
+const OPCODE_ADC: u32 = 5;
+const OPCODE_ST: u32 = 6;
+const OPCODE_ALU: u32 = 7;
+const OPCODE_BRANCH: u32 = 8;
+const OPCODE_END: u32 = 9;
+const OPCODE_HALT: u32 = 11;
+const OPCODE_LD: u32 = 13;
+
+const SUB_OPCODE_ST: u32 = 4;
+const SUB_OPCODE_ALU_REG: u32 = 0;
+const SUB_OPCODE_ALU_IMM: u32 = 1;
+const SUB_OPCODE_BR: u32 = 1;
+const SUB_OPCODE_END: u32 = 0;
+
+const ALU_SEL_ADD: u32 = 0;
+const ALU_SEL_AND: u32 = 2;
+const ALU_SEL_MOV: u32 = 4;
+
+const JUMPR_LT: u32 = 0;
+const JUMPR_GE: u32 = 1;
+
+const fn alu_imm(sel: u32, rd: u32, rs: u32, imm: u32) -> u32 {
+    (OPCODE_ALU << 28) | (SUB_OPCODE_ALU_IMM << 25) | (sel << 21) | (imm << 4) | (rs << 2) | rd
+}
+
+/// rd = imm
+const fn movi(rd: u32, imm: u32) -> u32 {
+    alu_imm(ALU_SEL_MOV, rd, 0, imm)
+}
+
+/// rd = rs + imm
+const fn addi(rd: u32, rs: u32, imm: u32) -> u32 {
+    alu_imm(ALU_SEL_ADD, rd, rs, imm)
+}
+
+/// rd = rs & imm
+const fn andi(rd: u32, rs: u32, imm: u32) -> u32 {
+    alu_imm(ALU_SEL_AND, rd, rs, imm)
+}
+
+/// rd = rs + rt
+const fn addr(rd: u32, rs: u32, rt: u32) -> u32 {
+    (OPCODE_ALU << 28)
+        | (SUB_OPCODE_ALU_REG << 25)
+        | (ALU_SEL_ADD << 21)
+        | (rt << 4)
+        | (rs << 2)
+        | rd
+}
+
+/// rd = memory[rs + offset]
+const fn ld(rd: u32, rs: u32, offset: u32) -> u32 {
+    (OPCODE_LD << 28) | (offset << 10) | (rs << 2) | rd
+}
+
+/// memory[rs + offset] = rv
+const fn st(rv: u32, rs: u32, offset: u32) -> u32 {
+    (OPCODE_ST << 28) | (SUB_OPCODE_ST << 25) | (offset << 10) | (rs << 2) | rv
+}
+
+/// rd = conversion of the pad `mux` of SAR ADC `sar`
+const fn adc(rd: u32, sar: u32, mux: u8) -> u32 {
+    (OPCODE_ADC << 28) | (sar << 6) | ((mux as u32) << 2) | rd
+}
+
+/// Jump forward by `offset` instructions if R0 compares to `threshold`
+const fn jumpr(offset: u32, threshold: u16, cmp: u32) -> u32 {
+    (OPCODE_BRANCH << 28) | (SUB_OPCODE_BR << 25) | (offset << 17) | (cmp << 16) | threshold as u32
+}
+
+/// Wake up the chip
+const fn wake() -> u32 {
+    (OPCODE_END << 28) | (SUB_OPCODE_END << 25) | 1
+}
+
+/// End the program until the next timer period
+const fn halt() -> u32 {
+    OPCODE_HALT << 28
+}
+
+fn ulp_stop() {
+    let rtc_cntl = unsafe { &*RTC_CNTL::ptr() };
+    rtc_cntl
+        .state0()
+        .modify(|_, w| w.ulp_cp_slp_timer_en().clear_bit());
+
+    // wait for at least one RTC_SLOW_CLK cycle
+    crate::rom::ets_delay_us(10);
+}
+
+fn ulp_run(entry: u16) {
+    let rtc_cntl = unsafe { &*RTC_CNTL::ptr() };
+    let sensors = unsafe { &*SENS::ptr() };
+
+    ulp_stop();
+
+    sensors
+        .sar_start_force()
+        .modify(|_, w| unsafe { w.pc_init().bits(entry).ulp_cp_force_start_top().clear_bit() });
+
+    // keep the voltage up while the RTC 8 MHz clock is enabled
+    rtc_cntl.options0().modify(|_, w| {
+        w.bias_i2c_folw_8m()
+            .set_bit()
+            .bias_core_folw_8m()
+            .set_bit()
+            .bias_sleep_folw_8m()
+            .set_bit()
+    });
+
+    rtc_cntl
+        .state0()
+        .modify(|_, w| w.ulp_cp_slp_timer_en().set_bit());
+}
+
+// Check the encodings against the words produced by the macros of ESP-IDF's
+// `ulp.h`
+const _: () = {
+    assert!(st(0, 3, 0) == 0x6800_000c); // I_ST(R0, R3, 0)
+    assert!(st(1, 3, 1) == 0x6800_040d); // I_ST(R1, R3, 1)
+    assert!(st(0, 2, 3) == 0x6800_0c08); // I_ST(R0, R2, 3)
+    assert!(ld(0, 3, 0) == 0xd000_000c); // I_LD(R0, R3, 0)
+    assert!(ld(1, 3, 1) == 0xd000_040d); // I_LD(R1, R3, 1)
+    assert!(adc(0, 0, 1) == 0x5000_0004); // I_ADC(R0, 0, 0)
+    assert!(adc(0, 0, 8) == 0x5000_0020); // I_ADC(R0, 0, 7)
+    assert!(movi(3, 100) == 0x7280_0643); // I_MOVI(R3, 100)
+    assert!(addi(0, 0, 1) == 0x7200_0010); // I_ADDI(R0, R0, 1)
+    assert!(andi(1, 1, 15) == 0x7240_00f5); // I_ANDI(R1, R1, 15)
+    assert!(addr(2, 3, 1) == 0x7000_001e); // I_ADDR(R2, R3, R1)
+    assert!(jumpr(2, 0xffff, JUMPR_GE) == 0x8205_ffff); // I_JUMPR(2, 0xffff, B_CMP_GE)
+    assert!(jumpr(2, 1000, JUMPR_LT) == 0x8204_03e8); // I_JUMPR(2, 1000, B_CMP_L)
+    assert!(wake() == 0x9000_0001); // I_WAKE()
+    assert!(halt() == 0xb000_0000); // I_HALT()
+};
@@ -1,4 +1,5 @@
-#[cfg(esp32s3)]
+use core::marker::PhantomData;
+
 pub use self::calibration::*;
 use super::{AdcCalScheme, AdcCalSource, AdcChannel, AdcConfig, AdcPin, Attenuation};
 #[cfg(esp32s3)]
@@ -508,6 +509,68 @@ where
         pin.cal_scheme.adc_val(converted_value)
     }
 
+    /// Enable a pin with the given attenuation after the driver was created
+    pub fn enable_pin<PIN>(&mut self, pin: PIN, attenuation: Attenuation) -> AdcPin<PIN, ADCI>
+    where
+        PIN: AdcChannel,
+    {
+        self.enable_pin_with_cal(pin, attenuation)
+    }
+
+    /// Enable a pin with the given attenuation and calibration scheme after
+    /// the driver was created
+    pub fn enable_pin_with_cal<PIN, CS>(
+        &mut self,
+        pin: PIN,
+        attenuation: Attenuation,
+    ) -> AdcPin<PIN, ADCI, CS>
+    where
+        PIN: AdcChannel,
+        CS: AdcCalScheme<ADCI>,
+    {
+        ADCI::set_attenuation(PIN::CHANNEL as usize, attenuation as u8);
+
+        AdcPin {
+            pin,
+            cal_scheme: CS::new_cal(attenuation),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Change the attenuation of an enabled pin, e.g. to widen the measured
+    /// range.
+    ///
+    /// The calibration scheme of the pin is set up again for the new
+    /// attenuation. A conversion in progress is abandoned.
+    pub fn set_attenuation<PIN, CS>(
+        &mut self,
+        pin: &mut AdcPin<PIN, ADCI, CS>,
+        attenuation: Attenuation,
+    ) where
+        PIN: AdcChannel,
+        CS: AdcCalScheme<ADCI>,
+    {
+        if self.active_channel.take().is_some() {
+            ADCI::reset();
+        }
+
+        ADCI::set_attenuation(PIN::CHANNEL as usize, attenuation as u8);
+        pin.cal_scheme = CS::new_cal(attenuation);
+    }
+
+    /// Disable a pin, returning the underlying GPIO
+    pub fn disable_pin<PIN, CS>(&mut self, pin: AdcPin<PIN, ADCI, CS>) -> PIN
+    where
+        PIN: AdcChannel,
+    {
+        if self.active_channel == Some(PIN::CHANNEL) {
+            self.active_channel = None;
+            ADCI::reset();
+        }
+
+        pin.pin
+    }
+
     /// Request that the ADC begin a conversion on the specified pin
     ///
     /// This method takes an [AdcPin](super::AdcPin) reference, as it is
@@ -554,6 +617,22 @@ where
         Ok(converted_value)
     }
 
+    /// Read the voltage of the pin in millivolts.
+    ///
+    /// The pin has to be enabled with a calibration scheme converting its
+    /// readings to millivolts, see
+    /// [`AdcCalMillivolts`](super::AdcCalMillivolts).
+    pub fn read_mv<PIN, CS>(
+        &mut self,
+        pin: &mut super::AdcPin<PIN, ADCI, CS>,
+    ) -> nb::Result<u16, ()>
+    where
+        PIN: super::AdcChannel,
+        CS: super::AdcCalMillivolts<ADCI>,
+    {
+        self.read_oneshot(pin)
+    }
+
     fn start_sample<PIN, CS>(&mut self, pin: &mut AdcPin<PIN, ADCI, CS>)
     where
         PIN: AdcChannel,
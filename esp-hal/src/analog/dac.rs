@@ -125,3 +125,16 @@ impl<'d> DAC2<'d> {
             .modify(|_, w| unsafe { w.pdac2_dac().bits(value) });
     }
 }
+
+/// Let both DACs output the data of I2S0 instead of the written values
+#[cfg(esp32)]
+pub(crate) fn enable_i2s_mode() {
+    let sensors = unsafe { &*peripherals::SENS::PTR };
+
+    sensors
+        .sar_dac_ctrl1()
+        .modify(|_, w| w.dac_dig_force().set_bit().dac_clk_inv().set_bit());
+    sensors
+        .sar_dac_ctrl2()
+        .modify(|_, w| w.dac_cw_en1().clear_bit().dac_cw_en2().clear_bit());
+}
@@ -1,6 +1,9 @@
+use fugit::HertzU32;
+
 use crate::{
     clock::{Clock, PllClock, XtalClock},
     regi2c_write,
+    regi2c_write_mask,
 };
 
 const REF_CLK_FREQ: u32 = 1000000;
@@ -41,6 +44,111 @@ const I2C_BBPLL_OC_LREF: u32 = 2;
 const I2C_BBPLL_OC_DIV_7_0: u32 = 3;
 const I2C_BBPLL_OC_DCUR: u32 = 5;
 
+const I2C_APLL: u32 = 0x6d;
+const I2C_APLL_HOSTID: u32 = 3;
+
+const I2C_APLL_IR_CAL_DELAY: u32 = 0;
+const I2C_APLL_OR_CAL_END: u32 = 3;
+const I2C_APLL_OR_CAL_END_MSB: u32 = 6;
+const I2C_APLL_OR_CAL_END_LSB: u32 = 6;
+const I2C_APLL_OR_OUTPUT_DIV: u32 = 4;
+const I2C_APLL_OR_OUTPUT_DIV_MSB: u32 = 4;
+const I2C_APLL_OR_OUTPUT_DIV_LSB: u32 = 0;
+const I2C_APLL_SDM_STOP: u32 = 5;
+const I2C_APLL_DSDM2: u32 = 7;
+const I2C_APLL_DSDM2_MSB: u32 = 5;
+const I2C_APLL_DSDM2_LSB: u32 = 0;
+const I2C_APLL_DSDM1: u32 = 8;
+const I2C_APLL_DSDM1_MSB: u32 = 7;
+const I2C_APLL_DSDM1_LSB: u32 = 0;
+const I2C_APLL_DSDM0: u32 = 9;
+const I2C_APLL_DSDM0_MSB: u32 = 7;
+const I2C_APLL_DSDM0_LSB: u32 = 0;
+
+const APLL_SDM_STOP_VAL_1: u32 = 0x09;
+const APLL_SDM_STOP_VAL_2_REV0: u32 = 0x69;
+const APLL_SDM_STOP_VAL_2_REV1: u32 = 0x49;
+
+const APLL_CAL_DELAY_1: u32 = 0x0f;
+const APLL_CAL_DELAY_2: u32 = 0x3f;
+const APLL_CAL_DELAY_3: u32 = 0x1f;
+
+const APLL_MULTIPLIER_OUT_MIN_HZ: u32 = 350_000_000;
+const APLL_MULTIPLIER_OUT_MAX_HZ: u32 = 500_000_000;
+
+/// Powers up the APLL and configures it to run as close as possible to
+/// `freq`. Returns the frequency it runs at, or `None` if `freq` is out of its
+/// range of about 5.3 to 125 MHz.
+pub(crate) fn esp32_rtc_apll_configure(xtal_freq: HertzU32, freq: u32) -> Option<u32> {
+    // this corresponds to `rtc_clk_apll_coeff_calc` and `rtc_clk_apll_coeff_set`
+    // in esp-idf:
+    //
+    // apll_freq = xtal_freq * (4 + sdm2 + sdm1 / 256 + sdm0 / 65536) / ((o_div + 2)
+    // * 2)
+    //
+    // where the multiplier output (the numerator) has to be between 350 and 500 MHz
+    let mut o_div = (APLL_MULTIPLIER_OUT_MIN_HZ / (freq * 2) + 1) as i32 - 2;
+    if o_div > 31 {
+        return None;
+    }
+    if o_div < 0 {
+        o_div = (APLL_MULTIPLIER_OUT_MAX_HZ / (freq * 2)) as i32 - 2;
+        if o_div < 0 {
+            return None;
+        }
+    }
+    let o_div = o_div as u64;
+    let xtal = xtal_freq.raw() as u64;
+
+    // 4 + sdm2 + sdm1 / 256 + sdm0 / 65536 in units of 1 / 65536
+    let multiplier = (o_div + 2) * 2 * freq as u64 * 65536 / xtal;
+    let mut sdm = multiplier - 4 * 65536;
+
+    // the first chip revision ignores the fractional part
+    let rev0 = crate::efuse::Efuse::read_field_le::<u8>(crate::efuse::CHIP_VER_REV1) == 0;
+    let sdm_stop_val_2 = if rev0 {
+        sdm &= !0xffff;
+        APLL_SDM_STOP_VAL_2_REV0
+    } else {
+        APLL_SDM_STOP_VAL_2_REV1
+    };
+
+    let rtc_cntl = unsafe { &*crate::peripherals::RTC_CNTL::ptr() };
+    rtc_cntl
+        .ana_conf()
+        .modify(|_, w| w.plla_force_pd().clear_bit().plla_force_pu().set_bit());
+    rtc_cntl
+        .options0()
+        .modify(|_, w| w.bias_i2c_force_pd().clear_bit());
+
+    regi2c_write_mask!(I2C_APLL, I2C_APLL_DSDM2, sdm >> 16);
+    regi2c_write_mask!(I2C_APLL, I2C_APLL_DSDM0, sdm & 0xff);
+    regi2c_write_mask!(I2C_APLL, I2C_APLL_DSDM1, (sdm >> 8) & 0xff);
+    regi2c_write!(I2C_APLL, I2C_APLL_SDM_STOP, APLL_SDM_STOP_VAL_1);
+    regi2c_write!(I2C_APLL, I2C_APLL_SDM_STOP, sdm_stop_val_2);
+    regi2c_write_mask!(I2C_APLL, I2C_APLL_OR_OUTPUT_DIV, o_div);
+
+    // calibration
+    regi2c_write!(I2C_APLL, I2C_APLL_IR_CAL_DELAY, APLL_CAL_DELAY_1);
+    regi2c_write!(I2C_APLL, I2C_APLL_IR_CAL_DELAY, APLL_CAL_DELAY_2);
+    regi2c_write!(I2C_APLL, I2C_APLL_IR_CAL_DELAY, APLL_CAL_DELAY_3);
+
+    while unsafe {
+        crate::rom::rom_i2c_readReg_Mask(
+            I2C_APLL,
+            I2C_APLL_HOSTID,
+            I2C_APLL_OR_CAL_END,
+            I2C_APLL_OR_CAL_END_MSB,
+            I2C_APLL_OR_CAL_END_LSB,
+        )
+    } == 0
+    {
+        crate::rom::ets_delay_us(1);
+    }
+
+    Some((xtal * (4 * 65536 + sdm) / (65536 * (o_div + 2) * 2)) as u32)
+}
+
 pub(crate) fn esp32_rtc_bbpll_configure(xtal_freq: XtalClock, pll_freq: PllClock) {
     let efuse = unsafe { &*crate::peripherals::EFUSE::ptr() };
     let rtc_cntl = unsafe { &*crate::peripherals::RTC_CNTL::ptr() };
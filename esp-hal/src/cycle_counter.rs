@@ -0,0 +1,144 @@
+//! # CPU cycle counter
+//!
+//! ## Overview
+//! This module provides access to the cycle counter of the CPU core it is
+//! called on (`CCOUNT` on Xtensa, the machine performance counter on RISC-V),
+//! extended to 64 bits in software.
+//!
+//! The hardware counter is only 32 bits wide and wraps around every few
+//! seconds (e.g. after ~17.9 s at 240 MHz). The extension detects a wrap
+//! whenever the counter is read, so the counter has to be read at least once
+//! per wrap period. On Xtensa, [CycleCounter::enable_overflow_interrupt]
+//! takes care of this by raising an interrupt twice per wrap period. The
+//! RISC-V performance counter doesn't provide an overflow interrupt.
+//!
+//! The counter stops while the CPU is stalled or in light sleep, and it
+//! follows changes of the CPU clock, so it's best suited for measuring short
+//! durations with high resolution.
+//!
+//! ## Example
+//! ```no_run
+//! let counter = CycleCounter::new(&clocks);
+//!
+//! let start = counter.cycles();
+//! do_work();
+//! let elapsed = counter.cycles() - start;
+//!
+//! println!("Took {} ns", counter.cycles_to_nanos(elapsed));
+//! ```
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use fugit::HertzU32;
+
+use crate::clock::Clocks;
+
+#[cfg(multi_core)]
+const NUM_CORES: usize = 2;
+#[cfg(not(multi_core))]
+const NUM_CORES: usize = 1;
+
+/// Per core: the last raw value read and the number of wraps seen.
+#[allow(clippy::declare_interior_mutable_const)]
+const INIT: Mutex<Cell<(u32, u32)>> = Mutex::new(Cell::new((0, 0)));
+static EXTENSION: [Mutex<Cell<(u32, u32)>>; NUM_CORES] = [INIT; NUM_CORES];
+
+/// The cycle counter of the current CPU core, extended to 64 bits.
+#[derive(Debug, Clone, Copy)]
+pub struct CycleCounter {
+    cpu_clock: HertzU32,
+}
+
+impl CycleCounter {
+    /// Create a new instance, starting the counter if needed.
+    pub fn new(clocks: &Clocks) -> Self {
+        #[cfg(all(riscv, not(esp32p4)))]
+        unsafe {
+            // count clock cycles (mpcer) and enable the counter (mpcmr)
+            core::arch::asm!("csrw 0x7e0, {0}", in(reg) 1);
+            core::arch::asm!("csrw 0x7e1, {0}", in(reg) 1);
+        }
+
+        Self {
+            cpu_clock: clocks.cpu_clock,
+        }
+    }
+
+    /// The raw 32 bit hardware counter of the current core.
+    #[inline(always)]
+    pub fn raw() -> u32 {
+        #[cfg(xtensa)]
+        let cycles = xtensa_lx::timer::get_cycle_count();
+
+        #[cfg(all(riscv, not(esp32p4)))]
+        let cycles = {
+            let cycles: u32;
+            unsafe { core::arch::asm!("csrr {0}, 0x7e2", out(reg) cycles) };
+            cycles
+        };
+
+        #[cfg(esp32p4)]
+        let cycles = {
+            let cycles: u32;
+            unsafe { core::arch::asm!("csrr {0}, mcycle", out(reg) cycles) };
+            cycles
+        };
+
+        cycles
+    }
+
+    /// The number of cycles counted by the current core.
+    pub fn cycles(&self) -> u64 {
+        critical_section::with(|cs| {
+            let extension = EXTENSION[crate::get_core() as usize].borrow(cs);
+            let (last, wraps) = extension.get();
+
+            let raw = Self::raw();
+            let wraps = if raw < last {
+                wraps.wrapping_add(1)
+            } else {
+                wraps
+            };
+            extension.set((raw, wraps));
+
+            ((wraps as u64) << 32) | raw as u64
+        })
+    }
+
+    /// Convert a number of cycles to nanoseconds, based on the CPU clock
+    /// configured when this instance was created.
+    pub fn cycles_to_nanos(&self, cycles: u64) -> u64 {
+        (cycles as u128 * 1_000_000_000 / self.cpu_clock.to_Hz() as u128) as u64
+    }
+
+    /// The number of cycles counted by the current core, in nanoseconds.
+    pub fn nanos(&self) -> u64 {
+        self.cycles_to_nanos(self.cycles())
+    }
+
+    /// Raise the `Timer0` CPU interrupt on the current core twice per wrap
+    /// period, so the 64 bit extension is kept up to date even if the counter
+    /// isn't read for a long time.
+    ///
+    /// The `Timer0` interrupt handler (see `xtensa_lx_rt`) has to call
+    /// [Self::on_overflow].
+    #[cfg(xtensa)]
+    pub fn enable_overflow_interrupt(&self) {
+        self.on_overflow();
+        unsafe {
+            xtensa_lx::interrupt::enable_mask(1 << 6);
+        }
+    }
+
+    /// Acknowledge the overflow interrupt and account for a wrap.
+    #[cfg(xtensa)]
+    pub fn on_overflow(&self) {
+        let cycles = self.cycles() as u32;
+
+        // Interrupt again when the counter reaches the other half of its range,
+        // so consecutive reads are never a full wrap apart. Writing CCOMPARE0
+        // clears the interrupt.
+        xtensa_lx::timer::set_ccompare0(cycles.wrapping_add(0x8000_0000) & 0x8000_0000);
+    }
+}
@@ -18,6 +18,16 @@
 //! delay.delay_ms(1000 as u32);
 //! ```
 //!
+//! A [TimerDelay] provides the same on top of any general purpose timer or
+//! `SYSTIMER` alarm, which counts down each delay:
+//!
+//! ```no_run
+//! let timg0 = TimerGroup::new(peripherals.TIMG0, &clocks, None);
+//! let mut delay = TimerDelay::new(timg0.timer0);
+//!
+//! delay.delay_millis(1000);
+//! ```
+//!
 //! [DelayMs]: embedded_hal_02::blocking::delay::DelayMs
 //! [DelayUs]: embedded_hal_02::blocking::delay::DelayUs
 //! [embedded-hal]: https://docs.rs/embedded-hal/0.2.7/embedded_hal/index.html
@@ -25,6 +35,8 @@
 use fugit::HertzU64;
 pub use fugit::MicrosDurationU64;
 
+use crate::timer::{AnyTimer, GenericTimer};
+
 /// Delay driver
 ///
 /// Uses the `SYSTIMER` peripheral internally for RISC-V devices, and the
@@ -72,19 +84,94 @@ impl embedded_hal::delay::DelayNs for Delay {
     }
 }
 
+/// Delay driver on top of a general purpose timer or a `SYSTIMER` alarm
+///
+/// Takes any timer which converts into an [AnyTimer]. Unlike [Delay], it
+/// doesn't depend on the CPU clock or the cores' own timers.
+pub struct TimerDelay {
+    timer: AnyTimer,
+}
+
+impl TimerDelay {
+    /// Create a new `TimerDelay` instance
+    pub fn new(timer: impl Into<AnyTimer>) -> Self {
+        Self {
+            timer: timer.into(),
+        }
+    }
+
+    /// Delay for the specified time
+    pub fn delay(&mut self, time: MicrosDurationU64) {
+        if time.ticks() == 0 {
+            return;
+        }
+
+        self.timer.start(time);
+        self.timer.wait();
+    }
+
+    /// Delay for the specified number of milliseconds
+    pub fn delay_millis(&mut self, ms: u32) {
+        self.delay(MicrosDurationU64::millis(ms as u64));
+    }
+
+    /// Delay for the specified number of microseconds
+    pub fn delay_micros(&mut self, us: u32) {
+        self.delay(MicrosDurationU64::micros(us as u64));
+    }
+
+    /// Delay for the specified number of nanoseconds, rounded up to whole
+    /// microseconds
+    pub fn delay_nanos(&mut self, ns: u32) {
+        self.delay(MicrosDurationU64::micros((ns as u64).div_ceil(1000)));
+    }
+
+    /// Return the timer
+    pub fn free(self) -> AnyTimer {
+        self.timer
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+impl<T> embedded_hal_02::blocking::delay::DelayMs<T> for TimerDelay
+where
+    T: Into<u32>,
+{
+    fn delay_ms(&mut self, ms: T) {
+        self.delay_millis(ms.into());
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+impl<T> embedded_hal_02::blocking::delay::DelayUs<T> for TimerDelay
+where
+    T: Into<u32>,
+{
+    fn delay_us(&mut self, us: T) {
+        self.delay_micros(us.into());
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl embedded_hal::delay::DelayNs for TimerDelay {
+    fn delay_ns(&mut self, ns: u32) {
+        self.delay_nanos(ns);
+    }
+}
+
 #[cfg(riscv)]
 mod implementation {
     use super::*;
-    use crate::{clock::Clocks, systimer::SystemTimer};
+    use crate::{
+        clock::Clocks,
+        systimer::{self, SystemTimer},
+    };
 
     impl Delay {
         /// Create a new `Delay` instance
         pub fn new(clocks: &Clocks) -> Self {
-            // The counters and comparators are driven using `XTAL_CLK`.
-            // The average clock frequency is fXTAL_CLK/2.5, which is 16 MHz.
-            // The timer counting is incremented by 1/16 μs on each `CNT_CLK` cycle.
             Self {
-                freq: HertzU64::MHz(clocks.xtal_clock.to_MHz() as u64 * 10 / 25),
+                freq: HertzU64::Hz(systimer::ticks_per_second(clocks)),
             }
         }
 
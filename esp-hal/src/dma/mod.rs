@@ -330,6 +330,9 @@ pub enum DmaError {
     Overflow,
     Exhausted,
     BufferTooSmall,
+    /// Reading from a circular transfer happened too late, the DMA already
+    /// overwrote data which wasn't popped yet.
+    Late,
 }
 
 /// DMA Priorities
@@ -429,6 +432,13 @@ pub trait AesPeripheral: PeripheralMarker {}
 /// Marks channels as usable for LCD_CAM
 pub trait LcdCamPeripheral: PeripheralMarker {}
 
+/// Marks channels as usable for UHCI
+pub trait UhciPeripheral: PeripheralMarker {}
+
+/// Marks channels as usable for ADC
+#[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+pub trait AdcPeripheral: PeripheralMarker {}
+
 /// DMA Rx
 pub trait Rx: RxPrivate {}
 
@@ -478,6 +488,9 @@ pub trait RxPrivate {
 
     fn drain_buffer(&mut self, dst: &mut [u8]) -> Result<usize, DmaError>;
 
+    /// Number of bytes written to the buffer of a non-circular transfer
+    fn received_len(&self) -> usize;
+
     /// Descriptor error detected
     fn has_error(&self) -> bool;
 
@@ -601,6 +614,7 @@ where
     pub available: usize,
     pub last_seen_handled_descriptor_ptr: *mut DmaDescriptor,
     pub read_buffer_start: *mut u8,
+    pub late: bool,
     pub _phantom: PhantomData<R>,
 }
 
@@ -618,9 +632,32 @@ where
             available: 0,
             last_seen_handled_descriptor_ptr: core::ptr::null_mut(),
             read_buffer_start: core::ptr::null_mut(),
+            late: false,
             _phantom: PhantomData,
         }
     }
+
+    /// Whether all descriptors of the circular chain starting at `start` are
+    /// filled and owned by the CPU
+    fn all_descriptors_filled(&self, start: *mut DmaDescriptor) -> bool {
+        let mut current = start;
+        loop {
+            let descriptor = unsafe { current.read_volatile() };
+            if descriptor.owner() == Owner::Dma || descriptor.is_empty() {
+                return false;
+            }
+
+            current = if descriptor.next.is_null() {
+                self.descriptors.as_ptr() as *mut DmaDescriptor
+            } else {
+                descriptor.next
+            };
+
+            if current == start {
+                return true;
+            }
+        }
+    }
 }
 
 impl<'a, T, R> Rx for ChannelRx<'a, T, R>
@@ -662,6 +699,7 @@ where
         self.read_descr_ptr = self.descriptors.as_mut_ptr();
         self.last_seen_handled_descriptor_ptr = core::ptr::null_mut();
         self.read_buffer_start = data;
+        self.late = false;
 
         self.rx_impl
             .prepare_transfer_without_start(self.descriptors, circular, peri, data, len)
@@ -713,6 +751,11 @@ where
         let mut dw0 = unsafe { descr_address.read_volatile() };
 
         if dw0.owner() == Owner::Cpu && !dw0.is_empty() {
+            // if all descriptors are owned by the CPU the DMA went around the whole
+            // buffer and keeps overwriting data which wasn't popped yet - in future
+            // we can enable `check_owner` and check the interrupt instead
+            self.late = self.all_descriptors_filled(descr_address);
+
             let descriptor_buffer = dw0.buffer;
             let next_descriptor = dw0.next;
 
@@ -738,6 +781,13 @@ where
     }
 
     fn pop(&mut self, data: &mut [u8]) -> Result<usize, DmaError> {
+        if self.late {
+            // the data might have been overwritten while copying it, drop it
+            self.late = false;
+            self.available = 0;
+            return Err(DmaError::Late);
+        }
+
         let avail = self.available;
 
         if avail < data.len() {
@@ -786,6 +836,19 @@ where
         Ok(len)
     }
 
+    fn received_len(&self) -> usize {
+        let mut len = 0;
+        for descriptor in self.descriptors.iter() {
+            len += descriptor.len();
+
+            if descriptor.flags.suc_eof() || descriptor.next.is_null() {
+                break;
+            }
+        }
+
+        len
+    }
+
     fn is_listening_eof(&self) -> bool {
         R::is_listening_in_eof()
     }
@@ -830,8 +893,18 @@ pub trait TxPrivate {
         len: usize,
     ) -> Result<(), DmaError>;
 
+    /// Prepare a single transfer of all `segments`, one after the other
+    fn prepare_segments_without_start(
+        &mut self,
+        peri: DmaPeripheral,
+        segments: &[&[u8]],
+    ) -> Result<(), DmaError>;
+
     fn start_transfer(&mut self) -> Result<(), DmaError>;
 
+    /// Stop a running (e.g. circular) transfer
+    fn stop_transfer(&mut self);
+
     fn clear_ch_out_done(&self);
 
     fn is_ch_out_done_set(&self) -> bool;
@@ -858,6 +931,9 @@ pub trait TxPrivate {
 
     fn push_with(&mut self, f: impl FnOnce(&mut [u8]) -> usize) -> Result<usize, DmaError>;
 
+    /// Whether a circular transfer ran out of pushed data since the last call
+    fn take_underrun(&mut self) -> bool;
+
     #[cfg(feature = "async")]
     fn waker() -> &'static embassy_sync::waitqueue::AtomicWaker;
 }
@@ -939,6 +1015,51 @@ where
         Ok(())
     }
 
+    fn prepare_segments_without_start(
+        &mut self,
+        descriptors: &mut [DmaDescriptor],
+        peri: DmaPeripheral,
+        segments: &[&[u8]],
+    ) -> Result<(), DmaError> {
+        descriptors.fill(DmaDescriptor::EMPTY);
+
+        compiler_fence(core::sync::atomic::Ordering::SeqCst);
+
+        let mut chunks = segments
+            .iter()
+            .flat_map(|segment| segment.chunks(CHUNK_SIZE))
+            .peekable();
+
+        let mut descr = 0;
+        while let Some(chunk) = chunks.next() {
+            let last = chunks.peek().is_none();
+
+            let next = if last {
+                core::ptr::null_mut()
+            } else {
+                addr_of_mut!(descriptors[descr + 1])
+            };
+
+            let dw0 = &mut descriptors[descr];
+
+            dw0.set_suc_eof(last);
+            dw0.set_owner(Owner::Dma);
+            dw0.set_size(chunk.len());
+            dw0.set_length(chunk.len());
+            dw0.buffer = chunk.as_ptr().cast_mut();
+            dw0.next = next;
+
+            descr += 1;
+        }
+
+        R::clear_out_interrupts();
+        R::reset_out();
+        R::set_out_descriptors(addr_of_mut!(descriptors[0]) as u32);
+        R::set_out_peripheral(peri as u8);
+
+        Ok(())
+    }
+
     fn start_transfer(&mut self) -> Result<(), DmaError> {
         R::start_out();
 
@@ -949,6 +1070,11 @@ where
         }
     }
 
+    fn stop_transfer(&mut self) {
+        R::reset_out();
+        R::clear_out_interrupts();
+    }
+
     fn clear_ch_out_done(&self) {
         R::clear_ch_out_done();
     }
@@ -1005,6 +1131,7 @@ where
     pub last_seen_handled_descriptor_ptr: *mut DmaDescriptor,
     pub buffer_start: *const u8,
     pub buffer_len: usize,
+    pub underrun: bool,
     pub _phantom: PhantomData<R>,
 }
 
@@ -1024,6 +1151,7 @@ where
             last_seen_handled_descriptor_ptr: core::ptr::null_mut(),
             buffer_start: core::ptr::null_mut(),
             buffer_len: 0,
+            underrun: false,
             _phantom: PhantomData,
         }
     }
@@ -1070,15 +1198,58 @@ where
         self.last_seen_handled_descriptor_ptr = self.descriptors.as_mut_ptr();
         self.buffer_start = data;
         self.buffer_len = len;
+        self.underrun = false;
 
         self.tx_impl
             .prepare_transfer_without_start(self.descriptors, circular, peri, data, len)
     }
 
+    fn prepare_segments_without_start(
+        &mut self,
+        peri: DmaPeripheral,
+        segments: &[&[u8]],
+    ) -> Result<(), DmaError> {
+        let segments_without_empty = || segments.iter().filter(|segment| !segment.is_empty());
+
+        if segments_without_empty().next().is_none() {
+            return Err(DmaError::BufferTooSmall);
+        }
+
+        // the descriptors are linked up front, the DMA would only reject a
+        // segment outside of RAM once it reaches it
+        if segments_without_empty()
+            .any(|segment| !crate::soc::is_valid_ram_address(segment.as_ptr() as u32))
+        {
+            return Err(DmaError::DescriptorError);
+        }
+
+        let descriptors_needed: usize = segments_without_empty()
+            .map(|segment| segment.len().div_ceil(CHUNK_SIZE))
+            .sum();
+        if self.descriptors.len() < descriptors_needed {
+            return Err(DmaError::OutOfDescriptors);
+        }
+
+        self.write_offset = 0;
+        self.available = 0;
+        self.write_descr_ptr = self.descriptors.as_mut_ptr();
+        self.last_seen_handled_descriptor_ptr = self.descriptors.as_mut_ptr();
+        self.buffer_start = core::ptr::null();
+        self.buffer_len = 0;
+        self.underrun = false;
+
+        self.tx_impl
+            .prepare_segments_without_start(self.descriptors, peri, segments)
+    }
+
     fn start_transfer(&mut self) -> Result<(), DmaError> {
         self.tx_impl.start_transfer()
     }
 
+    fn stop_transfer(&mut self) {
+        self.tx_impl.stop_transfer();
+    }
+
     fn clear_ch_out_done(&self) {
         self.tx_impl.clear_ch_out_done();
     }
@@ -1144,6 +1315,10 @@ where
             }
 
             if self.available >= self.buffer_len {
+                // the DMA went around the whole buffer without it being refilled and
+                // plays old data again
+                self.underrun = true;
+
                 unsafe {
                     let dw0 = self.write_descr_ptr.read_volatile();
                     let segment_len = dw0.len();
@@ -1220,6 +1395,10 @@ where
         Ok(written)
     }
 
+    fn take_underrun(&mut self) -> bool {
+        core::mem::take(&mut self.underrun)
+    }
+
     fn is_listening_eof(&self) -> bool {
         R::is_listening_out_eof()
     }
@@ -1457,13 +1636,11 @@ pub(crate) mod asynch {
         }
     }
 
-    #[cfg(any(i2s0, i2s1))]
     pub struct DmaTxDoneChFuture<'a, TX> {
         pub(crate) tx: &'a mut TX,
         _a: (),
     }
 
-    #[cfg(any(i2s0, i2s1))]
     impl<'a, TX> DmaTxDoneChFuture<'a, TX>
     where
         TX: Tx,
@@ -1474,7 +1651,6 @@ pub(crate) mod asynch {
         }
     }
 
-    #[cfg(any(i2s0, i2s1))]
     impl<'a, TX> core::future::Future for DmaTxDoneChFuture<'a, TX>
     where
         TX: Tx,
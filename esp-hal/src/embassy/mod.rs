@@ -9,8 +9,8 @@
 //! than low-level timer management.
 //!
 //! Here are important details about the module:
-//!   * `time_driver` module (`time_driver_systimer` or `time_driver_timg`,
-//!     depends on enabled feature)
+//!   * `time_driver` module (`time_driver_systimer`, `time_driver_timg` or
+//!     `time_driver_any`, depends on enabled feature)
 //!     - This module contains the implementations of the timer drivers for
 //!       different ESP chips.<br> It includes the `EmbassyTimer` struct, which
 //!       is responsible for handling alarms and timer events.
@@ -35,12 +35,16 @@
 //! #[cfg(feature = "embassy-time-systick")]
 //! embassy::init(
 //!     &clocks,
-//!     esp_hal::systimer::SystemTimer::new(peripherals.SYSTIMER),
+//!     esp_hal::systimer::SystemTimer::new(peripherals.SYSTIMER, &clocks),
 //! );
 //!
 //! #[cfg(feature = "embassy-time-timg0")]
 //! embassy::init(&clocks, timer_group0.timer0);
 //!
+//! // any general purpose timer or `SYSTIMER` alarm, see `timer::AnyTimer`
+//! #[cfg(feature = "embassy-time-any-timer")]
+//! embassy::init(&clocks, timer_group1.timer0);
+//!
 //! let io = IO::new(peripherals.GPIO, peripherals.IO_MUX);
 //! // GPIO 9 as input
 //! let input = io.pins.gpio9.into_pull_down_input();
@@ -96,6 +100,7 @@ use embassy_time_driver::{AlarmHandle, Driver};
     all(timg0, feature = "embassy-time-timg0"),
     path = "time_driver_timg.rs"
 )]
+#[cfg_attr(feature = "embassy-time-any-timer", path = "time_driver_any.rs")]
 mod time_driver;
 
 use time_driver::EmbassyTimer;
@@ -103,8 +108,11 @@ use time_driver::EmbassyTimer;
 use crate::clock::Clocks;
 
 /// Initialise embassy
-pub fn init(clocks: &Clocks, td: time_driver::TimerType) {
-    EmbassyTimer::init(clocks, td)
+///
+/// With the `embassy-time-any-timer` feature, `td` is any timer which converts
+/// into an [AnyTimer](crate::timer::AnyTimer).
+pub fn init(clocks: &Clocks, td: impl Into<time_driver::TimerType>) {
+    EmbassyTimer::init(clocks, td.into())
 }
 
 pub struct AlarmState {
@@ -0,0 +1,92 @@
+use core::cell::RefCell;
+
+use critical_section::{CriticalSection, Mutex};
+use procmacros::handler;
+
+use super::AlarmState;
+use crate::{
+    clock::Clocks,
+    timer::{AnyTimer, FreeRunning, GenericTimer},
+};
+
+pub const ALARM_COUNT: usize = 1;
+
+pub type TimerType = AnyTimer;
+
+pub struct EmbassyTimer {
+    pub(crate) alarms: Mutex<[AlarmState; ALARM_COUNT]>,
+    timer: Mutex<RefCell<Option<AnyTimer>>>,
+}
+
+const ALARM_STATE_NONE: AlarmState = AlarmState::new();
+
+embassy_time_driver::time_driver_impl!(static DRIVER: EmbassyTimer = EmbassyTimer {
+    alarms: Mutex::new([ALARM_STATE_NONE; ALARM_COUNT]),
+    timer: Mutex::new(RefCell::new(None)),
+});
+
+impl EmbassyTimer {
+    pub(crate) fn now() -> u64 {
+        critical_section::with(|cs| {
+            DRIVER
+                .timer
+                .borrow_ref(cs)
+                .as_ref()
+                .map_or(0, |timer| timer.now_micros())
+        })
+    }
+
+    fn trigger_alarm(&self, n: usize, cs: CriticalSection) {
+        let alarm = &self.alarms.borrow(cs)[n];
+
+        if let Some((f, ctx)) = alarm.callback.get() {
+            f(ctx);
+        }
+    }
+
+    pub(super) fn on_alarm_allocated(&self, _n: usize) {}
+
+    fn on_interrupt(&self) {
+        critical_section::with(|cs| {
+            if let Some(timer) = self.timer.borrow_ref_mut(cs).as_mut() {
+                timer.clear_interrupt();
+            }
+            self.trigger_alarm(0, cs);
+        });
+    }
+
+    pub fn init(_clocks: &Clocks, mut timer: TimerType) {
+        timer.start_free_running();
+        let interrupt = timer.interrupt();
+        critical_section::with(|cs| DRIVER.timer.replace(cs, Some(timer)));
+
+        unsafe {
+            crate::interrupt::bind_interrupt(interrupt, timer_handler.handler());
+            unwrap!(crate::interrupt::enable(
+                interrupt,
+                timer_handler.priority()
+            ));
+        }
+
+        #[handler(priority = crate::interrupt::Priority::max())]
+        fn timer_handler() {
+            DRIVER.on_interrupt();
+        }
+    }
+
+    pub(crate) fn set_alarm(
+        &self,
+        _alarm: embassy_time_driver::AlarmHandle,
+        timestamp: u64,
+    ) -> bool {
+        critical_section::with(|cs| {
+            if let Some(timer) = self.timer.borrow_ref_mut(cs).as_mut() {
+                timer.arm_at(timestamp);
+            }
+        });
+
+        // Returning `false` for timestamps which already passed makes embassy
+        // poll once more, instead of relying on the alarm firing for them
+        Self::now() < timestamp
+    }
+}
@@ -60,19 +60,7 @@ impl<const C: u8> EtmChannel<C> {
         E: EtmEvent,
         T: EtmTask,
     {
-        let etm = unsafe { crate::peripherals::SOC_ETM::steal() };
-
-        etm.ch(C as usize)
-            .evt_id()
-            .modify(|_, w| unsafe { w.evt_id().bits(event.id()) });
-        etm.ch(C as usize)
-            .task_id()
-            .modify(|_, w| unsafe { w.task_id().bits(task.id()) });
-        if C < 32 {
-            etm.ch_ena_ad0_set().write(|w| w.ch_set(C).set_bit());
-        } else {
-            etm.ch_ena_ad1_set().write(|w| w.ch_set(C - 32).set_bit());
-        }
+        enable_channel(C, event.id(), task.id());
 
         EtmConfiguredChannel {
             _event: event,
@@ -81,7 +69,24 @@ impl<const C: u8> EtmChannel<C> {
     }
 }
 
-fn disable_channel(channel: u8) {
+pub(crate) fn enable_channel(channel: u8, event_id: u8, task_id: u8) {
+    let etm = unsafe { crate::peripherals::SOC_ETM::steal() };
+
+    etm.ch(channel as usize)
+        .evt_id()
+        .modify(|_, w| unsafe { w.evt_id().bits(event_id) });
+    etm.ch(channel as usize)
+        .task_id()
+        .modify(|_, w| unsafe { w.task_id().bits(task_id) });
+    if channel < 32 {
+        etm.ch_ena_ad0_set().write(|w| w.ch_set(channel).set_bit());
+    } else {
+        etm.ch_ena_ad1_set()
+            .write(|w| w.ch_set(channel - 32).set_bit());
+    }
+}
+
+pub(crate) fn disable_channel(channel: u8) {
     let etm = unsafe { crate::peripherals::SOC_ETM::steal() };
     if channel < 32 {
         etm.ch_ena_ad0_clr().write(|w| w.ch_clr(channel).set_bit());
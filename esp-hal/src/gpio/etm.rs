@@ -29,6 +29,15 @@
 
 use crate::peripheral::{Peripheral, PeripheralRef};
 
+// ETM event and task ids of channel 0, the ids of the other channels follow
+// consecutively
+const GPIO_EVT_CH0_RISE_EDGE: u8 = 1;
+const GPIO_EVT_CH0_FALL_EDGE: u8 = 9;
+const GPIO_EVT_CH0_ANY_EDGE: u8 = 17;
+const GPIO_TASK_CH0_SET: u8 = 1;
+const GPIO_TASK_CH0_CLEAR: u8 = 9;
+const GPIO_TASK_CH0_TOGGLE: u8 = 17;
+
 /// All the GPIO ETM channels
 #[non_exhaustive]
 pub struct GpioEtmChannels<'d> {
@@ -140,7 +149,7 @@ where
     PIN: super::Pin,
 {
     fn id(&self) -> u8 {
-        1 + C
+        GPIO_EVT_CH0_RISE_EDGE + C
     }
 }
 
@@ -163,7 +172,7 @@ where
     PIN: super::Pin,
 {
     fn id(&self) -> u8 {
-        9 + C
+        GPIO_EVT_CH0_FALL_EDGE + C
     }
 }
 
@@ -186,7 +195,7 @@ where
     PIN: super::Pin,
 {
     fn id(&self) -> u8 {
-        17 + C
+        GPIO_EVT_CH0_ANY_EDGE + C
     }
 }
 
@@ -252,7 +261,7 @@ where
     PIN: super::Pin,
 {
     fn id(&self) -> u8 {
-        1 + C
+        GPIO_TASK_CH0_SET + C
     }
 }
 
@@ -272,7 +281,7 @@ where
     PIN: super::Pin,
 {
     fn id(&self) -> u8 {
-        9 + C
+        GPIO_TASK_CH0_CLEAR + C
     }
 }
 
@@ -292,11 +301,11 @@ where
     PIN: super::Pin,
 {
     fn id(&self) -> u8 {
-        17 + C
+        GPIO_TASK_CH0_TOGGLE + C
     }
 }
 
-fn enable_task_channel(channel: u8, pin: u8) {
+pub(crate) fn enable_task_channel(channel: u8, pin: u8) {
     let gpio_sd = unsafe { crate::peripherals::GPIO_SD::steal() };
     let ptr = unsafe { gpio_sd.etm_task_p0_cfg().as_ptr().add(pin as usize / 4) };
     let shift = 8 * (pin as usize % 4);
@@ -308,6 +317,15 @@ fn enable_task_channel(channel: u8, pin: u8) {
     }
 }
 
+pub(crate) fn disable_task_channel(pin: u8) {
+    let gpio_sd = unsafe { crate::peripherals::GPIO_SD::steal() };
+    let ptr = unsafe { gpio_sd.etm_task_p0_cfg().as_ptr().add(pin as usize / 4) };
+    let shift = 8 * (pin as usize % 4);
+    unsafe {
+        ptr.write_volatile(ptr.read_volatile() & !(0xf << shift));
+    }
+}
+
 fn enable_event_channel(channel: u8, pin: u8) {
     let gpio_sd = unsafe { crate::peripherals::GPIO_SD::steal() };
     gpio_sd
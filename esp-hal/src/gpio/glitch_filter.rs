@@ -0,0 +1,141 @@
+//! # Flexible glitch filters
+//!
+//! ## Overview
+//!
+//! In addition to the fixed two-cycle filter of every pin (see
+//! [GpioPin::set_glitch_filter](super::GpioPin::set_glitch_filter)), the
+//! ESP32-C6 and ESP32-H2 provide eight glitch filters with a configurable
+//! window, each of which can be attached to any input pin.
+//!
+//! A filter samples the pin with the IO_MUX clock. A level change is only
+//! passed on to the GPIO matrix, and therefore to interrupts and peripherals,
+//! once the new level was sampled at least `threshold` times within a window
+//! of `width` clock cycles.
+//!
+//! The filters are handed out by [IO](super::IO). They only use their own
+//! registers of the GPIO_SD peripheral, which stays available for e.g.
+//! [GPIO ETM channels](super::etm::GpioEtmChannels).
+//!
+//! ## Example
+//! ```no_run
+//! let io = IO::new(peripherals.GPIO, peripherals.IO_MUX);
+//! let mut button = io.pins.gpio9.into_pull_up_input();
+//!
+//! let _filter = io
+//!     .glitch_filters
+//!     .channel0
+//!     .attach(&button, GlitchFilterConfig::new(64, 60));
+//!
+//! // interrupts of `button` now only see stable level changes
+//! ```
+
+use crate::gpio::InputPin;
+
+/// Window of a glitch filter, in IO_MUX clock cycles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct GlitchFilterConfig {
+    width: u8,
+    threshold: u8,
+}
+
+impl GlitchFilterConfig {
+    /// Pass on a level once it was sampled `threshold` times within a window
+    /// of `width` cycles.
+    ///
+    /// `width` is limited to 1..=64 and `threshold` to 1..=`width`.
+    pub fn new(width: u8, threshold: u8) -> Self {
+        let width = width.clamp(1, 64);
+        let threshold = threshold.clamp(1, width);
+
+        Self { width, threshold }
+    }
+}
+
+/// All the glitch filter channels
+#[non_exhaustive]
+pub struct GlitchFilters {
+    pub channel0: GlitchFilterChannel<0>,
+    pub channel1: GlitchFilterChannel<1>,
+    pub channel2: GlitchFilterChannel<2>,
+    pub channel3: GlitchFilterChannel<3>,
+    pub channel4: GlitchFilterChannel<4>,
+    pub channel5: GlitchFilterChannel<5>,
+    pub channel6: GlitchFilterChannel<6>,
+    pub channel7: GlitchFilterChannel<7>,
+}
+
+impl GlitchFilters {
+    pub(crate) fn new() -> Self {
+        Self {
+            channel0: GlitchFilterChannel {},
+            channel1: GlitchFilterChannel {},
+            channel2: GlitchFilterChannel {},
+            channel3: GlitchFilterChannel {},
+            channel4: GlitchFilterChannel {},
+            channel5: GlitchFilterChannel {},
+            channel6: GlitchFilterChannel {},
+            channel7: GlitchFilterChannel {},
+        }
+    }
+}
+
+/// An unused glitch filter
+pub struct GlitchFilterChannel<const C: u8> {}
+
+impl<const C: u8> GlitchFilterChannel<C> {
+    /// Attach the filter to `pin`.
+    ///
+    /// The pin is only borrowed to configure the filter, which stays attached
+    /// to it until the returned [GlitchFilter] is dropped.
+    pub fn attach(self, pin: &impl InputPin, config: GlitchFilterConfig) -> GlitchFilter<C> {
+        let pin = pin.number();
+
+        // bit 0 = en, bit 1-6 = input_io_num, bit 7-12 = window_thres,
+        // bit 13-18 = window_width
+        let value = 1
+            | (pin as u32) << 1
+            | (config.threshold as u32 - 1) << 7
+            | (config.width as u32 - 1) << 13;
+
+        let gpio_sd = unsafe { crate::peripherals::GPIO_SD::steal() };
+        gpio_sd
+            .glitch_filter_ch(C as usize)
+            .write(|w| unsafe { w.bits(value) });
+
+        GlitchFilter {
+            pin,
+            _channel: self,
+        }
+    }
+}
+
+/// A glitch filter attached to a pin
+///
+/// Dropping it detaches the filter again.
+pub struct GlitchFilter<const C: u8> {
+    pin: u8,
+    _channel: GlitchFilterChannel<C>,
+}
+
+impl<const C: u8> GlitchFilter<C> {
+    /// The number of the pin the filter is attached to.
+    pub fn pin(&self) -> u8 {
+        self.pin
+    }
+
+    /// Detach the filter, returning the unused channel.
+    pub fn detach(self) -> GlitchFilterChannel<C> {
+        // the channel is disabled on drop
+        GlitchFilterChannel {}
+    }
+}
+
+impl<const C: u8> Drop for GlitchFilter<C> {
+    fn drop(&mut self) {
+        let gpio_sd = unsafe { crate::peripherals::GPIO_SD::steal() };
+        gpio_sd
+            .glitch_filter_ch(C as usize)
+            .write(|w| unsafe { w.bits(0) });
+    }
+}
@@ -0,0 +1,360 @@
+//! # Matrix keypad scanning
+//!
+//! ## Overview
+//!
+//! A [Keypad] scans a matrix of keys wired between row and column pins. The
+//! rows are open drain outputs and the columns are inputs with pull-ups: one
+//! row at a time is pulled low, and the columns reading low tell which keys of
+//! that row are pressed. Using open drain rows means that pressing several
+//! keys of the same column can't short two rows driven to different levels.
+//!
+//! Each key is debounced by requiring it to read the same state for a number
+//! of consecutive scans (see [KeypadConfig::with_debounce_scans]) before a
+//! [KeyEvent] is reported.
+//!
+//! Without diodes in the matrix, pressing three keys on the corners of a
+//! rectangle makes the fourth corner read as pressed, too ("ghosting"). With
+//! ghosting detection enabled, presses aren't reported while the matrix shows
+//! such a rectangle, since it's impossible to tell which of the keys are
+//! actually pressed. Releases are always reported.
+//!
+//! [Keypad::poll] performs a single scan and is meant to be called
+//! periodically, [Keypad::next_event_blocking] scans until there's an event.
+//! With the `async` feature, [Keypad::next_event] waits for a key press using
+//! the GPIO interrupt while all keys are released, so no scanning happens while
+//! the keypad is idle.
+//!
+//! ## Example
+//! ```no_run
+//! let rows = [
+//!     io.pins.gpio0.into_open_drain_output().into(),
+//!     io.pins.gpio1.into_open_drain_output().into(),
+//! ];
+//! let cols = [
+//!     io.pins.gpio2.into_pull_up_input().into(),
+//!     io.pins.gpio3.into_pull_up_input().into(),
+//!     io.pins.gpio4.into_pull_up_input().into(),
+//! ];
+//!
+//! let mut keypad = Keypad::new(rows, cols, KeypadConfig::default(), &clocks);
+//!
+//! loop {
+//!     match keypad.next_event_blocking() {
+//!         KeyEvent::Pressed { row, col } => println!("{row}/{col} pressed"),
+//!         KeyEvent::Released { row, col } => println!("{row}/{col} released"),
+//!     }
+//! }
+//! ```
+
+use super::{AnyPin, Input, OpenDrain, Output, PullUp};
+use crate::{clock::Clocks, delay::Delay};
+
+/// A change of the debounced state of a key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum KeyEvent {
+    /// The key at the given row and column got pressed
+    Pressed { row: u8, col: u8 },
+    /// The key at the given row and column got released
+    Released { row: u8, col: u8 },
+}
+
+/// Keypad configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct KeypadConfig {
+    debounce_scans: u8,
+    scan_interval_us: u32,
+    settle_time_us: u32,
+    ghosting_detection: bool,
+}
+
+impl Default for KeypadConfig {
+    fn default() -> Self {
+        Self {
+            debounce_scans: 4,
+            scan_interval_us: 5_000,
+            settle_time_us: 5,
+            ghosting_detection: true,
+        }
+    }
+}
+
+impl KeypadConfig {
+    /// Number of consecutive scans a key has to read the same state before
+    /// the change is reported. `0` and `1` disable debouncing.
+    pub fn with_debounce_scans(mut self, scans: u8) -> Self {
+        self.debounce_scans = scans;
+        self
+    }
+
+    /// Time between two scans while waiting for an event, in microseconds.
+    ///
+    /// Together with the number of debounce scans this determines the debounce
+    /// time.
+    pub fn with_scan_interval_us(mut self, us: u32) -> Self {
+        self.scan_interval_us = us;
+        self
+    }
+
+    /// Time between pulling a row low and reading the columns, in
+    /// microseconds. Long wires or weak pull-ups may need more time.
+    pub fn with_settle_time_us(mut self, us: u32) -> Self {
+        self.settle_time_us = us;
+        self
+    }
+
+    /// Hold back presses while the matrix state is ambiguous.
+    pub fn with_ghosting_detection(mut self, enable: bool) -> Self {
+        self.ghosting_detection = enable;
+        self
+    }
+}
+
+/// A matrix keypad of up to 32 columns
+pub struct Keypad<const ROWS: usize, const COLS: usize> {
+    rows: [AnyPin<Output<OpenDrain>>; ROWS],
+    cols: [AnyPin<Input<PullUp>>; COLS],
+    config: KeypadConfig,
+    delay: Delay,
+    /// Debounced state, one bit per column
+    debounced: [u32; ROWS],
+    /// State last reported by an event, one bit per column
+    reported: [u32; ROWS],
+    /// Number of consecutive scans a key differed from its debounced state
+    counters: [[u8; COLS]; ROWS],
+}
+
+impl<const ROWS: usize, const COLS: usize> Keypad<ROWS, COLS> {
+    /// Create a new keypad from its row and column pins.
+    ///
+    /// All rows are released (driven high) until the first scan.
+    pub fn new(
+        mut rows: [AnyPin<Output<OpenDrain>>; ROWS],
+        cols: [AnyPin<Input<PullUp>>; COLS],
+        config: KeypadConfig,
+        clocks: &Clocks,
+    ) -> Self {
+        assert!(COLS <= 32, "A keypad can have at most 32 columns");
+
+        for row in rows.iter_mut() {
+            row.set_high();
+        }
+
+        Self {
+            rows,
+            cols,
+            config,
+            delay: Delay::new(clocks),
+            debounced: [0; ROWS],
+            reported: [0; ROWS],
+            counters: [[0; COLS]; ROWS],
+        }
+    }
+
+    /// Release the row and column pins.
+    pub fn free(
+        self,
+    ) -> (
+        [AnyPin<Output<OpenDrain>>; ROWS],
+        [AnyPin<Input<PullUp>>; COLS],
+    ) {
+        (self.rows, self.cols)
+    }
+
+    /// Is the (debounced) key at the given row and column pressed?
+    pub fn is_pressed(&self, row: usize, col: usize) -> bool {
+        self.debounced[row] & (1 << col) != 0
+    }
+
+    /// Does the debounced state contain keys which might be ghosts?
+    pub fn is_ghosting(&self) -> bool {
+        for (i, a) in self.debounced.iter().enumerate() {
+            for b in &self.debounced[i + 1..] {
+                if (a & b).count_ones() >= 2 {
+                    return true;
+                }
+            }
+        }
+
+        false
+    }
+
+    /// Scan the matrix once and return the next event, if there is one.
+    ///
+    /// Call this periodically; debouncing counts calls, so the debounce time
+    /// is the number of debounce scans times the polling interval. Pending
+    /// events are returned one per call.
+    pub fn poll(&mut self) -> Option<KeyEvent> {
+        self.scan();
+        self.pending_event()
+    }
+
+    /// Scan the matrix until there is an event, waiting for the configured
+    /// scan interval between scans.
+    pub fn next_event_blocking(&mut self) -> KeyEvent {
+        loop {
+            if let Some(event) = self.poll() {
+                return event;
+            }
+
+            self.delay.delay_micros(self.config.scan_interval_us);
+        }
+    }
+
+    fn scan(&mut self) {
+        let debounce_scans = self.config.debounce_scans.max(1);
+
+        for r in 0..ROWS {
+            self.rows[r].set_low();
+            self.delay.delay_micros(self.config.settle_time_us);
+
+            let mut raw = 0u32;
+            for (c, col) in self.cols.iter().enumerate() {
+                if col.is_low() {
+                    raw |= 1 << c;
+                }
+            }
+
+            self.rows[r].set_high();
+
+            let changed = raw ^ self.debounced[r];
+            for c in 0..COLS {
+                let counter = &mut self.counters[r][c];
+                if changed & (1 << c) == 0 {
+                    *counter = 0;
+                    continue;
+                }
+
+                *counter += 1;
+                if *counter >= debounce_scans {
+                    *counter = 0;
+                    self.debounced[r] ^= 1 << c;
+                }
+            }
+        }
+    }
+
+    fn pending_event(&mut self) -> Option<KeyEvent> {
+        // report releases first, so a press hidden by ghosting can come through
+        // as soon as possible
+        for r in 0..ROWS {
+            let released = self.reported[r] & !self.debounced[r];
+            if released != 0 {
+                let col = released.trailing_zeros();
+                self.reported[r] &= !(1 << col);
+                return Some(KeyEvent::Released {
+                    row: r as u8,
+                    col: col as u8,
+                });
+            }
+        }
+
+        if self.config.ghosting_detection && self.is_ghosting() {
+            return None;
+        }
+
+        for r in 0..ROWS {
+            let pressed = self.debounced[r] & !self.reported[r];
+            if pressed != 0 {
+                let col = pressed.trailing_zeros();
+                self.reported[r] |= 1 << col;
+                return Some(KeyEvent::Pressed {
+                    row: r as u8,
+                    col: col as u8,
+                });
+            }
+        }
+
+        None
+    }
+
+    #[cfg(feature = "async")]
+    fn is_idle(&self) -> bool {
+        self.debounced.iter().all(|row| *row == 0)
+            && self.reported.iter().all(|row| *row == 0)
+            && self
+                .counters
+                .iter()
+                .all(|row| row.iter().all(|counter| *counter == 0))
+    }
+}
+
+#[cfg(feature = "async")]
+mod asynch {
+    use core::task::{Context, Poll};
+
+    use embedded_hal_async::delay::DelayNs;
+
+    use super::*;
+    use crate::gpio::{asynch::PIN_WAKERS, Event, Pin};
+
+    impl<const ROWS: usize, const COLS: usize> Keypad<ROWS, COLS> {
+        /// Wait for the next event.
+        ///
+        /// While all keys are released, all rows are pulled low and the task
+        /// sleeps until a column reads low. Otherwise the matrix is scanned
+        /// using `delay` to wait for the configured scan interval.
+        pub async fn next_event(&mut self, delay: &mut impl DelayNs) -> KeyEvent {
+            loop {
+                if let Some(event) = self.poll() {
+                    return event;
+                }
+
+                if self.is_idle() {
+                    for row in self.rows.iter_mut() {
+                        row.set_low();
+                    }
+
+                    AnyColumnLowFuture::new(&mut self.cols).await;
+
+                    for row in self.rows.iter_mut() {
+                        row.set_high();
+                    }
+                } else {
+                    delay.delay_us(self.config.scan_interval_us).await;
+                }
+            }
+        }
+    }
+
+    #[must_use = "futures do nothing unless you `.await` or poll them"]
+    struct AnyColumnLowFuture<'a, const COLS: usize> {
+        cols: &'a mut [AnyPin<Input<PullUp>>; COLS],
+    }
+
+    impl<'a, const COLS: usize> AnyColumnLowFuture<'a, COLS> {
+        fn new(cols: &'a mut [AnyPin<Input<PullUp>>; COLS]) -> Self {
+            for col in cols.iter_mut() {
+                col.listen(Event::LowLevel);
+            }
+
+            Self { cols }
+        }
+    }
+
+    impl<'a, const COLS: usize> core::future::Future for AnyColumnLowFuture<'a, COLS> {
+        type Output = ();
+
+        fn poll(self: core::pin::Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            for col in self.cols.iter() {
+                PIN_WAKERS[col.number() as usize].register(cx.waker());
+            }
+
+            // the interrupt handler stops listening on pins that triggered
+            if self.cols.iter().any(|col| !col.is_listening()) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl<'a, const COLS: usize> Drop for AnyColumnLowFuture<'a, COLS> {
+        fn drop(&mut self) {
+            for col in self.cols.iter_mut() {
+                col.unlisten();
+            }
+        }
+    }
+}
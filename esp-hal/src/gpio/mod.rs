@@ -20,6 +20,36 @@
 //! let mut led = io.pins.gpio5.into_push_pull_output();
 //! ```
 //!
+//! Pins can be type erased into an [AnyPin], which all drivers accept in place
+//! of a concrete pin. To choose pins at runtime, e.g. from configuration data,
+//! [Pins::into_any_pins] type erases all pins at once:
+//!
+//! ```no_run
+//! let mut pins = io.pins.into_any_pins();
+//! let tx = pins[config.uart_tx as usize].take().unwrap();
+//! let rx = pins[config.uart_rx as usize].take().unwrap();
+//!
+//! let uart = Uart::new_with_config(
+//!     peripherals.UART1,
+//!     Config::default(),
+//!     Some(TxRxPins::new_tx_rx(tx, rx)),
+//!     &clocks,
+//!     None,
+//! );
+//! ```
+//!
+//! Peripheral signals can be chosen at runtime as well, [InputSignal] and
+//! [OutputSignal] are created from their number with `TryFrom<u16>`:
+//!
+//! ```no_run
+//! let signal = OutputSignal::try_from(config.led_signal).unwrap();
+//! let mut led = pins[config.led as usize]
+//!     .take()
+//!     .unwrap()
+//!     .into_push_pull_output();
+//! led.connect_peripheral_to_output(signal);
+//! ```
+//!
 //! [embedded-hal]: https://docs.rs/embedded-hal/latest/embedded_hal/
 
 use core::{cell::Cell, marker::PhantomData};
@@ -28,19 +58,26 @@ use critical_section::Mutex;
 
 #[cfg(any(adc, dac))]
 pub(crate) use crate::analog;
-pub(crate) use crate::gpio;
 #[cfg(any(xtensa, esp32c3))]
 pub(crate) use crate::rtc_pins;
 pub use crate::soc::gpio::*;
+pub(crate) use crate::{gpio, signals};
 use crate::{
-    interrupt::InterruptHandler,
+    interrupt::{InterruptHandler, Priority},
     peripherals::{GPIO, IO_MUX},
 };
 
 #[cfg(soc_etm)]
 pub mod etm;
+#[cfg(any(esp32c6, esp32h2))]
+pub mod glitch_filter;
+#[cfg(any(xtensa, all(riscv, systimer)))]
+pub mod keypad;
 #[cfg(lp_io)]
 pub mod lp_io;
+#[cfg(any(dport, hp_sys, pcr, system))]
+pub mod one_wire;
+pub mod port;
 #[cfg(all(rtc_io, not(esp32)))]
 pub mod rtc_io;
 
@@ -52,6 +89,22 @@ pub const NO_PIN: Option<NoPinType> = None;
 
 static USER_INTERRUPT_HANDLER: Mutex<Cell<Option<InterruptHandler>>> = Mutex::new(Cell::new(None));
 
+/// Handler for the interrupt of a single pin, called with the number of the
+/// pin which triggered it.
+pub type PinInterruptHandler = fn(u8);
+
+#[derive(Clone, Copy)]
+struct PinHandler {
+    handler: PinInterruptHandler,
+    priority: Priority,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const NO_PIN_HANDLER: Mutex<Cell<Option<PinHandler>>> = Mutex::new(Cell::new(None));
+static PIN_HANDLERS: [Mutex<Cell<Option<PinHandler>>>; NUM_PINS] = [NO_PIN_HANDLER; NUM_PINS];
+
+static GPIO_PRIORITY: Mutex<Cell<Priority>> = Mutex::new(Cell::new(Priority::min()));
+
 #[derive(Copy, Clone)]
 pub enum Event {
     RisingEdge  = 1,
@@ -148,6 +201,9 @@ pub struct AF1;
 #[doc(hidden)]
 pub struct AF2;
 
+/// Drive strength of an output pin
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum DriveStrength {
     I5mA  = 0,
     I10mA = 1,
@@ -545,6 +601,17 @@ impl PinType for InputOnlyAnalogPinType {}
 impl IsInputPin for InputOnlyAnalogPinType {}
 impl IsAnalogPin for InputOnlyAnalogPinType {}
 
+/// The pin type of a fully type erased [AnyPin], i.e. `AnyPin<MODE>`.
+///
+/// The capabilities of such a pin are only checked at runtime: using an input
+/// only pin as an output panics.
+#[doc(hidden)]
+pub struct ErasedPinType;
+
+impl PinType for ErasedPinType {}
+impl IsOutputPin for ErasedPinType {}
+impl IsInputPin for ErasedPinType {}
+
 pub struct GpioPin<MODE, const GPIONUM: u8> {
     _mode: PhantomData<MODE>,
 }
@@ -694,6 +761,23 @@ where
     }
 }
 
+#[cfg(not(esp32))]
+impl<MODE, const GPIONUM: u8> GpioPin<MODE, GPIONUM>
+where
+    Self: GpioProperties,
+    MODE: InputMode,
+{
+    /// Enable or disable the glitch filter of this pin.
+    ///
+    /// The filter suppresses input pulses shorter than two IO_MUX clock
+    /// cycles. See the `glitch_filter` module for filters with a configurable
+    /// window on chips which have them.
+    pub fn set_glitch_filter(&mut self, enable: bool) -> &mut Self {
+        get_io_mux_reg(GPIONUM).modify(|_, w| w.filter_en().bit(enable));
+        self
+    }
+}
+
 impl<MODE, const GPIONUM: u8> InputPin for GpioPin<MODE, GPIONUM>
 where
     Self: GpioProperties,
@@ -923,6 +1007,46 @@ where
             self.set_high();
         }
     }
+
+    /// Set the drive strength of the pin.
+    pub fn set_drive_strength(&mut self, strength: DriveStrength) {
+        get_io_mux_reg(GPIONUM).modify(|_, w| unsafe { w.fun_drv().bits(strength as u8) });
+    }
+
+    /// The current drive strength of the pin.
+    pub fn drive_strength(&self) -> DriveStrength {
+        match get_io_mux_reg(GPIONUM).read().fun_drv().bits() {
+            0 => DriveStrength::I5mA,
+            1 => DriveStrength::I10mA,
+            2 => DriveStrength::I20mA,
+            _ => DriveStrength::I40mA,
+        }
+    }
+
+    /// Switch the pin between open drain and push pull operation, keeping its
+    /// output level and drive strength.
+    ///
+    /// This only changes the pad driver; the pin keeps the type it was created
+    /// with. In open drain operation the input stays enabled, so the actual
+    /// level of the line can be read back via the GPIO input register.
+    pub fn set_open_drain(&mut self, open_drain: bool) {
+        unsafe { &*GPIO::PTR }
+            .pin(GPIONUM as usize)
+            .modify(|_, w| w.pad_driver().bit(open_drain));
+
+        if open_drain {
+            get_io_mux_reg(GPIONUM).modify(|_, w| w.fun_ie().set_bit());
+        }
+    }
+
+    /// Is the pin operating as an open drain output?
+    pub fn is_open_drain(&self) -> bool {
+        unsafe { &*GPIO::PTR }
+            .pin(GPIONUM as usize)
+            .read()
+            .pad_driver()
+            .bit_is_set()
+    }
 }
 
 impl<MODE, const GPIONUM: u8> crate::peripheral::Peripheral for GpioPin<MODE, GPIONUM>
@@ -1351,18 +1475,28 @@ where
     }
 }
 
-impl<MODE, TYPE> From<AnyPin<MODE, TYPE>> for AnyPin<MODE>
-where
-    TYPE: PinType,
-{
-    fn from(pin: AnyPin<MODE, TYPE>) -> Self {
-        Self {
-            inner: pin.inner,
-            _type: core::marker::PhantomData,
-        }
-    }
+macro_rules! impl_from_typed_any_pin {
+    ($($type:ty),+) => {
+        $(
+            impl<MODE> From<AnyPin<MODE, $type>> for AnyPin<MODE> {
+                fn from(pin: AnyPin<MODE, $type>) -> Self {
+                    Self {
+                        inner: pin.inner,
+                        _type: core::marker::PhantomData,
+                    }
+                }
+            }
+        )+
+    };
 }
 
+impl_from_typed_any_pin!(
+    InputOutputPinType,
+    InputOnlyPinType,
+    InputOutputAnalogPinType,
+    InputOnlyAnalogPinType
+);
+
 impl<MODE, TYPE> crate::peripheral::Peripheral for AnyPin<MODE, TYPE>
 where
     TYPE: PinType,
@@ -1396,6 +1530,71 @@ where
     }
 }
 
+impl<MODE, TYPE> AnyPin<MODE, TYPE>
+where
+    TYPE: IsInputPin,
+{
+    /// Configures the pin to operate as a floating input pin
+    pub fn into_floating_input(self) -> AnyPin<Input<Floating>, TYPE> {
+        let inner = self.inner;
+        let pin: AnyPin<_> =
+            handle_gpio_input!(inner, target, { target.into_floating_input().into() });
+        AnyPin {
+            inner: pin.inner,
+            _type: PhantomData,
+        }
+    }
+
+    /// Configures the pin to operate as a pulled up input pin
+    pub fn into_pull_up_input(self) -> AnyPin<Input<PullUp>, TYPE> {
+        let inner = self.inner;
+        let pin: AnyPin<_> =
+            handle_gpio_input!(inner, target, { target.into_pull_up_input().into() });
+        AnyPin {
+            inner: pin.inner,
+            _type: PhantomData,
+        }
+    }
+
+    /// Configures the pin to operate as a pulled down input pin
+    pub fn into_pull_down_input(self) -> AnyPin<Input<PullDown>, TYPE> {
+        let inner = self.inner;
+        let pin: AnyPin<_> =
+            handle_gpio_input!(inner, target, { target.into_pull_down_input().into() });
+        AnyPin {
+            inner: pin.inner,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<MODE, TYPE> AnyPin<MODE, TYPE>
+where
+    TYPE: IsOutputPin,
+{
+    /// Configures the pin to operate as an push pull output pin
+    pub fn into_push_pull_output(self) -> AnyPin<Output<PushPull>, TYPE> {
+        let inner = self.inner;
+        let pin: AnyPin<_> =
+            handle_gpio_output!(inner, target, { target.into_push_pull_output().into() });
+        AnyPin {
+            inner: pin.inner,
+            _type: PhantomData,
+        }
+    }
+
+    /// Configures the pin to operate as an open drain output pin
+    pub fn into_open_drain_output(self) -> AnyPin<Output<OpenDrain>, TYPE> {
+        let inner = self.inner;
+        let pin: AnyPin<_> =
+            handle_gpio_output!(inner, target, { target.into_open_drain_output().into() });
+        AnyPin {
+            inner: pin.inner,
+            _type: PhantomData,
+        }
+    }
+}
+
 impl<MODE> AnyPin<MODE, InputOutputPinType> {
     pub fn into_input_type(self) -> AnyPin<MODE, InputOnlyPinType> {
         AnyPin {
@@ -1758,6 +1957,34 @@ impl<MODE, TYPE> AnyPin<Output<MODE>, TYPE> {
         let inner = &mut self.inner;
         handle_gpio_output!(inner, target, { target.toggle() })
     }
+
+    /// Set the drive strength of the pin.
+    pub fn set_drive_strength(&mut self, strength: DriveStrength) {
+        let inner = &mut self.inner;
+        handle_gpio_output!(inner, target, { target.set_drive_strength(strength) })
+    }
+
+    /// The current drive strength of the pin.
+    pub fn drive_strength(&self) -> DriveStrength {
+        let inner = &self.inner;
+        handle_gpio_output!(inner, target, { target.drive_strength() })
+    }
+
+    /// Switch the pin between open drain and push pull operation, keeping its
+    /// output level and drive strength.
+    ///
+    /// This only changes the pad driver; the pin keeps the type it was created
+    /// with.
+    pub fn set_open_drain(&mut self, open_drain: bool) {
+        let inner = &mut self.inner;
+        handle_gpio_output!(inner, target, { target.set_open_drain(open_drain) })
+    }
+
+    /// Is the pin operating as an open drain output?
+    pub fn is_open_drain(&self) -> bool {
+        let inner = &self.inner;
+        handle_gpio_output!(inner, target, { target.is_open_drain() })
+    }
 }
 
 #[cfg(feature = "async")]
@@ -1794,10 +2021,62 @@ impl<MODE, TYPE> AnyPin<Input<MODE>, TYPE> {
     }
 }
 
+impl<TYPE> AnyPin<Output<OpenDrain>, TYPE> {
+    /// Is the input pin high?
+    #[inline]
+    pub fn is_high(&self) -> bool {
+        let inner = &self.inner;
+        handle_gpio_output!(inner, target, { target.is_high() })
+    }
+
+    /// Is the input pin low?
+    #[inline]
+    pub fn is_low(&self) -> bool {
+        !self.is_high()
+    }
+}
+
+#[cfg(feature = "async")]
+impl<TYPE> AnyPin<Output<OpenDrain>, TYPE> {
+    /// Wait until the pin is high. If it is already high, return immediately.
+    pub async fn wait_for_high(&mut self) {
+        let inner = &mut self.inner;
+        handle_gpio_output!(inner, target, { target.wait_for_high().await })
+    }
+
+    /// Wait until the pin is low. If it is already low, return immediately.
+    pub async fn wait_for_low(&mut self) {
+        let inner = &mut self.inner;
+        handle_gpio_output!(inner, target, { target.wait_for_low().await })
+    }
+
+    /// Wait for the pin to undergo a transition from low to high.
+    pub async fn wait_for_rising_edge(&mut self) {
+        let inner = &mut self.inner;
+        handle_gpio_output!(inner, target, { target.wait_for_rising_edge().await })
+    }
+
+    /// Wait for the pin to undergo a transition from high to low.
+    pub async fn wait_for_falling_edge(&mut self) {
+        let inner = &mut self.inner;
+        handle_gpio_output!(inner, target, { target.wait_for_falling_edge().await })
+    }
+
+    /// Wait for the pin to undergo any transition, i.e low to high OR high to
+    /// low.
+    pub async fn wait_for_any_edge(&mut self) {
+        let inner = &mut self.inner;
+        handle_gpio_output!(inner, target, { target.wait_for_any_edge().await })
+    }
+}
+
 /// General Purpose Input/Output driver
 pub struct IO {
     _io_mux: IO_MUX,
     pub pins: Pins,
+    /// The flexible glitch filters
+    #[cfg(any(esp32c6, esp32h2))]
+    pub glitch_filters: glitch_filter::GlitchFilters,
 }
 
 impl IO {
@@ -1815,33 +2094,116 @@ impl IO {
         prio: crate::interrupt::Priority,
     ) -> Self {
         gpio.bind_gpio_interrupt(gpio_interrupt_handler);
-        crate::interrupt::enable(crate::peripherals::Interrupt::GPIO, prio).unwrap();
+        set_gpio_priority(prio);
 
         let pins = gpio.split();
 
         IO {
             _io_mux: io_mux,
             pins,
+            #[cfg(any(esp32c6, esp32h2))]
+            glitch_filters: glitch_filter::GlitchFilters::new(),
         }
     }
 
     /// Install the given interrupt handler replacing any previously set
     /// handler.
     ///
-    /// When the async feature is enabled the handler will be called first and
-    /// the internal async handler will run after. In that case it's
-    /// important to not reset the interrupt status when mixing sync and
-    /// async (i.e. using async wait) interrupt handling.
+    /// The handler runs after the handlers set with
+    /// [Self::set_pins_interrupt_handler], and doesn't see the pins those
+    /// handled. When the async feature is enabled the internal async handler
+    /// runs after it. In that case it's important to not reset the interrupt
+    /// status when mixing sync and async (i.e. using async wait) interrupt
+    /// handling.
+    ///
+    /// Like with the per-pin handlers, the priority of the GPIO interrupt is
+    /// only raised to the priority of `handler`, never lowered, as it's shared
+    /// by all of them.
     pub fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
         critical_section::with(|cs| {
-            crate::interrupt::enable(crate::peripherals::Interrupt::GPIO, handler.priority())
-                .unwrap();
+            if handler.priority() as u8 > GPIO_PRIORITY.borrow(cs).get() as u8 {
+                set_gpio_priority(handler.priority());
+            }
             USER_INTERRUPT_HANDLER.borrow(cs).set(Some(handler));
         });
     }
+
+    /// Install `handler` for the interrupt of `pin`, replacing any handler
+    /// previously set for it.
+    ///
+    /// See [Self::set_pins_interrupt_handler].
+    pub fn set_pin_interrupt_handler(
+        &mut self,
+        pin: &impl Pin,
+        priority: Priority,
+        handler: PinInterruptHandler,
+    ) {
+        self.set_pins_interrupt_handler(&[pin as &dyn Pin], priority, handler);
+    }
+
+    /// Install `handler` for the interrupts of all of `pins`, replacing any
+    /// handlers previously set for them.
+    ///
+    /// The handler is called with the number of the pin which triggered, after
+    /// its interrupt status got cleared. Interrupts still need to be enabled
+    /// with [Pin::listen]; handlers of level triggered interrupts have to stop
+    /// listening or change the level, otherwise they are called again right
+    /// away.
+    ///
+    /// All pins share the GPIO interrupt, which runs at the highest priority
+    /// of all handlers. When several pins are pending, their handlers are
+    /// called in the order of their priority, highest first. Pins with a
+    /// handler aren't seen by the handler set with
+    /// [Self::set_interrupt_handler] (their status is cleared before), nor
+    /// by async waits.
+    pub fn set_pins_interrupt_handler(
+        &mut self,
+        pins: &[&dyn Pin],
+        priority: Priority,
+        handler: PinInterruptHandler,
+    ) {
+        critical_section::with(|cs| {
+            for pin in pins {
+                PIN_HANDLERS[pin.number() as usize]
+                    .borrow(cs)
+                    .set(Some(PinHandler { handler, priority }));
+            }
+
+            if priority as u8 > GPIO_PRIORITY.borrow(cs).get() as u8 {
+                set_gpio_priority(priority);
+            }
+        });
+    }
+
+    /// Remove the handler installed for the interrupt of `pin`.
+    pub fn clear_pin_interrupt_handler(&mut self, pin: &impl Pin) {
+        critical_section::with(|cs| {
+            PIN_HANDLERS[pin.number() as usize].borrow(cs).set(None);
+        });
+    }
+}
+
+fn set_gpio_priority(priority: Priority) {
+    critical_section::with(|cs| {
+        crate::interrupt::enable(crate::peripherals::Interrupt::GPIO, priority).unwrap();
+        GPIO_PRIORITY.borrow(cs).set(priority);
+    });
 }
 
 extern "C" fn gpio_interrupt_handler() {
+    // the per-pin handlers come first, the pins they handle are cleared before
+    // the handler set with `IO::set_interrupt_handler` runs
+    dispatch_pin_interrupts::<Bank0GpioRegisterAccess>(
+        InterruptStatusRegisterAccessBank0::interrupt_status_read(),
+        0,
+    );
+
+    #[cfg(any(esp32, esp32s2, esp32s3, esp32p4))]
+    dispatch_pin_interrupts::<Bank1GpioRegisterAccess>(
+        InterruptStatusRegisterAccessBank1::interrupt_status_read(),
+        32,
+    );
+
     if let Some(user_handler) = critical_section::with(|cs| USER_INTERRUPT_HANDLER.borrow(cs).get())
     {
         user_handler.call();
@@ -1851,6 +2213,44 @@ extern "C" fn gpio_interrupt_handler() {
     asynch::handle_gpio_interrupt();
 }
 
+/// Call the handlers of the pending pins of a bank, by descending priority.
+fn dispatch_pin_interrupts<BANK: BankGpioRegisterAccess>(status: u32, first_pin: u8) {
+    let mut pending = status;
+
+    loop {
+        let mut next: Option<(u32, PinHandler)> = None;
+
+        let mut bits = pending;
+        while bits != 0 {
+            let bit = bits.trailing_zeros();
+            bits &= !(1 << bit);
+
+            let handler = critical_section::with(|cs| {
+                PIN_HANDLERS[(first_pin as u32 + bit) as usize]
+                    .borrow(cs)
+                    .get()
+            });
+
+            match handler {
+                None => pending &= !(1 << bit),
+                Some(handler) => {
+                    if next.map_or(true, |(_, n)| handler.priority as u8 > n.priority as u8) {
+                        next = Some((bit, handler));
+                    }
+                }
+            }
+        }
+
+        let Some((bit, handler)) = next else {
+            break;
+        };
+
+        pending &= !(1 << bit);
+        BANK::write_interrupt_status_clear(1 << bit);
+        (handler.handler)(first_pin + bit as u8);
+    }
+}
+
 pub trait GpioProperties {
     type Bank: BankGpioRegisterAccess;
     type InterruptStatus: InterruptStatusRegisterAccess;
@@ -1935,6 +2335,23 @@ macro_rules! gpio {
                 )+
             }
 
+            impl Pins {
+                /// Type erase all pins, indexed by their GPIO number.
+                ///
+                /// This allows picking pins at runtime, e.g. from configuration
+                /// data. The fully type erased pins can be passed to any driver.
+                pub fn into_any_pins(self) -> [Option<AnyPin<Unknown>>; $crate::gpio::NUM_PINS] {
+                    let mut pins: [Option<AnyPin<Unknown>>; $crate::gpio::NUM_PINS] =
+                        core::array::from_fn(|_| None);
+
+                    $(
+                        pins[$gpionum] = Some(self.[< gpio $gpionum >].into());
+                    )+
+
+                    pins
+                }
+            }
+
             $(
                 pub type [<Gpio $gpionum >]<MODE> = GpioPin<MODE, $gpionum>;
             )+
@@ -1945,7 +2362,7 @@ macro_rules! gpio {
                 )+
             }
 
-            pub struct AnyPin<MODE, TYPE = ()> {
+            pub struct AnyPin<MODE, TYPE = $crate::gpio::ErasedPinType> {
                 pub(crate) inner: ErasedPin<MODE>,
                 pub(crate) _type: core::marker::PhantomData<TYPE>,
             }
@@ -2254,6 +2671,38 @@ macro_rules! analog {
     }
 }
 
+/// Defines a peripheral signal enum, which can also be created from the
+/// signal's number, e.g. one read from configuration data.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! signals {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variant:ident $(= $value:expr)?),* $(,)?
+        }
+    ) => {
+        $(#[$meta])*
+        $vis enum $name {
+            $($variant $(= $value)?,)*
+        }
+
+        impl TryFrom<u16> for $name {
+            type Error = ();
+
+            fn try_from(value: u16) -> Result<Self, Self::Error> {
+                $(
+                    if value == Self::$variant as u16 {
+                        return Ok(Self::$variant);
+                    }
+                )*
+
+                Err(())
+            }
+        }
+    };
+}
+
 #[cfg(feature = "async")]
 mod asynch {
     use core::task::{Context, Poll};
@@ -2264,7 +2713,7 @@ mod asynch {
 
     #[allow(clippy::declare_interior_mutable_const)]
     const NEW_AW: AtomicWaker = AtomicWaker::new();
-    static PIN_WAKERS: [AtomicWaker; NUM_PINS] = [NEW_AW; NUM_PINS];
+    pub(super) static PIN_WAKERS: [AtomicWaker; NUM_PINS] = [NEW_AW; NUM_PINS];
 
     impl<MODE, const GPIONUM: u8> GpioPin<Input<MODE>, GPIONUM>
     where
@@ -2365,6 +2814,17 @@ mod asynch {
         }
     }
 
+    impl<'a, P> Drop for PinFuture<'a, P>
+    where
+        P: crate::gpio::Pin,
+    {
+        fn drop(&mut self) {
+            // Don't leave the interrupt enabled if the wait got cancelled, e.g.
+            // by a timeout.
+            self.pin.unlisten();
+        }
+    }
+
     pub(crate) fn set_int_enable(
         gpio_num: u8,
         int_ena: u8,
@@ -2716,6 +3176,33 @@ mod embedded_hal_async_impls {
         }
     }
 
+    impl<TYPE> embedded_hal_async::digital::Wait for AnyPin<Output<OpenDrain>, TYPE> {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_high().await;
+            Ok(())
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_low().await;
+            Ok(())
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_rising_edge().await;
+            Ok(())
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_falling_edge().await;
+            Ok(())
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_any_edge().await;
+            Ok(())
+        }
+    }
+
     impl<MODE, TYPE> embedded_hal_async::digital::Wait for AnyPin<Input<MODE>, TYPE> {
         async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
             self.wait_for_high().await;
@@ -0,0 +1,225 @@
+//! # Single wire open drain bus
+//!
+//! ## Overview
+//!
+//! [OneWire] drives a bus with a single open drain pin and an external pull-up,
+//! as used by 1-Wire devices (e.g. DS18B20 temperature sensors) and similar
+//! single wire protocols.
+//!
+//! Besides the 1-Wire reset, bit and byte transfers (standard speed), it
+//! provides the primitives needed to implement other protocols: pulling the
+//! bus low, releasing it, waiting for it to go high and sampling it at precise
+//! points in time. Timing is based on the CPU cycle counter (see
+//! [crate::cycle_counter]), so delays are accurate to a fraction of a
+//! microsecond. Time slots are executed in a critical section, so interrupts
+//! can't stretch them.
+//!
+//! ## Example
+//! ```no_run
+//! let pin = io.pins.gpio4.into_open_drain_output().into();
+//! let mut bus = OneWire::new(pin, &clocks);
+//!
+//! if bus.reset()? {
+//!     // skip ROM, convert temperature
+//!     bus.write_bytes(&[0xcc, 0x44]);
+//! }
+//! ```
+
+use super::{AnyPin, OpenDrain, Output};
+use crate::{clock::Clocks, cycle_counter::CycleCounter};
+
+// Standard speed timings in microseconds, see Maxim application note 126.
+const WRITE_1_LOW: u32 = 6;
+const WRITE_1_RELEASE: u32 = 64;
+const WRITE_0_LOW: u32 = 60;
+const WRITE_0_RELEASE: u32 = 10;
+const READ_SAMPLE: u32 = 9;
+const READ_RELEASE: u32 = 55;
+const RESET_LOW: u32 = 480;
+const RESET_SAMPLE: u32 = 70;
+const RESET_RELEASE: u32 = 410;
+
+/// Time the bus may take to be pulled up before it's considered stuck low
+const BUS_RELEASE_TIMEOUT: u32 = 250;
+
+/// Single wire bus errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The bus didn't go high within the timeout, e.g. because it is shorted
+    /// to ground or the pull-up is missing
+    BusHeldLow,
+}
+
+/// A single wire bus on an open drain pin
+pub struct OneWire {
+    pin: AnyPin<Output<OpenDrain>>,
+    cycles_per_us: u32,
+}
+
+impl OneWire {
+    /// Create a new bus, releasing the pin.
+    ///
+    /// The timing is based on the CPU clock at the time of creation.
+    pub fn new(mut pin: AnyPin<Output<OpenDrain>>, clocks: &Clocks) -> Self {
+        CycleCounter::new(clocks);
+        pin.set_high();
+
+        Self {
+            pin,
+            cycles_per_us: clocks.cpu_clock.to_MHz(),
+        }
+    }
+
+    /// Return the pin.
+    pub fn free(self) -> AnyPin<Output<OpenDrain>> {
+        self.pin
+    }
+
+    /// Stop driving the bus, so it's pulled up.
+    #[inline]
+    pub fn release(&mut self) {
+        self.pin.set_high();
+    }
+
+    /// Drive the bus low.
+    #[inline]
+    pub fn pull_low(&mut self) {
+        self.pin.set_low();
+    }
+
+    /// Is the bus high?
+    #[inline]
+    pub fn is_high(&self) -> bool {
+        self.pin.is_high()
+    }
+
+    /// Wait up to `timeout_us` microseconds for the bus to go high.
+    ///
+    /// Returns the time waited in microseconds.
+    pub fn wait_for_high(&self, timeout_us: u32) -> Result<u32, Error> {
+        let start = CycleCounter::raw();
+        let timeout = timeout_us * self.cycles_per_us;
+
+        loop {
+            let elapsed = CycleCounter::raw().wrapping_sub(start);
+            if self.pin.is_high() {
+                return Ok(elapsed / self.cycles_per_us);
+            }
+
+            if elapsed >= timeout {
+                return Err(Error::BusHeldLow);
+            }
+        }
+    }
+
+    /// Busy wait for the given number of microseconds.
+    #[inline]
+    pub fn delay_us(&self, us: u32) {
+        self.wait_until(CycleCounter::raw(), us);
+    }
+
+    /// Pull the bus low for the given number of microseconds, then release
+    /// it.
+    pub fn pulse_low(&mut self, us: u32) {
+        critical_section::with(|_| {
+            let start = CycleCounter::raw();
+            self.pull_low();
+            self.wait_until(start, us);
+            self.release();
+        })
+    }
+
+    /// Pull the bus low for `low_us` microseconds, release it and sample it
+    /// `sample_us` microseconds after the start of the pulse.
+    pub fn pulse_and_sample(&mut self, low_us: u32, sample_us: u32) -> bool {
+        critical_section::with(|_| {
+            let start = CycleCounter::raw();
+            self.pull_low();
+            self.wait_until(start, low_us);
+            self.release();
+            self.wait_until(start, sample_us);
+            self.pin.is_high()
+        })
+    }
+
+    /// Send a 1-Wire reset pulse.
+    ///
+    /// Returns whether any device answered with a presence pulse.
+    pub fn reset(&mut self) -> Result<bool, Error> {
+        self.wait_for_high(BUS_RELEASE_TIMEOUT)?;
+
+        // the reset pulse only has a minimum length, so interrupts don't hurt
+        self.pull_low();
+        self.delay_us(RESET_LOW);
+
+        let present = critical_section::with(|_| {
+            let start = CycleCounter::raw();
+            self.release();
+            self.wait_until(start, RESET_SAMPLE);
+            self.pin.is_low()
+        });
+        self.delay_us(RESET_RELEASE - RESET_SAMPLE);
+
+        // the presence pulse has to be over by now
+        self.wait_for_high(BUS_RELEASE_TIMEOUT)?;
+
+        Ok(present)
+    }
+
+    /// Write a single bit.
+    pub fn write_bit(&mut self, bit: bool) {
+        if bit {
+            self.pulse_low(WRITE_1_LOW);
+            self.delay_us(WRITE_1_RELEASE);
+        } else {
+            self.pulse_low(WRITE_0_LOW);
+            self.delay_us(WRITE_0_RELEASE);
+        }
+    }
+
+    /// Read a single bit.
+    pub fn read_bit(&mut self) -> bool {
+        let bit = self.pulse_and_sample(WRITE_1_LOW, WRITE_1_LOW + READ_SAMPLE);
+        self.delay_us(READ_RELEASE);
+        bit
+    }
+
+    /// Write a byte, least significant bit first.
+    pub fn write_byte(&mut self, byte: u8) {
+        for i in 0..8 {
+            self.write_bit(byte & (1 << i) != 0);
+        }
+    }
+
+    /// Read a byte, least significant bit first.
+    pub fn read_byte(&mut self) -> u8 {
+        let mut byte = 0;
+        for i in 0..8 {
+            if self.read_bit() {
+                byte |= 1 << i;
+            }
+        }
+        byte
+    }
+
+    /// Write all bytes of `bytes`.
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.write_byte(*byte);
+        }
+    }
+
+    /// Fill `buffer` with bytes read from the bus.
+    pub fn read_bytes(&mut self, buffer: &mut [u8]) {
+        for byte in buffer.iter_mut() {
+            *byte = self.read_byte();
+        }
+    }
+
+    #[inline(always)]
+    fn wait_until(&self, start: u32, us: u32) {
+        let cycles = us * self.cycles_per_us;
+        while CycleCounter::raw().wrapping_sub(start) < cycles {}
+    }
+}
@@ -0,0 +1,127 @@
+//! # Multi-pin port access
+//!
+//! ## Overview
+//!
+//! A [Port] groups pins of the same GPIO bank (GPIO0 to GPIO31 are in bank 0,
+//! the remaining ones in bank 1), so they can be driven and read with single
+//! register accesses. Setting or clearing any number of its pins is a single
+//! write to the `W1TS`/`W1TC` registers, so it is atomic and doesn't need a
+//! critical section.
+//!
+//! Masks and values use the bit positions of the pins within their bank, i.e.
+//! bit `n % 32` for GPIO `n`.
+//!
+//! ## Example
+//! ```no_run
+//! let mut d0 = io.pins.gpio4.into_push_pull_output();
+//! let mut d1 = io.pins.gpio5.into_push_pull_output();
+//!
+//! let mut port = Port0::new().with_pin(&mut d0).with_pin(&mut d1);
+//!
+//! port.set(1 << 4 | 1 << 5);
+//! port.clear(1 << 5);
+//! ```
+
+use core::marker::PhantomData;
+
+#[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+use super::Bank1GpioRegisterAccess;
+use super::{Bank0GpioRegisterAccess, BankGpioRegisterAccess, GpioPin, GpioProperties};
+
+/// Pins of GPIO bank 0
+pub type Port0<'d> = Port<'d, Bank0GpioRegisterAccess>;
+
+/// Pins of GPIO bank 1
+#[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+pub type Port1<'d> = Port<'d, Bank1GpioRegisterAccess>;
+
+/// A group of pins of the same GPIO bank
+pub struct Port<'d, BANK> {
+    mask: u32,
+    _pins: PhantomData<(&'d mut (), BANK)>,
+}
+
+impl<'d, BANK> Port<'d, BANK>
+where
+    BANK: BankGpioRegisterAccess,
+{
+    /// Create a port without any pins.
+    pub fn new() -> Self {
+        Self {
+            mask: 0,
+            _pins: PhantomData,
+        }
+    }
+
+    /// Add a pin to the port.
+    ///
+    /// The pin keeps its configuration, and stays borrowed for as long as the
+    /// port exists.
+    pub fn with_pin<MODE, const GPIONUM: u8>(mut self, _pin: &'d mut GpioPin<MODE, GPIONUM>) -> Self
+    where
+        GpioPin<MODE, GPIONUM>: GpioProperties<Bank = BANK>,
+    {
+        self.mask |= 1 << (GPIONUM % 32);
+        self
+    }
+
+    /// The mask of all pins of the port.
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    /// Drive the pins in `mask` high.
+    ///
+    /// Bits of pins which are not part of the port are ignored.
+    pub fn set(&mut self, mask: u32) {
+        BANK::write_output_set(mask & self.mask);
+    }
+
+    /// Drive the pins in `mask` low.
+    ///
+    /// Bits of pins which are not part of the port are ignored.
+    pub fn clear(&mut self, mask: u32) {
+        BANK::write_output_clear(mask & self.mask);
+    }
+
+    /// Toggle the pins in `mask`.
+    ///
+    /// This reads the output register and writes the `W1TS` and `W1TC`
+    /// registers, so the pins going high and the pins going low change in two
+    /// separate writes.
+    pub fn toggle(&mut self, mask: u32) {
+        let mask = mask & self.mask;
+        let output = BANK::read_output();
+
+        BANK::write_output_set(!output & mask);
+        BANK::write_output_clear(output & mask);
+    }
+
+    /// Drive all pins of the port to the levels given by `value`.
+    ///
+    /// The pins going high and the pins going low change in two separate
+    /// writes.
+    pub fn write(&mut self, value: u32) {
+        BANK::write_output_set(value & self.mask);
+        BANK::write_output_clear(!value & self.mask);
+    }
+
+    /// The input levels of the pins of the port.
+    pub fn read(&self) -> u32 {
+        BANK::read_input() & self.mask
+    }
+
+    /// The output levels the pins of the port are driven to.
+    pub fn read_output(&self) -> u32 {
+        BANK::read_output() & self.mask
+    }
+}
+
+impl<'d, BANK> Default for Port<'d, BANK>
+where
+    BANK: BankGpioRegisterAccess,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
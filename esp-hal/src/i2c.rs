@@ -11,6 +11,26 @@
 //! multiple I2C peripheral instances on `ESP32`, `ESP32H2`, `ESP32S2`, and
 //! `ESP32S3` chips
 //!
+//! On chips other than the `ESP32`, `ESP32S2` and `ESP32C2` the peripherals
+//! can also be used in slave mode, see [slave].
+//!
+//! Besides 7 bit addresses, the `embedded-hal` traits are also implemented for
+//! 10 bit addresses (`TenBitAddress`).
+//!
+//! SMBus transactions are implemented on top of the I2C master in [smbus].
+//!
+//! `write_read` and the `embedded-hal` `transaction` run all operations as one
+//! transaction on the bus: only a change of direction starts with a repeated
+//! START and the address, and the STOP is sent after the last operation.
+//! Longer operations are split into segments, pausing the bus in between, so
+//! on the `ESP32` and `ESP32S2` they aren't limited to the FIFO size.
+//!
+//! Async transfers created with `I2C::new_async` wait for the FIFO watermark
+//! and transaction interrupts instead of polling the peripheral, except on the
+//! `ESP32` and `ESP32S2` where transfers are limited to the FIFO size and only
+//! the end of the transaction is awaited. The I2C peripherals have no access to
+//! DMA, all data goes through the 32 byte FIFOs.
+//!
 //! ## Example
 //! Following code shows how to read data from a BMP180 sensor using I2C.
 //!
@@ -34,10 +54,11 @@
 
 use core::marker::PhantomData;
 
-use fugit::HertzU32;
+use fugit::{HertzU32, MicrosDurationU32};
 
 use crate::{
     clock::Clocks,
+    cycle_counter::CycleCounter,
     gpio::{InputPin, InputSignal, OutputPin, OutputSignal},
     interrupt::InterruptHandler,
     peripheral::{Peripheral, PeripheralRef},
@@ -45,6 +66,14 @@ use crate::{
     system::PeripheralClockControl,
 };
 
+#[cfg(not(any(esp32, esp32s2, esp32c2)))]
+pub mod slave;
+#[cfg(feature = "embedded-hal")]
+pub mod smbus;
+
+/// How long to wait for each step of the bus recovery, in microseconds
+const RECOVERY_TIMEOUT_US: u32 = 2000;
+
 cfg_if::cfg_if! {
     if #[cfg(esp32s2)] {
         const I2C_LL_INTR_MASK: u32 = 0x1ffff;
@@ -63,6 +92,8 @@ pub enum Error {
     ArbitrationLost,
     ExecIncomplete,
     CommandNrExceeded,
+    /// The bus is still busy after trying to recover it
+    BusBusy,
 }
 
 #[cfg(feature = "embedded-hal")]
@@ -82,6 +113,9 @@ impl embedded_hal::i2c::Error for Error {
 enum Command {
     Start,
     Stop,
+    /// Pauses the transaction, holding SCL low until the next commands are
+    /// started.
+    End,
     Write {
         /// This bit is to set an expected ACK value for the transmitter.
         ack_exp: Ack,
@@ -106,29 +140,30 @@ impl From<Command> for u16 {
         let opcode = match c {
             Command::Start => Opcode::RStart,
             Command::Stop => Opcode::Stop,
+            Command::End => Opcode::End,
             Command::Write { .. } => Opcode::Write,
             Command::Read { .. } => Opcode::Read,
         };
 
         let length = match c {
-            Command::Start | Command::Stop => 0,
+            Command::Start | Command::Stop | Command::End => 0,
             Command::Write { length: l, .. } | Command::Read { length: l, .. } => l,
         };
 
         let ack_exp = match c {
-            Command::Start | Command::Stop | Command::Read { .. } => Ack::Nack,
+            Command::Start | Command::Stop | Command::End | Command::Read { .. } => Ack::Nack,
             Command::Write { ack_exp: exp, .. } => exp,
         };
 
         let ack_check_en = match c {
-            Command::Start | Command::Stop | Command::Read { .. } => false,
+            Command::Start | Command::Stop | Command::End | Command::Read { .. } => false,
             Command::Write {
                 ack_check_en: en, ..
             } => en,
         };
 
         let ack_value = match c {
-            Command::Start | Command::Stop | Command::Write { .. } => Ack::Nack,
+            Command::Start | Command::Stop | Command::End | Command::Write { .. } => Ack::Nack,
             Command::Read { ack_value: ack, .. } => ack,
         };
 
@@ -158,11 +193,162 @@ impl From<Command> for u16 {
     }
 }
 
+#[derive(Clone, Copy)]
 enum OperationType {
     Write = 0,
     Read  = 1,
 }
 
+/// The address of a target device
+#[derive(Clone, Copy)]
+enum Address {
+    SevenBit(u8),
+    TenBit(u16),
+}
+
+impl Address {
+    /// The number of bytes the address takes on the bus
+    fn len(&self) -> u8 {
+        match self {
+            Address::SevenBit(_) => 1,
+            Address::TenBit(_) => 2,
+        }
+    }
+
+    /// The first byte sent, holding the 7 bit address or the two most
+    /// significant bits of a 10 bit address, and the R/W bit
+    fn header(&self, operation: OperationType) -> u8 {
+        match *self {
+            Address::SevenBit(addr) => addr << 1 | operation as u8,
+            Address::TenBit(addr) => 0xf0 | ((addr >> 7) as u8 & 0x06) | operation as u8,
+        }
+    }
+}
+
+impl From<u8> for Address {
+    fn from(addr: u8) -> Self {
+        Address::SevenBit(addr)
+    }
+}
+
+/// The most bytes written or read by a single segment of a transaction. On
+/// the ESP32 and ESP32-S2 a segment has to fit into the FIFO, next to the
+/// address bytes.
+#[cfg(any(esp32, esp32s2))]
+const MAX_SEGMENT_SIZE: usize = 29;
+#[cfg(not(any(esp32, esp32s2)))]
+const MAX_SEGMENT_SIZE: usize = 254;
+
+/// How a part of a transaction is put on the bus
+#[derive(Clone, Copy)]
+struct Framing {
+    /// Send a (repeated) START and the address first
+    start: bool,
+    /// End the transaction with a STOP, otherwise the bus is paused and SCL
+    /// held low until the next part is started
+    stop: bool,
+    /// NACK the last byte read, telling the slave the read is over
+    nack: bool,
+}
+
+impl Framing {
+    /// The framing of segment `index` out of `count` segments of an operation
+    fn segment(&self, index: usize, count: usize) -> Self {
+        let last = index == count - 1;
+        Self {
+            start: self.start && index == 0,
+            stop: self.stop && last,
+            nack: self.nack && last,
+        }
+    }
+}
+
+/// The framing of the write of a write-read
+const WRITE_BEFORE_READ: Framing = Framing {
+    start: true,
+    stop: false,
+    nack: false,
+};
+
+/// The framing of the read of a write-read
+const READ_AFTER_WRITE: Framing = Framing {
+    start: true,
+    stop: true,
+    nack: true,
+};
+
+/// The framing of the byte count read first by a block read
+const READ_BLOCK_COUNT: Framing = Framing {
+    start: true,
+    stop: false,
+    nack: false,
+};
+
+/// The framing of a read continuing a paused one, which is continued itself
+const READ_CONTINUE: Framing = Framing {
+    start: false,
+    stop: false,
+    nack: false,
+};
+
+/// The framing of the last read of a transaction, continuing a paused one
+const READ_END: Framing = Framing {
+    start: false,
+    stop: true,
+    nack: true,
+};
+
+/// The framing of the operation at `index` of an embedded-hal transaction, or
+/// `None` if it doesn't need to be put on the bus.
+///
+/// Consecutive operations of the same kind are merged, only a change of the
+/// direction starts with a repeated START and the address. Empty reads are
+/// skipped, as are empty writes which neither start nor end the transaction.
+#[cfg(feature = "embedded-hal")]
+fn operation_framing(
+    operations: &[embedded_hal::i2c::Operation<'_>],
+    index: usize,
+) -> Option<Framing> {
+    use embedded_hal::i2c::Operation;
+
+    fn is_read(operation: &Operation<'_>) -> bool {
+        matches!(operation, Operation::Read(_))
+    }
+
+    fn is_empty_read(operation: &Operation<'_>) -> bool {
+        matches!(operation, Operation::Read(buffer) if buffer.is_empty())
+    }
+
+    let operation = &operations[index];
+    if is_empty_read(operation) {
+        return None;
+    }
+
+    let previous = operations[..index]
+        .iter()
+        .rfind(|operation| !is_empty_read(operation));
+    let next = operations[index + 1..]
+        .iter()
+        .find(|operation| !is_empty_read(operation));
+
+    let framing = Framing {
+        start: previous.map_or(true, |previous| is_read(previous) != is_read(operation)),
+        stop: next.is_none(),
+        nack: is_read(operation) && !next.is_some_and(is_read),
+    };
+
+    match operation {
+        Operation::Write(bytes) if bytes.is_empty() && !framing.start && !framing.stop => None,
+        _ => Some(framing),
+    }
+}
+
+/// Whether `command` is an END command. END commands aren't marked as done,
+/// the peripheral pauses when it reaches them.
+fn is_end_command(command: u16) -> bool {
+    (command >> 11) & 0x7 == Opcode::End as u16
+}
+
 #[derive(Eq, PartialEq, Copy, Clone)]
 enum Ack {
     Ack,
@@ -175,6 +361,7 @@ enum Opcode {
     Write  = 1,
     Read   = 3,
     Stop   = 2,
+    End    = 4,
 }
 
 #[cfg(any(esp32, esp32s2))]
@@ -183,12 +370,72 @@ enum Opcode {
     Write  = 1,
     Read   = 2,
     Stop   = 3,
+    End    = 4,
+}
+
+/// Timeouts of the I2C master
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timeouts {
+    /// How long SCL may be held low, e.g. by a slave stretching the clock,
+    /// before a transaction fails with [Error::TimeOut].
+    ///
+    /// This is limited to what the peripheral supports, about 13 ms on the
+    /// ESP32, 200 ms on the ESP32-S2 and several seconds on the other chips.
+    /// On chips other than the ESP32 and ESP32-S2 it's rounded up to the
+    /// next power of two clock cycles.
+    pub scl_low: MicrosDurationU32,
+    /// How long a blocking transaction may take before it fails with
+    /// [Error::TimeOut], or `None` to not limit it.
+    pub transaction: Option<MicrosDurationU32>,
+}
+
+impl Timeouts {
+    /// The default timeouts for the given bus frequency.
+    ///
+    /// Devices may stretch the clock for 10 ms in standard mode, 5 ms in fast
+    /// mode and 1 ms at higher frequencies. Transactions aren't limited.
+    pub fn for_frequency(frequency: HertzU32) -> Self {
+        let scl_low = if frequency.raw() <= 100_000 {
+            MicrosDurationU32::millis(10)
+        } else if frequency.raw() <= 400_000 {
+            MicrosDurationU32::millis(5)
+        } else {
+            MicrosDurationU32::millis(1)
+        };
+
+        Self {
+            scl_low,
+            transaction: None,
+        }
+    }
+}
+
+/// The point in time a blocking transaction has to be finished by
+struct Deadline {
+    end: Option<(CycleCounter, u64)>,
+}
+
+impl Deadline {
+    fn new(counter: CycleCounter, timeout: Option<MicrosDurationU32>) -> Self {
+        Self {
+            end: timeout.map(|timeout| (counter, counter.nanos() + timeout.ticks() as u64 * 1000)),
+        }
+    }
+
+    fn expired(&self) -> bool {
+        match self.end {
+            Some((counter, end)) => counter.nanos() > end,
+            None => false,
+        }
+    }
 }
 
 /// I2C peripheral container (I2C)
 pub struct I2C<'d, T, DM: crate::Mode> {
     peripheral: PeripheralRef<'d, T>,
     phantom: PhantomData<DM>,
+    counter: CycleCounter,
+    transaction_timeout: Option<MicrosDurationU32>,
 }
 
 impl<T, DM> I2C<'_, T, DM>
@@ -198,12 +445,14 @@ where
 {
     /// Reads enough bytes from slave with `address` to fill `buffer`
     pub fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Error> {
-        self.peripheral.master_read(address, buffer)
+        self.peripheral
+            .master_read(address.into(), buffer, &self.deadline())
     }
 
     /// Writes bytes to slave with address `address`
     pub fn write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Error> {
-        self.peripheral.master_write(addr, bytes)
+        self.peripheral
+            .master_write(addr.into(), bytes, &self.deadline())
     }
 
     /// Writes bytes to slave with address `address` and then reads enough bytes
@@ -214,7 +463,65 @@ where
         bytes: &[u8],
         buffer: &mut [u8],
     ) -> Result<(), Error> {
-        self.peripheral.master_write_read(address, bytes, buffer)
+        self.peripheral
+            .master_write_read(address.into(), bytes, buffer, &self.deadline())
+    }
+
+    /// Writes bytes to slave with address `address`, then reads a byte count
+    /// followed by exactly that many bytes into `buffer` and `trailer.len()`
+    /// more bytes into `trailer` *in a single transaction*, e.g. an SMBus
+    /// block read.
+    ///
+    /// Returns the received count. If it's larger than `buffer`, or if there
+    /// is nothing to read after it, the read is ended right after the count
+    /// and neither `buffer` nor `trailer` are filled.
+    pub fn write_read_block(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+        trailer: &mut [u8],
+    ) -> Result<usize, Error> {
+        self.peripheral.master_write_read_block(
+            address.into(),
+            bytes,
+            buffer,
+            trailer,
+            &self.deadline(),
+        )
+    }
+
+    /// Change the SCL low and transaction timeouts.
+    ///
+    /// By default the timeouts are [Timeouts::for_frequency] of the bus
+    /// frequency, unless a raw timeout was passed to the constructor.
+    pub fn set_timeouts(&mut self, timeouts: Timeouts, clocks: &Clocks) {
+        let sclk = self.peripheral.sclk_frequency(clocks);
+        self.peripheral
+            .set_scl_timeout(scl_timeout_value(sclk, timeouts.scl_low), true);
+        self.peripheral.update_config();
+
+        self.transaction_timeout = timeouts.transaction;
+    }
+
+    fn deadline(&self) -> Deadline {
+        Deadline::new(self.counter, self.transaction_timeout)
+    }
+
+    /// Try to free a bus which is held by a slave, e.g. after a transaction was
+    /// interrupted by a reset of the chip.
+    ///
+    /// On the ESP32-C2, ESP32-C3, ESP32-C6, ESP32-H2 and ESP32-S3, up to 9
+    /// clock pulses are sent on SCL so a slave driving SDA low can finish the
+    /// byte it's sending. The ESP32 and ESP32-S2 can't send clock pulses
+    /// without a transaction, there a slave stuck in the middle of a byte is
+    /// not freed. On all chips a START and a STOP condition are generated
+    /// afterwards, to reset the state of the slaves.
+    ///
+    /// This is done automatically when starting a transaction while the bus is
+    /// busy, and after timeouts or lost arbitration.
+    pub fn recover_bus(&mut self) -> Result<(), Error> {
+        self.peripheral.recover_bus()
     }
 }
 
@@ -259,6 +566,56 @@ where
     }
 }
 
+#[cfg(feature = "embedded-hal-02")]
+impl<T, DM: crate::Mode>
+    embedded_hal_02::blocking::i2c::Read<embedded_hal_02::blocking::i2c::TenBitAddress>
+    for I2C<'_, T, DM>
+where
+    T: Instance,
+{
+    type Error = Error;
+
+    fn read(&mut self, address: u16, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.peripheral
+            .master_read(Address::TenBit(address), buffer, &self.deadline())
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+impl<T, DM: crate::Mode>
+    embedded_hal_02::blocking::i2c::Write<embedded_hal_02::blocking::i2c::TenBitAddress>
+    for I2C<'_, T, DM>
+where
+    T: Instance,
+{
+    type Error = Error;
+
+    fn write(&mut self, address: u16, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.peripheral
+            .master_write(Address::TenBit(address), bytes, &self.deadline())
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+impl<T, DM: crate::Mode>
+    embedded_hal_02::blocking::i2c::WriteRead<embedded_hal_02::blocking::i2c::TenBitAddress>
+    for I2C<'_, T, DM>
+where
+    T: Instance,
+{
+    type Error = Error;
+
+    fn write_read(
+        &mut self,
+        address: u16,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.peripheral
+            .master_write_read(Address::TenBit(address), bytes, buffer, &self.deadline())
+    }
+}
+
 #[cfg(feature = "embedded-hal")]
 impl<T, DM: crate::Mode> embedded_hal::i2c::ErrorType for I2C<'_, T, DM> {
     type Error = Error;
@@ -270,11 +627,13 @@ where
     T: Instance,
 {
     fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
-        self.peripheral.master_read(address, buffer)
+        self.peripheral
+            .master_read(address.into(), buffer, &self.deadline())
     }
 
     fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
-        self.peripheral.master_write(address, bytes)
+        self.peripheral
+            .master_write(address.into(), bytes, &self.deadline())
     }
 
     fn write_read(
@@ -283,15 +642,52 @@ where
         bytes: &[u8],
         buffer: &mut [u8],
     ) -> Result<(), Self::Error> {
-        self.peripheral.master_write_read(address, bytes, buffer)
+        self.peripheral
+            .master_write_read(address.into(), bytes, buffer, &self.deadline())
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.peripheral
+            .transaction(address.into(), operations, &self.deadline())
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<T, DM: crate::Mode> embedded_hal::i2c::I2c<embedded_hal::i2c::TenBitAddress> for I2C<'_, T, DM>
+where
+    T: Instance,
+{
+    fn read(&mut self, address: u16, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.peripheral
+            .master_read(Address::TenBit(address), buffer, &self.deadline())
+    }
+
+    fn write(&mut self, address: u16, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.peripheral
+            .master_write(Address::TenBit(address), bytes, &self.deadline())
+    }
+
+    fn write_read(
+        &mut self,
+        address: u16,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.peripheral
+            .master_write_read(Address::TenBit(address), bytes, buffer, &self.deadline())
     }
 
     fn transaction(
         &mut self,
-        _address: u8,
-        _operations: &mut [embedded_hal::i2c::Operation<'_>],
+        address: u16,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
     ) -> Result<(), Self::Error> {
-        todo!()
+        self.peripheral
+            .transaction(Address::TenBit(address), operations, &self.deadline())
     }
 }
 
@@ -320,6 +716,8 @@ where
         let mut i2c = I2C {
             peripheral: i2c,
             phantom: PhantomData,
+            counter: CycleCounter::new(clocks),
+            transaction_timeout: None,
         };
 
         // avoid SCL/SDA going low during configuration
@@ -454,11 +852,16 @@ mod asynch {
     const INIT: AtomicWaker = AtomicWaker::new();
     static WAKERS: [AtomicWaker; NUM_I2C] = [INIT; NUM_I2C];
 
+    #[cfg(not(any(esp32, esp32s2)))]
+    const I2C_FIFO_SIZE: usize = 32;
+
     pub(crate) enum Event {
         EndDetect,
         TxComplete,
         #[cfg(not(any(esp32, esp32s2)))]
         TxFifoWatermark,
+        #[cfg(not(any(esp32, esp32s2)))]
+        RxFifoWatermark,
     }
 
     pub(crate) struct I2cFuture<'a, T>
@@ -474,15 +877,24 @@ mod asynch {
         T: Instance,
     {
         pub fn new(event: Event, instance: &'a T) -> Self {
-            instance
-                .register_block()
-                .int_ena()
-                .modify(|_, w| match event {
+            instance.register_block().int_ena().modify(|_, w| {
+                let w = match event {
                     Event::EndDetect => w.end_detect().set_bit(),
                     Event::TxComplete => w.trans_complete().set_bit(),
                     #[cfg(not(any(esp32, esp32s2)))]
                     Event::TxFifoWatermark => w.txfifo_wm().set_bit(),
-                });
+                    #[cfg(not(any(esp32, esp32s2)))]
+                    Event::RxFifoWatermark => w.rxfifo_wm().set_bit(),
+                };
+
+                // also wake up on errors, a failed transfer won't trigger the event
+                let w = w.time_out().set_bit().arbitration_lost().set_bit();
+                #[cfg(esp32)]
+                let w = w.ack_err().set_bit();
+                #[cfg(not(esp32))]
+                let w = w.nack().set_bit();
+                w
+            });
 
             Self { event, instance }
         }
@@ -495,6 +907,8 @@ mod asynch {
                 Event::TxComplete => r.trans_complete().bit_is_clear(),
                 #[cfg(not(any(esp32, esp32s2)))]
                 Event::TxFifoWatermark => r.txfifo_wm().bit_is_clear(),
+                #[cfg(not(any(esp32, esp32s2)))]
+                Event::RxFifoWatermark => r.rxfifo_wm().bit_is_clear(),
             }
         }
     }
@@ -520,7 +934,9 @@ mod asynch {
     where
         T: Instance,
     {
-        async fn master_read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Error> {
+        async fn master_read(&mut self, addr: Address, buffer: &mut [u8]) -> Result<(), Error> {
+            self.peripheral.ensure_bus_idle()?;
+
             // Reset FIFO and command list
             self.peripheral.reset_fifo();
             self.peripheral.reset_command_list();
@@ -535,7 +951,7 @@ mod asynch {
 
         async fn perform_read<'a, I>(
             &self,
-            addr: u8,
+            addr: Address,
             buffer: &mut [u8],
             cmd_iterator: &mut I,
         ) -> Result<(), Error>
@@ -568,10 +984,37 @@ mod asynch {
 
         #[cfg(not(any(esp32, esp32s2)))]
         async fn read_all_from_fifo(&self, buffer: &mut [u8]) -> Result<(), Error> {
-            self.peripheral.read_all_from_fifo(buffer)
+            let register_block = self.peripheral.register_block();
+
+            let mut index = 0;
+            while index < buffer.len() {
+                self.peripheral.check_errors()?;
+
+                // clear the watermark before looking at the FIFO, bytes received in
+                // between trigger the interrupt again
+                register_block
+                    .int_clr()
+                    .write(|w| w.rxfifo_wm().clear_bit_by_one());
+
+                let available = register_block.sr().read().rxfifo_cnt().bits() as usize;
+                if available == 0 {
+                    I2cFuture::new(Event::RxFifoWatermark, self.inner()).await;
+                    continue;
+                }
+
+                let count = usize::min(available, buffer.len() - index);
+                for byte in buffer[index..][..count].iter_mut() {
+                    *byte = read_fifo(register_block);
+                }
+                index += count;
+            }
+
+            Ok(())
         }
 
-        async fn master_write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Error> {
+        async fn master_write(&mut self, addr: Address, bytes: &[u8]) -> Result<(), Error> {
+            self.peripheral.ensure_bus_idle()?;
+
             // Reset FIFO and command list
             self.peripheral.reset_fifo();
             self.peripheral.reset_command_list();
@@ -586,7 +1029,7 @@ mod asynch {
 
         async fn perform_write<'a, I>(
             &self,
-            addr: u8,
+            addr: Address,
             bytes: &[u8],
             cmd_iterator: &mut I,
         ) -> Result<(), Error>
@@ -628,26 +1071,132 @@ mod asynch {
             start_index: usize,
             bytes: &[u8],
         ) -> Result<(), Error> {
+            let register_block = self.peripheral.register_block();
+
             let mut index = start_index;
-            loop {
+            while index < bytes.len() {
                 self.peripheral.check_errors()?;
 
-                I2cFuture::new(Event::TxFifoWatermark, self.inner()).await;
-
-                self.peripheral
-                    .register_block()
+                // clear the watermark before looking at the FIFO, bytes sent in
+                // between trigger the interrupt again
+                register_block
                     .int_clr()
                     .write(|w| w.txfifo_wm().clear_bit_by_one());
 
-                I2cFuture::new(Event::TxFifoWatermark, self.inner()).await;
+                let queued = register_block.sr().read().txfifo_cnt().bits() as usize;
+                if queued >= I2C_FIFO_SIZE {
+                    I2cFuture::new(Event::TxFifoWatermark, self.inner()).await;
+                    continue;
+                }
+
+                let count = usize::min(I2C_FIFO_SIZE - queued, bytes.len() - index);
+                for b in &bytes[index..][..count] {
+                    write_fifo(register_block, *b);
+                }
+                index += count;
+            }
+
+            Ok(())
+        }
+
+        async fn master_write_read(
+            &mut self,
+            addr: Address,
+            bytes: &[u8],
+            buffer: &mut [u8],
+        ) -> Result<(), Error> {
+            if buffer.is_empty() {
+                return self.master_write(addr, bytes).await;
+            }
+
+            self.peripheral.ensure_bus_idle()?;
+
+            self.write_operation(addr, bytes, WRITE_BEFORE_READ).await?;
+            self.read_operation(addr, buffer, READ_AFTER_WRITE).await
+        }
+
+        async fn transaction(
+            &mut self,
+            addr: Address,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Error> {
+            self.peripheral.ensure_bus_idle()?;
+
+            for index in 0..operations.len() {
+                let Some(framing) = operation_framing(operations, index) else {
+                    continue;
+                };
 
-                if index >= bytes.len() {
-                    break Ok(());
+                match &mut operations[index] {
+                    Operation::Write(bytes) => self.write_operation(addr, bytes, framing).await?,
+                    Operation::Read(buffer) => self.read_operation(addr, buffer, framing).await?,
                 }
+            }
+
+            Ok(())
+        }
+
+        async fn write_operation(
+            &self,
+            addr: Address,
+            bytes: &[u8],
+            framing: Framing,
+        ) -> Result<(), Error> {
+            if bytes.is_empty() {
+                return self.write_segment(addr, bytes, framing).await;
+            }
+
+            let count = bytes.len().div_ceil(MAX_SEGMENT_SIZE);
+            for (index, segment) in bytes.chunks(MAX_SEGMENT_SIZE).enumerate() {
+                self.write_segment(addr, segment, framing.segment(index, count))
+                    .await?;
+            }
+
+            Ok(())
+        }
 
-                write_fifo(self.peripheral.register_block(), bytes[index]);
-                index += 1;
+        async fn write_segment(
+            &self,
+            addr: Address,
+            bytes: &[u8],
+            framing: Framing,
+        ) -> Result<(), Error> {
+            self.peripheral
+                .setup_segment(addr, OperationType::Write, bytes.len(), framing)?;
+            let index = self.peripheral.fill_tx_fifo(bytes);
+            self.peripheral.start_transmission();
+
+            self.write_remaining_tx_fifo(index, bytes).await?;
+            self.wait_for_completion().await
+        }
+
+        async fn read_operation(
+            &self,
+            addr: Address,
+            buffer: &mut [u8],
+            framing: Framing,
+        ) -> Result<(), Error> {
+            let count = buffer.len().div_ceil(MAX_SEGMENT_SIZE);
+            for (index, segment) in buffer.chunks_mut(MAX_SEGMENT_SIZE).enumerate() {
+                self.read_segment(addr, segment, framing.segment(index, count))
+                    .await?;
             }
+
+            Ok(())
+        }
+
+        async fn read_segment(
+            &self,
+            addr: Address,
+            buffer: &mut [u8],
+            framing: Framing,
+        ) -> Result<(), Error> {
+            self.peripheral
+                .setup_segment(addr, OperationType::Read, buffer.len(), framing)?;
+            self.peripheral.start_transmission();
+
+            self.read_all_from_fifo(buffer).await?;
+            self.wait_for_completion().await
         }
 
         async fn wait_for_completion(&self) -> Result<(), Error> {
@@ -659,8 +1208,14 @@ mod asynch {
             )
             .await;
 
+            self.peripheral.check_errors()?;
+
             for cmd in self.peripheral.register_block().comd_iter() {
-                if cmd.read().command().bits() != 0x0 && cmd.read().command_done().bit_is_clear() {
+                let command = cmd.read().command().bits();
+                if command != 0x0
+                    && !is_end_command(command)
+                    && cmd.read().command_done().bit_is_clear()
+                {
                     return Err(Error::ExecIncomplete);
                 }
             }
@@ -674,11 +1229,11 @@ mod asynch {
         T: Instance,
     {
         async fn read(&mut self, address: u8, read: &mut [u8]) -> Result<(), Self::Error> {
-            self.master_read(address, read).await
+            self.master_read(address.into(), read).await
         }
 
         async fn write(&mut self, address: u8, write: &[u8]) -> Result<(), Self::Error> {
-            self.master_write(address, write).await
+            self.master_write(address.into(), write).await
         }
 
         async fn write_read(
@@ -687,31 +1242,74 @@ mod asynch {
             write: &[u8],
             read: &mut [u8],
         ) -> Result<(), Self::Error> {
-            self.master_write(address, write).await?;
-            self.master_read(address, read).await?;
+            self.master_write_read(address.into(), write, read).await
+        }
 
-            Ok(())
+        async fn transaction(
+            &mut self,
+            address: u8,
+            operations: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            self.transaction(address.into(), operations).await
+        }
+    }
+
+    impl<'d, T> embedded_hal_async::i2c::I2c<embedded_hal::i2c::TenBitAddress>
+        for I2C<'d, T, crate::Async>
+    where
+        T: Instance,
+    {
+        async fn read(&mut self, address: u16, read: &mut [u8]) -> Result<(), Self::Error> {
+            self.master_read(Address::TenBit(address), read).await
+        }
+
+        async fn write(&mut self, address: u16, write: &[u8]) -> Result<(), Self::Error> {
+            self.master_write(Address::TenBit(address), write).await
+        }
+
+        async fn write_read(
+            &mut self,
+            address: u16,
+            write: &[u8],
+            read: &mut [u8],
+        ) -> Result<(), Self::Error> {
+            self.master_write_read(Address::TenBit(address), write, read)
+                .await
         }
 
         async fn transaction(
             &mut self,
-            _address: u8,
-            _operations: &mut [Operation<'_>],
+            address: u16,
+            operations: &mut [Operation<'_>],
         ) -> Result<(), Self::Error> {
-            todo!()
+            self.transaction(Address::TenBit(address), operations).await
         }
     }
 
+    fn disable_interrupts(register_block: &RegisterBlock) {
+        register_block.int_ena().modify(|_, w| {
+            let w = w
+                .end_detect()
+                .clear_bit()
+                .trans_complete()
+                .clear_bit()
+                .time_out()
+                .clear_bit()
+                .arbitration_lost()
+                .clear_bit();
+            #[cfg(esp32)]
+            let w = w.ack_err().clear_bit();
+            #[cfg(not(esp32))]
+            let w = w.nack().clear_bit();
+            #[cfg(not(any(esp32, esp32s2)))]
+            let w = w.txfifo_wm().clear_bit().rxfifo_wm().clear_bit();
+            w
+        });
+    }
+
     #[handler]
     pub(super) fn i2c0_handler() {
-        unsafe { &*crate::peripherals::I2C0::PTR }
-            .int_ena()
-            .modify(|_, w| w.end_detect().clear_bit().trans_complete().clear_bit());
-
-        #[cfg(not(any(esp32, esp32s2)))]
-        unsafe { &*crate::peripherals::I2C0::PTR }
-            .int_ena()
-            .modify(|_, w| w.txfifo_wm().clear_bit());
+        disable_interrupts(unsafe { &*crate::peripherals::I2C0::PTR });
 
         WAKERS[0].wake();
     }
@@ -719,14 +1317,7 @@ mod asynch {
     #[cfg(i2c1)]
     #[handler]
     pub(super) fn i2c1_handler() {
-        unsafe { &*crate::peripherals::I2C1::PTR }
-            .int_ena()
-            .modify(|_, w| w.end_detect().clear_bit().trans_complete().clear_bit());
-
-        #[cfg(not(any(esp32, esp32s2)))]
-        unsafe { &*crate::peripherals::I2C0::PTR }
-            .int_ena()
-            .modify(|_, w| w.txfifo_wm().clear_bit());
+        disable_interrupts(unsafe { &*crate::peripherals::I2C1::PTR });
 
         WAKERS[1].wake();
     }
@@ -822,6 +1413,90 @@ pub trait Instance: crate::private::Sealed {
         }
     }
 
+    fn bus_busy(&self) -> bool {
+        self.register_block().sr().read().bus_busy().bit_is_set()
+    }
+
+    /// Recovers the bus if it's still busy while no transaction is running
+    fn ensure_bus_idle(&self) -> Result<(), Error> {
+        if self.bus_busy() {
+            self.recover_bus()?;
+        }
+
+        Ok(())
+    }
+
+    /// Resets the peripheral after a failed transaction, recovering the bus if
+    /// it's still held
+    fn reset_after_error(&self) {
+        self.reset();
+
+        if self.bus_busy() {
+            self.recover_bus().ok();
+        }
+    }
+
+    /// Fails a transaction which took too long
+    fn check_deadline(&self, deadline: &Deadline) -> Result<(), Error> {
+        if deadline.expired() {
+            self.reset_after_error();
+            return Err(Error::TimeOut);
+        }
+
+        Ok(())
+    }
+
+    fn recover_bus(&self) -> Result<(), Error> {
+        self.reset();
+
+        // Clock out whatever a slave still wants to send, the ESP32 and ESP32-S2
+        // lack the hardware support for this
+        #[cfg(not(any(esp32, esp32s2)))]
+        {
+            self.register_block()
+                .scl_sp_conf()
+                .modify(|_, w| unsafe { w.scl_rst_slv_num().bits(9).scl_rst_slv_en().set_bit() });
+            self.update_config();
+
+            // the enable bit is cleared by the hardware once the pulses were sent
+            wait_us(RECOVERY_TIMEOUT_US, || {
+                self.register_block()
+                    .scl_sp_conf()
+                    .read()
+                    .scl_rst_slv_en()
+                    .bit_is_clear()
+            });
+
+            self.register_block()
+                .scl_sp_conf()
+                .modify(|_, w| w.scl_rst_slv_en().clear_bit());
+            self.update_config();
+        }
+
+        // Generate a START and a STOP condition
+        let mut cmd_iterator = self.register_block().comd_iter();
+        add_cmd(&mut cmd_iterator, Command::Start)?;
+        add_cmd(&mut cmd_iterator, Command::Stop)?;
+        self.update_config();
+        self.start_transmission();
+
+        wait_us(RECOVERY_TIMEOUT_US, || {
+            let interrupts = self.register_block().int_raw().read();
+            interrupts.end_detect().bit_is_set()
+                || interrupts.trans_complete().bit_is_set()
+                || interrupts.arbitration_lost().bit_is_set()
+                || interrupts.time_out().bit_is_set()
+        });
+
+        self.reset();
+
+        if self.bus_busy() {
+            Err(Error::BusBusy)
+        } else {
+            Ok(())
+        }
+    }
+
     /// Sets the filter with a supplied threshold in clock cycles for which a
     /// pulse must be present to pass the filter
     fn set_filter(&mut self, sda_threshold: Option<u8>, scl_threshold: Option<u8>) {
@@ -870,8 +1545,10 @@ pub trait Instance: crate::private::Sealed {
         let tout = if let Some(timeout) = timeout {
             timeout
         } else {
-            // default we set the timeout value to 10 bus cycles
-            half_cycle * 20
+            scl_timeout_value(
+                source_clk,
+                Timeouts::for_frequency(HertzU32::from_raw(bus_freq)).scl_low,
+            )
         };
 
         // SCL period. According to the TRM, we should always subtract 1 to SCL low
@@ -952,8 +1629,10 @@ pub trait Instance: crate::private::Sealed {
         let tout = if let Some(timeout) = timeout {
             timeout
         } else {
-            // default we set the timeout value to 10 bus cycles
-            half_cycle * 20
+            scl_timeout_value(
+                source_clk,
+                Timeouts::for_frequency(HertzU32::from_raw(bus_freq)).scl_low,
+            )
         };
 
         // scl period
@@ -1019,9 +1698,10 @@ pub trait Instance: crate::private::Sealed {
         let tout = if let Some(timeout) = timeout {
             timeout
         } else {
-            // default we set the timeout value to about 10 bus cycles
-            // log(20*half_cycle)/log(2) = log(half_cycle)/log(2) +  log(20)/log(2)
-            (4 * 8 - (5 * half_cycle).leading_zeros()) + 2
+            scl_timeout_value(
+                sclk_freq,
+                Timeouts::for_frequency(HertzU32::from_raw(bus_freq)).scl_low,
+            )
         };
 
         // According to the Technical Reference Manual, the following timings must be
@@ -1144,36 +1824,57 @@ pub trait Instance: crate::private::Sealed {
             self.register_block()
                 .scl_stop_hold()
                 .write(|w| w.time().bits(scl_stop_hold_time as u16));
+        }
 
-            // The ESP32 variant does not have an enable flag for the
-            // timeout mechanism
-            cfg_if::cfg_if! {
-                if #[cfg(esp32)] {
-                    // timeout
-                    self.register_block()
-                        .to()
-                        .write(|w| w.time_out().bits(time_out_value));
-                }
-                else {
-                    // timeout
-                    // FIXME: Enable timout for other chips!
-                    #[allow(clippy::useless_conversion)]
-                    self.register_block()
-                        .to()
-                        .write(|w| w.time_out_en().bit(time_out_en)
-                        .time_out_value()
-                        .variant(time_out_value.try_into().unwrap())
-                    );
-                }
+        self.set_scl_timeout(time_out_value, time_out_en);
+    }
+
+    /// Sets the raw SCL low timeout
+    fn set_scl_timeout(&self, time_out_value: u32, time_out_en: bool) {
+        // The ESP32 variant does not have an enable flag for the
+        // timeout mechanism
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                self.register_block()
+                    .to()
+                    .write(|w| unsafe { w.time_out().bits(time_out_value) });
+            }
+            else {
+                #[allow(clippy::useless_conversion)]
+                self.register_block()
+                    .to()
+                    .write(|w| w.time_out_en().bit(time_out_en)
+                    .time_out_value()
+                    .variant(time_out_value.try_into().unwrap())
+                );
+            }
+        }
+    }
+
+    /// The frequency of the clock the bus timing is derived from, in Hz
+    fn sclk_frequency(&self, clocks: &Clocks) -> u32 {
+        cfg_if::cfg_if! {
+            if #[cfg(esp32)] {
+                clocks.i2c_clock.to_Hz()
+            } else if #[cfg(esp32s2)] {
+                clocks.apb_clock.to_Hz()
+            } else {
+                let divider = self.register_block().clk_conf().read().sclk_div_num().bits() as u32 + 1;
+                clocks.xtal_clock.to_Hz() / divider
             }
         }
     }
 
-    fn setup_write<'a, I>(&self, addr: u8, bytes: &[u8], cmd_iterator: &mut I) -> Result<(), Error>
+    fn setup_write<'a, I>(
+        &self,
+        addr: Address,
+        bytes: &[u8],
+        cmd_iterator: &mut I,
+    ) -> Result<(), Error>
     where
         I: Iterator<Item = &'a COMD>,
     {
-        if bytes.len() > 254 {
+        if bytes.len() > 255 - addr.len() as usize {
             // we could support more by adding multiple write operations
             return Err(Error::ExceedingFifo);
         }
@@ -1190,7 +1891,7 @@ pub trait Instance: crate::private::Sealed {
             Command::Write {
                 ack_exp: Ack::Ack,
                 ack_check_en: true,
-                length: 1 + bytes.len() as u8,
+                length: addr.len() + bytes.len() as u8,
             },
         )?;
 
@@ -1199,19 +1900,20 @@ pub trait Instance: crate::private::Sealed {
         self.update_config();
 
         // Load address and R/W bit into FIFO
-        write_fifo(
-            self.register_block(),
-            addr << 1 | OperationType::Write as u8,
-        );
+        write_fifo(self.register_block(), addr.header(OperationType::Write));
+        if let Address::TenBit(addr) = addr {
+            write_fifo(self.register_block(), addr as u8);
+        }
 
         Ok(())
     }
 
     fn perform_write<'a, I>(
         &self,
-        addr: u8,
+        addr: Address,
         bytes: &[u8],
         cmd_iterator: &mut I,
+        deadline: &Deadline,
     ) -> Result<(), Error>
     where
         I: Iterator<Item = &'a COMD>,
@@ -1221,15 +1923,15 @@ pub trait Instance: crate::private::Sealed {
         self.start_transmission();
 
         // Fill the FIFO with the remaining bytes:
-        self.write_remaining_tx_fifo(index, bytes)?;
-        self.wait_for_completion()?;
+        self.write_remaining_tx_fifo(index, bytes, deadline)?;
+        self.wait_for_completion(deadline)?;
 
         Ok(())
     }
 
     fn setup_read<'a, I>(
         &self,
-        addr: u8,
+        addr: Address,
         buffer: &mut [u8],
         cmd_iterator: &mut I,
     ) -> Result<(), Error>
@@ -1247,6 +1949,21 @@ pub trait Instance: crate::private::Sealed {
         // RSTART command
         add_cmd(cmd_iterator, Command::Start)?;
 
+        if let Address::TenBit(_) = addr {
+            // WRITE command for the full 10 bit address
+            add_cmd(
+                cmd_iterator,
+                Command::Write {
+                    ack_exp: Ack::Ack,
+                    ack_check_en: true,
+                    length: 2,
+                },
+            )?;
+
+            // RSTART command to switch to reading
+            add_cmd(cmd_iterator, Command::Start)?;
+        }
+
         // WRITE command
         add_cmd(
             cmd_iterator,
@@ -1281,37 +1998,240 @@ pub trait Instance: crate::private::Sealed {
 
         self.update_config();
 
-        // Load address and R/W bit into FIFO
-        write_fifo(self.register_block(), addr << 1 | OperationType::Read as u8);
+        // Load address and R/W bit into FIFO, a 10 bit address is sent with the write
+        // bit first and the header is repeated with the read bit after a repeated START
+        if let Address::TenBit(low) = addr {
+            write_fifo(self.register_block(), addr.header(OperationType::Write));
+            write_fifo(self.register_block(), low as u8);
+        }
+        write_fifo(self.register_block(), addr.header(OperationType::Read));
 
         Ok(())
     }
 
     fn perform_read<'a, I>(
         &self,
-        addr: u8,
+        addr: Address,
         buffer: &mut [u8],
         cmd_iterator: &mut I,
+        deadline: &Deadline,
     ) -> Result<(), Error>
     where
         I: Iterator<Item = &'a COMD>,
     {
         self.setup_read(addr, buffer, cmd_iterator)?;
         self.start_transmission();
-        self.read_all_from_fifo(buffer)?;
-        self.wait_for_completion()?;
+        self.read_all_from_fifo(buffer, deadline)?;
+        self.wait_for_completion(deadline)?;
+
+        Ok(())
+    }
+
+    /// Loads the commands and the address bytes for a segment of a
+    /// transaction, which writes or reads `len` bytes
+    fn setup_segment(
+        &self,
+        addr: Address,
+        operation: OperationType,
+        len: usize,
+        framing: Framing,
+    ) -> Result<(), Error> {
+        // Reset FIFO and command list, and clear all I2C interrupts
+        self.reset_fifo();
+        self.reset_command_list();
+        self.clear_all_interrupts();
+
+        let cmd_iterator = &mut self.register_block().comd_iter();
+
+        if framing.start {
+            // RSTART command
+            add_cmd(cmd_iterator, Command::Start)?;
+
+            if let (OperationType::Read, Address::TenBit(_)) = (operation, addr) {
+                // WRITE command for the full 10 bit address
+                add_cmd(
+                    cmd_iterator,
+                    Command::Write {
+                        ack_exp: Ack::Ack,
+                        ack_check_en: true,
+                        length: 2,
+                    },
+                )?;
+
+                // RSTART command to switch to reading
+                add_cmd(cmd_iterator, Command::Start)?;
+            }
+
+            // WRITE command for the address
+            let length = match operation {
+                OperationType::Write => addr.len(),
+                OperationType::Read => 1,
+            };
+            add_cmd(
+                cmd_iterator,
+                Command::Write {
+                    ack_exp: Ack::Ack,
+                    ack_check_en: true,
+                    length,
+                },
+            )?;
+        }
+
+        match operation {
+            OperationType::Write if len > 0 => {
+                add_cmd(
+                    cmd_iterator,
+                    Command::Write {
+                        ack_exp: Ack::Ack,
+                        ack_check_en: true,
+                        length: len as u8,
+                    },
+                )?;
+            }
+            OperationType::Write => {}
+            OperationType::Read if framing.nack => {
+                if len > 1 {
+                    // READ command (N - 1)
+                    add_cmd(
+                        cmd_iterator,
+                        Command::Read {
+                            ack_value: Ack::Ack,
+                            length: len as u8 - 1,
+                        },
+                    )?;
+                }
+
+                // READ w/o ACK
+                add_cmd(
+                    cmd_iterator,
+                    Command::Read {
+                        ack_value: Ack::Nack,
+                        length: 1,
+                    },
+                )?;
+            }
+            OperationType::Read => {
+                // the read continues in the next segment, ACK all bytes
+                add_cmd(
+                    cmd_iterator,
+                    Command::Read {
+                        ack_value: Ack::Ack,
+                        length: len as u8,
+                    },
+                )?;
+            }
+        }
+
+        if framing.stop {
+            add_cmd(cmd_iterator, Command::Stop)?;
+        }
+        if !(framing.start && framing.stop) {
+            // a STOP alone doesn't reliably signal the completion of a transaction
+            // which was paused before, so end with an END in that case
+            add_cmd(cmd_iterator, Command::End)?;
+        }
+
+        self.update_config();
+
+        if framing.start {
+            // Load address and R/W bit into FIFO
+            match (operation, addr) {
+                (OperationType::Write, Address::TenBit(low)) => {
+                    write_fifo(self.register_block(), addr.header(OperationType::Write));
+                    write_fifo(self.register_block(), low as u8);
+                }
+                (OperationType::Read, Address::TenBit(low)) => {
+                    // the full address is written first, the header is repeated with
+                    // the read bit after the repeated START
+                    write_fifo(self.register_block(), addr.header(OperationType::Write));
+                    write_fifo(self.register_block(), low as u8);
+                    write_fifo(self.register_block(), addr.header(OperationType::Read));
+                }
+                (operation, Address::SevenBit(_)) => {
+                    write_fifo(self.register_block(), addr.header(operation));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sends `bytes` as a part of a transaction, in segments of at most
+    /// [MAX_SEGMENT_SIZE] bytes
+    fn write_operation(
+        &self,
+        addr: Address,
+        bytes: &[u8],
+        framing: Framing,
+        deadline: &Deadline,
+    ) -> Result<(), Error> {
+        if bytes.is_empty() {
+            return self.write_segment(addr, bytes, framing, deadline);
+        }
+
+        let count = bytes.len().div_ceil(MAX_SEGMENT_SIZE);
+        for (index, segment) in bytes.chunks(MAX_SEGMENT_SIZE).enumerate() {
+            self.write_segment(addr, segment, framing.segment(index, count), deadline)?;
+        }
 
         Ok(())
     }
 
+    fn write_segment(
+        &self,
+        addr: Address,
+        bytes: &[u8],
+        framing: Framing,
+        deadline: &Deadline,
+    ) -> Result<(), Error> {
+        self.setup_segment(addr, OperationType::Write, bytes.len(), framing)?;
+        let index = self.fill_tx_fifo(bytes);
+        self.start_transmission();
+
+        self.write_remaining_tx_fifo(index, bytes, deadline)?;
+        self.wait_for_completion(deadline)
+    }
+
+    /// Receives `buffer.len()` bytes as a part of a transaction, in segments
+    /// of at most [MAX_SEGMENT_SIZE] bytes
+    fn read_operation(
+        &self,
+        addr: Address,
+        buffer: &mut [u8],
+        framing: Framing,
+        deadline: &Deadline,
+    ) -> Result<(), Error> {
+        let count = buffer.len().div_ceil(MAX_SEGMENT_SIZE);
+        for (index, segment) in buffer.chunks_mut(MAX_SEGMENT_SIZE).enumerate() {
+            self.read_segment(addr, segment, framing.segment(index, count), deadline)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_segment(
+        &self,
+        addr: Address,
+        buffer: &mut [u8],
+        framing: Framing,
+        deadline: &Deadline,
+    ) -> Result<(), Error> {
+        self.setup_segment(addr, OperationType::Read, buffer.len(), framing)?;
+        self.start_transmission();
+
+        self.read_all_from_fifo(buffer, deadline)?;
+        self.wait_for_completion(deadline)
+    }
+
     #[cfg(not(any(esp32, esp32s2)))]
-    fn read_all_from_fifo(&self, buffer: &mut [u8]) -> Result<(), Error> {
+    fn read_all_from_fifo(&self, buffer: &mut [u8], deadline: &Deadline) -> Result<(), Error> {
         // Read bytes from FIFO
         // FIXME: Handle case where less data has been provided by the slave than
         // requested? Or is this prevented from a protocol perspective?
         for byte in buffer.iter_mut() {
             loop {
                 self.check_errors()?;
+                self.check_deadline(deadline)?;
 
                 let reg = self.register_block().fifo_st().read();
                 if reg.rxfifo_raddr().bits() != reg.rxfifo_waddr().bits() {
@@ -1326,7 +2246,7 @@ pub trait Instance: crate::private::Sealed {
     }
 
     #[cfg(any(esp32, esp32s2))]
-    fn read_all_from_fifo(&self, buffer: &mut [u8]) -> Result<(), Error> {
+    fn read_all_from_fifo(&self, buffer: &mut [u8], deadline: &Deadline) -> Result<(), Error> {
         // on ESP32/ESP32-S2 we currently don't support I2C transactions larger than the
         // FIFO apparently it would be possible by using non-fifo mode
         // see https://github.com/espressif/arduino-esp32/blob/7e9afe8c5ed7b5bf29624a5cd6e07d431c027b97/cores/esp32/esp32-hal-i2c.c#L615
@@ -1338,7 +2258,7 @@ pub trait Instance: crate::private::Sealed {
         // wait for completion - then we can just read the data from FIFO
         // once we change to non-fifo mode to support larger transfers that
         // won't work anymore
-        self.wait_for_completion()?;
+        self.wait_for_completion(deadline)?;
 
         // Read bytes from FIFO
         // FIXME: Handle case where less data has been provided by the slave than
@@ -1356,11 +2276,12 @@ pub trait Instance: crate::private::Sealed {
             .write(|w| unsafe { w.bits(I2C_LL_INTR_MASK) });
     }
 
-    fn wait_for_completion(&self) -> Result<(), Error> {
+    fn wait_for_completion(&self, deadline: &Deadline) -> Result<(), Error> {
         loop {
             let interrupts = self.register_block().int_raw().read();
 
             self.check_errors()?;
+            self.check_deadline(deadline)?;
 
             // Handle completion cases
             // A full transmission was completed
@@ -1369,7 +2290,11 @@ pub trait Instance: crate::private::Sealed {
             }
         }
         for cmd in self.register_block().comd_iter() {
-            if cmd.read().command().bits() != 0x0 && cmd.read().command_done().bit_is_clear() {
+            let command = cmd.read().command().bits();
+            if command != 0x0
+                && !is_end_command(command)
+                && cmd.read().command_done().bit_is_clear()
+            {
                 return Err(Error::ExecIncomplete);
             }
         }
@@ -1386,26 +2311,26 @@ pub trait Instance: crate::private::Sealed {
             if #[cfg(esp32)] {
                 // Handle error cases
                 if interrupts.time_out().bit_is_set() {
-                    self.reset();
+                    self.reset_after_error();
                     return Err(Error::TimeOut);
                 } else if interrupts.ack_err().bit_is_set() {
                     self.reset();
                     return Err(Error::AckCheckFailed);
                 } else if interrupts.arbitration_lost().bit_is_set() {
-                    self.reset();
+                    self.reset_after_error();
                     return Err(Error::ArbitrationLost);
                 }
             }
             else {
                 // Handle error cases
                 if interrupts.time_out().bit_is_set() {
-                    self.reset();
+                    self.reset_after_error();
                     return Err(Error::TimeOut);
                 } else if interrupts.nack().bit_is_set() {
                     self.reset();
                     return Err(Error::AckCheckFailed);
                 } else if interrupts.arbitration_lost().bit_is_set() {
-                    self.reset();
+                    self.reset_after_error();
                     return Err(Error::ArbitrationLost);
                 } else if  interrupts.trans_complete().bit_is_set() && self.register_block().sr().read().resp_rec().bit_is_clear() {
                     self.reset();
@@ -1463,7 +2388,12 @@ pub trait Instance: crate::private::Sealed {
     }
 
     #[cfg(not(any(esp32, esp32s2)))]
-    fn write_remaining_tx_fifo(&self, start_index: usize, bytes: &[u8]) -> Result<(), Error> {
+    fn write_remaining_tx_fifo(
+        &self,
+        start_index: usize,
+        bytes: &[u8],
+        deadline: &Deadline,
+    ) -> Result<(), Error> {
         let mut index = start_index;
         loop {
             self.check_errors()?;
@@ -1474,7 +2404,9 @@ pub trait Instance: crate::private::Sealed {
                 .read()
                 .txfifo_wm()
                 .bit_is_set()
-            {}
+            {
+                self.check_deadline(deadline)?;
+            }
 
             self.register_block()
                 .int_clr()
@@ -1486,7 +2418,9 @@ pub trait Instance: crate::private::Sealed {
                 .read()
                 .txfifo_wm()
                 .bit_is_set()
-            {}
+            {
+                self.check_deadline(deadline)?;
+            }
 
             if index >= bytes.len() {
                 break Ok(());
@@ -1503,8 +2437,10 @@ pub trait Instance: crate::private::Sealed {
         // FIFO apparently it would be possible by using non-fifo mode
         // see  https://github.com/espressif/arduino-esp32/blob/7e9afe8c5ed7b5bf29624a5cd6e07d431c027b97/cores/esp32/esp32-hal-i2c.c#L615
 
-        if bytes.len() > 31 {
-            panic!("On ESP32 and ESP32-S2 the max I2C transfer is limited to 31 bytes");
+        // the address was loaded into the FIFO already
+        let queued = self.register_block().sr().read().txfifo_cnt().bits() as usize;
+        if bytes.len() > 32 - queued {
+            panic!("On ESP32 and ESP32-S2 the max I2C transfer is limited to 31 bytes, 30 bytes with a 10 bit address");
         }
 
         for b in bytes {
@@ -1515,7 +2451,12 @@ pub trait Instance: crate::private::Sealed {
     }
 
     #[cfg(any(esp32, esp32s2))]
-    fn write_remaining_tx_fifo(&self, start_index: usize, bytes: &[u8]) -> Result<(), Error> {
+    fn write_remaining_tx_fifo(
+        &self,
+        start_index: usize,
+        bytes: &[u8],
+        deadline: &Deadline,
+    ) -> Result<(), Error> {
         // on ESP32/ESP32-S2 we currently don't support I2C transactions larger than the
         // FIFO apparently it would be possible by using non-fifo mode
         // see  https://github.com/espressif/arduino-esp32/blob/7e9afe8c5ed7b5bf29624a5cd6e07d431c027b97/cores/esp32/esp32-hal-i2c.c#L615
@@ -1529,6 +2470,7 @@ pub trait Instance: crate::private::Sealed {
         for b in bytes {
             write_fifo(self.register_block(), *b);
             self.check_errors()?;
+            self.check_deadline(deadline)?;
         }
 
         Ok(())
@@ -1595,22 +2537,46 @@ pub trait Instance: crate::private::Sealed {
 
     /// Send data bytes from the `bytes` array to a target slave with the
     /// address `addr`
-    fn master_write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Error> {
+    fn master_write(
+        &mut self,
+        addr: Address,
+        bytes: &[u8],
+        deadline: &Deadline,
+    ) -> Result<(), Error> {
+        self.ensure_bus_idle()?;
+
         // Reset FIFO and command list
         self.reset_fifo();
         self.reset_command_list();
-        self.perform_write(addr, bytes, &mut self.register_block().comd_iter())?;
+        self.perform_write(
+            addr,
+            bytes,
+            &mut self.register_block().comd_iter(),
+            deadline,
+        )?;
         Ok(())
     }
 
     /// Read bytes from a target slave with the address `addr`
     /// The number of read bytes is deterimed by the size of the `buffer`
     /// argument
-    fn master_read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Error> {
+    fn master_read(
+        &mut self,
+        addr: Address,
+        buffer: &mut [u8],
+        deadline: &Deadline,
+    ) -> Result<(), Error> {
+        self.ensure_bus_idle()?;
+
         // Reset FIFO and command list
         self.reset_fifo();
         self.reset_command_list();
-        self.perform_read(addr, buffer, &mut self.register_block().comd_iter())?;
+        self.perform_read(
+            addr,
+            buffer,
+            &mut self.register_block().comd_iter(),
+            deadline,
+        )?;
         Ok(())
     }
 
@@ -1618,20 +2584,125 @@ pub trait Instance: crate::private::Sealed {
     /// the `buffer` array with n being the size of the array.
     fn master_write_read(
         &mut self,
-        addr: u8,
+        addr: Address,
+        bytes: &[u8],
+        buffer: &mut [u8],
+        deadline: &Deadline,
+    ) -> Result<(), Error> {
+        if buffer.is_empty() {
+            return self.master_write(addr, bytes, deadline);
+        }
+
+        self.ensure_bus_idle()?;
+
+        // the read follows the write after a repeated START, without releasing the
+        // bus in between
+        self.write_operation(addr, bytes, WRITE_BEFORE_READ, deadline)?;
+        self.read_operation(addr, buffer, READ_AFTER_WRITE, deadline)
+    }
+
+    /// Write `bytes` and read a block preceded by its length into `buffer`,
+    /// followed by `trailer`, as a single transaction. Returns the length.
+    fn master_write_read_block(
+        &mut self,
+        addr: Address,
         bytes: &[u8],
         buffer: &mut [u8],
+        trailer: &mut [u8],
+        deadline: &Deadline,
+    ) -> Result<usize, Error> {
+        self.ensure_bus_idle()?;
+
+        self.write_operation(addr, bytes, WRITE_BEFORE_READ, deadline)?;
+
+        // the bus is paused after the count, until it's known how many bytes follow
+        let mut count = [0u8; 1];
+        self.read_operation(addr, &mut count, READ_BLOCK_COUNT, deadline)?;
+        let count = count[0] as usize;
+
+        if count > buffer.len() || count + trailer.len() == 0 {
+            // the count was ACKed already, a NACKed byte ends the read
+            self.read_operation(addr, &mut [0u8; 1], READ_END, deadline)?;
+            return Ok(count);
+        }
+
+        let block = &mut buffer[..count];
+        if trailer.is_empty() {
+            self.read_operation(addr, block, READ_END, deadline)?;
+        } else {
+            if !block.is_empty() {
+                self.read_operation(addr, block, READ_CONTINUE, deadline)?;
+            }
+            self.read_operation(addr, trailer, READ_END, deadline)?;
+        }
+
+        Ok(count)
+    }
+
+    /// Execute the `operations` of an embedded-hal transaction with the slave
+    /// with the address `addr`, as a single transaction on the bus
+    #[cfg(feature = "embedded-hal")]
+    fn transaction(
+        &mut self,
+        addr: Address,
+        operations: &mut [embedded_hal::i2c::Operation<'_>],
+        deadline: &Deadline,
     ) -> Result<(), Error> {
-        // it would be possible to combine the write and read
-        // in one transaction but filling the tx fifo with
-        // the current code is somewhat slow even in release mode
-        // which can cause issues
-        self.master_write(addr, bytes)?;
-        self.master_read(addr, buffer)?;
+        use embedded_hal::i2c::Operation;
+
+        self.ensure_bus_idle()?;
+
+        for index in 0..operations.len() {
+            let Some(framing) = operation_framing(operations, index) else {
+                continue;
+            };
+
+            match &mut operations[index] {
+                Operation::Write(bytes) => self.write_operation(addr, bytes, framing, deadline)?,
+                Operation::Read(buffer) => self.read_operation(addr, buffer, framing, deadline)?,
+            }
+        }
+
         Ok(())
     }
 }
 
+/// The raw SCL low timeout for `timeout`, with `sclk` the frequency of the
+/// clock the bus timing is derived from
+#[cfg(any(esp32, esp32s2))]
+fn scl_timeout_value(sclk: u32, timeout: MicrosDurationU32) -> u32 {
+    #[cfg(esp32)]
+    const MAX_SCL_TIMEOUT: u64 = 0xf_ffff;
+    #[cfg(esp32s2)]
+    const MAX_SCL_TIMEOUT: u64 = 0xff_ffff;
+
+    let cycles = sclk as u64 * timeout.ticks() as u64 / 1_000_000;
+    cycles.min(MAX_SCL_TIMEOUT) as u32
+}
+
+/// The raw SCL low timeout for `timeout`, with `sclk` the frequency of the
+/// clock the bus timing is derived from
+#[cfg(not(any(esp32, esp32s2)))]
+fn scl_timeout_value(sclk: u32, timeout: MicrosDurationU32) -> u32 {
+    // the timeout is 2^value clock cycles
+    const MAX_SCL_TIMEOUT: u32 = 31;
+
+    let cycles = (sclk as u64 * timeout.ticks() as u64 / 1_000_000).max(1);
+    let value = 64 - (cycles - 1).leading_zeros();
+    value.min(MAX_SCL_TIMEOUT)
+}
+
+/// Polls `done` every microsecond until it returns true, for at most
+/// `timeout_us` microseconds
+fn wait_us(timeout_us: u32, done: impl Fn() -> bool) {
+    for _ in 0..timeout_us {
+        if done() {
+            break;
+        }
+        crate::rom::ets_delay_us(1);
+    }
+}
+
 fn add_cmd<'a, I>(cmd_iterator: &mut I, command: Command) -> Result<(), Error>
 where
     I: Iterator<Item = &'a COMD>,
@@ -1760,6 +2831,12 @@ impl Instance for crate::peripherals::I2C1 {
 #[cfg(lp_i2c0)]
 pub mod lp_i2c {
     //! Low-power I2C driver
+    //!
+    //! The LP I2C master lives in the low-power domain, so the LP core can poll
+    //! sensors while the HP core is in deep sleep. It's configured here and
+    //! then handed to the LP core program, which drives the transactions
+    //! with the `i2c` module of `esp-lp-hal`. SDA and SCL are fixed to
+    //! GPIO6 and GPIO7.
 
     use fugit::HertzU32;
 
@@ -1957,6 +3034,7 @@ pub mod lp_i2c {
     // Configure LP_EXT_I2C_CK_EN high to enable the clock source of I2C_SCLK.
     // Adjust the timing registers accordingly when the clock frequency changes.
 
+    /// LP I2C master, to be passed to the LP core program
     pub struct LpI2c {
         i2c: LP_I2C0,
     }
@@ -1968,7 +3046,7 @@ pub mod lp_i2c {
             _scl: LowPowerPin<OpenDrain, 7>,
             frequency: HertzU32,
         ) -> Self {
-            let me = Self { i2c };
+            let mut me = Self { i2c };
 
             // Configure LP I2C GPIOs
             // Initialize IO Pins
@@ -2069,6 +3147,40 @@ pub mod lp_i2c {
                 .lpperi()
                 .modify(|_, w| w.lp_i2c_clk_sel().clear_bit());
 
+            me.set_frequency(frequency);
+
+            // Enable SDA and SCL filtering. This configuration matches the HP I2C filter
+            // config
+
+            me.i2c
+                .filter_cfg()
+                .modify(|_, w| unsafe { w.sda_filter_thres().bits(LP_I2C_FILTER_CYC_NUM_DEF) });
+            me.i2c
+                .filter_cfg()
+                .modify(|_, w| unsafe { w.scl_filter_thres().bits(LP_I2C_FILTER_CYC_NUM_DEF) });
+
+            me.i2c
+                .filter_cfg()
+                .modify(|_, w| w.sda_filter_en().set_bit());
+            me.i2c
+                .filter_cfg()
+                .modify(|_, w| w.scl_filter_en().set_bit());
+
+            // Configure the I2C master to send a NACK when the Rx FIFO count is full
+            me.i2c.ctr().modify(|_, w| w.rx_full_ack_level().set_bit());
+
+            // Synchronize the config register values to the LP I2C peripheral clock
+            me.lp_i2c_update();
+
+            me
+        }
+
+        /// Change the bus frequency.
+        ///
+        /// This reconfigures the bus timing, including the timeout of about 10
+        /// bus cycles. It must not be called while the LP core uses the
+        /// peripheral.
+        pub fn set_frequency(&mut self, frequency: HertzU32) {
             // Configure LP I2C timing paramters. source_clk is ignored for LP_I2C in this
             // call
 
@@ -2123,7 +3235,7 @@ pub mod lp_i2c {
 
             // Write data to registers
             unsafe {
-                me.i2c.clk_conf().modify(|_, w| {
+                self.i2c.clk_conf().modify(|_, w| {
                     w.sclk_sel()
                         .clear_bit()
                         .sclk_div_num()
@@ -2131,41 +3243,41 @@ pub mod lp_i2c {
                 });
 
                 // scl period
-                me.i2c
+                self.i2c
                     .scl_low_period()
                     .write(|w| w.scl_low_period().bits(scl_low_period as u16));
 
-                me.i2c.scl_high_period().write(|w| {
+                self.i2c.scl_high_period().write(|w| {
                     w.scl_high_period()
                         .bits(scl_high_period as u16)
                         .scl_wait_high_period()
                         .bits(scl_wait_high_period as u8)
                 });
                 // sda sample
-                me.i2c
+                self.i2c
                     .sda_hold()
                     .write(|w| w.time().bits(sda_hold_time as u16));
-                me.i2c
+                self.i2c
                     .sda_sample()
                     .write(|w| w.time().bits(sda_sample_time as u16));
 
                 // setup
-                me.i2c
+                self.i2c
                     .scl_rstart_setup()
                     .write(|w| w.time().bits(scl_rstart_setup_time as u16));
-                me.i2c
+                self.i2c
                     .scl_stop_setup()
                     .write(|w| w.time().bits(scl_stop_setup_time as u16));
 
                 // hold
-                me.i2c
+                self.i2c
                     .scl_start_hold()
                     .write(|w| w.time().bits(scl_start_hold_time as u16));
-                me.i2c
+                self.i2c
                     .scl_stop_hold()
                     .write(|w| w.time().bits(scl_stop_hold_time as u16));
 
-                me.i2c.to().write(|w| {
+                self.i2c.to().write(|w| {
                     w.time_out_en()
                         .bit(time_out_en)
                         .time_out_value()
@@ -2173,30 +3285,7 @@ pub mod lp_i2c {
                 });
             }
 
-            // Enable SDA and SCL filtering. This configuration matches the HP I2C filter
-            // config
-
-            me.i2c
-                .filter_cfg()
-                .modify(|_, w| unsafe { w.sda_filter_thres().bits(LP_I2C_FILTER_CYC_NUM_DEF) });
-            me.i2c
-                .filter_cfg()
-                .modify(|_, w| unsafe { w.scl_filter_thres().bits(LP_I2C_FILTER_CYC_NUM_DEF) });
-
-            me.i2c
-                .filter_cfg()
-                .modify(|_, w| w.sda_filter_en().set_bit());
-            me.i2c
-                .filter_cfg()
-                .modify(|_, w| w.scl_filter_en().set_bit());
-
-            // Configure the I2C master to send a NACK when the Rx FIFO count is full
-            me.i2c.ctr().modify(|_, w| w.rx_full_ack_level().set_bit());
-
-            // Synchronize the config register values to the LP I2C peripheral clock
-            me.lp_i2c_update();
-
-            me
+            self.lp_i2c_update();
         }
 
         /// Update I2C configuration
@@ -0,0 +1,600 @@
+//! # I2C slave driver
+//!
+//! ## Overview
+//!
+//! In slave mode the I2C peripheral answers to its own address on the bus, so
+//! the chip can emulate an I2C device or act as a co-processor for another
+//! controller.
+//!
+//! The I2C peripherals aren't connected to the DMA controllers, data goes
+//! through the 32 byte RX and TX FIFOs. Transactions are handled in one of
+//! these ways:
+//!
+//! - [I2cSlave::receive] and [I2cSlave::transmit] block until the master ended
+//!   the next transaction with a STOP condition, moving data between the FIFOs
+//!   and the given buffers while it's running.
+//! - With the `async` feature, [I2cSlave::receive_async] and
+//!   [I2cSlave::transmit_async] do the same, woken by the FIFO watermark and
+//!   transaction complete interrupts.
+//! - For a callback based driver, bind a handler with
+//!   [I2cSlave::set_interrupt_handler], enable the [Event]s of interest with
+//!   [I2cSlave::listen] and move the data with [I2cSlave::read_fifo] and
+//!   [I2cSlave::write_fifo] in the handler.
+//!
+//! The ESP32-C6 and ESP32-H2 can also answer to the general call address, see
+//! [I2cSlave::set_general_call].
+//!
+//! ## Example
+//! ```no_run
+//! let mut i2c = I2cSlave::new(
+//!     peripherals.I2C0,
+//!     io.pins.gpio1,
+//!     io.pins.gpio2,
+//!     SlaveAddress::SevenBit(0x42),
+//!     100.kHz(),
+//!     &clocks,
+//! );
+//!
+//! let mut command = [0u8; 8];
+//! loop {
+//!     let len = i2c.receive(&mut command).unwrap();
+//!     if i2c.last_transaction_was_read() {
+//!         // the master read, there was no response queued
+//!         continue;
+//!     }
+//!
+//!     let response = handle(&command[..len]);
+//!     i2c.transmit(&response).unwrap();
+//! }
+//! ```
+
+use core::marker::PhantomData;
+
+use enumset::{EnumSet, EnumSetType};
+use fugit::HertzU32;
+
+use super::{read_fifo, write_fifo, Error, Instance};
+use crate::{
+    clock::Clocks,
+    gpio::{InputPin, OutputPin},
+    interrupt::InterruptHandler,
+    peripheral::{Peripheral, PeripheralRef},
+    peripherals::i2c0::RegisterBlock,
+    system::PeripheralClockControl,
+};
+
+/// The size of the RX and TX FIFOs
+const FIFO_SIZE: usize = 32;
+
+/// The address the slave answers to
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SlaveAddress {
+    /// A 7 bit address
+    SevenBit(u8),
+    /// A 10 bit address
+    TenBit(u16),
+}
+
+/// I2C slave interrupts
+#[derive(EnumSetType, Debug)]
+pub enum Event {
+    /// The RX FIFO holds at least one byte
+    RxFifoWatermark,
+    /// The TX FIFO holds 8 bytes or less
+    TxFifoWatermark,
+    /// The master ended a transaction with a STOP condition
+    TransactionComplete,
+    /// A received byte was lost because the RX FIFO was full
+    RxFifoOverflow,
+}
+
+/// I2C peripheral in slave mode
+pub struct I2cSlave<'d, T, DM: crate::Mode> {
+    peripheral: PeripheralRef<'d, T>,
+    phantom: PhantomData<DM>,
+}
+
+impl<'d, T, DM: crate::Mode> I2cSlave<'d, T, DM>
+where
+    T: Instance,
+{
+    fn new_internal<SDA: OutputPin + InputPin, SCL: OutputPin + InputPin>(
+        i2c: impl Peripheral<P = T> + 'd,
+        sda: impl Peripheral<P = SDA> + 'd,
+        scl: impl Peripheral<P = SCL> + 'd,
+        address: SlaveAddress,
+        frequency: HertzU32,
+        clocks: &Clocks,
+        isr: Option<InterruptHandler>,
+    ) -> Self {
+        crate::into_ref!(i2c, sda, scl);
+
+        PeripheralClockControl::enable(match i2c.i2c_number() {
+            0 => crate::system::Peripheral::I2cExt0,
+            #[cfg(i2c1)]
+            1 => crate::system::Peripheral::I2cExt1,
+            _ => unreachable!(), // will never happen
+        });
+
+        let mut i2c = I2cSlave {
+            peripheral: i2c,
+            phantom: PhantomData,
+        };
+
+        scl.set_to_open_drain_output()
+            .enable_input(true)
+            .internal_pull_up(true)
+            .connect_peripheral_to_output(i2c.peripheral.scl_output_signal())
+            .connect_input_to_peripheral(i2c.peripheral.scl_input_signal());
+
+        sda.set_to_open_drain_output()
+            .enable_input(true)
+            .internal_pull_up(true)
+            .connect_peripheral_to_output(i2c.peripheral.sda_output_signal())
+            .connect_input_to_peripheral(i2c.peripheral.sda_input_signal());
+
+        // the bus timing is used for sampling and holding SDA
+        i2c.peripheral.setup(frequency, clocks, None);
+
+        i2c.register_block()
+            .ctr()
+            .modify(|_, w| w.ms_mode().clear_bit().slv_tx_auto_start_en().set_bit());
+        i2c.set_address(address);
+
+        if let Some(interrupt) = isr {
+            unsafe {
+                crate::interrupt::bind_interrupt(T::interrupt(), interrupt.handler());
+                crate::interrupt::enable(T::interrupt(), interrupt.priority()).unwrap();
+            }
+        }
+
+        i2c
+    }
+
+    fn register_block(&self) -> &RegisterBlock {
+        self.peripheral.register_block()
+    }
+
+    /// Change the address the slave answers to.
+    pub fn set_address(&mut self, address: SlaveAddress) {
+        let (address, ten_bit) = match address {
+            SlaveAddress::SevenBit(address) => (address as u16 & 0x7f, false),
+            SlaveAddress::TenBit(address) => (address & 0x3ff, true),
+        };
+
+        self.register_block()
+            .slave_addr()
+            .write(|w| unsafe { w.slave_addr().bits(address).addr_10bit_en().bit(ten_bit) });
+
+        self.peripheral.update_config();
+    }
+
+    /// Also answer to the general call address (0x00).
+    #[cfg(any(esp32c6, esp32h2))]
+    pub fn set_general_call(&mut self, enable: bool) {
+        self.register_block()
+            .ctr()
+            .modify(|_, w| w.addr_broadcasting_en().bit(enable));
+
+        self.peripheral.update_config();
+    }
+
+    /// Whether the master read from the slave in the last transaction, or
+    /// wrote to it.
+    pub fn last_transaction_was_read(&self) -> bool {
+        self.register_block().sr().read().slave_rw().bit_is_set()
+    }
+
+    /// Read the received bytes from the RX FIFO into `buffer`.
+    ///
+    /// Returns the number of bytes read.
+    pub fn read_fifo(&mut self, buffer: &mut [u8]) -> usize {
+        let available = self.register_block().sr().read().rxfifo_cnt().bits() as usize;
+        let count = usize::min(available, buffer.len());
+
+        for byte in buffer[..count].iter_mut() {
+            *byte = read_fifo(self.register_block());
+        }
+
+        self.register_block()
+            .int_clr()
+            .write(|w| w.rxfifo_wm().clear_bit_by_one());
+
+        count
+    }
+
+    /// Queue as many bytes of `data` as fit into the TX FIFO, to be read by
+    /// the master.
+    ///
+    /// Returns the number of bytes queued.
+    pub fn write_fifo(&mut self, data: &[u8]) -> usize {
+        let queued = self.register_block().sr().read().txfifo_cnt().bits() as usize;
+        let count = usize::min(FIFO_SIZE - queued, data.len());
+
+        for byte in &data[..count] {
+            write_fifo(self.register_block(), *byte);
+        }
+
+        self.register_block()
+            .int_clr()
+            .write(|w| w.txfifo_wm().clear_bit_by_one());
+
+        count
+    }
+
+    /// Discard the bytes queued in the TX FIFO.
+    pub fn clear_tx_fifo(&mut self) {
+        self.register_block()
+            .fifo_conf()
+            .modify(|_, w| w.tx_fifo_rst().set_bit());
+        self.register_block()
+            .fifo_conf()
+            .modify(|_, w| w.tx_fifo_rst().clear_bit());
+    }
+
+    /// Receive the data of the next transaction in which the master writes to
+    /// the slave, blocking until the master ended it.
+    ///
+    /// Returns the number of bytes received, which is 0 if the master read
+    /// instead. Bytes which don't fit into `buffer` are dropped.
+    pub fn receive(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+        self.clear_interrupts(Event::TransactionComplete.into());
+
+        let mut count = 0;
+        loop {
+            let done = self.interrupts().contains(Event::TransactionComplete);
+            count += self.read_fifo_discarding(&mut buffer[count..]);
+            self.check_rx_overflow()?;
+
+            if done {
+                self.clear_interrupts(Event::TransactionComplete.into());
+                return Ok(count);
+            }
+        }
+    }
+
+    /// Send `data` in the next transaction in which the master reads from the
+    /// slave, blocking until the master ended it.
+    ///
+    /// Returns the number of bytes the master read. Bytes which weren't read
+    /// are discarded.
+    pub fn transmit(&mut self, data: &[u8]) -> Result<usize, Error> {
+        self.clear_tx_fifo();
+        self.clear_interrupts(Event::TransactionComplete.into());
+
+        let mut queued = 0;
+        loop {
+            let done = self.interrupts().contains(Event::TransactionComplete);
+            if done {
+                self.clear_interrupts(Event::TransactionComplete.into());
+                return Ok(self.finish_transmit(queued));
+            }
+
+            queued += self.write_fifo(&data[queued..]);
+        }
+    }
+
+    /// Read the RX FIFO into `buffer`, dropping the bytes which don't fit
+    fn read_fifo_discarding(&mut self, buffer: &mut [u8]) -> usize {
+        let count = self.read_fifo(buffer);
+
+        let mut discard = [0u8; FIFO_SIZE];
+        while self.read_fifo(&mut discard) > 0 {}
+
+        count
+    }
+
+    fn check_rx_overflow(&mut self) -> Result<(), Error> {
+        if self.interrupts().contains(Event::RxFifoOverflow) {
+            self.clear_interrupts(Event::RxFifoOverflow.into());
+            Err(Error::ExceedingFifo)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The number of queued bytes the master read, discards the others
+    fn finish_transmit(&mut self, queued: usize) -> usize {
+        let left = self.register_block().sr().read().txfifo_cnt().bits() as usize;
+        self.clear_tx_fifo();
+
+        queued.saturating_sub(left)
+    }
+
+    /// Listen for the given interrupts
+    pub fn listen(&mut self, events: EnumSet<Event>) {
+        self.register_block().int_ena().modify(|_, w| {
+            for event in events {
+                match event {
+                    Event::RxFifoWatermark => w.rxfifo_wm().set_bit(),
+                    Event::TxFifoWatermark => w.txfifo_wm().set_bit(),
+                    Event::TransactionComplete => w.trans_complete().set_bit(),
+                    Event::RxFifoOverflow => w.rxfifo_ovf().set_bit(),
+                };
+            }
+            w
+        });
+    }
+
+    /// Unlisten the given interrupts
+    pub fn unlisten(&mut self, events: EnumSet<Event>) {
+        self.register_block().int_ena().modify(|_, w| {
+            for event in events {
+                match event {
+                    Event::RxFifoWatermark => w.rxfifo_wm().clear_bit(),
+                    Event::TxFifoWatermark => w.txfifo_wm().clear_bit(),
+                    Event::TransactionComplete => w.trans_complete().clear_bit(),
+                    Event::RxFifoOverflow => w.rxfifo_ovf().clear_bit(),
+                };
+            }
+            w
+        });
+    }
+
+    /// Gets asserted interrupts
+    pub fn interrupts(&mut self) -> EnumSet<Event> {
+        let mut res = EnumSet::new();
+        let ints = self.register_block().int_raw().read();
+
+        if ints.rxfifo_wm().bit_is_set() {
+            res.insert(Event::RxFifoWatermark);
+        }
+        if ints.txfifo_wm().bit_is_set() {
+            res.insert(Event::TxFifoWatermark);
+        }
+        if ints.trans_complete().bit_is_set() {
+            res.insert(Event::TransactionComplete);
+        }
+        if ints.rxfifo_ovf().bit_is_set() {
+            res.insert(Event::RxFifoOverflow);
+        }
+
+        res
+    }
+
+    /// Resets asserted interrupts
+    pub fn clear_interrupts(&mut self, events: EnumSet<Event>) {
+        self.register_block().int_clr().write(|w| {
+            for event in events {
+                match event {
+                    Event::RxFifoWatermark => w.rxfifo_wm().clear_bit_by_one(),
+                    Event::TxFifoWatermark => w.txfifo_wm().clear_bit_by_one(),
+                    Event::TransactionComplete => w.trans_complete().clear_bit_by_one(),
+                    Event::RxFifoOverflow => w.rxfifo_ovf().clear_bit_by_one(),
+                };
+            }
+            w
+        });
+    }
+}
+
+impl<'d, T> I2cSlave<'d, T, crate::Blocking>
+where
+    T: Instance,
+{
+    /// Create a new I2C slave answering to `address`.
+    ///
+    /// `frequency` is the expected bus frequency, which the SDA sample and
+    /// hold times are derived from.
+    pub fn new<SDA: OutputPin + InputPin, SCL: OutputPin + InputPin>(
+        i2c: impl Peripheral<P = T> + 'd,
+        sda: impl Peripheral<P = SDA> + 'd,
+        scl: impl Peripheral<P = SCL> + 'd,
+        address: SlaveAddress,
+        frequency: HertzU32,
+        clocks: &Clocks,
+    ) -> Self {
+        Self::new_internal(i2c, sda, scl, address, frequency, clocks, None)
+    }
+
+    /// Sets the interrupt handler, enables it with
+    /// [crate::interrupt::Priority::min()]
+    ///
+    /// Interrupts are not enabled at the peripheral level here.
+    pub fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        unsafe {
+            crate::interrupt::bind_interrupt(T::interrupt(), handler.handler());
+            crate::interrupt::enable(T::interrupt(), handler.priority()).unwrap();
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+impl<'d, T> I2cSlave<'d, T, crate::Async>
+where
+    T: Instance,
+{
+    /// Create a new I2C slave answering to `address`, for async
+    /// transactions.
+    ///
+    /// `frequency` is the expected bus frequency, which the SDA sample and
+    /// hold times are derived from.
+    pub fn new_async<SDA: OutputPin + InputPin, SCL: OutputPin + InputPin>(
+        i2c: impl Peripheral<P = T> + 'd,
+        sda: impl Peripheral<P = SDA> + 'd,
+        scl: impl Peripheral<P = SCL> + 'd,
+        address: SlaveAddress,
+        frequency: HertzU32,
+        clocks: &Clocks,
+    ) -> Self {
+        let handler = Some(match T::I2C_NUMBER {
+            0 => asynch::i2c0_slave_handler,
+            #[cfg(i2c1)]
+            1 => asynch::i2c1_slave_handler,
+            _ => panic!("Unexpected I2C peripheral"),
+        });
+        Self::new_internal(i2c, sda, scl, address, frequency, clocks, handler)
+    }
+}
+
+#[cfg(feature = "async")]
+mod asynch {
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use embassy_sync::waitqueue::AtomicWaker;
+    use procmacros::handler;
+
+    use super::*;
+
+    #[cfg(i2c1)]
+    const NUM_I2C: usize = 2;
+    #[cfg(not(i2c1))]
+    const NUM_I2C: usize = 1;
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: AtomicWaker = AtomicWaker::new();
+    static WAKERS: [AtomicWaker; NUM_I2C] = [INIT; NUM_I2C];
+
+    /// Resolves when one of the events occurred
+    struct SlaveFuture<'a, T>
+    where
+        T: Instance,
+    {
+        instance: &'a T,
+    }
+
+    impl<'a, T> SlaveFuture<'a, T>
+    where
+        T: Instance,
+    {
+        fn new(instance: &'a T, events: EnumSet<Event>) -> Self {
+            instance.register_block().int_ena().modify(|_, w| {
+                for event in events {
+                    match event {
+                        Event::RxFifoWatermark => w.rxfifo_wm().set_bit(),
+                        Event::TxFifoWatermark => w.txfifo_wm().set_bit(),
+                        Event::TransactionComplete => w.trans_complete().set_bit(),
+                        Event::RxFifoOverflow => w.rxfifo_ovf().set_bit(),
+                    };
+                }
+                w
+            });
+
+            Self { instance }
+        }
+    }
+
+    impl<'a, T> core::future::Future for SlaveFuture<'a, T>
+    where
+        T: Instance,
+    {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+            WAKERS[self.instance.i2c_number()].register(ctx.waker());
+
+            // the interrupt handler disables all interrupts once one fired
+            let ena = self.instance.register_block().int_ena().read();
+            if ena.rxfifo_wm().bit_is_clear()
+                && ena.txfifo_wm().bit_is_clear()
+                && ena.trans_complete().bit_is_clear()
+                && ena.rxfifo_ovf().bit_is_clear()
+            {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl<'a, T> Drop for SlaveFuture<'a, T>
+    where
+        T: Instance,
+    {
+        fn drop(&mut self) {
+            disable_interrupts(self.instance.register_block());
+        }
+    }
+
+    impl<'d, T> I2cSlave<'d, T, crate::Async>
+    where
+        T: Instance,
+    {
+        /// Receive the data of the next transaction in which the master
+        /// writes to the slave, waiting until the master ended it.
+        ///
+        /// Returns the number of bytes received, which is 0 if the master
+        /// read instead. Bytes which don't fit into `buffer` are dropped.
+        pub async fn receive_async(&mut self, buffer: &mut [u8]) -> Result<usize, Error> {
+            self.clear_interrupts(Event::TransactionComplete.into());
+
+            let mut count = 0;
+            loop {
+                let done = self.interrupts().contains(Event::TransactionComplete);
+                count += self.read_fifo_discarding(&mut buffer[count..]);
+                self.check_rx_overflow()?;
+
+                if done {
+                    self.clear_interrupts(Event::TransactionComplete.into());
+                    return Ok(count);
+                }
+
+                SlaveFuture::new(
+                    &*self.peripheral,
+                    Event::RxFifoWatermark | Event::TransactionComplete | Event::RxFifoOverflow,
+                )
+                .await;
+            }
+        }
+
+        /// Send `data` in the next transaction in which the master reads from
+        /// the slave, waiting until the master ended it.
+        ///
+        /// Returns the number of bytes the master read. Bytes which weren't
+        /// read are discarded.
+        pub async fn transmit_async(&mut self, data: &[u8]) -> Result<usize, Error> {
+            self.clear_tx_fifo();
+            self.clear_interrupts(Event::TransactionComplete.into());
+
+            let mut queued = 0;
+            loop {
+                let done = self.interrupts().contains(Event::TransactionComplete);
+                if done {
+                    self.clear_interrupts(Event::TransactionComplete.into());
+                    return Ok(self.finish_transmit(queued));
+                }
+
+                queued += self.write_fifo(&data[queued..]);
+
+                let events = if queued < data.len() {
+                    Event::TxFifoWatermark | Event::TransactionComplete
+                } else {
+                    Event::TransactionComplete.into()
+                };
+                SlaveFuture::new(&*self.peripheral, events).await;
+            }
+        }
+    }
+
+    fn disable_interrupts(register_block: &RegisterBlock) {
+        register_block.int_ena().modify(|_, w| {
+            w.rxfifo_wm()
+                .clear_bit()
+                .txfifo_wm()
+                .clear_bit()
+                .trans_complete()
+                .clear_bit()
+                .rxfifo_ovf()
+                .clear_bit()
+        });
+    }
+
+    #[handler]
+    pub(super) fn i2c0_slave_handler() {
+        disable_interrupts(unsafe { &*crate::peripherals::I2C0::PTR });
+
+        WAKERS[0].wake();
+    }
+
+    #[cfg(i2c1)]
+    #[handler]
+    pub(super) fn i2c1_slave_handler() {
+        disable_interrupts(unsafe { &*crate::peripherals::I2C1::PTR });
+
+        WAKERS[1].wake();
+    }
+}
@@ -0,0 +1,393 @@
+//! # SMBus
+//!
+//! ## Overview
+//! SMBus is a protocol on top of I2C, used e.g. by battery fuel gauges, PMBus
+//! power supplies and temperature sensors. [SmBus] wraps an I2C master and
+//! implements the SMBus transactions on top of it. Optionally it appends the
+//! Packet Error Code (PEC), a CRC-8 over all bytes of a transaction, to writes
+//! and checks it on reads.
+//!
+//! The length of a block is only known once it's received, so block reads
+//! ([SmBus::block_read] and [SmBus::block_process_call]) need an I2C master
+//! which reads the length first and then exactly as many bytes, in the same
+//! transaction, see [BlockRead].
+//!
+//! Devices send Host Notify messages as a master to [HOST_ADDRESS]. Receive
+//! them with an I2C slave answering to that address and decode them with
+//! [HostNotify::parse].
+//!
+//! ## Example
+//! ```no_run
+//! let i2c = I2C::new(
+//!     peripherals.I2C0,
+//!     io.pins.gpio1,
+//!     io.pins.gpio2,
+//!     100.kHz(),
+//!     &clocks,
+//!     None,
+//! );
+//!
+//! let mut smbus = SmBus::new(i2c);
+//! smbus.set_pec(true);
+//!
+//! // read the voltage and the manufacturer name of a smart battery
+//! let voltage = smbus.read_word(0x0b, 0x09).unwrap();
+//! let mut name = [0u8; 32];
+//! let len = smbus.block_read(0x0b, 0x20, &mut name).unwrap();
+//! ```
+
+use embedded_hal::i2c::I2c;
+
+use super::{Instance, I2C};
+
+/// The address of the SMBus host, devices send Host Notify messages to it
+pub const HOST_ADDRESS: u8 = 0x08;
+
+/// The maximum length of a block
+pub const MAX_BLOCK_SIZE: usize = 32;
+
+/// SMBus errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error<E> {
+    /// The I2C transfer failed
+    I2c(E),
+    /// The received PEC doesn't match the received data
+    PecMismatch,
+    /// A block is longer than [MAX_BLOCK_SIZE] or doesn't fit into the buffer
+    InvalidBlockLength,
+}
+
+/// Calculates the PEC over the concatenation of `parts`.
+///
+/// The PEC is a CRC-8 with the polynomial x^8 + x^2 + x + 1. It covers all
+/// bytes of a transaction, including the address bytes with their R/W bits.
+pub fn pec(parts: &[&[u8]]) -> u8 {
+    let mut crc = 0u8;
+    for byte in parts.iter().flat_map(|part| part.iter()) {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 {
+                (crc << 1) ^ 0x07
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// I2C masters which can do block reads
+pub trait BlockRead: I2c {
+    /// Write `data`, then read a byte count followed by exactly that many
+    /// bytes into `buffer` and `trailer.len()` more bytes into `trailer`, in a
+    /// single transaction.
+    ///
+    /// Returns the received count. If it's larger than `buffer`, nothing
+    /// else is read.
+    fn write_read_block(
+        &mut self,
+        address: u8,
+        data: &[u8],
+        buffer: &mut [u8],
+        trailer: &mut [u8],
+    ) -> Result<usize, Self::Error>;
+}
+
+impl<T, DM> BlockRead for I2C<'_, T, DM>
+where
+    T: Instance,
+    DM: crate::Mode,
+{
+    fn write_read_block(
+        &mut self,
+        address: u8,
+        data: &[u8],
+        buffer: &mut [u8],
+        trailer: &mut [u8],
+    ) -> Result<usize, Self::Error> {
+        I2C::write_read_block(self, address, data, buffer, trailer)
+    }
+}
+
+/// A Host Notify message
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct HostNotify {
+    /// The address of the device which sent the message
+    pub address: u8,
+    /// The status reported by the device
+    pub data: u16,
+}
+
+impl HostNotify {
+    /// Decodes a message received at [HOST_ADDRESS].
+    ///
+    /// Returns `None` if the message doesn't have the length of a Host
+    /// Notify message.
+    pub fn parse(message: &[u8]) -> Option<Self> {
+        match *message {
+            [address, low, high] => Some(Self {
+                address: address >> 1,
+                data: u16::from_le_bytes([low, high]),
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// SMBus transactions on top of an I2C master
+pub struct SmBus<I> {
+    i2c: I,
+    pec: bool,
+}
+
+impl<I> SmBus<I>
+where
+    I: I2c,
+{
+    /// Wrap an I2C master, without PEC.
+    pub fn new(i2c: I) -> Self {
+        Self { i2c, pec: false }
+    }
+
+    /// Enable or disable appending and checking the PEC.
+    pub fn set_pec(&mut self, enable: bool) {
+        self.pec = enable;
+    }
+
+    /// Return the I2C master.
+    pub fn free(self) -> I {
+        self.i2c
+    }
+
+    /// Send a single byte to the device.
+    pub fn send_byte(&mut self, address: u8, data: u8) -> Result<(), Error<I::Error>> {
+        self.write(address, &[data])
+    }
+
+    /// Receive a single byte from the device.
+    pub fn receive_byte(&mut self, address: u8) -> Result<u8, Error<I::Error>> {
+        let mut received = [0u8; 2];
+        let len = 1 + self.pec as usize;
+        self.i2c
+            .read(address, &mut received[..len])
+            .map_err(Error::I2c)?;
+
+        if self.pec && pec(&[&[read_header(address)], &received[..1]]) != received[1] {
+            return Err(Error::PecMismatch);
+        }
+
+        Ok(received[0])
+    }
+
+    /// Write a byte to the register `command`.
+    pub fn write_byte(
+        &mut self,
+        address: u8,
+        command: u8,
+        data: u8,
+    ) -> Result<(), Error<I::Error>> {
+        self.write(address, &[command, data])
+    }
+
+    /// Write a word to the register `command`.
+    pub fn write_word(
+        &mut self,
+        address: u8,
+        command: u8,
+        data: u16,
+    ) -> Result<(), Error<I::Error>> {
+        let [low, high] = data.to_le_bytes();
+        self.write(address, &[command, low, high])
+    }
+
+    /// Read a byte from the register `command`.
+    pub fn read_byte(&mut self, address: u8, command: u8) -> Result<u8, Error<I::Error>> {
+        let mut data = [0u8; 1];
+        self.write_read(address, &[command], &mut data)?;
+
+        Ok(data[0])
+    }
+
+    /// Read a word from the register `command`.
+    pub fn read_word(&mut self, address: u8, command: u8) -> Result<u16, Error<I::Error>> {
+        let mut data = [0u8; 2];
+        self.write_read(address, &[command], &mut data)?;
+
+        Ok(u16::from_le_bytes(data))
+    }
+
+    /// Send a word to the device and read back the word it answers with.
+    pub fn process_call(
+        &mut self,
+        address: u8,
+        command: u8,
+        data: u16,
+    ) -> Result<u16, Error<I::Error>> {
+        let [low, high] = data.to_le_bytes();
+        let mut response = [0u8; 2];
+        self.write_read(address, &[command, low, high], &mut response)?;
+
+        Ok(u16::from_le_bytes(response))
+    }
+
+    /// Write a block of up to [MAX_BLOCK_SIZE] bytes to the register
+    /// `command`.
+    pub fn block_write(
+        &mut self,
+        address: u8,
+        command: u8,
+        data: &[u8],
+    ) -> Result<(), Error<I::Error>> {
+        let mut message = [0u8; MAX_BLOCK_SIZE + 2];
+        let len = block_message(&mut message, command, data)?;
+
+        self.write(address, &message[..len])
+    }
+
+    /// Write `data`, followed by the PEC if enabled
+    fn write(&mut self, address: u8, data: &[u8]) -> Result<(), Error<I::Error>> {
+        let mut message = [0u8; MAX_BLOCK_SIZE + 3];
+        message[..data.len()].copy_from_slice(data);
+
+        let mut len = data.len();
+        if self.pec {
+            message[len] = pec(&[&[write_header(address)], data]);
+            len += 1;
+        }
+
+        self.i2c.write(address, &message[..len]).map_err(Error::I2c)
+    }
+
+    /// Write `data` and read `buffer.len()` bytes, followed by the PEC if
+    /// enabled
+    fn write_read(
+        &mut self,
+        address: u8,
+        data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Error<I::Error>> {
+        let mut received = [0u8; 3];
+        let len = buffer.len() + self.pec as usize;
+        self.i2c
+            .write_read(address, data, &mut received[..len])
+            .map_err(Error::I2c)?;
+
+        let (response, tail) = received.split_at(buffer.len());
+        if self.pec && !pec_matches(address, data, response, tail[0]) {
+            return Err(Error::PecMismatch);
+        }
+
+        buffer.copy_from_slice(response);
+
+        Ok(())
+    }
+}
+
+impl<I> SmBus<I>
+where
+    I: BlockRead,
+{
+    /// Read a block from the register `command` into `buffer`.
+    ///
+    /// Returns the length of the block, or [Error::InvalidBlockLength] if it
+    /// doesn't fit into `buffer`.
+    pub fn block_read(
+        &mut self,
+        address: u8,
+        command: u8,
+        buffer: &mut [u8],
+    ) -> Result<usize, Error<I::Error>> {
+        self.write_read_block(address, &[command], buffer)
+    }
+
+    /// Send a block to the device and read back the block it answers with
+    /// into `buffer`.
+    ///
+    /// Returns the length of the received block, or
+    /// [Error::InvalidBlockLength] if it doesn't fit into `buffer`.
+    pub fn block_process_call(
+        &mut self,
+        address: u8,
+        command: u8,
+        data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<usize, Error<I::Error>> {
+        let mut message = [0u8; MAX_BLOCK_SIZE + 2];
+        let len = block_message(&mut message, command, data)?;
+
+        self.write_read_block(address, &message[..len], buffer)
+    }
+
+    /// Write `data` and read a block preceded by its length, followed by the
+    /// PEC if enabled
+    fn write_read_block(
+        &mut self,
+        address: u8,
+        data: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<usize, Error<I::Error>> {
+        let mut received_pec = [0u8; 1];
+        let trailer = &mut received_pec[..self.pec as usize];
+        let count = self
+            .i2c
+            .write_read_block(address, data, buffer, trailer)
+            .map_err(Error::I2c)?;
+
+        if count > buffer.len() {
+            return Err(Error::InvalidBlockLength);
+        }
+
+        if self.pec {
+            let mut response = [0u8; 256];
+            response[0] = count as u8;
+            response[1..][..count].copy_from_slice(&buffer[..count]);
+
+            if !pec_matches(address, data, &response[..1 + count], received_pec[0]) {
+                return Err(Error::PecMismatch);
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+/// The first byte of a write to `address`
+fn write_header(address: u8) -> u8 {
+    address << 1
+}
+
+/// The first byte of a read from `address`
+fn read_header(address: u8) -> u8 {
+    address << 1 | 1
+}
+
+/// Whether `received` is the PEC of a write of `data` followed by a read of
+/// `response`
+fn pec_matches(address: u8, data: &[u8], response: &[u8], received: u8) -> bool {
+    pec(&[
+        &[write_header(address)],
+        data,
+        &[read_header(address)],
+        response,
+    ]) == received
+}
+
+/// Writes `command`, the length of `data` and `data` to `message`, returns
+/// the length of the message
+fn block_message<E>(
+    message: &mut [u8; MAX_BLOCK_SIZE + 2],
+    command: u8,
+    data: &[u8],
+) -> Result<usize, Error<E>> {
+    if data.len() > MAX_BLOCK_SIZE {
+        return Err(Error::InvalidBlockLength);
+    }
+
+    message[0] = command;
+    message[1] = data.len() as u8;
+    message[2..][..data.len()].copy_from_slice(data);
+
+    Ok(2 + data.len())
+}
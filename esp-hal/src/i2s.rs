@@ -64,6 +64,184 @@
 //!     }
 //! }
 //! ```
+//!
+//! ### Async reading
+//! A circular transfer splits the buffer into frames, one per DMA descriptor,
+//! which the DMA fills in turn while the previous ones are read. Frames are at
+//! most 4092 bytes long, buffers of up to two frames are split into three.
+//!
+//! ```no_run
+//! let mut transfer = i2s_rx.read_dma_circular_async(buffer).unwrap();
+//!
+//! let mut frame = [0u8; 4092];
+//! loop {
+//!     match transfer.read(&mut frame).await {
+//!         Ok(len) => process(&frame[..len]),
+//!         // the DMA overwrote frames which weren't read in time
+//!         Err(Error::DmaError(DmaError::Late)) => println!("Overrun"),
+//!         Err(err) => panic!("{:?}", err),
+//!     }
+//! }
+//! ```
+//!
+//! ### Circular writing
+//! A circular transfer keeps playing the buffer without gaps while new samples
+//! are pushed into the part the DMA already played. If the samples aren't
+//! pushed in time the DMA plays old data again, which is reported to the
+//! underrun callback.
+//!
+//! ```no_run
+//! let mut transfer = i2s_tx.write_dma_circular_async(buffer).unwrap();
+//! transfer.set_underrun_callback(|| println!("Underrun"));
+//!
+//! loop {
+//!     let samples = next_samples();
+//!     transfer.push_all(&samples).await.unwrap();
+//! }
+//! ```
+//!
+//! ### Full-duplex
+//! With [I2s::with_shared_clocks] RX is clocked by TX, so capture and playback
+//! stay synchronized, e.g. for a codec used for voice. Both directions run
+//! their own DMA transfers and can be driven by separate tasks.
+//!
+//! ```no_run
+//! let i2s = I2s::new(
+//!     peripherals.I2S0,
+//!     Standard::Philips,
+//!     DataFormat::Data16Channel16,
+//!     16000.Hz(),
+//!     dma_channel.configure_for_async(
+//!         false,
+//!         &mut tx_descriptors,
+//!         &mut rx_descriptors,
+//!         DmaPriority::Priority0,
+//!     ),
+//!     &clocks,
+//! )
+//! .with_shared_clocks();
+//!
+//! let i2s_tx = i2s
+//!     .i2s_tx
+//!     .with_bclk(io.pins.gpio1)
+//!     .with_ws(io.pins.gpio2)
+//!     .with_dout(io.pins.gpio3)
+//!     .build();
+//! let i2s_rx = i2s.i2s_rx.with_din(io.pins.gpio4).build();
+//!
+//! // start capturing first, RX waits for the clocks of TX
+//! let mut capture = i2s_rx.read_dma_circular_async(rx_buffer).unwrap();
+//! let mut playback = i2s_tx.write_dma_circular_async(tx_buffer).unwrap();
+//! ```
+//!
+//! ### Built-in ADC and DAC
+//! On the ESP32, I2S0 can stream the conversions of ADC1 into DMA buffers and
+//! stream DMA buffers out through the DACs, for analog capture and waveform
+//! playback at high sample rates.
+//!
+//! ```no_run
+//! let i2s_tx = i2s
+//!     .i2s_tx
+//!     .with_builtin_dac(
+//!         Some(DAC1::new(peripherals.DAC1, io.pins.gpio25.into_analog())),
+//!         None,
+//!     )
+//!     .build();
+//! let i2s_rx = i2s
+//!     .i2s_rx
+//!     .with_builtin_adc(
+//!         peripherals.ADC1,
+//!         io.pins.gpio36.into_analog(),
+//!         Attenuation::Attenuation11dB,
+//!     )
+//!     .build();
+//! ```
+//!
+//! ### TDM
+//! On the ESP32-C3, ESP32-C6, ESP32-H2 and ESP32-S3, multi-channel codecs and
+//! amplifiers are driven with [Standard::Tdm]. This configures frames of
+//! eight 16 bit slots of which the first four carry data:
+//!
+//! ```no_run
+//! let i2s = I2s::new(
+//!     peripherals.I2S0,
+//!     Standard::Tdm {
+//!         slots: 8,
+//!         active_slots: 0b0000_1111,
+//!     },
+//!     DataFormat::Data16Channel16,
+//!     48000.Hz(),
+//!     dma_channel.configure(
+//!         false,
+//!         &mut tx_descriptors,
+//!         &mut rx_descriptors,
+//!         DmaPriority::Priority0,
+//!     ),
+//!     &clocks,
+//! );
+//! ```
+//!
+//! ### PDM microphones
+//! On the ESP32 and ESP32-S3, I2S0 can capture PDM microphones directly. The
+//! driver derives the PDM clock from the sample rate and the down-sampling
+//! ratio, e.g. 16 kHz with a ratio of 64 results in a 1.024 MHz PDM clock.
+//!
+//! ```no_run
+//! let i2s = I2s::new(
+//!     peripherals.I2S0,
+//!     Standard::Pdm(PdmDownsampling::Ratio64),
+//!     DataFormat::Data16Channel16,
+//!     16000.Hz(),
+//!     dma_channel.configure(
+//!         false,
+//!         &mut tx_descriptors,
+//!         &mut rx_descriptors,
+//!         DmaPriority::Priority0,
+//!     ),
+//!     &clocks,
+//! );
+//!
+//! // the PDM clock is output on WS
+//! let i2s_rx = i2s
+//!     .i2s_rx
+//!     .with_ws(io.pins.gpio2)
+//!     .with_din(io.pins.gpio5)
+//!     .build();
+//! ```
+//!
+//! ### Sample rate and MCLK
+//! The clocks are derived from the source clock with a fractional divider,
+//! not every sample rate can be generated exactly. [I2s::sample_rate] and
+//! [I2s::sample_rate_error_ppm] report the rate which is actually achieved.
+//! On the ESP32, [I2s::with_apll] tunes the audio PLL to the sample rate
+//! instead, which generates 44.1 kHz and other standard rates exactly.
+//!
+//! MCLK is output with [I2s::with_mclk]. On the ESP32 it's only available on
+//! GPIO0, GPIO1 and GPIO3.
+//!
+//! ```no_run
+//! let i2s = I2s::new(
+//!     peripherals.I2S0,
+//!     Standard::Philips,
+//!     DataFormat::Data16Channel16,
+//!     44100.Hz(),
+//!     dma_channel.configure(
+//!         false,
+//!         &mut tx_descriptors,
+//!         &mut rx_descriptors,
+//!         DmaPriority::Priority0,
+//!     ),
+//!     &clocks,
+//! )
+//! .with_apll(&clocks)
+//! .with_mclk(io.pins.gpio0);
+//!
+//! println!(
+//!     "{} ({} ppm)",
+//!     i2s.sample_rate(),
+//!     i2s.sample_rate_error_ppm()
+//! );
+//! ```
 
 use core::marker::PhantomData;
 
@@ -71,6 +249,9 @@ use embedded_dma::{ReadBuffer, WriteBuffer};
 use enumset::{EnumSet, EnumSetType};
 use private::*;
 
+#[cfg(esp32)]
+pub mod parallel;
+
 #[cfg(any(esp32, esp32s3))]
 use crate::dma::I2s1Peripheral;
 use crate::{
@@ -139,8 +320,50 @@ impl From<DmaError> for Error {
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Standard {
     Philips,
-    // Tdm,
-    // Pdm,
+    /// TDM with `slots` slots per frame, of which the ones set in the
+    /// `active_slots` bit mask carry data
+    ///
+    /// Slots are as wide as the channels of the [DataFormat]. WS is high
+    /// during the first slot of a frame. The DMA buffers only contain the
+    /// samples of the active slots, interleaved. Up to 16 slots with a total
+    /// frame length of up to 512 bits are supported.
+    #[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+    Tdm {
+        slots: u8,
+        active_slots: u16,
+    },
+    /// PDM input, e.g. from MEMS microphones
+    ///
+    /// The PDM clock is output on WS and the PDM data is sampled on DIN. The
+    /// hardware filter converts the PDM stream into 16 bit PCM samples at the
+    /// given sample rate, use it with [DataFormat::Data16Channel16]. Only
+    /// receiving is supported and only by I2S0.
+    #[cfg(any(esp32, esp32s3))]
+    Pdm(PdmDownsampling),
+}
+
+/// Down-sampling ratio of the PDM to PCM filter
+///
+/// The PDM clock runs at the sample rate multiplied by the ratio.
+#[cfg(any(esp32, esp32s3))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum PdmDownsampling {
+    /// 64 PDM bits per sample
+    Ratio64,
+    /// 128 PDM bits per sample
+    Ratio128,
+}
+
+#[cfg(any(esp32, esp32s3))]
+impl PdmDownsampling {
+    /// The number of PDM bits per PCM sample
+    pub fn ratio(&self) -> u32 {
+        match self {
+            PdmDownsampling::Ratio64 => 64,
+            PdmDownsampling::Ratio128 => 128,
+        }
+    }
 }
 
 /// Supported data formats
@@ -219,6 +442,7 @@ where
     DmaMode: Mode,
 {
     i2s_tx: &'t mut I2sTx<'d, T, CH, DmaMode>,
+    underrun_callback: Option<fn()>,
 }
 
 impl<'t, 'd, T, CH, DmaMode> I2sWriteDmaTransfer<'t, 'd, T, CH, DmaMode>
@@ -230,13 +454,17 @@ where
     /// Amount of bytes which can be pushed.
     /// Only useful for circular DMA transfers
     pub fn available(&mut self) -> usize {
-        self.i2s_tx.tx_channel.available()
+        let available = self.i2s_tx.tx_channel.available();
+        self.check_underrun();
+        available
     }
 
     /// Push bytes into the DMA buffer.
     /// Only useful for circular DMA transfers
     pub fn push(&mut self, data: &[u8]) -> Result<usize, Error> {
-        Ok(self.i2s_tx.tx_channel.push(data)?)
+        let written = self.i2s_tx.tx_channel.push(data)?;
+        self.check_underrun();
+        Ok(written)
     }
 
     /// Push bytes into the DMA buffer via the given closure.
@@ -244,7 +472,42 @@ where
     /// The closure *might* get called with a slice which is smaller than the
     /// total available buffer. Only useful for circular DMA transfers
     pub fn push_with(&mut self, f: impl FnOnce(&mut [u8]) -> usize) -> Result<usize, Error> {
-        Ok(self.i2s_tx.tx_channel.push_with(f)?)
+        let written = self.i2s_tx.tx_channel.push_with(f)?;
+        self.check_underrun();
+        Ok(written)
+    }
+
+    /// Push all of `data` into the DMA buffer, blocking until there is
+    /// enough space.
+    /// Only useful for circular DMA transfers
+    pub fn push_all(&mut self, mut data: &[u8]) -> Result<(), Error> {
+        while !data.is_empty() {
+            let len = usize::min(self.available(), data.len());
+            if len > 0 {
+                self.push(&data[..len])?;
+                data = &data[len..];
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set a function to be called when the DMA ran out of pushed data and
+    /// played the whole buffer again.
+    ///
+    /// Underruns are noticed, and the callback is called, while pushing or
+    /// checking for available space.
+    /// Only useful for circular DMA transfers
+    pub fn set_underrun_callback(&mut self, callback: fn()) {
+        self.underrun_callback = Some(callback);
+    }
+
+    fn check_underrun(&mut self) {
+        if self.i2s_tx.tx_channel.take_underrun() {
+            if let Some(callback) = self.underrun_callback {
+                callback();
+            }
+        }
     }
 
     /// Stop for the DMA transfer and return the buffer and the
@@ -351,6 +614,10 @@ where
         self.i2s_rx.rx_channel.available()
     }
 
+    /// Pop the available bytes into `data`.
+    ///
+    /// Fails with [DmaError::Late] if the DMA overwrote data which wasn't
+    /// popped in time, the oldest data is dropped in that case.
     pub fn pop(&mut self, data: &mut [u8]) -> Result<usize, Error> {
         Ok(self.i2s_rx.rx_channel.pop(data)?)
     }
@@ -450,6 +717,12 @@ where
 {
     pub i2s_tx: TxCreator<'d, I, CH, DmaMode>,
     pub i2s_rx: RxCreator<'d, I, CH, DmaMode>,
+    #[cfg(esp32)]
+    standard: Standard,
+    #[cfg(esp32)]
+    data_format: DataFormat,
+    sample_rate: fugit::HertzU32,
+    achieved_rate_millihz: u64,
     phantom: PhantomData<DmaMode>,
 }
 
@@ -465,7 +738,7 @@ where
         data_format: DataFormat,
         sample_rate: impl Into<fugit::HertzU32>,
         mut channel: Channel<'d, CH, DmaMode>,
-        clocks: &Clocks,
+        _clocks: &Clocks,
     ) -> Self {
         // on ESP32-C3 / ESP32-S3 and later RX and TX are independent and
         // could be configured totally independently but for now handle all
@@ -473,12 +746,17 @@ where
 
         channel.tx.init_channel();
         PeripheralClockControl::enable(I::get_peripheral());
-        I::set_clock(calculate_clock(
-            sample_rate,
-            2,
-            data_format.channel_bits(),
-            clocks,
-        ));
+
+        let sample_rate = sample_rate.into();
+        let (mclk, bclk) = clock_frequencies(&standard, &data_format, sample_rate.raw());
+        let clock_settings = calculate_clock(
+            crate::soc::constants::I2S_SCLK,
+            sample_rate.raw(),
+            mclk,
+            bclk,
+        );
+        let achieved_rate_millihz = clock_settings.achieved_rate_millihz;
+        I::set_clock(clock_settings);
         I::configure(&standard, &data_format);
         I::set_master();
         I::update();
@@ -494,9 +772,30 @@ where
                 rx_channel: channel.rx,
                 phantom: PhantomData,
             },
+            #[cfg(esp32)]
+            standard,
+            #[cfg(esp32)]
+            data_format,
+            sample_rate,
+            achieved_rate_millihz,
             phantom: PhantomData,
         }
     }
+
+    /// The sample rate actually generated by the clock dividers.
+    ///
+    /// Not every sample rate can be derived exactly from the I2S source
+    /// clock, this is the closest the dividers get to the requested rate.
+    pub fn sample_rate(&self) -> fugit::HertzU32 {
+        fugit::HertzU32::Hz(((self.achieved_rate_millihz + 500) / 1000) as u32)
+    }
+
+    /// The deviation of the generated sample rate from the requested one, in
+    /// parts per million
+    pub fn sample_rate_error_ppm(&self) -> i32 {
+        let requested = self.sample_rate.raw() as i64 * 1000;
+        ((self.achieved_rate_millihz as i64 - requested) * 1_000_000 / requested) as i32
+    }
 }
 
 impl<'d, I, CH, DmaMode> I2s<'d, I, CH, DmaMode>
@@ -573,13 +872,67 @@ where
         I: I2s1Instance,
         CH::P: I2sPeripheral + I2s1Peripheral,
     {
+        #[cfg(any(esp32, esp32s3))]
+        if let Standard::Pdm(_) = standard {
+            panic!("PDM is only supported by I2S0");
+        }
+
         Self::new_internal(i2s, standard, data_format, sample_rate, channel, clocks)
     }
 
+    /// Output MCLK on `pin`.
+    ///
+    /// On the ESP32 MCLK can only be output on GPIO0, GPIO1 and GPIO3.
     pub fn with_mclk<P: OutputPin>(self, pin: impl Peripheral<P = P> + 'd) -> Self {
         into_ref!(pin);
+
+        #[cfg(not(esp32))]
+        let signal = I::mclk_signal();
+        #[cfg(esp32)]
+        let signal = I::mclk_signal(pin.number());
+
         pin.set_to_push_pull_output()
-            .connect_peripheral_to_output(I::mclk_signal());
+            .connect_peripheral_to_output(signal);
+        self
+    }
+
+    /// Let RX share BCLK and WS with TX, for full-duplex operation.
+    ///
+    /// RX is switched to slave mode and clocked by TX, only configure the
+    /// BCLK and WS pins of TX. RX and TX keep their own DMA transfers, but RX
+    /// only receives while TX is transmitting.
+    pub fn with_shared_clocks(self) -> Self {
+        I::set_shared_clocks();
+        I::update();
+        self
+    }
+
+    /// Clock I2S from the audio PLL instead of the 160 MHz PLL.
+    ///
+    /// The APLL is tuned to an integer multiple of MCLK, so standard audio
+    /// rates like 44.1 kHz are generated (almost) exactly. Use
+    /// [I2s::sample_rate] to get the rate which is actually achieved.
+    #[cfg(esp32)]
+    pub fn with_apll(mut self, clocks: &Clocks) -> Self {
+        // this corresponds to `i2s_set_get_apll_freq` in esp-idf
+        const APLL_MIN_FREQ: u32 = 5_303_031;
+
+        let rate = self.sample_rate.raw();
+        let (mclk, bclk) = clock_frequencies(&self.standard, &self.data_format, rate);
+        let mclk_divider = (APLL_MIN_FREQ / mclk + 1).max(2);
+
+        let Some(apll) = crate::clock::clocks_ll::esp32_rtc_apll_configure(
+            clocks.xtal_clock,
+            mclk * mclk_divider,
+        ) else {
+            panic!("Sample rate not supported by the APLL");
+        };
+
+        let clock_settings = calculate_clock(apll, rate, mclk, bclk);
+        self.achieved_rate_millihz = clock_settings.achieved_rate_millihz;
+        I::set_clock(clock_settings);
+        I::use_apll();
+        I::update();
         self
     }
 }
@@ -670,7 +1023,10 @@ where
         // start: set I2S_TX_START
         T::tx_start();
 
-        Ok(I2sWriteDmaTransfer { i2s_tx: self })
+        Ok(I2sWriteDmaTransfer {
+            i2s_tx: self,
+            underrun_callback: None,
+        })
     }
 
     fn wait_tx_dma_done(&self) -> Result<(), Error> {
@@ -941,8 +1297,9 @@ mod private {
     use core::marker::PhantomData;
 
     use enumset::EnumSet;
-    use fugit::HertzU32;
 
+    #[cfg(any(esp32, esp32s3))]
+    use super::PdmDownsampling;
     use super::{
         DataFormat,
         I2sInterrupt,
@@ -959,7 +1316,6 @@ mod private {
     #[cfg(any(esp32, esp32s3))]
     use crate::peripherals::{i2s1::RegisterBlock, I2S1};
     use crate::{
-        clock::Clocks,
         dma::{ChannelTypes, DmaPeripheral},
         gpio::{InputPin, InputSignal, OutputPin, OutputSignal},
         interrupt::InterruptHandler,
@@ -1019,6 +1375,25 @@ mod private {
                 .connect_peripheral_to_output(T::dout_signal());
             self
         }
+
+        /// Output the data on the built-in DACs instead of a pin.
+        ///
+        /// DAC1 outputs the most significant byte of the right channel and
+        /// DAC2 the one of the left channel, use it with
+        /// [DataFormat::Data16Channel16](super::DataFormat::Data16Channel16).
+        #[cfg(esp32)]
+        pub fn with_builtin_dac(
+            self,
+            _dac1: Option<crate::analog::dac::DAC1<'d>>,
+            _dac2: Option<crate::analog::dac::DAC2<'d>>,
+        ) -> Self
+        where
+            T: I2s0Instance,
+        {
+            crate::analog::dac::enable_i2s_mode();
+            T::enable_builtin_dac();
+            self
+        }
     }
 
     pub struct RxCreator<'d, T, CH, DmaMode>
@@ -1071,6 +1446,29 @@ mod private {
                 .connect_input_to_peripheral(T::din_signal());
             self
         }
+
+        /// Receive the conversions of the built-in ADC1 instead of the data
+        /// on a pin.
+        ///
+        /// The ADC converts the channel of `pin` once per sample. Every
+        /// sample is a 16 bit word with the channel in the upper 4 bits and
+        /// the 12 bit result in the lower bits. Use it with
+        /// [DataFormat::Data16Channel16](super::DataFormat::Data16Channel16).
+        #[cfg(esp32)]
+        pub fn with_builtin_adc<PIN>(
+            self,
+            _adc: impl crate::peripheral::Peripheral<P = crate::peripherals::ADC1> + 'd,
+            _pin: PIN,
+            attenuation: crate::analog::adc::Attenuation,
+        ) -> Self
+        where
+            T: I2s0Instance,
+            PIN: crate::analog::adc::AdcChannel,
+        {
+            crate::analog::adc::enable_i2s_mode(PIN::CHANNEL, attenuation);
+            T::enable_builtin_adc();
+            self
+        }
     }
 
     pub trait Signals {
@@ -1078,8 +1476,13 @@ mod private {
 
         fn get_dma_peripheral() -> DmaPeripheral;
 
+        #[cfg(not(esp32))]
         fn mclk_signal() -> OutputSignal;
 
+        /// The clock output signal of `pin` after routing MCLK to it
+        #[cfg(esp32)]
+        fn mclk_signal(pin: u8) -> OutputSignal;
+
         fn bclk_signal() -> OutputSignal;
 
         fn ws_signal() -> OutputSignal;
@@ -1194,7 +1597,8 @@ mod private {
             });
         }
 
-        fn configure(_standard: &Standard, data_format: &DataFormat) {
+        #[cfg_attr(esp32s2, allow(unused_variables))]
+        fn configure(standard: &Standard, data_format: &DataFormat) {
             let i2s = Self::register_block();
 
             let fifo_mod = match data_format {
@@ -1260,6 +1664,24 @@ mod private {
 
             i2s.conf2()
                 .modify(|_, w| w.camera_en().clear_bit().lcd_en().clear_bit());
+
+            #[cfg(esp32)]
+            match standard {
+                Standard::Philips => {
+                    i2s.pdm_conf()
+                        .modify(|_, w| w.rx_pdm_en().clear_bit().pdm2pcm_conv_en().clear_bit());
+                }
+                Standard::Pdm(downsampling) => {
+                    i2s.pdm_conf().modify(|_, w| {
+                        w.rx_pdm_en()
+                            .set_bit()
+                            .pdm2pcm_conv_en()
+                            .set_bit()
+                            .rx_pdm_sinc_dsr_16_en()
+                            .bit(*downsampling == PdmDownsampling::Ratio128)
+                    });
+                }
+            }
         }
 
         fn set_master() {
@@ -1268,6 +1690,51 @@ mod private {
                 .modify(|_, w| w.rx_slave_mod().clear_bit().tx_slave_mod().clear_bit());
         }
 
+        fn set_shared_clocks() {
+            let i2s = Self::register_block();
+            i2s.conf()
+                .modify(|_, w| w.sig_loopback().set_bit().rx_slave_mod().set_bit());
+        }
+
+        #[cfg(esp32)]
+        fn enable_builtin_dac() {
+            let i2s = Self::register_block();
+            i2s.conf2()
+                .modify(|_, w| w.lcd_en().set_bit().camera_en().clear_bit());
+            i2s.conf().modify(|_, w| {
+                w.tx_right_first()
+                    .clear_bit()
+                    .tx_msb_shift()
+                    .clear_bit()
+                    .tx_short_sync()
+                    .clear_bit()
+            });
+        }
+
+        #[cfg(esp32)]
+        fn enable_builtin_adc() {
+            let i2s = Self::register_block();
+            i2s.conf2()
+                .modify(|_, w| w.lcd_en().set_bit().camera_en().clear_bit());
+            i2s.conf().modify(|_, w| {
+                w.rx_msb_shift()
+                    .clear_bit()
+                    .rx_short_sync()
+                    .clear_bit()
+                    .rx_mono()
+                    .clear_bit()
+            });
+            // 16 bit samples of a single channel
+            i2s.fifo_conf().modify(|_, w| w.rx_fifo_mod().variant(1));
+            i2s.conf_chan().modify(|_, w| w.rx_chan_mod().variant(1));
+        }
+
+        #[cfg(esp32)]
+        fn use_apll() {
+            let i2s = Self::register_block();
+            i2s.clkm_conf().modify(|_, w| w.clka_ena().set_bit());
+        }
+
         fn update() {
             // nothing to do
         }
@@ -1652,9 +2119,20 @@ mod private {
             });
         }
 
-        fn configure(_standard: &Standard, data_format: &DataFormat) {
+        fn configure(standard: &Standard, data_format: &DataFormat) {
             let i2s = Self::register_block();
 
+            let (slots, active_slots) = match *standard {
+                Standard::Tdm {
+                    slots,
+                    active_slots,
+                } => (slots, active_slots),
+                _ => (2, 0b11),
+            };
+            // the register holds half the frame length minus one, a 512 bit frame still
+            // fits into it
+            let half_sample_bits = (slots as u32 * data_format.channel_bits() as u32 / 2 - 1) as u8;
+
             #[allow(clippy::useless_conversion)]
             i2s.tx_conf1().modify(|_, w| {
                 w.tx_tdm_ws_width()
@@ -1664,7 +2142,7 @@ mod private {
                     .tx_tdm_chan_bits()
                     .variant(data_format.channel_bits() - 1)
                     .tx_half_sample_bits()
-                    .variant(data_format.channel_bits() - 1)
+                    .variant(half_sample_bits)
             });
             #[cfg(not(esp32h2))]
             i2s.tx_conf1().modify(|_, w| w.tx_msb_shift().set_bit());
@@ -1693,40 +2171,12 @@ mod private {
                     .variant(0)
             });
 
-            i2s.tx_tdm_ctrl().modify(|_, w| {
-                w.tx_tdm_tot_chan_num()
-                    .variant(1)
-                    .tx_tdm_chan0_en()
-                    .set_bit()
-                    .tx_tdm_chan1_en()
-                    .set_bit()
-                    .tx_tdm_chan2_en()
-                    .clear_bit()
-                    .tx_tdm_chan3_en()
-                    .clear_bit()
-                    .tx_tdm_chan4_en()
-                    .clear_bit()
-                    .tx_tdm_chan5_en()
-                    .clear_bit()
-                    .tx_tdm_chan6_en()
-                    .clear_bit()
-                    .tx_tdm_chan7_en()
-                    .clear_bit()
-                    .tx_tdm_chan8_en()
-                    .clear_bit()
-                    .tx_tdm_chan9_en()
-                    .clear_bit()
-                    .tx_tdm_chan10_en()
-                    .clear_bit()
-                    .tx_tdm_chan11_en()
-                    .clear_bit()
-                    .tx_tdm_chan12_en()
-                    .clear_bit()
-                    .tx_tdm_chan13_en()
-                    .clear_bit()
-                    .tx_tdm_chan14_en()
-                    .clear_bit()
-                    .tx_tdm_chan15_en()
+            // the channel enable bits are the lowest 16 bits of the register
+            i2s.tx_tdm_ctrl().modify(|r, w| unsafe {
+                w.bits(r.bits() & !0xffff | active_slots as u32)
+                    .tx_tdm_tot_chan_num()
+                    .variant(slots - 1)
+                    .tx_tdm_skip_msk_en()
                     .clear_bit()
             });
 
@@ -1739,7 +2189,7 @@ mod private {
                     .rx_tdm_chan_bits()
                     .variant(data_format.channel_bits() - 1)
                     .rx_half_sample_bits()
-                    .variant(data_format.channel_bits() - 1)
+                    .variant(half_sample_bits)
             });
             #[cfg(not(esp32h2))]
             i2s.rx_conf1().modify(|_, w| w.rx_msb_shift().set_bit());
@@ -1765,42 +2215,27 @@ mod private {
                     .clear_bit()
             });
 
-            i2s.rx_tdm_ctrl().modify(|_, w| {
-                w.rx_tdm_tot_chan_num()
-                    .variant(1)
-                    .rx_tdm_pdm_chan0_en()
-                    .set_bit()
-                    .rx_tdm_pdm_chan1_en()
-                    .set_bit()
-                    .rx_tdm_pdm_chan2_en()
-                    .clear_bit()
-                    .rx_tdm_pdm_chan3_en()
-                    .clear_bit()
-                    .rx_tdm_pdm_chan4_en()
-                    .clear_bit()
-                    .rx_tdm_pdm_chan5_en()
-                    .clear_bit()
-                    .rx_tdm_pdm_chan6_en()
-                    .clear_bit()
-                    .rx_tdm_pdm_chan7_en()
-                    .clear_bit()
-                    .rx_tdm_chan8_en()
-                    .clear_bit()
-                    .rx_tdm_chan9_en()
-                    .clear_bit()
-                    .rx_tdm_chan10_en()
-                    .clear_bit()
-                    .rx_tdm_chan11_en()
-                    .clear_bit()
-                    .rx_tdm_chan12_en()
-                    .clear_bit()
-                    .rx_tdm_chan13_en()
-                    .clear_bit()
-                    .rx_tdm_chan14_en()
-                    .clear_bit()
-                    .rx_tdm_chan15_en()
-                    .clear_bit()
+            i2s.rx_tdm_ctrl().modify(|r, w| unsafe {
+                w.bits(r.bits() & !0xffff | active_slots as u32)
+                    .rx_tdm_tot_chan_num()
+                    .variant(slots - 1)
             });
+
+            // the PDM to PCM converter only exists in I2S0
+            #[cfg(esp32s3)]
+            if let Standard::Pdm(downsampling) = standard {
+                let i2s0 = unsafe { &*crate::peripherals::I2S0::PTR };
+                i2s0.rx_conf().modify(|_, w| {
+                    w.rx_tdm_en()
+                        .clear_bit()
+                        .rx_pdm_en()
+                        .set_bit()
+                        .rx_pdm2pcm_en()
+                        .set_bit()
+                        .rx_pdm_sinc_dsr_16_en()
+                        .bit(*downsampling == PdmDownsampling::Ratio128)
+                });
+            }
         }
 
         fn set_master() {
@@ -1809,6 +2244,12 @@ mod private {
             i2s.rx_conf().modify(|_, w| w.rx_slave_mod().clear_bit());
         }
 
+        fn set_shared_clocks() {
+            let i2s = Self::register_block();
+            i2s.tx_conf().modify(|_, w| w.sig_loopback().set_bit());
+            i2s.rx_conf().modify(|_, w| w.rx_slave_mod().set_bit());
+        }
+
         fn update() {
             let i2s = Self::register_block();
             i2s.tx_conf().modify(|_, w| w.tx_update().clear_bit());
@@ -1993,6 +2434,25 @@ mod private {
         }
     }
 
+    /// The ESP32 has no MCLK signal in the GPIO matrix, MCLK can only be
+    /// output on the CLK_OUT function of GPIO0, GPIO1 or GPIO3
+    #[cfg(esp32)]
+    fn route_mclk(pin: u8, source: u32) -> OutputSignal {
+        let (signal, shift) = match pin {
+            0 => (OutputSignal::CLK_OUT1, 0),
+            3 => (OutputSignal::CLK_OUT2, 4),
+            1 => (OutputSignal::CLK_OUT3, 8),
+            _ => panic!("MCLK is only available on GPIO0, GPIO1 and GPIO3"),
+        };
+
+        let io_mux = unsafe { &*crate::peripherals::IO_MUX::PTR };
+        io_mux
+            .pin_ctrl()
+            .modify(|r, w| unsafe { w.bits(r.bits() & !(0xf << shift) | source << shift) });
+
+        signal
+    }
+
     #[cfg(esp32)]
     impl Signals for crate::peripherals::I2S0 {
         fn get_peripheral() -> Peripheral {
@@ -2003,8 +2463,8 @@ mod private {
             DmaPeripheral::I2s0
         }
 
-        fn mclk_signal() -> OutputSignal {
-            panic!("MCLK currently not supported on ESP32");
+        fn mclk_signal(pin: u8) -> OutputSignal {
+            route_mclk(pin, 0x0)
         }
 
         fn bclk_signal() -> OutputSignal {
@@ -2042,8 +2502,8 @@ mod private {
             DmaPeripheral::I2s1
         }
 
-        fn mclk_signal() -> OutputSignal {
-            panic!("MCLK currently not supported on ESP32");
+        fn mclk_signal(pin: u8) -> OutputSignal {
+            route_mclk(pin, 0xf)
         }
 
         fn bclk_signal() -> OutputSignal {
@@ -2158,29 +2618,70 @@ mod private {
         bclk_divider: u32,
         denominator: u32,
         numerator: u32,
-    }
+        /// The sample rate these dividers achieve, in mHz
+        pub achieved_rate_millihz: u64,
+    }
+
+    /// The MCLK and BCLK frequencies for `rate`
+    pub fn clock_frequencies(
+        standard: &Standard,
+        data_format: &DataFormat,
+        rate: u32,
+    ) -> (u32, u32) {
+        let bclk = match *standard {
+            Standard::Philips => rate * 2 * data_format.channel_bits() as u32,
+            #[cfg(any(esp32c3, esp32c6, esp32h2, esp32s3))]
+            Standard::Tdm {
+                slots,
+                active_slots,
+            } => {
+                assert!(
+                    (1..=16).contains(&slots)
+                        && slots as u32 * data_format.channel_bits() as u32 <= 512,
+                    "Unsupported number of TDM slots"
+                );
+                assert!(
+                    active_slots != 0 && (active_slots as u32) >> slots == 0,
+                    "Invalid TDM slot mask"
+                );
+
+                rate * slots as u32 * data_format.channel_bits() as u32
+            }
+            #[cfg(any(esp32, esp32s3))]
+            Standard::Pdm(downsampling) => {
+                // this corresponds to `i2s_pdm_rx_calculate_clock` in esp-idf, the PDM
+                // clock is output as BCLK and MCLK is fixed to eight times that
+                let bclk = rate * downsampling.ratio();
+                return (bclk * 8, bclk);
+            }
+        };
 
-    pub fn calculate_clock(
-        sample_rate: impl Into<fugit::HertzU32>,
-        channels: u8,
-        data_bits: u8,
-        _clocks: &Clocks,
-    ) -> I2sClockDividers {
-        // this loosely corresponds to `i2s_std_calculate_clock` and
-        // `i2s_ll_tx_set_mclk` in esp-idf
+        // this loosely corresponds to `i2s_std_calculate_clock` in esp-idf
         //
-        // main difference is we are using fixed-point arithmetic here
-
         // If data_bits is a power of two, use 256 as the mclk_multiple
         // If data_bits is 24, use 192 (24 * 8) as the mclk_multiple
-        let mclk_multiple = if data_bits == 24 { 192 } else { 256 };
-        let sclk = crate::soc::constants::I2S_SCLK; // for now it's fixed 160MHz and 96MHz (just H2)
+        let mclk_multiple = if data_format.channel_bits() == 24 {
+            192
+        } else {
+            256
+        };
+        let mut mclk = rate * mclk_multiple;
 
-        let rate_hz: HertzU32 = sample_rate.into();
-        let rate = rate_hz.raw();
+        // with many TDM slots BCLK gets too close to MCLK, data goes wrong if
+        // the BCLK divider isn't larger than 2
+        while mclk / bclk <= 2 {
+            mclk *= 2;
+        }
 
-        let bclk = rate * channels as u32 * data_bits as u32;
-        let mclk = rate * mclk_multiple;
+        (mclk, bclk)
+    }
+
+    /// Calculates the dividers to derive `mclk` and `bclk` from the source
+    /// clock `sclk`
+    pub fn calculate_clock(sclk: u32, rate: u32, mclk: u32, bclk: u32) -> I2sClockDividers {
+        // this loosely corresponds to `i2s_ll_tx_set_mclk` in esp-idf
+        //
+        // main difference is we are using fixed-point arithmetic here
         let bclk_divider = mclk / bclk;
         let mut mclk_divider = sclk / mclk;
 
@@ -2214,18 +2715,29 @@ mod private {
 
                     if mb.abs_diff(ma) < min {
                         denominator = a as u32;
-                        numerator = b as u32;
+                        numerator = (b / 10000) as u32;
                         min = mb.abs_diff(ma);
                     }
                 }
             }
         }
 
+        // MCLK = SCLK / (mclk_divider + numerator / denominator) and there are
+        // BCLK / rate bit clocks per frame
+        let (a, b) = if denominator == 0 {
+            (1, 0)
+        } else {
+            (denominator as u64, numerator as u64)
+        };
+        let achieved_rate_millihz = sclk as u64 * 1000 * a
+            / ((mclk_divider as u64 * a + b) * bclk_divider as u64 * (bclk / rate) as u64);
+
         I2sClockDividers {
             mclk_divider,
             bclk_divider,
             denominator,
             numerator,
+            achieved_rate_millihz,
         }
     }
 }
@@ -2239,6 +2751,7 @@ pub mod asynch {
         dma::{
             asynch::{DmaRxDoneChFuture, DmaRxFuture, DmaTxDoneChFuture, DmaTxFuture},
             ChannelTypes,
+            DmaError,
             RxPrivate,
             TxPrivate,
         },
@@ -2295,6 +2808,7 @@ pub mod asynch {
             Ok(I2sWriteDmaTransferAsync {
                 i2s_tx: self,
                 _buffer: words,
+                underrun_callback: None,
             })
         }
     }
@@ -2309,6 +2823,7 @@ pub mod asynch {
     {
         i2s_tx: I2sTx<'d, T, CH, Async>,
         _buffer: BUFFER,
+        underrun_callback: Option<fn()>,
     }
 
     impl<'d, T, CH, BUFFER> I2sWriteDmaTransferAsync<'d, T, CH, BUFFER>
@@ -2321,6 +2836,7 @@ pub mod asynch {
         pub async fn available(&mut self) -> usize {
             loop {
                 let res = self.i2s_tx.tx_channel.available();
+                self.check_underrun();
 
                 if res != 0 {
                     break res;
@@ -2350,6 +2866,34 @@ pub mod asynch {
             let _avail = self.available().await;
             Ok(self.i2s_tx.tx_channel.push_with(f)?)
         }
+
+        /// Push all of `data` into the DMA transaction, waiting for space
+        /// as needed.
+        pub async fn push_all(&mut self, mut data: &[u8]) -> Result<(), Error> {
+            while !data.is_empty() {
+                let written = self.push(data).await?;
+                data = &data[written..];
+            }
+
+            Ok(())
+        }
+
+        /// Set a function to be called when the DMA ran out of pushed data
+        /// and played the whole buffer again.
+        ///
+        /// Underruns are noticed, and the callback is called, while pushing
+        /// or waiting for available space.
+        pub fn set_underrun_callback(&mut self, callback: fn()) {
+            self.underrun_callback = Some(callback);
+        }
+
+        fn check_underrun(&mut self) {
+            if self.i2s_tx.tx_channel.take_underrun() {
+                if let Some(callback) = self.underrun_callback {
+                    callback();
+                }
+            }
+        }
     }
 
     /// Initiate an async DMA rx transfer
@@ -2444,5 +2988,21 @@ pub mod asynch {
             let to_rcv = usize::min(avail, data.len());
             Ok(self.i2s_rx.rx_channel.pop(&mut data[..to_rcv])?)
         }
+
+        /// Wait for the next filled frame and copy it into `frame`.
+        ///
+        /// Returns the length of the frame. Fails with
+        /// [DmaError::BufferTooSmall] without consuming the frame if it
+        /// doesn't fit into `frame`. If the DMA overwrote frames which weren't
+        /// read in time, this fails with [DmaError::Late] and drops the oldest
+        /// frame, reading can continue afterwards.
+        pub async fn read(&mut self, frame: &mut [u8]) -> Result<usize, Error> {
+            let avail = self.available().await;
+            if avail > frame.len() {
+                return Err(Error::DmaError(DmaError::BufferTooSmall));
+            }
+
+            Ok(self.i2s_rx.rx_channel.pop(&mut frame[..avail])?)
+        }
     }
 }
@@ -0,0 +1,717 @@
+//! # I2S parallel output (LCD mode)
+//!
+//! ## Overview
+//! The ESP32 doesn't have the LCD_CAM peripheral of the ESP32-S3, but its I2S
+//! peripherals can be switched into an LCD mode in which they output 8 or 16
+//! bits in parallel, clocked by WS. Together with DMA this drives i8080
+//! displays, HUB75 LED matrices and other parallel interfaces.
+//!
+//! Only TX is supported. The data pins are given as [TxEightBits] or
+//! [TxSixteenBits], the clock is output on `clock_pin` at the given
+//! frequency.
+//!
+//! ## Data order
+//! The FIFO swaps the halves of every 32 bit word:
+//! - 8 bit: `[A, B, C, D]` is output as `[C, D, A, B]`
+//! - 16 bit: `[A, B, C, D]` is output as `[B, A, D, C]`
+//!
+//! The buffers have to be prepared in this order. I2S0 doesn't support true
+//! 8 bit output, it only outputs the even bytes. Use I2S1 for 8 bit buses.
+//!
+//! ## Example
+//! ```no_run
+//! let pins = TxEightBits::new(
+//!     io.pins.gpio16,
+//!     io.pins.gpio4,
+//!     io.pins.gpio17,
+//!     io.pins.gpio18,
+//!     io.pins.gpio5,
+//!     io.pins.gpio19,
+//!     io.pins.gpio12,
+//!     io.pins.gpio14,
+//! );
+//!
+//! let mut parallel = I2sParallel::new_i2s1(
+//!     peripherals.I2S1,
+//!     dma_channel.configure(
+//!         false,
+//!         &mut tx_descriptors,
+//!         &mut rx_descriptors,
+//!         DmaPriority::Priority0,
+//!     ),
+//!     1.MHz(),
+//!     pins,
+//!     io.pins.gpio25,
+//! );
+//!
+//! parallel.send(&buffer).unwrap();
+//! ```
+
+use core::mem::size_of;
+
+use embedded_dma::ReadBuffer;
+use fugit::HertzU32;
+
+use super::{
+    private::{calculate_clock, I2s0Instance, I2s1Instance, RegBlock, RegisterAccessPrivate},
+    Error,
+    RegisterAccess,
+};
+use crate::{
+    dma::{Channel, ChannelTypes, I2s0Peripheral, I2s1Peripheral, I2sPeripheral, TxPrivate},
+    gpio::{OutputPin, OutputSignal},
+    into_ref,
+    peripheral::{Peripheral, PeripheralRef},
+    system::PeripheralClockControl,
+    Mode,
+};
+
+/// An I2S peripheral in parallel output mode
+pub struct I2sParallel<'d, I, CH, P, DmaMode>
+where
+    I: Instance,
+    CH: ChannelTypes,
+    DmaMode: Mode,
+{
+    _i2s: PeripheralRef<'d, I>,
+    tx_channel: CH::Tx<'d>,
+    _pins: P,
+    _mode: core::marker::PhantomData<DmaMode>,
+}
+
+impl<'d, I, CH, P, DmaMode> I2sParallel<'d, I, CH, P, DmaMode>
+where
+    I: Instance,
+    CH: ChannelTypes,
+    P: TxPins,
+    DmaMode: Mode,
+{
+    /// Switch I2S0 into parallel output mode.
+    ///
+    /// I2S0 only outputs every other byte of an 8 bit bus, use
+    /// [I2sParallel::new_i2s1] for 8 bit buses.
+    pub fn new<CLK: OutputPin>(
+        i2s: impl Peripheral<P = I> + 'd,
+        channel: Channel<'d, CH, DmaMode>,
+        frequency: HertzU32,
+        pins: P,
+        clock_pin: impl Peripheral<P = CLK> + 'd,
+    ) -> Self
+    where
+        I: I2s0Instance,
+        CH::P: I2sPeripheral + I2s0Peripheral,
+    {
+        Self::new_internal(i2s, channel, frequency, pins, clock_pin)
+    }
+
+    /// Switch I2S1 into parallel output mode.
+    pub fn new_i2s1<CLK: OutputPin>(
+        i2s: impl Peripheral<P = I> + 'd,
+        channel: Channel<'d, CH, DmaMode>,
+        frequency: HertzU32,
+        pins: P,
+        clock_pin: impl Peripheral<P = CLK> + 'd,
+    ) -> Self
+    where
+        I: I2s1Instance,
+        CH::P: I2sPeripheral + I2s1Peripheral,
+    {
+        Self::new_internal(i2s, channel, frequency, pins, clock_pin)
+    }
+
+    fn new_internal<CLK: OutputPin>(
+        i2s: impl Peripheral<P = I> + 'd,
+        mut channel: Channel<'d, CH, DmaMode>,
+        frequency: HertzU32,
+        mut pins: P,
+        clock_pin: impl Peripheral<P = CLK> + 'd,
+    ) -> Self {
+        into_ref!(i2s, clock_pin);
+
+        channel.tx.init_channel();
+        PeripheralClockControl::enable(I::get_peripheral());
+
+        I::setup(frequency, P::BUS_WIDTH);
+
+        clock_pin
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::ws_signal());
+        pins.configure::<I>();
+
+        Self {
+            _i2s: i2s,
+            tx_channel: channel.tx,
+            _pins: pins,
+            _mode: core::marker::PhantomData,
+        }
+    }
+
+    /// Send `data` and wait until it's sent completely.
+    pub fn send(&mut self, data: &[P::Word]) -> Result<(), Error> {
+        self.start_send(data.as_ptr() as _, core::mem::size_of_val(data), false)?;
+        I::wait_for_tx_done();
+
+        if self.tx_channel.has_error() {
+            Err(Error::DmaError(crate::dma::DmaError::DescriptorError))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Start sending `data`.
+    pub fn send_dma<'t, TXBUF>(
+        &'t mut self,
+        data: &'t TXBUF,
+    ) -> Result<I2sParallelTransfer<'t, 'd, I, CH, P, DmaMode>, Error>
+    where
+        TXBUF: ReadBuffer<Word = P::Word>,
+    {
+        let (ptr, len) = unsafe { data.read_buffer() };
+        self.start_send(ptr as _, len * size_of::<P::Word>(), false)?;
+
+        Ok(I2sParallelTransfer { instance: self })
+    }
+
+    /// Start sending `data` over and over, until the transfer is stopped.
+    ///
+    /// This keeps e.g. a LED matrix refreshed without involving the CPU.
+    pub fn send_dma_circular<'t, TXBUF>(
+        &'t mut self,
+        data: &'t TXBUF,
+    ) -> Result<I2sParallelTransfer<'t, 'd, I, CH, P, DmaMode>, Error>
+    where
+        TXBUF: ReadBuffer<Word = P::Word>,
+    {
+        let (ptr, len) = unsafe { data.read_buffer() };
+        self.start_send(ptr as _, len * size_of::<P::Word>(), true)?;
+
+        Ok(I2sParallelTransfer { instance: self })
+    }
+
+    fn start_send(&mut self, ptr: *const u8, len: usize, circular: bool) -> Result<(), Error> {
+        I::reset_parallel_tx();
+
+        self.tx_channel
+            .prepare_transfer_without_start(I::get_dma_peripheral(), circular, ptr, len)
+            .and_then(|_| self.tx_channel.start_transfer())?;
+
+        I::start_parallel_tx();
+
+        Ok(())
+    }
+}
+
+impl<'d, I, CH, P, DmaMode> core::fmt::Debug for I2sParallel<'d, I, CH, P, DmaMode>
+where
+    I: Instance,
+    CH: ChannelTypes,
+    DmaMode: Mode,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("I2sParallel").finish()
+    }
+}
+
+/// An in-progress transfer
+#[must_use]
+pub struct I2sParallelTransfer<'t, 'd, I, CH, P, DmaMode>
+where
+    I: Instance,
+    CH: ChannelTypes,
+    P: TxPins,
+    DmaMode: Mode,
+{
+    instance: &'t mut I2sParallel<'d, I, CH, P, DmaMode>,
+}
+
+impl<'t, 'd, I, CH, P, DmaMode> I2sParallelTransfer<'t, 'd, I, CH, P, DmaMode>
+where
+    I: Instance,
+    CH: ChannelTypes,
+    P: TxPins,
+    DmaMode: Mode,
+{
+    /// Wait for the transfer to finish.
+    ///
+    /// A circular transfer never finishes, stop it by dropping it instead.
+    pub fn wait(self) -> Result<(), Error> {
+        I::wait_for_tx_done();
+
+        if self.instance.tx_channel.has_error() {
+            Err(Error::DmaError(crate::dma::DmaError::DescriptorError))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Whether the transfer is finished
+    pub fn is_done(&self) -> bool {
+        I::register_block().state().read().tx_idle().bit_is_set()
+    }
+}
+
+impl<'t, 'd, I, CH, P, DmaMode> Drop for I2sParallelTransfer<'t, 'd, I, CH, P, DmaMode>
+where
+    I: Instance,
+    CH: ChannelTypes,
+    P: TxPins,
+    DmaMode: Mode,
+{
+    fn drop(&mut self) {
+        I::tx_stop();
+    }
+}
+
+/// An I2S peripheral which supports the parallel output mode
+pub trait Instance: RegisterAccess {
+    /// The output signal of data bit `bit` for a bus of `bus_width` bits
+    fn data_out_signal(bit: u8, bus_width: u8) -> OutputSignal;
+
+    fn setup(frequency: HertzU32, bus_width: u8) {
+        // this corresponds to `i2s_lcd_select_periph_clock` and
+        // `i2s_lcd_set_pixel_clock` in esp-idf, the WS clock runs at half MCLK
+        let mclk = frequency.raw() * 2;
+        let bclk_divider = if bus_width == 8 { 2 } else { 1 };
+        Self::set_clock(calculate_clock(
+            crate::soc::constants::I2S_SCLK,
+            frequency.raw(),
+            mclk,
+            mclk / bclk_divider,
+        ));
+
+        let i2s = Self::register_block();
+
+        i2s.conf().modify(|_, w| {
+            w.rx_reset()
+                .set_bit()
+                .tx_reset()
+                .set_bit()
+                .rx_fifo_reset()
+                .set_bit()
+                .tx_fifo_reset()
+                .set_bit()
+        });
+        i2s.conf().modify(|_, w| {
+            w.rx_reset()
+                .clear_bit()
+                .tx_reset()
+                .clear_bit()
+                .rx_fifo_reset()
+                .clear_bit()
+                .tx_fifo_reset()
+                .clear_bit()
+        });
+        i2s.lc_conf()
+            .modify(|_, w| w.in_rst().set_bit().out_rst().set_bit());
+        i2s.lc_conf()
+            .modify(|_, w| w.in_rst().clear_bit().out_rst().clear_bit());
+
+        // 8 bit mode needs WRX2, otherwise the data changes on both clock edges
+        i2s.conf2()
+            .write(|w| w.lcd_tx_wrx2_en().bit(bus_width == 8).lcd_en().set_bit());
+
+        i2s.sample_rate_conf().modify(|_, w| {
+            w.rx_bits_mod()
+                .variant(bus_width)
+                .tx_bits_mod()
+                .variant(bus_width)
+        });
+
+        i2s.fifo_conf().write(|w| {
+            w.rx_fifo_mod_force_en()
+                .set_bit()
+                .tx_fifo_mod_force_en()
+                .set_bit()
+                .rx_fifo_mod()
+                .variant(1)
+                .tx_fifo_mod()
+                .variant(1)
+                .rx_data_num()
+                .variant(32)
+                .tx_data_num()
+                .variant(32)
+                .dscr_en()
+                .set_bit()
+        });
+
+        i2s.conf1().write(|w| {
+            w.tx_stop_en()
+                .set_bit()
+                .rx_pcm_bypass()
+                .set_bit()
+                .tx_pcm_bypass()
+                .set_bit()
+        });
+
+        i2s.conf_chan()
+            .write(|w| w.rx_chan_mod().variant(1).tx_chan_mod().variant(1));
+
+        i2s.conf().modify(|_, w| {
+            w.rx_mono()
+                .set_bit()
+                .tx_mono()
+                .set_bit()
+                .rx_right_first()
+                .set_bit()
+                .tx_right_first()
+                .set_bit()
+        });
+        i2s.timing().reset();
+
+        i2s.pd_conf()
+            .modify(|_, w| w.fifo_force_pu().set_bit().fifo_force_pd().clear_bit());
+    }
+
+    fn reset_parallel_tx() {
+        let i2s = Self::register_block();
+
+        i2s.conf().modify(|_, w| w.tx_reset().set_bit());
+        // without this delay the next transfer hangs waiting for TX to leave
+        // idle
+        crate::rom::ets_delay_us(1);
+        i2s.conf().modify(|_, w| w.tx_reset().clear_bit());
+
+        i2s.conf().modify(|_, w| w.tx_fifo_reset().set_bit());
+        i2s.conf().modify(|_, w| w.tx_fifo_reset().clear_bit());
+
+        i2s.lc_conf().modify(|_, w| w.out_rst().set_bit());
+        i2s.lc_conf().modify(|_, w| w.out_rst().clear_bit());
+
+        i2s.int_clr().write(|w| {
+            w.out_done()
+                .clear_bit_by_one()
+                .out_total_eof()
+                .clear_bit_by_one()
+        });
+    }
+
+    fn start_parallel_tx() {
+        let i2s = Self::register_block();
+
+        // wait for the DMA to fill the FIFO, otherwise garbage is sent first
+        while i2s.int_raw().read().tx_rempty().bit_is_clear() {
+            // wait
+        }
+        crate::rom::ets_delay_us(1);
+
+        Self::tx_start();
+    }
+}
+
+impl Instance for crate::peripherals::I2S0 {
+    fn data_out_signal(bit: u8, _bus_width: u8) -> OutputSignal {
+        // the data signals of I2S0 start at bit 8 for both bus widths, see
+        // `esp_lcd_panel_io_i2s.c` in esp-idf
+        match bit + 8 {
+            8 => OutputSignal::I2S0O_DATA_8,
+            9 => OutputSignal::I2S0O_DATA_9,
+            10 => OutputSignal::I2S0O_DATA_10,
+            11 => OutputSignal::I2S0O_DATA_11,
+            12 => OutputSignal::I2S0O_DATA_12,
+            13 => OutputSignal::I2S0O_DATA_13,
+            14 => OutputSignal::I2S0O_DATA_14,
+            15 => OutputSignal::I2S0O_DATA_15,
+            16 => OutputSignal::I2S0O_DATA_16,
+            17 => OutputSignal::I2S0O_DATA_17,
+            18 => OutputSignal::I2S0O_DATA_18,
+            19 => OutputSignal::I2S0O_DATA_19,
+            20 => OutputSignal::I2S0O_DATA_20,
+            21 => OutputSignal::I2S0O_DATA_21,
+            22 => OutputSignal::I2S0O_DATA_22,
+            23 => OutputSignal::I2S0O_DATA_23,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl Instance for crate::peripherals::I2S1 {
+    fn data_out_signal(bit: u8, bus_width: u8) -> OutputSignal {
+        // the data signals of I2S1 start at bit 0 for 8 bit and at bit 8 for 16
+        // bit buses
+        let offset = if bus_width == 16 { 8 } else { 0 };
+        match bit + offset {
+            0 => OutputSignal::I2S1O_DATA_0,
+            1 => OutputSignal::I2S1O_DATA_1,
+            2 => OutputSignal::I2S1O_DATA_2,
+            3 => OutputSignal::I2S1O_DATA_3,
+            4 => OutputSignal::I2S1O_DATA_4,
+            5 => OutputSignal::I2S1O_DATA_5,
+            6 => OutputSignal::I2S1O_DATA_6,
+            7 => OutputSignal::I2S1O_DATA_7,
+            8 => OutputSignal::I2S1O_DATA_8,
+            9 => OutputSignal::I2S1O_DATA_9,
+            10 => OutputSignal::I2S1O_DATA_10,
+            11 => OutputSignal::I2S1O_DATA_11,
+            12 => OutputSignal::I2S1O_DATA_12,
+            13 => OutputSignal::I2S1O_DATA_13,
+            14 => OutputSignal::I2S1O_DATA_14,
+            15 => OutputSignal::I2S1O_DATA_15,
+            16 => OutputSignal::I2S1O_DATA_16,
+            17 => OutputSignal::I2S1O_DATA_17,
+            18 => OutputSignal::I2S1O_DATA_18,
+            19 => OutputSignal::I2S1O_DATA_19,
+            20 => OutputSignal::I2S1O_DATA_20,
+            21 => OutputSignal::I2S1O_DATA_21,
+            22 => OutputSignal::I2S1O_DATA_22,
+            23 => OutputSignal::I2S1O_DATA_23,
+            _ => unreachable!(),
+        }
+    }
+}
+
+/// The pins of a bus of 8 bits
+pub struct TxEightBits<'d, P0, P1, P2, P3, P4, P5, P6, P7> {
+    pin_0: PeripheralRef<'d, P0>,
+    pin_1: PeripheralRef<'d, P1>,
+    pin_2: PeripheralRef<'d, P2>,
+    pin_3: PeripheralRef<'d, P3>,
+    pin_4: PeripheralRef<'d, P4>,
+    pin_5: PeripheralRef<'d, P5>,
+    pin_6: PeripheralRef<'d, P6>,
+    pin_7: PeripheralRef<'d, P7>,
+}
+
+impl<'d, P0, P1, P2, P3, P4, P5, P6, P7> TxEightBits<'d, P0, P1, P2, P3, P4, P5, P6, P7>
+where
+    P0: OutputPin,
+    P1: OutputPin,
+    P2: OutputPin,
+    P3: OutputPin,
+    P4: OutputPin,
+    P5: OutputPin,
+    P6: OutputPin,
+    P7: OutputPin,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        pin_0: impl Peripheral<P = P0> + 'd,
+        pin_1: impl Peripheral<P = P1> + 'd,
+        pin_2: impl Peripheral<P = P2> + 'd,
+        pin_3: impl Peripheral<P = P3> + 'd,
+        pin_4: impl Peripheral<P = P4> + 'd,
+        pin_5: impl Peripheral<P = P5> + 'd,
+        pin_6: impl Peripheral<P = P6> + 'd,
+        pin_7: impl Peripheral<P = P7> + 'd,
+    ) -> Self {
+        into_ref!(pin_0, pin_1, pin_2, pin_3, pin_4, pin_5, pin_6, pin_7);
+
+        Self {
+            pin_0,
+            pin_1,
+            pin_2,
+            pin_3,
+            pin_4,
+            pin_5,
+            pin_6,
+            pin_7,
+        }
+    }
+}
+
+impl<'d, P0, P1, P2, P3, P4, P5, P6, P7> TxPins for TxEightBits<'d, P0, P1, P2, P3, P4, P5, P6, P7>
+where
+    P0: OutputPin,
+    P1: OutputPin,
+    P2: OutputPin,
+    P3: OutputPin,
+    P4: OutputPin,
+    P5: OutputPin,
+    P6: OutputPin,
+    P7: OutputPin,
+{
+    type Word = u8;
+    const BUS_WIDTH: u8 = 8;
+
+    fn configure<I: Instance>(&mut self) {
+        self.pin_0
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(0, 8));
+        self.pin_1
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(1, 8));
+        self.pin_2
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(2, 8));
+        self.pin_3
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(3, 8));
+        self.pin_4
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(4, 8));
+        self.pin_5
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(5, 8));
+        self.pin_6
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(6, 8));
+        self.pin_7
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(7, 8));
+    }
+}
+
+/// The pins of a bus of 16 bits
+pub struct TxSixteenBits<'d, P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13, P14, P15> {
+    pin_0: PeripheralRef<'d, P0>,
+    pin_1: PeripheralRef<'d, P1>,
+    pin_2: PeripheralRef<'d, P2>,
+    pin_3: PeripheralRef<'d, P3>,
+    pin_4: PeripheralRef<'d, P4>,
+    pin_5: PeripheralRef<'d, P5>,
+    pin_6: PeripheralRef<'d, P6>,
+    pin_7: PeripheralRef<'d, P7>,
+    pin_8: PeripheralRef<'d, P8>,
+    pin_9: PeripheralRef<'d, P9>,
+    pin_10: PeripheralRef<'d, P10>,
+    pin_11: PeripheralRef<'d, P11>,
+    pin_12: PeripheralRef<'d, P12>,
+    pin_13: PeripheralRef<'d, P13>,
+    pin_14: PeripheralRef<'d, P14>,
+    pin_15: PeripheralRef<'d, P15>,
+}
+
+impl<'d, P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13, P14, P15>
+    TxSixteenBits<'d, P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13, P14, P15>
+where
+    P0: OutputPin,
+    P1: OutputPin,
+    P2: OutputPin,
+    P3: OutputPin,
+    P4: OutputPin,
+    P5: OutputPin,
+    P6: OutputPin,
+    P7: OutputPin,
+    P8: OutputPin,
+    P9: OutputPin,
+    P10: OutputPin,
+    P11: OutputPin,
+    P12: OutputPin,
+    P13: OutputPin,
+    P14: OutputPin,
+    P15: OutputPin,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        pin_0: impl Peripheral<P = P0> + 'd,
+        pin_1: impl Peripheral<P = P1> + 'd,
+        pin_2: impl Peripheral<P = P2> + 'd,
+        pin_3: impl Peripheral<P = P3> + 'd,
+        pin_4: impl Peripheral<P = P4> + 'd,
+        pin_5: impl Peripheral<P = P5> + 'd,
+        pin_6: impl Peripheral<P = P6> + 'd,
+        pin_7: impl Peripheral<P = P7> + 'd,
+        pin_8: impl Peripheral<P = P8> + 'd,
+        pin_9: impl Peripheral<P = P9> + 'd,
+        pin_10: impl Peripheral<P = P10> + 'd,
+        pin_11: impl Peripheral<P = P11> + 'd,
+        pin_12: impl Peripheral<P = P12> + 'd,
+        pin_13: impl Peripheral<P = P13> + 'd,
+        pin_14: impl Peripheral<P = P14> + 'd,
+        pin_15: impl Peripheral<P = P15> + 'd,
+    ) -> Self {
+        into_ref!(pin_0, pin_1, pin_2, pin_3, pin_4, pin_5, pin_6, pin_7);
+        into_ref!(pin_8, pin_9, pin_10, pin_11, pin_12, pin_13, pin_14, pin_15);
+
+        Self {
+            pin_0,
+            pin_1,
+            pin_2,
+            pin_3,
+            pin_4,
+            pin_5,
+            pin_6,
+            pin_7,
+            pin_8,
+            pin_9,
+            pin_10,
+            pin_11,
+            pin_12,
+            pin_13,
+            pin_14,
+            pin_15,
+        }
+    }
+}
+
+impl<'d, P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13, P14, P15> TxPins
+    for TxSixteenBits<'d, P0, P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12, P13, P14, P15>
+where
+    P0: OutputPin,
+    P1: OutputPin,
+    P2: OutputPin,
+    P3: OutputPin,
+    P4: OutputPin,
+    P5: OutputPin,
+    P6: OutputPin,
+    P7: OutputPin,
+    P8: OutputPin,
+    P9: OutputPin,
+    P10: OutputPin,
+    P11: OutputPin,
+    P12: OutputPin,
+    P13: OutputPin,
+    P14: OutputPin,
+    P15: OutputPin,
+{
+    type Word = u16;
+    const BUS_WIDTH: u8 = 16;
+
+    fn configure<I: Instance>(&mut self) {
+        self.pin_0
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(0, 16));
+        self.pin_1
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(1, 16));
+        self.pin_2
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(2, 16));
+        self.pin_3
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(3, 16));
+        self.pin_4
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(4, 16));
+        self.pin_5
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(5, 16));
+        self.pin_6
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(6, 16));
+        self.pin_7
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(7, 16));
+        self.pin_8
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(8, 16));
+        self.pin_9
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(9, 16));
+        self.pin_10
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(10, 16));
+        self.pin_11
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(11, 16));
+        self.pin_12
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(12, 16));
+        self.pin_13
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(13, 16));
+        self.pin_14
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(14, 16));
+        self.pin_15
+            .set_to_push_pull_output()
+            .connect_peripheral_to_output(I::data_out_signal(15, 16));
+    }
+}
+
+/// The data pins of a parallel bus
+pub trait TxPins {
+    type Word: Copy;
+    const BUS_WIDTH: u8;
+
+    fn configure<I: Instance>(&mut self);
+}
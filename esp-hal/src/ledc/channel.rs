@@ -28,6 +28,9 @@ pub enum FadeError {
     DutyRange,
     /// Duration too long for timer frequency and duty resolution
     Duration,
+    /// Too few or too many fade curve segments, or a segment out of range
+    #[cfg(any(esp32c6, esp32h2))]
+    Segments,
 }
 
 /// Channel errors
@@ -80,6 +83,30 @@ pub mod config {
     }
 }
 
+/// Maximum number of segments of a fade curve
+#[cfg(any(esp32c6, esp32h2))]
+pub const MAX_FADE_SEGMENTS: usize = 16;
+
+/// Maximum number of steps, PWM periods per step and duty change per step of a
+/// fade curve segment
+#[cfg(any(esp32c6, esp32h2))]
+const MAX_FADE_SEGMENT_VALUE: u32 = 1023;
+
+/// One segment of a fade curve, see [Channel::start_duty_fade_segments]
+#[cfg(any(esp32c6, esp32h2))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct FadeSegment {
+    /// Whether the duty increases or decreases
+    pub increase: bool,
+    /// Number of steps, 1 to 1023
+    pub steps: u16,
+    /// PWM periods per step, 1 to 1023
+    pub cycles_per_step: u16,
+    /// Duty change per step, 0 to 1023
+    pub duty_per_step: u16,
+}
+
 /// Channel interface
 pub trait ChannelIFace<'a, S: TimerSpeed + 'a, O: OutputPin + 'a>
 where
@@ -91,6 +118,17 @@ where
     /// Set channel duty HW
     fn set_duty(&self, duty_pct: u8) -> Result<(), Error>;
 
+    /// Set the duty in steps of the timer's duty resolution, from 0 to
+    /// `2^bits` (100 %)
+    fn set_duty_raw(&self, duty: u32) -> Result<(), Error>;
+
+    /// Set the duty in 1/16 steps of the timer's duty resolution, from 0 to
+    /// `2^bits * 16` (100 %).
+    ///
+    /// The hardware dithers the fractional part by lengthening some of 16
+    /// consecutive PWM periods, which gives 4 bits more resolution on average.
+    fn set_duty_dithered(&self, duty_x16: u32) -> Result<(), Error>;
+
     /// Start a duty-cycle fade
     fn start_duty_fade(
         &self,
@@ -101,6 +139,15 @@ where
 
     /// Check whether a duty-cycle fade is running
     fn is_duty_fade_running(&self) -> bool;
+
+    /// Enable the interrupt raised when a duty-cycle fade ends
+    fn listen_fade_end(&self);
+
+    /// Disable the interrupt raised when a duty-cycle fade ends
+    fn unlisten_fade_end(&self);
+
+    /// Clear the fade end interrupt status
+    fn clear_fade_end_interrupt(&self);
 }
 
 /// Channel HW interface
@@ -111,7 +158,12 @@ pub trait ChannelHW<O: OutputPin> {
     fn configure_hw_with_pin_config(&mut self, cfg: config::PinConfig) -> Result<(), Error>;
 
     /// Set channel duty HW
-    fn set_duty_hw(&self, duty: u32);
+    fn set_duty_hw(&self, duty: u32) {
+        self.set_duty_x16_hw(duty << 4);
+    }
+
+    /// Set channel duty HW, including the 4 fractional bits
+    fn set_duty_x16_hw(&self, duty_x16: u32);
 
     /// Start a duty-cycle fade HW
     fn start_duty_fade_hw(
@@ -125,6 +177,15 @@ pub trait ChannelHW<O: OutputPin> {
 
     /// Check whether a duty-cycle fade is running HW
     fn is_duty_fade_running_hw(&self) -> bool;
+
+    /// Read the current duty of the channel HW
+    fn get_duty_hw(&self) -> u32;
+
+    /// Enable or disable the fade end interrupt HW
+    fn set_fade_end_interrupt_hw(&self, enable: bool);
+
+    /// Clear the fade end interrupt status HW
+    fn clear_fade_end_interrupt_hw(&self);
 }
 
 /// Channel struct
@@ -189,6 +250,28 @@ where
         Ok(())
     }
 
+    /// Set the duty in steps of the timer's duty resolution
+    fn set_duty_raw(&self, duty: u32) -> Result<(), Error> {
+        if duty > self.max_duty()? {
+            return Err(Error::Duty);
+        }
+
+        self.set_duty_hw(duty);
+
+        Ok(())
+    }
+
+    /// Set the duty in 1/16 steps of the timer's duty resolution
+    fn set_duty_dithered(&self, duty_x16: u32) -> Result<(), Error> {
+        if duty_x16 > self.max_duty()? << 4 {
+            return Err(Error::Duty);
+        }
+
+        self.set_duty_x16_hw(duty_x16);
+
+        Ok(())
+    }
+
     /// Start a duty fade from one % to another.
     ///
     /// There's a constraint on the combination of timer frequency, timer PWM
@@ -234,6 +317,53 @@ where
         let start_duty_value = (duty_range * start_duty_pct as u32) / 100;
         let end_duty_value = (duty_range * end_duty_pct as u32) / 100;
 
+        self.start_duty_fade_values(start_duty_value, end_duty_value, frequency, duration_ms)
+    }
+
+    fn is_duty_fade_running(&self) -> bool {
+        self.is_duty_fade_running_hw()
+    }
+
+    fn listen_fade_end(&self) {
+        self.set_fade_end_interrupt_hw(true);
+    }
+
+    fn unlisten_fade_end(&self) {
+        self.set_fade_end_interrupt_hw(false);
+    }
+
+    fn clear_fade_end_interrupt(&self) {
+        self.clear_fade_end_interrupt_hw();
+    }
+}
+
+impl<'a, S: TimerSpeed, O: OutputPin> Channel<'a, S, O>
+where
+    Channel<'a, S, O>: ChannelHW<O>,
+{
+    /// The duty value of a 100 % duty cycle
+    fn max_duty(&self) -> Result<u32, Error> {
+        let timer = self.timer.ok_or(Error::Channel)?;
+        let duty_exp = timer.get_duty().ok_or(Error::Timer)? as u32;
+
+        Ok(1 << duty_exp)
+    }
+
+    /// Fade from the duty `start_duty_value` to `end_duty_value` (in the
+    /// resolution of the timer) over `duration_ms`.
+    fn start_duty_fade_values(
+        &self,
+        start_duty_value: u32,
+        end_duty_value: u32,
+        frequency: u32,
+        duration_ms: u16,
+    ) -> Result<(), Error> {
+        if start_duty_value == end_duty_value {
+            // nothing to fade, the hardware can't do zero steps
+            self.set_duty_hw(end_duty_value);
+            return Ok(());
+        }
+
         // NB: since we do the multiplication first here, there's no loss of
         // precision from using milliseconds instead of (e.g.) nanoseconds.
         let pwm_cycles = (duration_ms as u32) * frequency / 1000;
@@ -273,8 +403,304 @@ where
         Ok(())
     }
 
-    fn is_duty_fade_running(&self) -> bool {
-        self.is_duty_fade_running_hw()
+    /// Route the output of the channel to another pin too, e.g. to drive two
+    /// backlights from the same PWM.
+    ///
+    /// The GPIO matrix can connect one output signal to any number of pins.
+    /// The pin keeps following the channel until it's detached with
+    /// [Channel::detach_pin]. The channel has to be configured first.
+    pub fn attach_pin<P: OutputPin>(
+        &self,
+        pin: impl Peripheral<P = P>,
+        cfg: config::PinConfig,
+    ) -> Result<(), Error> {
+        if self.timer.is_none() {
+            return Err(Error::Channel);
+        }
+
+        crate::into_ref!(pin);
+
+        match cfg {
+            config::PinConfig::PushPull => pin.set_to_push_pull_output(),
+            config::PinConfig::OpenDrain => pin.set_to_open_drain_output(),
+        };
+        pin.connect_peripheral_to_output(self.output_signal());
+
+        Ok(())
+    }
+
+    /// Disconnect a pin attached with [Channel::attach_pin] from the channel
+    pub fn detach_pin<P: OutputPin>(&self, pin: impl Peripheral<P = P>) {
+        crate::into_ref!(pin);
+
+        pin.disconnect_peripheral_from_output();
+    }
+
+    /// Fade from the current duty to `duty_pct` over `duration_ms` and wait
+    /// for the fade to end.
+    ///
+    /// The interrupt handler installed by [super::LEDC::new_async] wakes the
+    /// task when the fade ends. See [ChannelIFace::start_duty_fade] for the
+    /// limits of the fade parameters.
+    #[cfg(feature = "async")]
+    pub async fn fade_to(&self, duty_pct: u8, duration_ms: u16) -> Result<(), Error> {
+        if duty_pct > 100u8 {
+            return Err(Error::Fade(FadeError::EndDuty));
+        }
+
+        let timer = self.timer.ok_or(Error::Channel)?;
+        let duty_exp = timer.get_duty().ok_or(Error::Timer)? as u32;
+        let frequency = timer.get_frequency();
+        if frequency == 0 {
+            return Err(Error::Timer);
+        }
+
+        let duty_range = (1u32 << duty_exp) - 1;
+        let end_duty_value = (duty_range * duty_pct as u32) / 100;
+        let start_duty_value = self.get_duty_hw();
+        if start_duty_value == end_duty_value {
+            return Ok(());
+        }
+
+        self.set_fade_end_interrupt_hw(false);
+        self.start_duty_fade_values(start_duty_value, end_duty_value, frequency, duration_ms)?;
+        self.set_fade_end_interrupt_hw(true);
+
+        asynch::FadeFuture::new(self).await;
+
+        Ok(())
+    }
+
+    /// Fade through the duties in `points` (in steps of the timer's duty
+    /// resolution), spending the same share of `duration_ms` between each
+    /// two of them.
+    ///
+    /// The whole curve runs in hardware, from the gamma RAM of the channel.
+    /// Up to [MAX_FADE_SEGMENTS] + 1 points are supported, e.g. samples of a
+    /// perceptually linear brightness ramp:
+    ///
+    /// ```no_run
+    /// // x^2 from 0 to 1024 (10 bit duty resolution)
+    /// let points: [u32; 17] = core::array::from_fn(|i| (i * i * 4) as u32);
+    /// channel0.start_duty_fade_curve(&points, 2000).unwrap();
+    /// ```
+    ///
+    /// Like for [ChannelIFace::start_duty_fade], the end of the fade raises
+    /// the fade end interrupt.
+    #[cfg(any(esp32c6, esp32h2))]
+    pub fn start_duty_fade_curve(&self, points: &[u32], duration_ms: u32) -> Result<(), Error> {
+        let mut segments = [FadeSegment::default(); MAX_FADE_SEGMENTS];
+        let len = self.fade_curve_segments(points, duration_ms, &mut segments)?;
+
+        self.start_duty_fade_segments(points[0], &segments[..len])
+    }
+
+    /// Start a fade from `start_duty` (in steps of the timer's duty
+    /// resolution) through up to [MAX_FADE_SEGMENTS] segments
+    #[cfg(any(esp32c6, esp32h2))]
+    pub fn start_duty_fade_segments(
+        &self,
+        start_duty: u32,
+        segments: &[FadeSegment],
+    ) -> Result<(), Error> {
+        if start_duty > self.max_duty()? {
+            return Err(Error::Fade(FadeError::StartDuty));
+        }
+        if segments.is_empty() || segments.len() > MAX_FADE_SEGMENTS {
+            return Err(Error::Fade(FadeError::Segments));
+        }
+
+        let in_range =
+            |value: u16, min: u16| (min..=MAX_FADE_SEGMENT_VALUE as u16).contains(&value);
+        if !segments.iter().all(|segment| {
+            in_range(segment.steps, 1)
+                && in_range(segment.cycles_per_step, 1)
+                && in_range(segment.duty_per_step, 0)
+        }) {
+            return Err(Error::Fade(FadeError::Segments));
+        }
+
+        self.start_gamma_fade_hw(start_duty, segments);
+
+        Ok(())
+    }
+
+    /// Fade through the duties in `points` over `duration_ms` and wait for
+    /// the fade to end, see [Channel::start_duty_fade_curve]
+    #[cfg(all(feature = "async", any(esp32c6, esp32h2)))]
+    pub async fn fade_curve(&self, points: &[u32], duration_ms: u32) -> Result<(), Error> {
+        self.set_fade_end_interrupt_hw(false);
+        self.start_duty_fade_curve(points, duration_ms)?;
+        self.set_fade_end_interrupt_hw(true);
+
+        asynch::FadeFuture::new(self).await;
+
+        Ok(())
+    }
+
+    /// Split the curve through `points` into hardware fade segments, returning
+    /// how many of `segments` are used
+    #[cfg(any(esp32c6, esp32h2))]
+    fn fade_curve_segments(
+        &self,
+        points: &[u32],
+        duration_ms: u32,
+        segments: &mut [FadeSegment; MAX_FADE_SEGMENTS],
+    ) -> Result<usize, Error> {
+        let timer = self.timer.ok_or(Error::Channel)?;
+        let frequency = timer.get_frequency();
+        if frequency == 0 {
+            return Err(Error::Timer);
+        }
+
+        if points.len() < 2 || points.len() > MAX_FADE_SEGMENTS + 1 {
+            return Err(Error::Fade(FadeError::Segments));
+        }
+        let max_duty = self.max_duty()?;
+        if points.iter().any(|&point| point > max_duty) {
+            return Err(Error::Duty);
+        }
+
+        let len = points.len() - 1;
+        let segment_cycles = duration_ms as u64 * frequency as u64 / 1000 / len as u64;
+
+        // Each segment starts where the previous one really ended, so rounding
+        // the duty per step doesn't add up along the curve
+        let mut duty = points[0];
+        for (segment, &target) in segments.iter_mut().zip(&points[1..]) {
+            let duty_diff = target.abs_diff(duty);
+            let (steps, duty_per_step) = if duty_diff == 0 {
+                // hold the duty, as long as needed
+                let steps = segment_cycles
+                    .div_ceil(MAX_FADE_SEGMENT_VALUE as u64)
+                    .max(1);
+                (steps.min(MAX_FADE_SEGMENT_VALUE as u64) as u32, 0)
+            } else {
+                let duty_per_step = duty_diff.div_ceil(MAX_FADE_SEGMENT_VALUE);
+                (duty_diff / duty_per_step, duty_per_step)
+            };
+
+            let cycles_per_step = segment_cycles / steps as u64;
+            if !(1..=MAX_FADE_SEGMENT_VALUE as u64).contains(&cycles_per_step) {
+                return Err(Error::Fade(FadeError::Duration));
+            }
+
+            *segment = FadeSegment {
+                increase: target > duty,
+                steps: steps as u16,
+                cycles_per_step: cycles_per_step as u16,
+                duty_per_step: duty_per_step as u16,
+            };
+
+            if target > duty {
+                duty += steps * duty_per_step;
+            } else {
+                duty -= steps * duty_per_step;
+            }
+        }
+
+        Ok(len)
+    }
+
+    #[cfg(feature = "async")]
+    fn waker_index(&self) -> usize {
+        if S::IS_HS {
+            self.number as usize + 8
+        } else {
+            self.number as usize
+        }
+    }
+}
+
+#[cfg(feature = "async")]
+pub(crate) mod asynch {
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use embassy_sync::waitqueue::AtomicWaker;
+    use procmacros::handler;
+
+    use super::{Channel, ChannelHW};
+    use crate::{gpio::OutputPin, ledc::timer::TimerSpeed};
+
+    #[cfg(esp32)]
+    const NUM_WAKERS: usize = 16;
+    #[cfg(not(esp32))]
+    const NUM_WAKERS: usize = 8;
+
+    #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2))]
+    const NUM_CHANNELS: u8 = 6;
+    #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+    const NUM_CHANNELS: u8 = 8;
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: AtomicWaker = AtomicWaker::new();
+    static WAKERS: [AtomicWaker; NUM_WAKERS] = [INIT; NUM_WAKERS];
+
+    pub(crate) struct FadeFuture<'c, 'a, S: TimerSpeed, O: OutputPin> {
+        channel: &'c Channel<'a, S, O>,
+    }
+
+    impl<'c, 'a, S: TimerSpeed, O: OutputPin> FadeFuture<'c, 'a, S, O> {
+        pub fn new(channel: &'c Channel<'a, S, O>) -> Self {
+            Self { channel }
+        }
+    }
+
+    impl<'c, 'a, S: TimerSpeed, O: OutputPin> core::future::Future for FadeFuture<'c, 'a, S, O>
+    where
+        Channel<'a, S, O>: ChannelHW<O>,
+    {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            WAKERS[self.channel.waker_index()].register(cx.waker());
+
+            if self.channel.is_duty_fade_running_hw() {
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        }
+    }
+
+    impl<'c, 'a, S: TimerSpeed, O: OutputPin> Drop for FadeFuture<'c, 'a, S, O> {
+        fn drop(&mut self) {
+            // the fade keeps running, but nobody waits for its end anymore
+            self.channel.set_fade_end_interrupt_hw(false);
+        }
+    }
+
+    #[handler]
+    pub(crate) fn interrupt_handler() {
+        let ledc = unsafe { &*crate::peripherals::LEDC::PTR };
+        let status = ledc.int_st().read();
+
+        for ch in 0..NUM_CHANNELS {
+            #[cfg(esp32)]
+            {
+                if status.duty_chng_end_hsch(ch).bit_is_set() {
+                    ledc.int_ena()
+                        .modify(|_, w| w.duty_chng_end_hsch(ch).clear_bit());
+                    WAKERS[ch as usize + 8].wake();
+                }
+
+                if status.duty_chng_end_lsch(ch).bit_is_set() {
+                    ledc.int_ena()
+                        .modify(|_, w| w.duty_chng_end_lsch(ch).clear_bit());
+                    WAKERS[ch as usize].wake();
+                }
+            }
+
+            #[cfg(not(esp32))]
+            if status.duty_chng_end_ch(ch).bit_is_set() {
+                ledc.int_ena()
+                    .modify(|_, w| w.duty_chng_end_ch(ch).clear_bit());
+                WAKERS[ch as usize].wake();
+            }
+        }
     }
 }
 
@@ -282,7 +708,7 @@ where
 mod ehal1 {
     use embedded_hal::pwm::{self, ErrorKind, ErrorType, SetDutyCycle};
 
-    use super::{Channel, ChannelHW, ChannelIFace, Error};
+    use super::{Channel, ChannelHW, Error};
     use crate::{gpio::OutputPin, ledc::timer::TimerSpeed};
 
     impl pwm::Error for Error {
@@ -295,46 +721,80 @@ mod ehal1 {
         type Error = Error;
     }
 
+    /// The duty cycle is in steps of the timer's duty resolution. Resolutions
+    /// above 16 bits (ESP32 only) are scaled to the 16 bits of the trait.
     impl<'a, S: TimerSpeed, O: OutputPin> SetDutyCycle for Channel<'a, S, O>
     where
         Channel<'a, S, O>: ChannelHW<O>,
     {
         fn max_duty_cycle(&self) -> u16 {
-            let duty_exp;
-
-            if let Some(timer_duty) = self.timer.and_then(|timer| timer.get_duty()) {
-                duty_exp = timer_duty as u32;
-            } else {
-                return 0;
-            }
-
-            let duty_range = 2u32.pow(duty_exp);
-
-            #[cfg(esp32)]
-            {
-                let duty_range_pct = 100 / (u32::MAX / duty_range);
-                (u16::MAX / 100) * duty_range_pct as u16
-            }
-            #[cfg(not(esp32))]
-            {
-                // Supports up to 14 bits
-                duty_range as u16
+            match self.max_duty() {
+                Ok(duty_range) => duty_range.min(u16::MAX as u32) as u16,
+                Err(_) => 0,
             }
         }
 
         fn set_duty_cycle(&mut self, duty: u16) -> Result<(), Self::Error> {
-            if duty == 0 {
-                self.set_duty(0)?;
-            } else {
-                let duty_pct = 100 / (self.max_duty_cycle() / duty);
-                self.set_duty(duty_pct as u8)?;
+            let duty_range = self.max_duty()?;
+            let max_duty = duty_range.min(u16::MAX as u32);
+            if duty as u32 > max_duty {
+                return Err(Error::Duty);
             }
+
+            let duty_value = (duty as u64 * duty_range as u64 / max_duty as u64) as u32;
+            self.set_duty_hw(duty_value);
+
             Ok(())
         }
     }
 }
 
 impl<'a, O: OutputPin, S: crate::ledc::timer::TimerSpeed> Channel<'a, S, O> {
+    /// The output signal of the channel in the GPIO matrix
+    #[cfg(esp32)]
+    fn output_signal(&self) -> OutputSignal {
+        if S::IS_HS {
+            match self.number {
+                Number::Channel0 => OutputSignal::LEDC_HS_SIG0,
+                Number::Channel1 => OutputSignal::LEDC_HS_SIG1,
+                Number::Channel2 => OutputSignal::LEDC_HS_SIG2,
+                Number::Channel3 => OutputSignal::LEDC_HS_SIG3,
+                Number::Channel4 => OutputSignal::LEDC_HS_SIG4,
+                Number::Channel5 => OutputSignal::LEDC_HS_SIG5,
+                Number::Channel6 => OutputSignal::LEDC_HS_SIG6,
+                Number::Channel7 => OutputSignal::LEDC_HS_SIG7,
+            }
+        } else {
+            match self.number {
+                Number::Channel0 => OutputSignal::LEDC_LS_SIG0,
+                Number::Channel1 => OutputSignal::LEDC_LS_SIG1,
+                Number::Channel2 => OutputSignal::LEDC_LS_SIG2,
+                Number::Channel3 => OutputSignal::LEDC_LS_SIG3,
+                Number::Channel4 => OutputSignal::LEDC_LS_SIG4,
+                Number::Channel5 => OutputSignal::LEDC_LS_SIG5,
+                Number::Channel6 => OutputSignal::LEDC_LS_SIG6,
+                Number::Channel7 => OutputSignal::LEDC_LS_SIG7,
+            }
+        }
+    }
+
+    /// The output signal of the channel in the GPIO matrix
+    #[cfg(not(esp32))]
+    fn output_signal(&self) -> OutputSignal {
+        match self.number {
+            Number::Channel0 => OutputSignal::LEDC_LS_SIG0,
+            Number::Channel1 => OutputSignal::LEDC_LS_SIG1,
+            Number::Channel2 => OutputSignal::LEDC_LS_SIG2,
+            Number::Channel3 => OutputSignal::LEDC_LS_SIG3,
+            Number::Channel4 => OutputSignal::LEDC_LS_SIG4,
+            Number::Channel5 => OutputSignal::LEDC_LS_SIG5,
+            #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+            Number::Channel6 => OutputSignal::LEDC_LS_SIG6,
+            #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+            Number::Channel7 => OutputSignal::LEDC_LS_SIG7,
+        }
+    }
+
     #[cfg(esp32)]
     fn set_channel(&mut self, timer_number: u8) {
         if S::IS_HS {
@@ -404,6 +864,13 @@ impl<'a, O: OutputPin, S: crate::ledc::timer::TimerSpeed> Channel<'a, S, O> {
                 w.ch_gamma_scale().bits(0x0)
             }
         });
+        // drop the rest of a fade curve
+        self.ledc
+            .ch_gamma_wr_addr(cnum)
+            .write(|w| unsafe { w.ch_gamma_wr_addr().bits(0) });
+        self.ledc
+            .ch_gamma_conf(cnum)
+            .write(|w| unsafe { w.ch_gamma_entry_num().bits(0x1) });
     }
     #[cfg(not(any(esp32, esp32c6, esp32h2)))]
     fn start_duty_without_fading(&self) {
@@ -492,6 +959,44 @@ impl<'a, O: OutputPin, S: crate::ledc::timer::TimerSpeed> Channel<'a, S, O> {
             .write(|w| unsafe { w.ch_gamma_entry_num().bits(0x1) });
     }
 
+    #[cfg(any(esp32c6, esp32h2))]
+    fn start_gamma_fade_hw(&self, start_duty: u32, segments: &[FadeSegment]) {
+        let cnum = self.number as usize;
+        self.ledc
+            .ch(cnum)
+            .duty()
+            .write(|w| unsafe { w.duty().bits(start_duty << 4) });
+        self.ledc
+            .int_clr()
+            .write(|w| w.duty_chng_end_ch(self.number as u8).clear_bit_by_one());
+
+        // every write of the address stores the entry into the gamma RAM
+        for (index, segment) in segments.iter().enumerate() {
+            self.ledc.ch_gamma_wr(cnum).write(|w| unsafe {
+                w.ch_gamma_duty_inc()
+                    .variant(segment.increase)
+                    .ch_gamma_duty_num()
+                    .bits(segment.steps)
+                    .ch_gamma_duty_cycle()
+                    .bits(segment.cycles_per_step)
+                    .ch_gamma_scale()
+                    .bits(segment.duty_per_step)
+            });
+            self.ledc
+                .ch_gamma_wr_addr(cnum)
+                .write(|w| unsafe { w.ch_gamma_wr_addr().bits(index as u8) });
+        }
+        self.ledc
+            .ch_gamma_conf(cnum)
+            .write(|w| unsafe { w.ch_gamma_entry_num().bits(segments.len() as u8) });
+
+        self.ledc
+            .ch(cnum)
+            .conf1()
+            .write(|w| w.duty_start().set_bit());
+        self.update_channel();
+    }
+
     #[cfg(not(any(esp32, esp32c6, esp32h2)))]
     fn start_duty_fade_inner(
         &self,
@@ -557,46 +1062,7 @@ where
             self.set_channel(timer_number);
             self.update_channel();
 
-            #[cfg(esp32)]
-            let signal = if S::IS_HS {
-                #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
-                match self.number {
-                    Number::Channel0 => OutputSignal::LEDC_HS_SIG0,
-                    Number::Channel1 => OutputSignal::LEDC_HS_SIG1,
-                    Number::Channel2 => OutputSignal::LEDC_HS_SIG2,
-                    Number::Channel3 => OutputSignal::LEDC_HS_SIG3,
-                    Number::Channel4 => OutputSignal::LEDC_HS_SIG4,
-                    Number::Channel5 => OutputSignal::LEDC_HS_SIG5,
-                    Number::Channel6 => OutputSignal::LEDC_HS_SIG6,
-                    Number::Channel7 => OutputSignal::LEDC_HS_SIG7,
-                }
-            } else {
-                match self.number {
-                    Number::Channel0 => OutputSignal::LEDC_LS_SIG0,
-                    Number::Channel1 => OutputSignal::LEDC_LS_SIG1,
-                    Number::Channel2 => OutputSignal::LEDC_LS_SIG2,
-                    Number::Channel3 => OutputSignal::LEDC_LS_SIG3,
-                    Number::Channel4 => OutputSignal::LEDC_LS_SIG4,
-                    Number::Channel5 => OutputSignal::LEDC_LS_SIG5,
-                    #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
-                    Number::Channel6 => OutputSignal::LEDC_LS_SIG6,
-                    #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
-                    Number::Channel7 => OutputSignal::LEDC_LS_SIG7,
-                }
-            };
-            #[cfg(not(esp32))]
-            let signal = match self.number {
-                Number::Channel0 => OutputSignal::LEDC_LS_SIG0,
-                Number::Channel1 => OutputSignal::LEDC_LS_SIG1,
-                Number::Channel2 => OutputSignal::LEDC_LS_SIG2,
-                Number::Channel3 => OutputSignal::LEDC_LS_SIG3,
-                Number::Channel4 => OutputSignal::LEDC_LS_SIG4,
-                Number::Channel5 => OutputSignal::LEDC_LS_SIG5,
-                #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
-                Number::Channel6 => OutputSignal::LEDC_LS_SIG6,
-                #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
-                Number::Channel7 => OutputSignal::LEDC_LS_SIG7,
-            };
+            let signal = self.output_signal();
             self.output_pin.connect_peripheral_to_output(signal);
         } else {
             return Err(Error::Timer);
@@ -607,17 +1073,17 @@ where
 
     /// Set duty in channel HW
     #[cfg(esp32)]
-    fn set_duty_hw(&self, duty: u32) {
+    fn set_duty_x16_hw(&self, duty_x16: u32) {
         if S::IS_HS {
             self.ledc
                 .hsch(self.number as usize)
                 .duty()
-                .write(|w| unsafe { w.duty().bits(duty << 4) });
+                .write(|w| unsafe { w.duty().bits(duty_x16) });
         } else {
             self.ledc
-                .hsch(self.number as usize)
+                .lsch(self.number as usize)
                 .duty()
-                .write(|w| unsafe { w.duty().bits(duty << 4) });
+                .write(|w| unsafe { w.duty().bits(duty_x16) });
         }
         self.start_duty_without_fading();
         self.update_channel();
@@ -625,11 +1091,11 @@ where
 
     /// Set duty in channel HW
     #[cfg(not(esp32))]
-    fn set_duty_hw(&self, duty: u32) {
+    fn set_duty_x16_hw(&self, duty_x16: u32) {
         self.ledc
             .ch(self.number as usize)
             .duty()
-            .write(|w| unsafe { w.duty().bits(duty << 4) });
+            .write(|w| unsafe { w.duty().bits(duty_x16) });
         self.start_duty_without_fading();
         self.update_channel();
     }
@@ -704,4 +1170,79 @@ where
             .duty_chng_end_ch(self.number as u8)
             .bit_is_clear()
     }
+
+    #[cfg(esp32)]
+    fn get_duty_hw(&self) -> u32 {
+        let duty = if S::IS_HS {
+            self.ledc
+                .hsch(self.number as usize)
+                .duty_r()
+                .read()
+                .duty_r()
+                .bits()
+        } else {
+            self.ledc
+                .lsch(self.number as usize)
+                .duty_r()
+                .read()
+                .duty_r()
+                .bits()
+        };
+        duty >> 4
+    }
+
+    #[cfg(not(esp32))]
+    fn get_duty_hw(&self) -> u32 {
+        self.ledc
+            .ch(self.number as usize)
+            .duty_r()
+            .read()
+            .duty_r()
+            .bits()
+            >> 4
+    }
+
+    #[cfg(esp32)]
+    fn set_fade_end_interrupt_hw(&self, enable: bool) {
+        critical_section::with(|_| {
+            if S::IS_HS {
+                self.ledc
+                    .int_ena()
+                    .modify(|_, w| w.duty_chng_end_hsch(self.number as u8).bit(enable));
+            } else {
+                self.ledc
+                    .int_ena()
+                    .modify(|_, w| w.duty_chng_end_lsch(self.number as u8).bit(enable));
+            }
+        });
+    }
+
+    #[cfg(not(esp32))]
+    fn set_fade_end_interrupt_hw(&self, enable: bool) {
+        critical_section::with(|_| {
+            self.ledc
+                .int_ena()
+                .modify(|_, w| w.duty_chng_end_ch(self.number as u8).bit(enable));
+        });
+    }
+
+    #[cfg(esp32)]
+    fn clear_fade_end_interrupt_hw(&self) {
+        if S::IS_HS {
+            self.ledc
+                .int_clr()
+                .write(|w| w.duty_chng_end_hsch(self.number as u8).clear_bit_by_one());
+        } else {
+            self.ledc
+                .int_clr()
+                .write(|w| w.duty_chng_end_lsch(self.number as u8).clear_bit_by_one());
+        }
+    }
+
+    #[cfg(not(esp32))]
+    fn clear_fade_end_interrupt_hw(&self) {
+        self.ledc
+            .int_clr()
+            .write(|w| w.duty_chng_end_ch(self.number as u8).clear_bit_by_one());
+    }
 }
@@ -1,8 +1,7 @@
 //! # LEDC (LED PWM Controller) peripheral control
 //!
-//! Currently only supports fixed-frequency output. Interrupts are not currently
-//! implemented. High Speed channels are available for the ESP32 only, while Low
-//! Speed channels are available for all supported chips.
+//! High Speed channels are available for the ESP32 only, while Low Speed
+//! channels are available for all supported chips.
 //!
 //! # LowSpeed Example:
 //!
@@ -57,10 +56,95 @@
 //!     .unwrap();
 //! ```
 //!
-//! # TODO
+//! # Several pins
 //!
-//! - Source clock selection
-//! - Interrupts
+//! A channel drives its own pin, and can drive more pins through the GPIO
+//! matrix, see [channel::Channel::attach_pin]:
+//!
+//! ```no_run
+//! channel0
+//!     .attach_pin(&mut led2, channel::config::PinConfig::PushPull)
+//!     .unwrap();
+//! // ...
+//! channel0.detach_pin(&mut led2);
+//! ```
+//!
+//! # Choosing the duty resolution
+//!
+//! Higher frequencies leave fewer clock cycles per PWM period, and so a lower
+//! duty resolution. [timer::TimerIFace::configure_for_frequency] picks the
+//! highest resolution for a frequency and returns it, along with the frequency
+//! the timer actually runs at:
+//!
+//! ```no_run
+//! let (duty, frequency) = lstimer0
+//!     .configure_for_frequency(timer::LSClockSource::APBClk, 24.kHz())
+//!     .unwrap();
+//! ```
+//!
+//! # Runtime updates
+//!
+//! The duty of a channel and the frequency of a timer can be changed while
+//! they're running. The hardware applies the new values at the end of the
+//! current PWM period, so the output doesn't glitch:
+//!
+//! ```no_run
+//! lstimer0.set_frequency(20.kHz()).unwrap();
+//! channel0.set_duty_raw(12).unwrap();
+//!
+//! // 12.5 steps of the 5 bit resolution, dithered by the hardware
+//! channel0.set_duty_dithered(12 * 16 + 8).unwrap();
+//! ```
+//!
+//! # Fades
+//!
+//! The hardware fades the duty of a channel on its own, see
+//! [channel::ChannelIFace::start_duty_fade]. The end of a fade raises an
+//! interrupt. With an LEDC created by [LEDC::new_async], a task can wait for
+//! it:
+//!
+//! ```no_run
+//! let ledc = LEDC::new_async(peripherals.LEDC, &clocks);
+//! // configure the timer and channel as above
+//!
+//! channel0.fade_to(100, 1000).await.unwrap();
+//! channel0.fade_to(0, 1000).await.unwrap();
+//! ```
+//!
+//! The ESP32-C6 and ESP32-H2 run fades from a small gamma RAM per channel,
+//! which holds a curve of up to 16 linear segments, see
+//! [channel::Channel::start_duty_fade_curve] and
+//! [channel::Channel::fade_curve].
+//!
+//! # Clock sources
+//!
+//! High speed timers count either the APB clock or the 1 MHz REF_TICK
+//! ([timer::HSClockSource]). On the ESP32, low speed timers count either the
+//! global slow clock set by [LEDC::set_global_slow_clock] or REF_TICK
+//! ([timer::LSClockSource]). Timers configured for the APB clock switch to
+//! REF_TICK on their own when the requested frequency is too low for the APB
+//! clock.
+//!
+//! The internal 8 MHz RC oscillator ([LSGlobalClkSource::RTC8MClk]) keeps
+//! running in light sleep, so low speed timers counting it keep their
+//! channels going while the CPU sleeps, as long as the sleep configuration
+//! doesn't power the oscillator down (see
+//! [crate::rtc_cntl::sleep::RtcSleepConfig::int_8m_pd_en]):
+//!
+//! ```no_run
+//! ledc.set_global_slow_clock(LSGlobalClkSource::RTC8MClk);
+//!
+//! let mut lstimer0 = ledc.get_timer::<LowSpeed>(timer::Number::Timer0);
+//! lstimer0
+//!     .configure(timer::config::Config {
+//!         duty: timer::config::Duty::Duty10Bit,
+//!         clock_source: timer::LSClockSource::SLOWClk,
+//!         frequency: 1.kHz(),
+//!     })
+//!     .unwrap();
+//! ```
+//!
+//! The frequency of the RC oscillator is only accurate to a few percent.
 
 use self::{
     channel::Channel,
@@ -69,7 +153,9 @@ use self::{
 use crate::{
     clock::Clocks,
     gpio::OutputPin,
+    interrupt::{self, InterruptHandler},
     peripheral::{Peripheral, PeripheralRef},
+    peripherals::Interrupt,
     system::{Peripheral as PeripheralEnable, PeripheralClockControl},
 };
 
@@ -79,7 +165,11 @@ pub mod timer;
 /// Global slow clock source
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum LSGlobalClkSource {
+    /// APB clock
     APBClk,
+    /// Internal 8 MHz RC oscillator, which keeps running in light sleep
+    #[cfg(esp32)]
+    RTC8MClk,
 }
 
 /// LEDC (LED PWM Controller)
@@ -126,10 +216,51 @@ impl<'d> LEDC<'d> {
         }
     }
 
+    /// Return a new LEDC which wakes the tasks waiting for
+    /// [channel::Channel::fade_to] from its interrupt
+    #[cfg(feature = "async")]
+    pub fn new_async(
+        _instance: impl Peripheral<P = crate::peripherals::LEDC> + 'd,
+        clock_control_config: &'d Clocks,
+    ) -> Self {
+        let mut this = Self::new(_instance, clock_control_config);
+        this.set_interrupt_handler(channel::asynch::interrupt_handler);
+        this
+    }
+
+    /// Install an interrupt handler for the LEDC interrupts, e.g. the fade end
+    /// interrupts enabled by [channel::ChannelIFace::listen_fade_end]
+    pub fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        unsafe {
+            interrupt::bind_interrupt(Interrupt::LEDC, handler.handler());
+            interrupt::enable(Interrupt::LEDC, handler.priority()).unwrap();
+        }
+    }
+
     /// Set global slow clock source
     #[cfg(esp32)]
-    pub fn set_global_slow_clock(&mut self, _clock_source: LSGlobalClkSource) {
-        self.ledc.conf().write(|w| w.apb_clk_sel().set_bit());
+    pub fn set_global_slow_clock(&mut self, clock_source: LSGlobalClkSource) {
+        match clock_source {
+            LSGlobalClkSource::APBClk => {
+                self.ledc.conf().write(|w| w.apb_clk_sel().set_bit());
+            }
+            LSGlobalClkSource::RTC8MClk => {
+                let rtc_cntl = unsafe { &*crate::peripherals::LPWR::PTR };
+
+                // Power up the oscillator and let it settle, then gate it
+                // through to the digital peripherals
+                if rtc_cntl.clk_conf().read().enb_ck8m().bit_is_set() {
+                    rtc_cntl.clk_conf().modify(|_, w| w.enb_ck8m().clear_bit());
+                    crate::rom::ets_delay_us(50);
+                }
+                rtc_cntl
+                    .clk_conf()
+                    .modify(|_, w| w.dig_clk8m_en().set_bit());
+                crate::rom::ets_delay_us(5);
+
+                self.ledc.conf().write(|w| w.apb_clk_sel().clear_bit());
+            }
+        }
         self.ledc
             .lstimer(0)
             .conf()
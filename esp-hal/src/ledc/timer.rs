@@ -9,11 +9,15 @@
 //! duty cycles and frequencies, making it ideal for Pulse-Width Modulation
 //! (PWM) applications and LED lighting control.
 
+use core::cell::Cell;
+
 use fugit::HertzU32;
 
 #[cfg(esp32)]
 use super::HighSpeed;
 use super::{LowSpeed, Speed};
+#[cfg(esp32)]
+use crate::{clock::Clock, rtc_cntl::RtcFastClock};
 use crate::{clock::Clocks, peripherals::ledc};
 
 const LEDC_TIMER_DIV_NUM_MAX: u64 = 0x3FFFF;
@@ -24,6 +28,8 @@ const LEDC_TIMER_DIV_NUM_MAX: u64 = 0x3FFFF;
 pub enum Error {
     /// Invalid Divisor
     Divisor,
+    /// Timer not configured
+    NotConfigured,
 }
 
 #[cfg(esp32)]
@@ -31,16 +37,28 @@ pub enum Error {
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum HSClockSource {
+    /// APB clock
     APBClk,
-    // TODO RefTick,
+    /// 1 MHz REF_TICK
+    RefTick,
 }
 
 /// Clock source for LS Timers
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum LSClockSource {
+    /// APB clock
+    ///
+    /// On the ESP32 this is the global slow clock, which has to be set to
+    /// [super::LSGlobalClkSource::APBClk]. Otherwise the timer counts the
+    /// configured global slow clock, and reports its frequency.
     APBClk,
-    // TODO SLOWClk
+    /// Global slow clock set by [super::LEDC::set_global_slow_clock]
+    #[cfg(esp32)]
+    SLOWClk,
+    /// 1 MHz REF_TICK
+    #[cfg(esp32)]
+    RefTick,
 }
 
 /// Timer number
@@ -89,6 +107,48 @@ pub mod config {
         Duty20Bit,
     }
 
+    impl Duty {
+        /// The highest duty resolution of the chip
+        #[cfg(esp32)]
+        pub(crate) const MAX: Duty = Duty::Duty20Bit;
+        /// The highest duty resolution of the chip
+        #[cfg(not(esp32))]
+        pub(crate) const MAX: Duty = Duty::Duty14Bit;
+
+        /// The duty resolution with the given number of bits
+        pub(crate) fn from_bits(bits: u32) -> Option<Duty> {
+            Some(match bits {
+                1 => Duty::Duty1Bit,
+                2 => Duty::Duty2Bit,
+                3 => Duty::Duty3Bit,
+                4 => Duty::Duty4Bit,
+                5 => Duty::Duty5Bit,
+                6 => Duty::Duty6Bit,
+                7 => Duty::Duty7Bit,
+                8 => Duty::Duty8Bit,
+                9 => Duty::Duty9Bit,
+                10 => Duty::Duty10Bit,
+                11 => Duty::Duty11Bit,
+                12 => Duty::Duty12Bit,
+                13 => Duty::Duty13Bit,
+                14 => Duty::Duty14Bit,
+                #[cfg(esp32)]
+                15 => Duty::Duty15Bit,
+                #[cfg(esp32)]
+                16 => Duty::Duty16Bit,
+                #[cfg(esp32)]
+                17 => Duty::Duty17Bit,
+                #[cfg(esp32)]
+                18 => Duty::Duty18Bit,
+                #[cfg(esp32)]
+                19 => Duty::Duty19Bit,
+                #[cfg(esp32)]
+                20 => Duty::Duty20Bit,
+                _ => return None,
+            })
+        }
+    }
+
     /// Timer configuration
     #[derive(Copy, Clone)]
     pub struct Config<CS> {
@@ -122,6 +182,18 @@ pub trait TimerIFace<S: TimerSpeed> {
     /// Configure the timer
     fn configure(&mut self, config: config::Config<S::ClockSourceType>) -> Result<(), Error>;
 
+    /// Configure the timer for `frequency` with the highest duty resolution
+    /// the clock source allows.
+    ///
+    /// Returns the chosen duty resolution and the frequency the timer actually
+    /// runs at, which differs slightly from `frequency` when the clock source
+    /// isn't a multiple of it.
+    fn configure_for_frequency(
+        &mut self,
+        clock_source: S::ClockSourceType,
+        frequency: HertzU32,
+    ) -> Result<(config::Duty, HertzU32), Error>;
+
     /// Check if the timer has been configured
     fn is_configured(&self) -> bool;
 
@@ -133,6 +205,14 @@ pub trait TimerIFace<S: TimerSpeed> {
 
     /// Return the timer frequency, or 0 if not configured
     fn get_frequency(&self) -> u32;
+
+    /// Change the frequency of a configured timer, keeping its duty
+    /// resolution and the duty cycle of its channels.
+    ///
+    /// Low speed timers switch to the new frequency at the end of the current
+    /// PWM period, so the outputs don't glitch. The ESP32's high speed timers
+    /// switch right away.
+    fn set_frequency(&self, frequency: HertzU32) -> Result<(), Error>;
 }
 
 /// Interface for HW configuration of timer
@@ -145,6 +225,10 @@ pub trait TimerHW<S: TimerSpeed> {
 
     /// Update the timer in HW
     fn update_hw(&self);
+
+    /// Whether the timer may count REF_TICK instead of its clock source when
+    /// the divisor for the requested frequency is too large
+    fn ref_tick_fallback_hw(&self) -> bool;
 }
 
 /// Timer struct
@@ -153,9 +237,9 @@ pub struct Timer<'a, S: TimerSpeed> {
     clock_control_config: &'a Clocks<'a>,
     number: Number,
     duty: Option<config::Duty>,
-    frequency: u32,
+    frequency: Cell<u32>,
     configured: bool,
-    use_ref_tick: bool,
+    use_ref_tick: Cell<bool>,
     clock_source: Option<S::ClockSourceType>,
 }
 
@@ -173,26 +257,10 @@ where
         self.duty = Some(config.duty);
         self.clock_source = Some(config.clock_source);
 
-        // TODO: we should return some error here if `unwrap()` fails
-        let src_freq: u32 = self.get_freq().unwrap().to_Hz();
-        let precision = 1 << config.duty as u32;
-        let frequency: u32 = config.frequency.raw();
-        self.frequency = frequency;
-
-        let mut divisor = ((src_freq as u64) << 8) / frequency as u64 / precision as u64;
-
-        if divisor > LEDC_TIMER_DIV_NUM_MAX {
-            // APB_CLK results in divisor which too high. Try using REF_TICK as clock
-            // source.
-            self.use_ref_tick = true;
-            divisor = (1_000_000u64 << 8) / frequency as u64 / precision as u64;
-        }
-
-        if !(256..LEDC_TIMER_DIV_NUM_MAX).contains(&divisor) {
-            return Err(Error::Divisor);
-        }
+        let divisor = self.calculate_divisor(config.duty, config.frequency)?;
+        self.frequency.set(config.frequency.raw());
 
-        self.configure_hw(divisor as u32);
+        self.configure_hw(divisor);
         self.update_hw();
 
         self.configured = true;
@@ -200,6 +268,41 @@ where
         Ok(())
     }
 
+    /// Configure the timer with the highest possible duty resolution
+    fn configure_for_frequency(
+        &mut self,
+        clock_source: S::ClockSourceType,
+        frequency: HertzU32,
+    ) -> Result<(config::Duty, HertzU32), Error> {
+        self.clock_source = Some(clock_source);
+
+        // The first resolution which leaves a divisor of at least 1 is the
+        // highest one
+        for bits in (1..=config::Duty::MAX as u32).rev() {
+            let duty = unwrap!(config::Duty::from_bits(bits));
+            if let Ok(divisor) = self.calculate_divisor(duty, frequency) {
+                let src_freq = if self.use_ref_tick.get() {
+                    1_000_000
+                } else {
+                    unwrap!(self.get_freq()).to_Hz()
+                };
+                let actual = ((src_freq as u64) << 8) / divisor as u64 / (1u64 << bits);
+
+                self.duty = Some(duty);
+                self.frequency.set(actual as u32);
+
+                self.configure_hw(divisor);
+                self.update_hw();
+
+                self.configured = true;
+
+                return Ok((duty, HertzU32::Hz(actual as u32)));
+            }
+        }
+
+        Err(Error::Divisor)
+    }
+
     /// Check if the timer has been configured
     fn is_configured(&self) -> bool {
         self.configured
@@ -217,7 +320,54 @@ where
 
     /// Return the timer frequency
     fn get_frequency(&self) -> u32 {
-        self.frequency
+        self.frequency.get()
+    }
+
+    /// Change the frequency of a configured timer
+    fn set_frequency(&self, frequency: HertzU32) -> Result<(), Error> {
+        if !self.configured {
+            return Err(Error::NotConfigured);
+        }
+
+        let divisor = self.calculate_divisor(unwrap!(self.duty), frequency)?;
+        self.frequency.set(frequency.raw());
+
+        self.configure_hw(divisor);
+        self.update_hw();
+
+        Ok(())
+    }
+}
+
+impl<'a, S: TimerSpeed> Timer<'a, S>
+where
+    Timer<'a, S>: TimerHW<S>,
+{
+    /// Calculate the divisor (with 8 fractional bits) for `frequency` and
+    /// select the clock the timer counts
+    fn calculate_divisor(&self, duty: config::Duty, frequency: HertzU32) -> Result<u32, Error> {
+        let src_freq: u32 = self.get_freq().ok_or(Error::NotConfigured)?.to_Hz();
+        let precision = 1 << duty as u32;
+        let frequency: u32 = frequency.raw();
+        if frequency == 0 {
+            return Err(Error::Divisor);
+        }
+
+        let mut divisor = ((src_freq as u64) << 8) / frequency as u64 / precision as u64;
+        self.use_ref_tick.set(false);
+
+        if divisor > LEDC_TIMER_DIV_NUM_MAX && self.ref_tick_fallback_hw() {
+            // APB_CLK results in divisor which too high. Try using REF_TICK as clock
+            // source.
+            self.use_ref_tick.set(true);
+            divisor = (1_000_000u64 << 8) / frequency as u64 / precision as u64;
+        }
+
+        if !(256..LEDC_TIMER_DIV_NUM_MAX).contains(&divisor) {
+            return Err(Error::Divisor);
+        }
+
+        Ok(divisor as u32)
     }
 }
 
@@ -233,9 +383,9 @@ impl<'a, S: TimerSpeed> Timer<'a, S> {
             clock_control_config,
             number,
             duty: None,
-            frequency: 0u32,
+            frequency: Cell::new(0),
             configured: false,
-            use_ref_tick: false,
+            use_ref_tick: Cell::new(false),
             clock_source: None,
         }
     }
@@ -246,7 +396,20 @@ impl<'a> TimerHW<LowSpeed> for Timer<'a, LowSpeed> {
     /// Get the current source timer frequency from the HW
     fn get_freq_hw(&self) -> Option<fugit::HertzU32> {
         self.clock_source.map(|cs| match cs {
+            #[cfg(not(esp32))]
             LSClockSource::APBClk => self.clock_control_config.apb_clock,
+            // Both count the global slow clock, which is only the APB clock if
+            // it was configured so
+            #[cfg(esp32)]
+            LSClockSource::APBClk | LSClockSource::SLOWClk => {
+                if self.ledc.conf().read().apb_clk_sel().bit_is_set() {
+                    self.clock_control_config.apb_clock
+                } else {
+                    RtcFastClock::RtcFastClock8m.frequency()
+                }
+            }
+            #[cfg(esp32)]
+            LSClockSource::RefTick => HertzU32::MHz(1),
         })
     }
 
@@ -254,13 +417,17 @@ impl<'a> TimerHW<LowSpeed> for Timer<'a, LowSpeed> {
     /// Configure the HW for the timer
     fn configure_hw(&self, divisor: u32) {
         let duty = unwrap!(self.duty) as u8;
-        let use_apb = !self.use_ref_tick;
+        let use_slow_clk = match unwrap!(self.clock_source) {
+            LSClockSource::APBClk => !self.use_ref_tick.get(),
+            LSClockSource::SLOWClk => true,
+            LSClockSource::RefTick => false,
+        };
 
         self.ledc
             .lstimer(self.number as usize)
             .conf()
             .modify(|_, w| unsafe {
-                w.tick_sel().bit(use_apb);
+                w.tick_sel().bit(use_slow_clk);
                 w.rst().clear_bit();
                 w.pause().clear_bit();
                 w.div_num().bits(divisor);
@@ -272,7 +439,7 @@ impl<'a> TimerHW<LowSpeed> for Timer<'a, LowSpeed> {
     /// Configure the HW for the timer
     fn configure_hw(&self, divisor: u32) {
         let duty = unwrap!(self.duty) as u8;
-        let use_ref_tick = self.use_ref_tick;
+        let use_ref_tick = self.use_ref_tick.get();
 
         self.ledc
             .timer(self.number as usize)
@@ -295,6 +462,12 @@ impl<'a> TimerHW<LowSpeed> for Timer<'a, LowSpeed> {
 
         tmr.conf().modify(|_, w| w.para_up().set_bit());
     }
+
+    /// The slow clock is the one that keeps running in light sleep, so only
+    /// the APB clock falls back to REF_TICK
+    fn ref_tick_fallback_hw(&self) -> bool {
+        self.clock_source == Some(LSClockSource::APBClk)
+    }
 }
 
 #[cfg(esp32)]
@@ -303,15 +476,16 @@ impl<'a> TimerHW<HighSpeed> for Timer<'a, HighSpeed> {
     /// Get the current source timer frequency from the HW
     fn get_freq_hw(&self) -> Option<HertzU32> {
         self.clock_source.map(|cs| match cs {
-            // TODO RefTick HSClockSource::RefTick => self.clock_control_config.apb_clock,
             HSClockSource::APBClk => self.clock_control_config.apb_clock,
+            HSClockSource::RefTick => HertzU32::MHz(1),
         })
     }
 
     /// Configure the HW for the timer
     fn configure_hw(&self, divisor: u32) {
         let duty = unwrap!(self.duty) as u8;
-        let sel_hstimer = self.clock_source == Some(HSClockSource::APBClk);
+        let sel_hstimer =
+            self.clock_source == Some(HSClockSource::APBClk) && !self.use_ref_tick.get();
 
         self.ledc
             .hstimer(self.number as usize)
@@ -329,4 +503,8 @@ impl<'a> TimerHW<HighSpeed> for Timer<'a, HighSpeed> {
     fn update_hw(&self) {
         // Nothing to do for HS timers
     }
+
+    fn ref_tick_fallback_hw(&self) -> bool {
+        true
+    }
 }
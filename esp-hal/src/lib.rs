@@ -46,6 +46,8 @@ pub mod analog;
 pub mod assist_debug;
 #[cfg(any(dport, hp_sys, pcr, system))]
 pub mod clock;
+#[cfg(any(dport, hp_sys, pcr, system))]
+pub mod cycle_counter;
 #[cfg(any(xtensa, all(riscv, systimer)))]
 pub mod delay;
 #[cfg(any(gdma, pdma))]
@@ -101,6 +103,8 @@ pub mod spi;
 pub mod system;
 #[cfg(systimer)]
 pub mod systimer;
+#[cfg(all(systimer, any(lp_clkrst, rtc_cntl)))]
+pub mod time;
 #[cfg(any(timg0, timg1))]
 pub mod timer;
 #[cfg(trace0)]
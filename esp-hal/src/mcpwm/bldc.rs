@@ -0,0 +1,292 @@
+//! # MCPWM peripheral - BLDC six-step commutation
+//!
+//! ## Overview
+//! The `bldc` module drives a sensored brushless DC motor with the three
+//! operators of a MCPWM peripheral, one per motor phase. Output A of each
+//! operator drives the high side transistor of its phase, output B the low
+//! side transistor.
+//!
+//! In each of the six commutation steps one phase switches its high side with
+//! the PWM of its operator, one phase has its low side switched on and the
+//! third phase floats. [`SixStep::commutate`] picks the step for the current
+//! hall sensor state. The new step takes effect when the timer of the
+//! operators next equals zero, so all three phases switch together. Connect
+//! all three operators to the same timer.
+//!
+//! ## Example
+//! ```no_run
+//! # use esp_hal::mcpwm;
+//! use mcpwm::{
+//!     bldc::{hall_state, SixStep, HALL_120_DEGREES},
+//!     operator::PwmPinConfig,
+//! };
+//!
+//! let u = mcpwm.operator0.with_pins(
+//!     u_high,
+//!     PwmPinConfig::UP_ACTIVE_HIGH,
+//!     u_low,
+//!     PwmPinConfig::UP_ACTIVE_HIGH,
+//! );
+//! let v = mcpwm.operator1.with_pins(
+//!     v_high,
+//!     PwmPinConfig::UP_ACTIVE_HIGH,
+//!     v_low,
+//!     PwmPinConfig::UP_ACTIVE_HIGH,
+//! );
+//! let w = mcpwm.operator2.with_pins(
+//!     w_high,
+//!     PwmPinConfig::UP_ACTIVE_HIGH,
+//!     w_low,
+//!     PwmPinConfig::UP_ACTIVE_HIGH,
+//! );
+//!
+//! let mut motor = SixStep::new(u, v, w, HALL_120_DEGREES);
+//! motor.set_duty(30);
+//!
+//! loop {
+//!     let hall = hall_state(hall_u.is_high(), hall_v.is_high(), hall_w.is_high());
+//!     motor.commutate(hall).unwrap();
+//! }
+//! ```
+//!
+//! Capturing both edges of the hall sensors with the
+//! [`capture`](super::capture) channels gives the time between two hall
+//! transitions, which [`HallSpeed`] turns into the speed of the motor.
+
+use fugit::HertzU32;
+
+use crate::{
+    gpio::OutputPin,
+    mcpwm::{operator::PwmPin, PwmPeripheral},
+};
+
+/// BLDC commutation errors
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// The hall sensor state has no commutation step, the motor coasts
+    InvalidHallState(u8),
+}
+
+/// Commutation steps for hall sensors 120 degrees apart.
+///
+/// Hall states 0b001, 0b011, 0b010, 0b110, 0b100 and 0b101 follow each other
+/// when the motor turns forward, they map to steps 0 to 5. The states 0b000
+/// and 0b111 don't occur with working sensors. Motors whose sensors or phases
+/// are wired differently need their own table.
+pub const HALL_120_DEGREES: [Option<u8>; 8] = [
+    None,
+    Some(0),
+    Some(2),
+    Some(1),
+    Some(4),
+    Some(5),
+    Some(3),
+    None,
+];
+
+/// The state of the three hall sensors as used by [`SixStep::commutate`]
+pub fn hall_state(u: bool, v: bool, w: bool) -> u8 {
+    (u as u8) | ((v as u8) << 1) | ((w as u8) << 2)
+}
+
+#[derive(Copy, Clone)]
+enum Phase {
+    /// The high side follows the PWM
+    Pwm,
+    /// The low side is on
+    Low,
+    /// Both sides are off
+    Float,
+}
+
+/// The state of phases U, V and W in each of the six steps
+const STEPS: [[Phase; 3]; 6] = [
+    [Phase::Pwm, Phase::Low, Phase::Float],
+    [Phase::Pwm, Phase::Float, Phase::Low],
+    [Phase::Float, Phase::Pwm, Phase::Low],
+    [Phase::Low, Phase::Pwm, Phase::Float],
+    [Phase::Low, Phase::Float, Phase::Pwm],
+    [Phase::Float, Phase::Low, Phase::Pwm],
+];
+
+/// Six-step commutation of a sensored BLDC motor on the three operators of
+/// a MCPWM peripheral
+pub struct SixStep<'d, PWM, UH, UL, VH, VL, WH, WL> {
+    u: (PwmPin<'d, UH, PWM, 0, true>, PwmPin<'d, UL, PWM, 0, false>),
+    v: (PwmPin<'d, VH, PWM, 1, true>, PwmPin<'d, VL, PWM, 1, false>),
+    w: (PwmPin<'d, WH, PWM, 2, true>, PwmPin<'d, WL, PWM, 2, false>),
+    table: [Option<u8>; 8],
+    reverse: bool,
+}
+
+impl<'d, PWM, UH, UL, VH, VL, WH, WL> SixStep<'d, PWM, UH, UL, VH, VL, WH, WL>
+where
+    PWM: PwmPeripheral,
+    UH: OutputPin,
+    UL: OutputPin,
+    VH: OutputPin,
+    VL: OutputPin,
+    WH: OutputPin,
+    WL: OutputPin,
+{
+    /// Drive the motor with the pins of operators 0 (phase U), 1 (phase V) and
+    /// 2 (phase W), mapping hall sensor states to steps with `table`.
+    ///
+    /// The actions of the A pins make the PWM of the high sides, the motor
+    /// coasts until the first [`SixStep::commutate`].
+    pub fn new(
+        u: (PwmPin<'d, UH, PWM, 0, true>, PwmPin<'d, UL, PWM, 0, false>),
+        v: (PwmPin<'d, VH, PWM, 1, true>, PwmPin<'d, VL, PWM, 1, false>),
+        w: (PwmPin<'d, WH, PWM, 2, true>, PwmPin<'d, WL, PWM, 2, false>),
+        table: [Option<u8>; 8],
+    ) -> Self {
+        let mut this = SixStep {
+            u,
+            v,
+            w,
+            table,
+            reverse: false,
+        };
+        this.coast();
+        this
+    }
+
+    /// Set the PWM timestamp of the high sides, i.e. the duty of the motor
+    pub fn set_duty(&mut self, timestamp: u16) {
+        self.u.0.set_timestamp(timestamp);
+        self.v.0.set_timestamp(timestamp);
+        self.w.0.set_timestamp(timestamp);
+    }
+
+    /// Turn the motor backwards, by driving the step opposite to the one for
+    /// the hall state
+    pub fn set_reverse(&mut self, reverse: bool) {
+        self.reverse = reverse;
+    }
+
+    /// Switch to the step for the given hall sensor state, see [`hall_state`].
+    ///
+    /// The step takes effect when the timer next equals zero. If the table has
+    /// no step for the state, the motor coasts from now on.
+    pub fn commutate(&mut self, hall: u8) -> Result<(), Error> {
+        let step = match self.table.get(hall as usize).copied().flatten() {
+            Some(step) if step < 6 => step,
+            _ => {
+                self.coast();
+                return Err(Error::InvalidHallState(hall));
+            }
+        };
+
+        let step = if self.reverse { (step + 3) % 6 } else { step };
+        let [u, v, w] = STEPS[step as usize];
+        force::<PWM, 0>(u);
+        force::<PWM, 1>(v);
+        force::<PWM, 2>(w);
+
+        Ok(())
+    }
+
+    /// Switch all transistors off right away
+    pub fn coast(&mut self) {
+        force_now::<PWM, 0>(Phase::Float);
+        force_now::<PWM, 1>(Phase::Float);
+        force_now::<PWM, 2>(Phase::Float);
+    }
+
+    /// Switch all low sides on right away, which shorts the motor windings
+    pub fn brake(&mut self) {
+        force_now::<PWM, 0>(Phase::Low);
+        force_now::<PWM, 1>(Phase::Low);
+        force_now::<PWM, 2>(Phase::Low);
+    }
+
+    /// Stop forcing the outputs and return the pins
+    #[allow(clippy::type_complexity)]
+    pub fn release(
+        self,
+    ) -> (
+        (PwmPin<'d, UH, PWM, 0, true>, PwmPin<'d, UL, PWM, 0, false>),
+        (PwmPin<'d, VH, PWM, 1, true>, PwmPin<'d, VL, PWM, 1, false>),
+        (PwmPin<'d, WH, PWM, 2, true>, PwmPin<'d, WL, PWM, 2, false>),
+    ) {
+        write_force::<PWM, 0>(0);
+        write_force::<PWM, 1>(0);
+        write_force::<PWM, 2>(0);
+
+        (self.u, self.v, self.w)
+    }
+}
+
+/// Continuous software force of output A (bits 7:6) and B (bits 9:8), 1
+/// forces low and 2 forces high
+fn force_bits(phase: Phase) -> u32 {
+    match phase {
+        Phase::Pwm => 0b01 << 8,
+        Phase::Low => (0b01 << 6) | (0b10 << 8),
+        Phase::Float => (0b01 << 6) | (0b01 << 8),
+    }
+}
+
+/// Force the outputs of an operator when its timer equals zero
+fn force<PWM: PwmPeripheral, const OP: u8>(phase: Phase) {
+    write_force::<PWM, OP>(force_bits(phase) | 0b1);
+}
+
+/// Force the outputs of an operator right away
+fn force_now<PWM: PwmPeripheral, const OP: u8>(phase: Phase) {
+    write_force::<PWM, OP>(force_bits(phase));
+}
+
+fn write_force<PWM: PwmPeripheral, const OP: u8>(bits: u32) {
+    // SAFETY:
+    // The operator's pins belong to the SixStep, so we own its GENx_FORCE
+    // register
+    let ch = unsafe { &*PWM::block() }.ch(OP as usize);
+    ch.gen_force().write(|w| unsafe { w.bits(bits) });
+}
+
+/// Motor speed from the time between hall sensor transitions
+///
+/// Feed it the timestamps of a capture channel capturing both edges of all
+/// three hall sensors, or of one sensor with [`HallSpeed::with_edges_per_turn`]
+/// set accordingly.
+pub struct HallSpeed {
+    timer_frequency: HertzU32,
+    edges_per_turn: u32,
+    last: Option<u32>,
+}
+
+impl HallSpeed {
+    /// Speed from the edges of all three hall sensors, six per electrical
+    /// turn, with timestamps counting at `timer_frequency`
+    pub fn new(timer_frequency: HertzU32) -> Self {
+        Self::with_edges_per_turn(timer_frequency, 6)
+    }
+
+    /// Speed from `edges_per_turn` hall edges per electrical turn
+    pub fn with_edges_per_turn(timer_frequency: HertzU32, edges_per_turn: u32) -> Self {
+        HallSpeed {
+            timer_frequency,
+            edges_per_turn,
+            last: None,
+        }
+    }
+
+    /// Add the timestamp of a hall edge, returning the electrical speed in
+    /// turns per minute since the previous edge
+    pub fn update(&mut self, timestamp: u32) -> Option<u32> {
+        let last = self.last.replace(timestamp)?;
+        let ticks = timestamp.wrapping_sub(last) as u64 * self.edges_per_turn as u64;
+        if ticks == 0 {
+            return None;
+        }
+
+        Some((self.timer_frequency.raw() as u64 * 60 / ticks) as u32)
+    }
+
+    /// Forget the last edge, e.g. after the motor stopped
+    pub fn reset(&mut self) {
+        self.last = None;
+    }
+}
@@ -0,0 +1,313 @@
+//! # MCPWM peripheral - capture module
+//!
+//! ## Overview
+//! The `capture` module is a part of the `MCPWM` peripheral driver for `ESP`
+//! chips. Every MCPWM peripheral has three capture channels, which store the
+//! value of a free running capture timer when an edge arrives at their input.
+//!
+//! The capture timer is shared by the three channels. On the ESP32 and the
+//! ESP32-S3 it counts the APB clock, on the ESP32-C6 and the ESP32-H2 the
+//! MCPWM peripheral clock. Subtracting two timestamps gives the time between
+//! two edges, e.g. the period and the high time of a PWM input or the time
+//! between two hall sensor transitions.
+
+use core::marker::PhantomData;
+
+use crate::{
+    gpio::InputPin,
+    mcpwm::PwmPeripheral,
+    peripheral::{Peripheral, PeripheralRef},
+};
+
+/// The edges a capture channel captures
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CaptureEdge {
+    /// Capture on rising edges
+    Rising  = 0b10,
+    /// Capture on falling edges
+    Falling = 0b01,
+    /// Capture on both edges
+    Both    = 0b11,
+}
+
+/// The edge which triggered a capture
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CapturedEdge {
+    /// A rising edge
+    Rising,
+    /// A falling edge
+    Falling,
+}
+
+/// Configuration of a capture channel
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CaptureConfig {
+    /// The edges to capture
+    pub edge: CaptureEdge,
+    /// Capture every `prescaler + 1`th edge
+    pub prescaler: u8,
+    /// Invert the input before detecting edges
+    pub invert: bool,
+}
+
+impl Default for CaptureConfig {
+    fn default() -> Self {
+        Self {
+            edge: CaptureEdge::Rising,
+            prescaler: 0,
+            invert: false,
+        }
+    }
+}
+
+/// A MCPWM capture channel
+///
+/// Connect it to a pin with [`CaptureChannel::with_pin`].
+pub struct CaptureChannel<const CAP: u8, PWM> {
+    phantom: PhantomData<PWM>,
+}
+
+impl<const CAP: u8, PWM: PwmPeripheral> CaptureChannel<CAP, PWM> {
+    pub(super) fn new() -> Self {
+        CaptureChannel {
+            phantom: PhantomData,
+        }
+    }
+
+    /// Capture the timer on edges of the given pin
+    pub fn with_pin<'d, Pin: InputPin>(
+        self,
+        pin: impl Peripheral<P = Pin> + 'd,
+        config: CaptureConfig,
+    ) -> Capture<'d, Pin, PWM, CAP> {
+        Capture::new(pin, config)
+    }
+}
+
+/// A pin whose edges are captured by a MCPWM capture channel
+pub struct Capture<'d, Pin, PWM, const CAP: u8> {
+    _pin: PeripheralRef<'d, Pin>,
+    phantom: PhantomData<PWM>,
+}
+
+impl<'d, Pin: InputPin, PWM: PwmPeripheral, const CAP: u8> Capture<'d, Pin, PWM, CAP> {
+    fn new(pin: impl Peripheral<P = Pin> + 'd, config: CaptureConfig) -> Self {
+        crate::into_ref!(pin);
+        pin.enable_input(true)
+            .connect_input_to_peripheral(PWM::capture_signal::<CAP>());
+
+        // SAFETY:
+        // The capture timer is shared, but only ever switched on
+        let block = unsafe { &*PWM::block() };
+        block
+            .cap_timer_cfg()
+            .modify(|_, w| w.cap_timer_en().set_bit());
+
+        let mut capture = Capture {
+            _pin: pin,
+            phantom: PhantomData,
+        };
+        capture.set_config(config);
+        capture
+    }
+
+    /// Change the edges, prescaler and inversion of the capture channel
+    pub fn set_config(&mut self, config: CaptureConfig) {
+        // SAFETY:
+        // We only write to our CAP_CHx_CFG register
+        let block = unsafe { &*PWM::block() };
+        block.cap_ch_cfg(CAP as usize).write(|w| unsafe {
+            w.en().set_bit();
+            w.mode().bits(config.edge as u8);
+            w.prescale().bits(config.prescaler);
+            w.in_invert().bit(config.invert)
+        });
+    }
+
+    /// Capture the timer right now, as if an edge arrived
+    pub fn trigger(&mut self) {
+        // SAFETY:
+        // We only write to our CAP_CHx_CFG register
+        let block = unsafe { &*PWM::block() };
+        block
+            .cap_ch_cfg(CAP as usize)
+            .modify(|_, w| w.sw().set_bit());
+    }
+
+    /// The capture timer value of the last capture
+    pub fn last_capture(&self) -> u32 {
+        // SAFETY:
+        // We only read from our CAP_CHx register
+        let block = unsafe { &*PWM::block() };
+        block.cap_ch(CAP as usize).read().value().bits()
+    }
+
+    /// The edge which triggered the last capture
+    pub fn last_edge(&self) -> CapturedEdge {
+        // SAFETY:
+        // We only read from our bit of the CAP_STATUS register
+        let status = unsafe { &*PWM::block() }.cap_status().read();
+        let falling = match CAP {
+            0 => status.cap0_edge().bit_is_set(),
+            1 => status.cap1_edge().bit_is_set(),
+            2 => status.cap2_edge().bit_is_set(),
+            _ => unreachable!(),
+        };
+
+        if falling {
+            CapturedEdge::Falling
+        } else {
+            CapturedEdge::Rising
+        }
+    }
+
+    /// Enable the capture interrupt of this channel
+    pub fn listen(&mut self) {
+        set_interrupt_enabled::<PWM, CAP>(true);
+    }
+
+    /// Disable the capture interrupt of this channel
+    pub fn unlisten(&mut self) {
+        set_interrupt_enabled::<PWM, CAP>(false);
+    }
+
+    /// Whether the channel captured since the interrupt was last cleared
+    pub fn is_interrupt_set(&self) -> bool {
+        is_interrupt_raw_set::<PWM, CAP>()
+    }
+
+    /// Clear the capture interrupt of this channel
+    pub fn clear_interrupt(&mut self) {
+        clear_interrupt::<PWM, CAP>();
+    }
+
+    /// Wait for the next capture, returning the capture timer value and the
+    /// edge which triggered it
+    ///
+    /// Needs the interrupt handler installed by
+    /// [`MCPWM::new_async`](super::MCPWM::new_async).
+    #[cfg(feature = "async")]
+    pub async fn next_capture(&mut self) -> (u32, CapturedEdge) {
+        self.clear_interrupt();
+        asynch::CaptureFuture::<PWM, CAP>::new().await;
+        self.clear_interrupt();
+
+        (self.last_capture(), self.last_edge())
+    }
+}
+
+fn set_interrupt_enabled<PWM: PwmPeripheral, const CAP: u8>(enable: bool) {
+    let block = unsafe { &*PWM::block() };
+    critical_section::with(|_| {
+        block.int_ena().modify(|_, w| match CAP {
+            0 => w.cap0().bit(enable),
+            1 => w.cap1().bit(enable),
+            2 => w.cap2().bit(enable),
+            _ => unreachable!(),
+        });
+    });
+}
+
+fn is_interrupt_raw_set<PWM: PwmPeripheral, const CAP: u8>() -> bool {
+    let raw = unsafe { &*PWM::block() }.int_raw().read();
+    match CAP {
+        0 => raw.cap0().bit_is_set(),
+        1 => raw.cap1().bit_is_set(),
+        2 => raw.cap2().bit_is_set(),
+        _ => unreachable!(),
+    }
+}
+
+fn clear_interrupt<PWM: PwmPeripheral, const CAP: u8>() {
+    let block = unsafe { &*PWM::block() };
+    block.int_clr().write(|w| match CAP {
+        0 => w.cap0().clear_bit_by_one(),
+        1 => w.cap1().clear_bit_by_one(),
+        2 => w.cap2().clear_bit_by_one(),
+        _ => unreachable!(),
+    });
+}
+
+#[cfg(feature = "async")]
+pub(super) mod asynch {
+    use core::{
+        marker::PhantomData,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use embassy_sync::waitqueue::AtomicWaker;
+
+    use super::{is_interrupt_raw_set, set_interrupt_enabled};
+    use crate::mcpwm::{PwmPeripheral, RegisterBlock};
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: AtomicWaker = AtomicWaker::new();
+    // three capture channels for each of up to two MCPWM peripherals
+    static WAKERS: [AtomicWaker; 6] = [INIT; 6];
+
+    pub(crate) struct CaptureFuture<PWM, const CAP: u8> {
+        phantom: PhantomData<PWM>,
+    }
+
+    impl<PWM: PwmPeripheral, const CAP: u8> CaptureFuture<PWM, CAP> {
+        pub fn new() -> Self {
+            set_interrupt_enabled::<PWM, CAP>(true);
+            Self {
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<PWM: PwmPeripheral, const CAP: u8> core::future::Future for CaptureFuture<PWM, CAP> {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            WAKERS[PWM::NUMBER * 3 + CAP as usize].register(cx.waker());
+
+            if is_interrupt_raw_set::<PWM, CAP>() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl<PWM: PwmPeripheral, const CAP: u8> Drop for CaptureFuture<PWM, CAP> {
+        fn drop(&mut self) {
+            set_interrupt_enabled::<PWM, CAP>(false);
+        }
+    }
+
+    /// Disable the capture interrupts which fired and wake their tasks
+    pub(crate) fn handle_interrupt(block: &RegisterBlock, number: usize) {
+        let status = block.int_st().read();
+        let fired = [
+            status.cap0().bit_is_set(),
+            status.cap1().bit_is_set(),
+            status.cap2().bit_is_set(),
+        ];
+
+        critical_section::with(|_| {
+            block.int_ena().modify(|_, w| {
+                if fired[0] {
+                    w.cap0().clear_bit();
+                }
+                if fired[1] {
+                    w.cap1().clear_bit();
+                }
+                if fired[2] {
+                    w.cap2().clear_bit();
+                }
+                w
+            });
+        });
+
+        for (cap, _) in fired.iter().enumerate().filter(|(_, fired)| **fired) {
+            WAKERS[number * 3 + cap].wake();
+        }
+    }
+}
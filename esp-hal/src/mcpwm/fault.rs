@@ -0,0 +1,449 @@
+//! # MCPWM peripheral - fault module
+//!
+//! ## Overview
+//! The `fault` module is a part of the `MCPWM` peripheral driver for `ESP`
+//! chips. Every MCPWM peripheral has three fault detectors, which watch input
+//! pins for fault conditions, e.g. an over-current comparator of a motor
+//! driver.
+//!
+//! Every operator decides on its own how it reacts to faults, see
+//! [`BrakeCfg`]:
+//! * A cycle-by-cycle (CBC) brake forces the outputs as long as the fault is
+//!   active and releases them at the next configured timer event after the
+//!   fault is gone.
+//! * A one-shot (OST) brake forces the outputs until software clears it with
+//!   [`Brake::clear_one_shot`].
+//!
+//! ## Example
+//! ```no_run
+//! use mcpwm::{
+//!     fault::{BrakeCfg, BrakeMode, Fault, FaultPolarity},
+//!     operator::UpdateAction,
+//! };
+//!
+//! // over-current comparator pulls the pin low
+//! let fault = mcpwm.fault0.with_pin(pin, FaultPolarity::ActiveLow);
+//!
+//! // switch both outputs off until software clears the brake
+//! let brake = mcpwm.operator0.set_brake(
+//!     BrakeCfg::new()
+//!         .enable_fault(Fault::Fault0, BrakeMode::OneShot)
+//!         .action_a(
+//!             BrakeMode::OneShot,
+//!             Some(UpdateAction::SetLow),
+//!             Some(UpdateAction::SetLow),
+//!         )
+//!         .action_b(
+//!             BrakeMode::OneShot,
+//!             Some(UpdateAction::SetLow),
+//!             Some(UpdateAction::SetLow),
+//!         ),
+//! );
+//!
+//! // ... once the fault is gone
+//! if !fault.is_active() {
+//!     brake.clear_one_shot();
+//! }
+//! ```
+
+use core::marker::PhantomData;
+
+use crate::{
+    gpio::InputPin,
+    mcpwm::{operator::UpdateAction, PwmPeripheral},
+    peripheral::{Peripheral, PeripheralRef},
+};
+
+/// A fault detector
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Fault {
+    /// Fault detector 0
+    Fault0 = 0,
+    /// Fault detector 1
+    Fault1 = 1,
+    /// Fault detector 2
+    Fault2 = 2,
+}
+
+/// The level of a fault input which signals a fault
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FaultPolarity {
+    /// A high level signals a fault
+    ActiveHigh,
+    /// A low level signals a fault
+    ActiveLow,
+}
+
+/// A fault interrupt
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FaultEvent {
+    /// The fault became active
+    Enter,
+    /// The fault went away
+    Clear,
+}
+
+/// How an operator brakes on a fault
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BrakeMode {
+    /// Force the outputs while the fault is active, release them at the next
+    /// timer event selected by [`BrakeCfg::cycle_by_cycle_release`]
+    CycleByCycle,
+    /// Force the outputs until [`Brake::clear_one_shot`]
+    OneShot,
+}
+
+/// A MCPWM fault detector
+///
+/// Connect it to a pin with [`FaultDetector::with_pin`].
+pub struct FaultDetector<const F: u8, PWM> {
+    phantom: PhantomData<PWM>,
+}
+
+impl<const F: u8, PWM: PwmPeripheral> FaultDetector<F, PWM> {
+    pub(super) fn new() -> Self {
+        FaultDetector {
+            phantom: PhantomData,
+        }
+    }
+
+    /// Detect faults on the given pin
+    pub fn with_pin<'d, Pin: InputPin>(
+        self,
+        pin: impl Peripheral<P = Pin> + 'd,
+        polarity: FaultPolarity,
+    ) -> FaultInput<'d, Pin, PWM, F> {
+        FaultInput::new(pin, polarity)
+    }
+}
+
+/// A pin watched by a MCPWM fault detector
+pub struct FaultInput<'d, Pin, PWM, const F: u8> {
+    _pin: PeripheralRef<'d, Pin>,
+    phantom: PhantomData<PWM>,
+}
+
+impl<'d, Pin: InputPin, PWM: PwmPeripheral, const F: u8> FaultInput<'d, Pin, PWM, F> {
+    fn new(pin: impl Peripheral<P = Pin> + 'd, polarity: FaultPolarity) -> Self {
+        crate::into_ref!(pin);
+        pin.enable_input(true)
+            .connect_input_to_peripheral(PWM::fault_signal::<F>());
+
+        // SAFETY:
+        // We only change our bits of the FAULT_DETECT register
+        let block = unsafe { &*PWM::block() };
+        let active_high = polarity == FaultPolarity::ActiveHigh;
+        critical_section::with(|_| {
+            block.fault_detect().modify(|r, w| unsafe {
+                let bits =
+                    (r.bits() & !(1 << (F + 3))) | (1 << F) | ((active_high as u32) << (F + 3));
+                w.bits(bits)
+            });
+        });
+
+        FaultInput {
+            _pin: pin,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Whether the fault is active right now
+    pub fn is_active(&self) -> bool {
+        // SAFETY:
+        // We only read our bit of the FAULT_DETECT register
+        let block = unsafe { &*PWM::block() };
+        block.fault_detect().read().bits() & (1 << (F + 6)) != 0
+    }
+
+    /// Enable the interrupt for the given fault event
+    pub fn listen(&mut self, event: FaultEvent) {
+        self.set_interrupt_enabled(event, true);
+    }
+
+    /// Disable the interrupt for the given fault event
+    pub fn unlisten(&mut self, event: FaultEvent) {
+        self.set_interrupt_enabled(event, false);
+    }
+
+    /// Whether the given fault event happened since its interrupt was last
+    /// cleared
+    pub fn is_interrupt_set(&self, event: FaultEvent) -> bool {
+        let raw = unsafe { &*PWM::block() }.int_raw().read();
+        match (event, F) {
+            (FaultEvent::Enter, 0) => raw.fault0().bit_is_set(),
+            (FaultEvent::Enter, 1) => raw.fault1().bit_is_set(),
+            (FaultEvent::Enter, 2) => raw.fault2().bit_is_set(),
+            (FaultEvent::Clear, 0) => raw.fault0_clr().bit_is_set(),
+            (FaultEvent::Clear, 1) => raw.fault1_clr().bit_is_set(),
+            (FaultEvent::Clear, 2) => raw.fault2_clr().bit_is_set(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Clear the interrupt of the given fault event
+    pub fn clear_interrupt(&mut self, event: FaultEvent) {
+        let block = unsafe { &*PWM::block() };
+        block.int_clr().write(|w| match (event, F) {
+            (FaultEvent::Enter, 0) => w.fault0().clear_bit_by_one(),
+            (FaultEvent::Enter, 1) => w.fault1().clear_bit_by_one(),
+            (FaultEvent::Enter, 2) => w.fault2().clear_bit_by_one(),
+            (FaultEvent::Clear, 0) => w.fault0_clr().clear_bit_by_one(),
+            (FaultEvent::Clear, 1) => w.fault1_clr().clear_bit_by_one(),
+            (FaultEvent::Clear, 2) => w.fault2_clr().clear_bit_by_one(),
+            _ => unreachable!(),
+        });
+    }
+
+    fn set_interrupt_enabled(&mut self, event: FaultEvent, enable: bool) {
+        let block = unsafe { &*PWM::block() };
+        critical_section::with(|_| {
+            block.int_ena().modify(|_, w| match (event, F) {
+                (FaultEvent::Enter, 0) => w.fault0().bit(enable),
+                (FaultEvent::Enter, 1) => w.fault1().bit(enable),
+                (FaultEvent::Enter, 2) => w.fault2().bit(enable),
+                (FaultEvent::Clear, 0) => w.fault0_clr().bit(enable),
+                (FaultEvent::Clear, 1) => w.fault1_clr().bit(enable),
+                (FaultEvent::Clear, 2) => w.fault2_clr().bit(enable),
+                _ => unreachable!(),
+            });
+        });
+    }
+}
+
+/// Settings for how an operator reacts to faults
+///
+/// Each fault detector and software can trigger either brake mode. Each brake
+/// mode has its own actions for both outputs, depending on the direction the
+/// timer counts in.
+#[derive(Copy, Clone)]
+pub struct BrakeCfg {
+    cfg0: u32,
+    cbc_release: u32,
+}
+
+impl BrakeCfg {
+    /// No fault triggers a brake, and brakes don't change the outputs
+    pub const fn new() -> Self {
+        BrakeCfg {
+            cfg0: 0,
+            cbc_release: 0b01,
+        }
+    }
+
+    /// Brake when the given fault is active
+    pub const fn enable_fault(mut self, fault: Fault, mode: BrakeMode) -> Self {
+        // F0 is the highest of the three bits of each mode
+        let offset = match mode {
+            BrakeMode::CycleByCycle => 3 - fault as u32,
+            BrakeMode::OneShot => 7 - fault as u32,
+        };
+        self.cfg0 |= 1 << offset;
+        self
+    }
+
+    /// Allow software to trigger the brake with [`Brake::force`]
+    pub const fn enable_software(mut self, mode: BrakeMode) -> Self {
+        let offset = match mode {
+            BrakeMode::CycleByCycle => 0,
+            BrakeMode::OneShot => 4,
+        };
+        self.cfg0 |= 1 << offset;
+        self
+    }
+
+    /// Choose what a brake does to output A, while the timer counts up and
+    /// while it counts down
+    pub const fn action_a(
+        self,
+        mode: BrakeMode,
+        up: Option<UpdateAction>,
+        down: Option<UpdateAction>,
+    ) -> Self {
+        let offset = match mode {
+            BrakeMode::CycleByCycle => 8,
+            BrakeMode::OneShot => 12,
+        };
+        self.with_actions(offset, up, down)
+    }
+
+    /// Choose what a brake does to output B, while the timer counts up and
+    /// while it counts down
+    pub const fn action_b(
+        self,
+        mode: BrakeMode,
+        up: Option<UpdateAction>,
+        down: Option<UpdateAction>,
+    ) -> Self {
+        let offset = match mode {
+            BrakeMode::CycleByCycle => 16,
+            BrakeMode::OneShot => 20,
+        };
+        self.with_actions(offset, up, down)
+    }
+
+    /// Choose the timer events which release a cycle-by-cycle brake once the
+    /// fault is gone. By default that's the timer equalling zero.
+    pub const fn cycle_by_cycle_release(mut self, on_zero: bool, on_period: bool) -> Self {
+        self.cbc_release = (on_zero as u32) | ((on_period as u32) << 1);
+        self
+    }
+
+    const fn with_actions(
+        mut self,
+        offset: u32,
+        up: Option<UpdateAction>,
+        down: Option<UpdateAction>,
+    ) -> Self {
+        let up = match up {
+            Some(action) => action as u32,
+            None => 0,
+        };
+        let down = match down {
+            Some(action) => action as u32,
+            None => 0,
+        };
+        // the down action comes first
+        self.cfg0 &= !(0b1111 << offset);
+        self.cfg0 |= (down << offset) | (up << (offset + 2));
+        self
+    }
+}
+
+impl Default for BrakeCfg {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The brake status of an operator
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BrakeEvent {
+    /// A cycle-by-cycle brake
+    CycleByCycle,
+    /// A one-shot brake
+    OneShot,
+}
+
+/// The fault handler of an operator, returned by
+/// [`Operator::set_brake`](super::operator::Operator::set_brake)
+pub struct Brake<PWM, const OP: u8> {
+    phantom: PhantomData<PWM>,
+}
+
+impl<PWM: PwmPeripheral, const OP: u8> Brake<PWM, OP> {
+    pub(super) fn new(cfg: BrakeCfg) -> Self {
+        // SAFETY:
+        // We only write to our FHx registers
+        let ch = unsafe { &*PWM::block() }.ch(OP as usize);
+        ch.fh_cfg0().write(|w| unsafe { w.bits(cfg.cfg0) });
+        // keep the clear and force bits, changing them could trigger them
+        ch.fh_cfg1()
+            .modify(|r, w| unsafe { w.bits((r.bits() & !0b110) | (cfg.cbc_release << 1)) });
+
+        Brake {
+            phantom: PhantomData,
+        }
+    }
+
+    /// Whether the outputs are braked
+    pub fn is_active(&self, mode: BrakeMode) -> bool {
+        let status = unsafe { &*PWM::block() }
+            .ch(OP as usize)
+            .fh_status()
+            .read()
+            .bits();
+        match mode {
+            BrakeMode::CycleByCycle => status & 0b01 != 0,
+            BrakeMode::OneShot => status & 0b10 != 0,
+        }
+    }
+
+    /// Release a one-shot brake
+    ///
+    /// If a fault which triggers it is still active, the brake is applied
+    /// again right away.
+    pub fn clear_one_shot(&self) {
+        // SAFETY:
+        // We only write to our FHx_CFG1 register
+        let ch = unsafe { &*PWM::block() }.ch(OP as usize);
+        // clears on the rising edge
+        ch.fh_cfg1().modify(|r, w| unsafe { w.bits(r.bits() | 1) });
+        ch.fh_cfg1().modify(|r, w| unsafe { w.bits(r.bits() & !1) });
+    }
+
+    /// Brake as if a fault happened, if software is allowed to by
+    /// [`BrakeCfg::enable_software`]
+    pub fn force(&self, mode: BrakeMode) {
+        match mode {
+            BrakeMode::CycleByCycle => self.toggle_cfg1(3),
+            BrakeMode::OneShot => self.toggle_cfg1(4),
+        }
+    }
+
+    /// Enable the interrupt raised when the brake is applied
+    pub fn listen(&mut self, event: BrakeEvent) {
+        self.set_interrupt_enabled(event, true);
+    }
+
+    /// Disable the interrupt raised when the brake is applied
+    pub fn unlisten(&mut self, event: BrakeEvent) {
+        self.set_interrupt_enabled(event, false);
+    }
+
+    /// Whether the brake was applied since its interrupt was last cleared
+    pub fn is_interrupt_set(&self, event: BrakeEvent) -> bool {
+        let raw = unsafe { &*PWM::block() }.int_raw().read();
+        match (event, OP) {
+            (BrakeEvent::CycleByCycle, 0) => raw.tz0_cbc().bit_is_set(),
+            (BrakeEvent::CycleByCycle, 1) => raw.tz1_cbc().bit_is_set(),
+            (BrakeEvent::CycleByCycle, 2) => raw.tz2_cbc().bit_is_set(),
+            (BrakeEvent::OneShot, 0) => raw.tz0_ost().bit_is_set(),
+            (BrakeEvent::OneShot, 1) => raw.tz1_ost().bit_is_set(),
+            (BrakeEvent::OneShot, 2) => raw.tz2_ost().bit_is_set(),
+            _ => unreachable!(),
+        }
+    }
+
+    /// Clear the interrupt of the given brake event
+    pub fn clear_interrupt(&mut self, event: BrakeEvent) {
+        let block = unsafe { &*PWM::block() };
+        block.int_clr().write(|w| match (event, OP) {
+            (BrakeEvent::CycleByCycle, 0) => w.tz0_cbc().clear_bit_by_one(),
+            (BrakeEvent::CycleByCycle, 1) => w.tz1_cbc().clear_bit_by_one(),
+            (BrakeEvent::CycleByCycle, 2) => w.tz2_cbc().clear_bit_by_one(),
+            (BrakeEvent::OneShot, 0) => w.tz0_ost().clear_bit_by_one(),
+            (BrakeEvent::OneShot, 1) => w.tz1_ost().clear_bit_by_one(),
+            (BrakeEvent::OneShot, 2) => w.tz2_ost().clear_bit_by_one(),
+            _ => unreachable!(),
+        });
+    }
+
+    fn set_interrupt_enabled(&mut self, event: BrakeEvent, enable: bool) {
+        let block = unsafe { &*PWM::block() };
+        critical_section::with(|_| {
+            block.int_ena().modify(|_, w| match (event, OP) {
+                (BrakeEvent::CycleByCycle, 0) => w.tz0_cbc().bit(enable),
+                (BrakeEvent::CycleByCycle, 1) => w.tz1_cbc().bit(enable),
+                (BrakeEvent::CycleByCycle, 2) => w.tz2_cbc().bit(enable),
+                (BrakeEvent::OneShot, 0) => w.tz0_ost().bit(enable),
+                (BrakeEvent::OneShot, 1) => w.tz1_ost().bit(enable),
+                (BrakeEvent::OneShot, 2) => w.tz2_ost().bit(enable),
+                _ => unreachable!(),
+            });
+        });
+    }
+
+    /// The force bits of FHx_CFG1 act on toggling
+    fn toggle_cfg1(&self, bit: u8) {
+        // SAFETY:
+        // We only write to our FHx_CFG1 register
+        let ch = unsafe { &*PWM::block() }.ch(OP as usize);
+        ch.fh_cfg1()
+            .modify(|r, w| unsafe { w.bits(r.bits() ^ (1 << bit)) });
+    }
+}
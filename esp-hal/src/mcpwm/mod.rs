@@ -7,21 +7,24 @@
 //!     * The 16-bit counter in the PWM timer can work in count-up mode,
 //!       count-down mode or count-up-down mode.
 //!     * A hardware sync or software sync can trigger a reload on the PWM timer
-//!       with a phase register (Not yet implemented)
+//!       with a phase register
 //! * PWM Operators 0, 1 and 2
 //!     * Every PWM operator has two PWM outputs: PWMxA and PWMxB. They can work
 //!       independently, in symmetric and asymmetric configuration.
 //!     * Software, asynchronously override control of PWM signals.
 //!     * Configurable dead-time on rising and falling edges; each set up
-//!       independently. (Not yet implemented)
+//!       independently.
 //!     * All events can trigger CPU interrupts. (Not yet implemented)
 //!     * Modulating of PWM output by high-frequency carrier signals, useful
-//!       when gate drivers are insulated with a transformer. (Not yet
-//!       implemented)
+//!       when gate drivers are insulated with a transformer.
 //!     * Period, time stamps and important control registers have shadow
 //!       registers with flexible updating methods.
-//! * Fault Detection Module (Not yet implemented)
-//! * Capture Module (Not yet implemented)
+//! * Fault Detection Module
+//!     * Three fault detectors trigger cycle-by-cycle or one-shot brakes of the
+//!       operators.
+//! * Capture Module
+//!     * Three capture channels store the value of a free running 32-bit timer
+//!       on edges of their inputs.
 //!
 //! ## Example
 //! Uses timer0 and operator0 of the MCPWM0 peripheral to output a 50% duty
@@ -51,22 +54,75 @@
 //! // pin will be high 50% of the time
 //! pwm_pin.set_timestamp(50);
 //! ```
+//!
+//! ## Synchronization example
+//! Runs timer1 a third of a period behind timer0, e.g. for interleaved
+//! converters:
+//!
+//! ```no_run
+//! use mcpwm::timer::{CounterDirection, SyncOutput, SyncSource};
+//!
+//! mcpwm.timer0.set_sync_output(SyncOutput::Zero);
+//! mcpwm
+//!     .timer1
+//!     .set_sync_input(SyncSource::Timer0, 66, CounterDirection::Increasing);
+//!
+//! mcpwm.timer0.start(timer_clock_cfg);
+//! mcpwm.timer1.start(timer_clock_cfg);
+//! ```
+//!
+//! ## Burst example
+//! Outputs 200 periods, e.g. 200 steps of a stepper motor, and waits for them
+//! to be done:
+//!
+//! ```no_run
+//! mcpwm.timer0.start_burst(timer_clock_cfg, 200);
+//! nb::block!(mcpwm.timer0.poll_burst()).unwrap();
+//! ```
+//!
+//! ## Capture example
+//! Measures the period of a signal on `pin`. With an MCPWM created by
+//! [`MCPWM::new_async`] a task can wait for the captures:
+//!
+//! ```no_run
+//! # use esp_hal::{mcpwm, prelude::*};
+//! use mcpwm::{capture::CaptureConfig, PeripheralClockConfig, MCPWM};
+//!
+//! let clock_cfg = PeripheralClockConfig::with_frequency(&clocks, 40.MHz()).unwrap();
+//! let mut mcpwm = MCPWM::new_async(peripherals.PWM0, clock_cfg);
+//!
+//! let mut capture = mcpwm.capture0.with_pin(pin, CaptureConfig::default());
+//!
+//! let (first, _) = capture.next_capture().await;
+//! let (second, _) = capture.next_capture().await;
+//! let period_in_timer_ticks = second.wrapping_sub(first);
+//! ```
 
 #![deny(missing_docs)]
 
 use core::{marker::PhantomData, ops::Deref};
 
+use capture::CaptureChannel;
+use fault::FaultDetector;
 use fugit::HertzU32;
 use operator::Operator;
 use timer::Timer;
 
 use crate::{
     clock::Clocks,
-    gpio::OutputSignal,
+    gpio::{InputPin, InputSignal, OutputSignal},
+    interrupt::{self, InterruptHandler},
     peripheral::{Peripheral, PeripheralRef},
+    peripherals::Interrupt,
     system::{Peripheral as PeripheralEnable, PeripheralClockControl},
 };
 
+/// BLDC motor commutation
+pub mod bldc;
+/// MCPWM capture channels
+pub mod capture;
+/// MCPWM fault detection
+pub mod fault;
 /// MCPWM operators
 pub mod operator;
 /// MCPWM timers
@@ -90,6 +146,24 @@ pub struct MCPWM<'d, PWM> {
     pub operator1: Operator<1, PWM>,
     /// Operator2
     pub operator2: Operator<2, PWM>,
+    /// Capture channel 0
+    pub capture0: CaptureChannel<0, PWM>,
+    /// Capture channel 1
+    pub capture1: CaptureChannel<1, PWM>,
+    /// Capture channel 2
+    pub capture2: CaptureChannel<2, PWM>,
+    /// Fault detector 0
+    pub fault0: FaultDetector<0, PWM>,
+    /// Fault detector 1
+    pub fault1: FaultDetector<1, PWM>,
+    /// Fault detector 2
+    pub fault2: FaultDetector<2, PWM>,
+    /// External sync input 0
+    pub sync0: SyncInput<0, PWM>,
+    /// External sync input 1
+    pub sync1: SyncInput<1, PWM>,
+    /// External sync input 2
+    pub sync2: SyncInput<2, PWM>,
 }
 
 impl<'d, PWM: PwmPeripheral> MCPWM<'d, PWM> {
@@ -154,8 +228,82 @@ impl<'d, PWM: PwmPeripheral> MCPWM<'d, PWM> {
             operator0: Operator::new(),
             operator1: Operator::new(),
             operator2: Operator::new(),
+            capture0: CaptureChannel::new(),
+            capture1: CaptureChannel::new(),
+            capture2: CaptureChannel::new(),
+            fault0: FaultDetector::new(),
+            fault1: FaultDetector::new(),
+            fault2: FaultDetector::new(),
+            sync0: SyncInput::new(),
+            sync1: SyncInput::new(),
+            sync2: SyncInput::new(),
         }
     }
+
+    /// Create a new MCPWM which wakes the tasks waiting for
+    /// [`capture::Capture::next_capture`] from its interrupt
+    #[cfg(feature = "async")]
+    pub fn new_async(
+        peripheral: impl Peripheral<P = PWM> + 'd,
+        peripheral_clock: PeripheralClockConfig,
+    ) -> Self {
+        let mut this = Self::new(peripheral, peripheral_clock);
+        this.set_interrupt_handler(PWM::interrupt_handler());
+        this
+    }
+
+    /// Install an interrupt handler for the interrupts of this MCPWM
+    /// peripheral, e.g. the capture interrupts enabled by
+    /// [`capture::Capture::listen`]
+    pub fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        unsafe {
+            interrupt::bind_interrupt(PWM::interrupt(), handler.handler());
+            interrupt::enable(PWM::interrupt(), handler.priority()).unwrap();
+        }
+    }
+}
+
+/// An external sync input of the MCPWM peripheral
+///
+/// Timers use it as [`timer::SyncSource::External0`] (or 1, 2) once it's
+/// connected to a pin with [`SyncInput::with_pin`].
+pub struct SyncInput<const S: u8, PWM> {
+    phantom: PhantomData<PWM>,
+}
+
+impl<const S: u8, PWM: PwmPeripheral> SyncInput<S, PWM> {
+    fn new() -> Self {
+        SyncInput {
+            phantom: PhantomData,
+        }
+    }
+
+    /// Take the sync input from the given pin, optionally inverted
+    pub fn with_pin<'d, Pin: InputPin>(
+        self,
+        pin: impl Peripheral<P = Pin> + 'd,
+        invert: bool,
+    ) -> PeripheralRef<'d, Pin> {
+        crate::into_ref!(pin);
+        pin.enable_input(true)
+            .connect_input_to_peripheral(PWM::sync_signal::<S>());
+
+        // SAFETY:
+        // We only change our bit of the TIMER_SYNCI_CFG register
+        let block = unsafe { &*PWM::block() };
+        critical_section::with(|_| {
+            block.timer_synci_cfg().modify(|r, w| unsafe {
+                let bit = 1 << (9 + S);
+                w.bits(if invert {
+                    r.bits() | bit
+                } else {
+                    r.bits() & !bit
+                })
+            });
+        });
+
+        pin
+    }
 }
 
 /// Clock configuration of the MCPWM peripheral
@@ -283,16 +431,31 @@ pub struct FrequencyError;
 
 /// A MCPWM peripheral
 pub trait PwmPeripheral: Deref<Target = RegisterBlock> + crate::private::Sealed {
+    /// The index of the peripheral, 0 for MCPWM0
+    const NUMBER: usize;
     /// Enable peripheral
     fn enable();
     /// Get a pointer to the peripheral RegisterBlock
     fn block() -> *const RegisterBlock;
     /// Get operator GPIO mux output signal
     fn output_signal<const OP: u8, const IS_A: bool>() -> OutputSignal;
+    /// Get capture channel GPIO mux input signal
+    fn capture_signal<const CAP: u8>() -> InputSignal;
+    /// Get fault detector GPIO mux input signal
+    fn fault_signal<const F: u8>() -> InputSignal;
+    /// Get external sync GPIO mux input signal
+    fn sync_signal<const S: u8>() -> InputSignal;
+    /// Get the interrupt of the peripheral
+    fn interrupt() -> Interrupt;
+    /// Get the interrupt handler used by [`MCPWM::new_async`] and timer
+    /// bursts
+    fn interrupt_handler() -> InterruptHandler;
 }
 
 #[cfg(mcpwm0)]
 impl PwmPeripheral for crate::peripherals::MCPWM0 {
+    const NUMBER: usize = 0;
+
     fn enable() {
         PeripheralClockControl::enable(PeripheralEnable::Mcpwm0)
     }
@@ -312,10 +475,50 @@ impl PwmPeripheral for crate::peripherals::MCPWM0 {
             _ => unreachable!(),
         }
     }
+
+    fn capture_signal<const CAP: u8>() -> InputSignal {
+        match CAP {
+            0 => InputSignal::PWM0_CAP0,
+            1 => InputSignal::PWM0_CAP1,
+            2 => InputSignal::PWM0_CAP2,
+            _ => unreachable!(),
+        }
+    }
+
+    fn fault_signal<const F: u8>() -> InputSignal {
+        match F {
+            0 => InputSignal::PWM0_F0,
+            1 => InputSignal::PWM0_F1,
+            2 => InputSignal::PWM0_F2,
+            _ => unreachable!(),
+        }
+    }
+
+    fn sync_signal<const S: u8>() -> InputSignal {
+        match S {
+            0 => InputSignal::PWM0_SYNC0,
+            1 => InputSignal::PWM0_SYNC1,
+            2 => InputSignal::PWM0_SYNC2,
+            _ => unreachable!(),
+        }
+    }
+
+    fn interrupt() -> Interrupt {
+        #[cfg(esp32)]
+        return Interrupt::PWM0;
+        #[cfg(not(esp32))]
+        return Interrupt::MCPWM0;
+    }
+
+    fn interrupt_handler() -> InterruptHandler {
+        handlers::mcpwm0_interrupt_handler
+    }
 }
 
 #[cfg(mcpwm1)]
 impl PwmPeripheral for crate::peripherals::MCPWM1 {
+    const NUMBER: usize = 1;
+
     fn enable() {
         PeripheralClockControl::enable(PeripheralEnable::Mcpwm1)
     }
@@ -335,4 +538,64 @@ impl PwmPeripheral for crate::peripherals::MCPWM1 {
             _ => unreachable!(),
         }
     }
+
+    fn capture_signal<const CAP: u8>() -> InputSignal {
+        match CAP {
+            0 => InputSignal::PWM1_CAP0,
+            1 => InputSignal::PWM1_CAP1,
+            2 => InputSignal::PWM1_CAP2,
+            _ => unreachable!(),
+        }
+    }
+
+    fn fault_signal<const F: u8>() -> InputSignal {
+        match F {
+            0 => InputSignal::PWM1_F0,
+            1 => InputSignal::PWM1_F1,
+            2 => InputSignal::PWM1_F2,
+            _ => unreachable!(),
+        }
+    }
+
+    fn sync_signal<const S: u8>() -> InputSignal {
+        match S {
+            0 => InputSignal::PWM1_SYNC0,
+            1 => InputSignal::PWM1_SYNC1,
+            2 => InputSignal::PWM1_SYNC2,
+            _ => unreachable!(),
+        }
+    }
+
+    fn interrupt() -> Interrupt {
+        #[cfg(esp32)]
+        return Interrupt::PWM1;
+        #[cfg(not(esp32))]
+        return Interrupt::MCPWM1;
+    }
+
+    fn interrupt_handler() -> InterruptHandler {
+        handlers::mcpwm1_interrupt_handler
+    }
+}
+
+mod handlers {
+    use procmacros::handler;
+
+    #[cfg(mcpwm0)]
+    #[handler]
+    pub(super) fn mcpwm0_interrupt_handler() {
+        let block = unsafe { &*crate::peripherals::MCPWM0::PTR };
+        #[cfg(feature = "async")]
+        super::capture::asynch::handle_interrupt(block, 0);
+        super::timer::handle_interrupt(block, 0);
+    }
+
+    #[cfg(mcpwm1)]
+    #[handler]
+    pub(super) fn mcpwm1_interrupt_handler() {
+        let block = unsafe { &*crate::peripherals::MCPWM1::PTR };
+        #[cfg(feature = "async")]
+        super::capture::asynch::handle_interrupt(block, 1);
+        super::timer::handle_interrupt(block, 1);
+    }
 }
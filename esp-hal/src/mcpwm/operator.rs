@@ -12,9 +12,16 @@
 
 use core::marker::PhantomData;
 
+use fugit::HertzU32;
+
 use crate::{
     gpio::OutputPin,
-    mcpwm::{timer::Timer, PwmPeripheral},
+    mcpwm::{
+        fault::{Brake, BrakeCfg},
+        timer::Timer,
+        PeripheralClockConfig,
+        PwmPeripheral,
+    },
     peripheral::{Peripheral, PeripheralRef},
 };
 
@@ -28,18 +35,41 @@ pub enum PWMStream {
 
 /// Configuration for MCPWM Operator DeadTime
 /// It's recommended to reference the technical manual for configuration
+#[derive(Copy, Clone)]
 pub struct DeadTimeCfg {
     cfg_reg: u32,
     rising_edge_delay: u16,
     falling_edge_delay: u16,
 }
 
-#[allow(clippy::unusual_byte_groupings)]
 impl DeadTimeCfg {
     // NOTE: it's a bit difficult to make this typestate
     // due to the different interconnections (FED/RED vs PWMxA/PWMxB) and
     // the many mode of operation
 
+    /// B_OUTBYPASS
+    const S0: u8 = 16;
+    /// A_OUTBYPASS
+    const S1: u8 = 15;
+    /// RED_OUTINVERT
+    const S2: u8 = 13;
+    /// FED_OUTINVERT
+    const S3: u8 = 14;
+    /// RED_INSEL
+    const S4: u8 = 11;
+    /// FED_INSEL
+    const S5: u8 = 12;
+    /// A_OUTSWAP
+    const S6: u8 = 9;
+    /// B_OUTSWAP
+    const S7: u8 = 10;
+    /// DEB_MODE
+    const S8: u8 = 8;
+    /// Use PT_clk instead of PWM_clk
+    const CLK_SEL: u8 = 17;
+    /// Offset of RED_UPMETHOD, FED_UPMETHOD is at 0
+    const RED_UPMETHOD: u8 = 4;
+
     /// Uses the following configuration:
     /// * Clock: PWM_clk
     /// * Bypass: A & B
@@ -51,7 +81,7 @@ impl DeadTimeCfg {
     /// * FED/RED = 0
     pub fn new_bypass() -> DeadTimeCfg {
         DeadTimeCfg {
-            cfg_reg: 0b0_11_00_00_00_0_000_000,
+            cfg_reg: (1 << Self::S0) | (1 << Self::S1),
             rising_edge_delay: 0,
             falling_edge_delay: 0,
         }
@@ -66,13 +96,46 @@ impl DeadTimeCfg {
     ///
     /// Default delay on both rising (red) and falling (fed) edge is 16 cycles
     pub fn new_ahc(red_delay: Option<u16>, fed_delay: Option<u16>) -> DeadTimeCfg {
+        Self::from_input_a(1 << Self::S3, red_delay, fed_delay)
+    }
+
+    /// Active Low Complementary (ALC) from Technical Reference manual
+    ///
+    /// Like [`DeadTimeCfg::new_ahc`], but PWMA is inverted instead of PWMB, so
+    /// both outputs are low active and never active at the same time
+    ///
+    /// Default delay on both rising (red) and falling (fed) edge is 16 cycles
+    pub fn new_alc(red_delay: Option<u16>, fed_delay: Option<u16>) -> DeadTimeCfg {
+        Self::from_input_a(1 << Self::S2, red_delay, fed_delay)
+    }
+
+    /// Active High (AH) from Technical Reference manual
+    ///
+    /// PWMA is input PWMA with a delayed rising edge, PWMB is input PWMA with
+    /// a delayed falling edge, neither is inverted
+    ///
+    /// Default delay on both rising (red) and falling (fed) edge is 16 cycles
+    pub fn new_ah(red_delay: Option<u16>, fed_delay: Option<u16>) -> DeadTimeCfg {
+        Self::from_input_a(0, red_delay, fed_delay)
+    }
+
+    /// Active Low (AL) from Technical Reference manual
+    ///
+    /// Like [`DeadTimeCfg::new_ah`], but both outputs are inverted
+    ///
+    /// Default delay on both rising (red) and falling (fed) edge is 16 cycles
+    pub fn new_al(red_delay: Option<u16>, fed_delay: Option<u16>) -> DeadTimeCfg {
+        Self::from_input_a((1 << Self::S2) | (1 << Self::S3), red_delay, fed_delay)
+    }
+
+    /// Both delays take input PWMA, RED drives PWMA and FED drives PWMB
+    fn from_input_a(cfg_reg: u32, red_delay: Option<u16>, fed_delay: Option<u16>) -> DeadTimeCfg {
         DeadTimeCfg {
-            cfg_reg: 0b0_00_10_00_00_0_000_000,
+            cfg_reg,
             rising_edge_delay: red_delay.unwrap_or(16u16),
             falling_edge_delay: fed_delay.unwrap_or(16u16),
         }
     }
-    // TODO: Add some common configurations ~AHC~,ALC,AH,AC
 
     fn set_flag(&mut self, offset: u8, val: bool) {
         let mask = !(1 << offset);
@@ -88,8 +151,8 @@ impl DeadTimeCfg {
     /// Sets FED/RED output inverter
     /// Inverts the output of the FED/RED module (excl DEB mode feedback)
     pub fn invert_output(&mut self, fed: bool, red: bool) {
-        self.set_flag(13, fed);
-        self.set_flag(14, red);
+        self.set_flag(Self::S3, fed);
+        self.set_flag(Self::S2, red);
     }
 
     /// Swaps the output of a PWM Stream
@@ -99,8 +162,8 @@ impl DeadTimeCfg {
     pub fn set_output_swap(&mut self, stream: PWMStream, swap: bool) {
         self.set_flag(
             match stream {
-                PWMStream::PWMA => 9,
-                PWMStream::PWMB => 10,
+                PWMStream::PWMA => Self::S6,
+                PWMStream::PWMB => Self::S7,
             },
             swap,
         );
@@ -111,35 +174,99 @@ impl DeadTimeCfg {
     pub fn set_bypass(&mut self, stream: PWMStream, enable: bool) {
         self.set_flag(
             match stream {
-                PWMStream::PWMA => 15,
-                PWMStream::PWMB => 16,
+                PWMStream::PWMA => Self::S1,
+                PWMStream::PWMB => Self::S0,
             },
             enable,
         );
     }
 
+    /// Dual-edge B mode: applies both the rising and the falling edge delay
+    /// to the PWMB path
+    pub fn set_dual_edge_b(&mut self, enable: bool) {
+        self.set_flag(Self::S8, enable);
+    }
+
     /// Select Between PWMClk & PT_Clk
     pub fn select_clock(&mut self, pwm_clock: bool) {
-        self.set_flag(17, pwm_clock);
+        self.set_flag(Self::CLK_SEL, pwm_clock);
     }
 
     /// Select which stream is used for the input of FED/RED
     pub fn select_input(&mut self, fed: PWMStream, red: PWMStream) {
         self.set_flag(
-            12,
+            Self::S5,
             match fed {
                 PWMStream::PWMA => false,
                 PWMStream::PWMB => true,
             },
         );
         self.set_flag(
-            11,
+            Self::S4,
             match red {
                 PWMStream::PWMA => false,
                 PWMStream::PWMB => true,
             },
         );
     }
+
+    /// Set when new delays written with
+    /// [`LinkedPins::set_rising_edge_deadtime`]
+    /// and [`LinkedPins::set_falling_edge_deadtime`] take effect
+    pub fn set_update_method(&mut self, update_method: PwmUpdateMethod) {
+        let bits = update_method.0 as u32;
+        self.cfg_reg &= !(0xff);
+        self.cfg_reg |= (bits << Self::RED_UPMETHOD) | bits;
+    }
+}
+
+/// Configuration of the carrier which an operator superimposes on its outputs
+///
+/// Gate drivers insulated with a pulse transformer can't pass a PWM signal
+/// with long high times. The carrier chops the high times of the PWM signal
+/// into pulses of a much higher frequency:
+/// * The carrier frequency is the peripheral clock / 8 / (`prescaler` + 1).
+/// * Each carrier pulse is high for `duty` / 8 of the carrier period.
+/// * The first pulse of each high time is wider, to switch the transistor on
+///   quickly. It lasts `first_pulse_width` + 1 carrier periods.
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct CarrierCfg {
+    prescaler: u8,
+    duty: u8,
+    first_pulse_width: u8,
+    invert_output: bool,
+    invert_input: bool,
+}
+
+impl CarrierCfg {
+    /// A carrier with the given prescaler (0 to 15), duty in eighths of the
+    /// carrier period (0 to 7) and first pulse width in carrier periods minus
+    /// one (0 to 15). Values out of range are clamped.
+    pub fn new(prescaler: u8, duty: u8, first_pulse_width: u8) -> Self {
+        CarrierCfg {
+            prescaler: prescaler.min(15),
+            duty: duty.min(7),
+            first_pulse_width: first_pulse_width.min(15),
+            invert_output: false,
+            invert_input: false,
+        }
+    }
+
+    /// Invert the output of the carrier
+    pub fn invert_output(&mut self, invert: bool) {
+        self.invert_output = invert;
+    }
+
+    /// Invert the PWM signal before the carrier modulates it
+    pub fn invert_input(&mut self, invert: bool) {
+        self.invert_input = invert;
+    }
+
+    /// The carrier frequency with the given peripheral clock
+    pub fn frequency(&self, clock: &PeripheralClockConfig) -> HertzU32 {
+        clock.frequency() / 8 / (self.prescaler as u32 + 1)
+    }
 }
 
 /// A MCPWM operator
@@ -148,10 +275,9 @@ impl DeadTimeCfg {
 /// * Generates a PWM signal pair, based on timing references obtained from the
 ///   corresponding PWM timer.
 /// * Each signal out of the PWM signal pair includes a specific pattern of dead
-///   time. (Not yet implemented)
-/// * Superimposes a carrier on the PWM signal, if configured to do so. (Not yet
-///   implemented)
-/// * Handles response under fault conditions. (Not yet implemented)
+///   time.
+/// * Superimposes a carrier on the PWM signal, if configured to do so.
+/// * Handles response under fault conditions.
 pub struct Operator<const OP: u8, PWM> {
     phantom: PhantomData<PWM>,
 }
@@ -188,25 +314,34 @@ impl<const OP: u8, PWM: PwmPeripheral> Operator<OP, PWM> {
         });
     }
 
+    /// Configure how this operator reacts to faults, returning the handle to
+    /// watch and clear its brakes
+    pub fn set_brake(&mut self, cfg: BrakeCfg) -> Brake<PWM, OP> {
+        Brake::new(cfg)
+    }
+
+    /// Superimpose a carrier on the outputs of this operator, or stop doing
+    /// so with `None`
+    pub fn set_carrier(&mut self, cfg: Option<CarrierCfg>) {
+        // SAFETY:
+        // We only write to our CARRIERx_CFG register
+        let ch = unsafe { &*PWM::block() }.ch(OP as usize);
+        let bits = match cfg {
+            Some(cfg) => {
+                1 | ((cfg.prescaler as u32) << 1)
+                    | ((cfg.duty as u32) << 5)
+                    | ((cfg.first_pulse_width as u32) << 8)
+                    | ((cfg.invert_output as u32) << 12)
+                    | ((cfg.invert_input as u32) << 13)
+            }
+            None => 0,
+        };
+        ch.carrier_cfg().write(|w| unsafe { w.bits(bits) });
+    }
+
     /// Configures deadtime for this operator
     pub fn set_deadtime(&mut self, cfg: &DeadTimeCfg) {
-        let ch = unsafe { &*PWM::block() }.ch(OP as usize);
-        #[cfg(esp32s3)]
-        {
-            ch.db_fed_cfg()
-                .write(|w| unsafe { w.bits(cfg.falling_edge_delay as u32) });
-            ch.db_red_cfg()
-                .write(|w| unsafe { w.bits(cfg.rising_edge_delay as u32) });
-            ch.db_cfg().write(|w| unsafe { w.bits(cfg.cfg_reg) });
-        }
-        #[cfg(not(esp32s3))]
-        {
-            ch.dt_fed_cfg()
-                .write(|w| unsafe { w.bits(cfg.falling_edge_delay as u32) });
-            ch.dt_red_cfg()
-                .write(|w| unsafe { w.bits(cfg.rising_edge_delay as u32) });
-            ch.dt_cfg().write(|w| unsafe { w.bits(cfg.cfg_reg) });
-        }
+        write_deadtime::<PWM, OP>(cfg);
     }
 
     /// Use the A output with the given pin and configuration
@@ -240,6 +375,44 @@ impl<const OP: u8, PWM: PwmPeripheral> Operator<OP, PWM> {
     ) {
         (PwmPin::new(pin_a, config_a), PwmPin::new(pin_b, config_b))
     }
+
+    /// Use both the A and the B output, with the dead-time generator between
+    /// the operator and the pins.
+    ///
+    /// The dead-time configuration is applied before the pins are connected,
+    /// so the outputs never overlap, not even while setting up.
+    pub fn with_linked_pins<'d, PinA: OutputPin, PinB: OutputPin>(
+        self,
+        pin_a: impl Peripheral<P = PinA> + 'd,
+        config_a: PwmPinConfig<true>,
+        pin_b: impl Peripheral<P = PinB> + 'd,
+        config_b: PwmPinConfig<false>,
+        config_dt: DeadTimeCfg,
+    ) -> LinkedPins<'d, PinA, PinB, PWM, OP> {
+        LinkedPins::new(pin_a, config_a, pin_b, config_b, config_dt)
+    }
+}
+
+fn write_deadtime<PWM: PwmPeripheral, const OP: u8>(cfg: &DeadTimeCfg) {
+    // SAFETY:
+    // We only write to our DTx registers
+    let ch = unsafe { &*PWM::block() }.ch(OP as usize);
+    #[cfg(esp32s3)]
+    {
+        ch.db_fed_cfg()
+            .write(|w| unsafe { w.bits(cfg.falling_edge_delay as u32) });
+        ch.db_red_cfg()
+            .write(|w| unsafe { w.bits(cfg.rising_edge_delay as u32) });
+        ch.db_cfg().write(|w| unsafe { w.bits(cfg.cfg_reg) });
+    }
+    #[cfg(not(esp32s3))]
+    {
+        ch.dt_fed_cfg()
+            .write(|w| unsafe { w.bits(cfg.falling_edge_delay as u32) });
+        ch.dt_red_cfg()
+            .write(|w| unsafe { w.bits(cfg.rising_edge_delay as u32) });
+        ch.dt_cfg().write(|w| unsafe { w.bits(cfg.cfg_reg) });
+    }
 }
 
 /// Configuration describing how the operator generates a signal on a connected
@@ -426,6 +599,113 @@ impl<'d, Pin: OutputPin, PWM: PwmPeripheral, const OP: u8, const IS_A: bool>
     }
 }
 
+/// Two pins driven by the A and B outputs of an MCPWM operator through its
+/// dead-time generator
+///
+/// The dead-time generator delays the rising and the falling edges, so the
+/// two transistors of a half bridge are never on at the same time:
+///
+/// ```no_run
+/// // PWMA drives the high side, PWMB the inverted low side, with 20 cycles
+/// // of the peripheral clock where both are off
+/// let mut pins = mcpwm.operator0.with_linked_pins(
+///     pin_high,
+///     PwmPinConfig::UP_ACTIVE_HIGH,
+///     pin_low,
+///     PwmPinConfig::new(PwmActions::empty(), PwmUpdateMethod::SYNC_ON_ZERO),
+///     DeadTimeCfg::new_ahc(Some(20), Some(20)),
+/// );
+///
+/// pins.set_timestamp_a(40);
+/// ```
+pub struct LinkedPins<'d, PinA, PinB, PWM, const OP: u8> {
+    pin_a: PwmPin<'d, PinA, PWM, OP, true>,
+    pin_b: PwmPin<'d, PinB, PWM, OP, false>,
+}
+
+impl<'d, PinA: OutputPin, PinB: OutputPin, PWM: PwmPeripheral, const OP: u8>
+    LinkedPins<'d, PinA, PinB, PWM, OP>
+{
+    fn new(
+        pin_a: impl Peripheral<P = PinA> + 'd,
+        config_a: PwmPinConfig<true>,
+        pin_b: impl Peripheral<P = PinB> + 'd,
+        config_b: PwmPinConfig<false>,
+        config_dt: DeadTimeCfg,
+    ) -> Self {
+        write_deadtime::<PWM, OP>(&config_dt);
+
+        LinkedPins {
+            pin_a: PwmPin::new(pin_a, config_a),
+            pin_b: PwmPin::new(pin_b, config_b),
+        }
+    }
+
+    /// Replace the dead-time configuration
+    pub fn set_deadtime_cfg(&mut self, config_dt: DeadTimeCfg) {
+        write_deadtime::<PWM, OP>(&config_dt);
+    }
+
+    /// Set the rising edge delay in cycles of the dead-time clock
+    pub fn set_rising_edge_deadtime(&mut self, cycles: u16) {
+        self.pin_a.update_red(cycles);
+    }
+
+    /// Set the falling edge delay in cycles of the dead-time clock
+    pub fn set_falling_edge_deadtime(&mut self, cycles: u16) {
+        self.pin_a.update_fed(cycles);
+    }
+
+    /// Configure what actions should be taken on timing events of output A
+    pub fn set_actions_a(&mut self, value: PwmActions<true>) {
+        self.pin_a.set_actions(value)
+    }
+
+    /// Configure what actions should be taken on timing events of output B
+    pub fn set_actions_b(&mut self, value: PwmActions<false>) {
+        self.pin_b.set_actions(value)
+    }
+
+    /// Set how a new timestamp A syncs with the timer
+    pub fn set_update_method_a(&mut self, update_method: PwmUpdateMethod) {
+        self.pin_a.set_update_method(update_method)
+    }
+
+    /// Set how a new timestamp B syncs with the timer
+    pub fn set_update_method_b(&mut self, update_method: PwmUpdateMethod) {
+        self.pin_b.set_update_method(update_method)
+    }
+
+    /// Write a new timestamp A.
+    /// The written value will take effect according to the set
+    /// [`PwmUpdateMethod`].
+    pub fn set_timestamp_a(&mut self, value: u16) {
+        self.pin_a.set_timestamp(value)
+    }
+
+    /// Write a new timestamp B.
+    /// The written value will take effect according to the set
+    /// [`PwmUpdateMethod`].
+    pub fn set_timestamp_b(&mut self, value: u16) {
+        self.pin_b.set_timestamp(value)
+    }
+
+    /// Get the old timestamp A.
+    pub fn get_timestamp_a(&self) -> u16 {
+        self.pin_a.get_timestamp()
+    }
+
+    /// Get the old timestamp B.
+    pub fn get_timestamp_b(&self) -> u16 {
+        self.pin_b.get_timestamp()
+    }
+
+    /// Get the period of the timer.
+    pub fn get_period(&self) -> u16 {
+        self.pin_a.get_period()
+    }
+}
+
 #[cfg(feature = "embedded-hal-02")]
 impl<'d, Pin: OutputPin, PWM: PwmPeripheral, const OP: u8, const IS_A: bool> embedded_hal_02::PwmPin
     for PwmPin<'d, Pin, PWM, OP, IS_A>
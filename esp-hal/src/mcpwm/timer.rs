@@ -9,12 +9,23 @@
 use core::marker::PhantomData;
 
 use fugit::HertzU32;
+use portable_atomic::{AtomicBool, AtomicU32, Ordering};
 
 use crate::{
     clock::Clocks,
-    mcpwm::{FrequencyError, PeripheralClockConfig, PwmPeripheral},
+    interrupt,
+    mcpwm::{FrequencyError, PeripheralClockConfig, PwmPeripheral, RegisterBlock},
 };
 
+#[allow(clippy::declare_interior_mutable_const)]
+const REMAINING_INIT: AtomicU32 = AtomicU32::new(0);
+#[allow(clippy::declare_interior_mutable_const)]
+const OVERRUN_INIT: AtomicBool = AtomicBool::new(false);
+// the bursts of three timers for each of up to two MCPWM peripherals, counted
+// down by the interrupt handler
+static BURST_REMAINING: [AtomicU32; 6] = [REMAINING_INIT; 6];
+static BURST_OVERRUN: [AtomicBool; 6] = [OVERRUN_INIT; 6];
+
 /// A MCPWM timer
 ///
 /// Every timer of a particular [`MCPWM`](super::MCPWM) peripheral can be used
@@ -58,6 +69,133 @@ impl<const TIM: u8, PWM: PwmPeripheral> Timer<TIM, PWM> {
         });
     }
 
+    /// Output `periods` PWM periods and stop the timer at the end of the last
+    /// one, e.g. for stepper motor pulses or ultrasonic bursts.
+    ///
+    /// A single period is timed by the hardware alone. For longer bursts the
+    /// interrupt handler of the MCPWM peripheral counts the periods and tells
+    /// the timer to stop at the end of the last one. This binds that handler,
+    /// replacing a handler set with
+    /// [`MCPWM::set_interrupt_handler`](super::MCPWM::set_interrupt_handler).
+    /// The handler has to run within a period of the counter passing zero.
+    /// If it is too late to stop the timer after the last period,
+    /// [`Timer::poll_burst`] reports [`BurstError::Overrun`].
+    ///
+    /// Poll the end of the burst with [`Timer::poll_burst`], or use
+    /// [`Timer::burst`]. [`TimerEvent::Stop`] fires once the burst is done.
+    ///
+    /// Start bursts from a stopped timer. Nothing happens if `periods` is zero.
+    pub fn start_burst(&mut self, timer_config: TimerClockConfig, periods: u32) {
+        if periods == 0 {
+            return;
+        }
+
+        let index = Self::index();
+        self.clear_interrupt(TimerEvent::Zero);
+        self.clear_interrupt(TimerEvent::Stop);
+        BURST_OVERRUN[index].store(false, Ordering::Relaxed);
+        BURST_REMAINING[index].store(periods, Ordering::Relaxed);
+
+        if periods > 1 {
+            let handler = PWM::interrupt_handler();
+            unsafe {
+                interrupt::bind_interrupt(PWM::interrupt(), handler.handler());
+                interrupt::enable(PWM::interrupt(), handler.priority()).unwrap();
+            }
+            set_interrupt_enabled::<PWM, TIM>(TimerEvent::Zero, true);
+        }
+
+        self.cfg0().write(|w| unsafe {
+            w.prescale().bits(timer_config.prescaler);
+            w.period().bits(timer_config.period);
+            w.period_upmethod().bits(0)
+        });
+
+        // run on, or stop when the counter next equals zero
+        let start = if periods == 1 { 3 } else { 2 };
+        self.cfg1().write(|w| unsafe {
+            w.start().bits(start);
+            w.mod_().bits(timer_config.mode as u8)
+        });
+    }
+
+    /// Check whether a burst started with [`Timer::start_burst`] is done.
+    ///
+    /// Returns `Ok` once the timer stopped after the last period, or
+    /// [`BurstError::Overrun`] if the interrupt handler was too late to stop
+    /// the timer in time.
+    pub fn poll_burst(&mut self) -> nb::Result<(), BurstError> {
+        let index = Self::index();
+
+        if BURST_OVERRUN[index].swap(false, Ordering::Relaxed) {
+            BURST_REMAINING[index].store(0, Ordering::Relaxed);
+            return Err(nb::Error::Other(BurstError::Overrun));
+        }
+
+        if BURST_REMAINING[index].load(Ordering::Relaxed) == 0
+            || self.is_interrupt_set(TimerEvent::Stop)
+        {
+            set_interrupt_enabled::<PWM, TIM>(TimerEvent::Zero, false);
+            BURST_REMAINING[index].store(0, Ordering::Relaxed);
+            return Ok(());
+        }
+
+        Err(nb::Error::WouldBlock)
+    }
+
+    /// Output `periods` PWM periods and wait until the timer stopped, see
+    /// [`Timer::start_burst`]
+    #[cfg(feature = "async")]
+    pub async fn burst(
+        &mut self,
+        timer_config: TimerClockConfig<'_>,
+        periods: u32,
+    ) -> Result<(), BurstError> {
+        self.start_burst(timer_config, periods);
+        loop {
+            match self.poll_burst() {
+                Ok(()) => break,
+                Err(nb::Error::Other(error)) => return Err(error),
+                Err(nb::Error::WouldBlock) => asynch::TimerFuture::<PWM, TIM>::new().await,
+            }
+        }
+        self.clear_interrupt(TimerEvent::Stop);
+
+        Ok(())
+    }
+
+    /// Enable the interrupt of the given timer event
+    pub fn listen(&mut self, event: TimerEvent) {
+        set_interrupt_enabled::<PWM, TIM>(event, true);
+    }
+
+    /// Disable the interrupt of the given timer event
+    pub fn unlisten(&mut self, event: TimerEvent) {
+        set_interrupt_enabled::<PWM, TIM>(event, false);
+    }
+
+    /// Whether the given event happened since its interrupt was last cleared
+    pub fn is_interrupt_set(&self, event: TimerEvent) -> bool {
+        is_interrupt_raw_set::<PWM, TIM>(event)
+    }
+
+    /// Clear the interrupt of the given timer event
+    pub fn clear_interrupt(&mut self, event: TimerEvent) {
+        let block = unsafe { &*PWM::block() };
+        block.int_clr().write(|w| match (event, TIM) {
+            (TimerEvent::Stop, 0) => w.timer0_stop().clear_bit_by_one(),
+            (TimerEvent::Stop, 1) => w.timer1_stop().clear_bit_by_one(),
+            (TimerEvent::Stop, 2) => w.timer2_stop().clear_bit_by_one(),
+            (TimerEvent::Zero, 0) => w.timer0_tez().clear_bit_by_one(),
+            (TimerEvent::Zero, 1) => w.timer1_tez().clear_bit_by_one(),
+            (TimerEvent::Zero, 2) => w.timer2_tez().clear_bit_by_one(),
+            (TimerEvent::Period, 0) => w.timer0_tep().clear_bit_by_one(),
+            (TimerEvent::Period, 1) => w.timer1_tep().clear_bit_by_one(),
+            (TimerEvent::Period, 2) => w.timer2_tep().clear_bit_by_one(),
+            _ => unreachable!(),
+        });
+    }
+
     /// Stop the timer in its current state
     pub fn stop(&mut self) {
         // freeze the timer
@@ -69,16 +207,63 @@ impl<const TIM: u8, PWM: PwmPeripheral> Timer<TIM, PWM> {
         // SAFETY:
         // We only write to our TIMERx_SYNC register
         let tmr = unsafe { Self::tmr() };
-        let sw = tmr.sync().read().sw().bit_is_set();
-        tmr.sync().write(|w| {
+        // keep the sync input and output configuration
+        tmr.sync().modify(|r, w| {
             w.phase_direction().bit(direction as u8 != 0);
             unsafe {
                 w.phase().bits(phase);
             }
-            w.sw().bit(!sw)
+            w.sw().bit(!r.sw().bit_is_set())
         });
     }
 
+    /// Reload the counter with `phase` and `direction` whenever the given sync
+    /// source fires.
+    ///
+    /// Timers syncing to the same source with different phases stay
+    /// phase-locked, e.g. for interleaved PWM. [`SyncSource::None`] stops
+    /// syncing.
+    pub fn set_sync_input(&mut self, source: SyncSource, phase: u16, direction: CounterDirection) {
+        // SAFETY:
+        // We only write to our TIMERx_SYNC register and, in a critical section,
+        // our bits of the TIMER_SYNCI_CFG register
+        let block = unsafe { &*PWM::block() };
+        critical_section::with(|_| {
+            block.timer_synci_cfg().modify(|r, w| unsafe {
+                let offset = TIM * 3;
+                w.bits((r.bits() & !(0b111 << offset)) | ((source as u32) << offset))
+            });
+        });
+
+        let tmr = unsafe { Self::tmr() };
+        tmr.sync().modify(|_, w| {
+            w.synci_en().bit(source != SyncSource::None);
+            w.phase_direction().bit(direction as u8 != 0);
+            unsafe { w.phase().bits(phase) }
+        });
+    }
+
+    /// Choose what the sync output of this timer forwards to timers using
+    /// [`SyncSource::Timer0`] (or 1, 2) as their sync source
+    pub fn set_sync_output(&mut self, output: SyncOutput) {
+        // SAFETY:
+        // We only write to our TIMERx_SYNC register
+        let tmr = unsafe { Self::tmr() };
+        tmr.sync()
+            .modify(|_, w| unsafe { w.synco_sel().bits(output as u8) });
+    }
+
+    /// Sync the timer by software, reloading the phase set with
+    /// [`Timer::set_sync_input`]. With [`SyncOutput::Software`] the sync is
+    /// forwarded to the timers syncing to this one.
+    pub fn software_sync(&mut self) {
+        // SAFETY:
+        // We only write to our TIMERx_SYNC register
+        let tmr = unsafe { Self::tmr() };
+        // a toggle triggers the sync
+        tmr.sync().modify(|r, w| w.sw().bit(!r.sw().bit_is_set()));
+    }
+
     /// Read the counter value and counter direction of the timer
     pub fn status(&self) -> (u16, CounterDirection) {
         // SAFETY:
@@ -103,6 +288,121 @@ impl<const TIM: u8, PWM: PwmPeripheral> Timer<TIM, PWM> {
         let block = unsafe { &*PWM::block() };
         block.timer(TIM as usize)
     }
+
+    fn index() -> usize {
+        PWM::NUMBER * 3 + TIM as usize
+    }
+}
+
+/// Count down the periods of running bursts and stop their timers at the end
+/// of the last period
+pub(super) fn handle_interrupt(block: &RegisterBlock, number: usize) {
+    let status = block.int_st().read();
+    let zero = [
+        status.timer0_tez().bit_is_set(),
+        status.timer1_tez().bit_is_set(),
+        status.timer2_tez().bit_is_set(),
+    ];
+
+    for (tim, _) in zero.iter().enumerate().filter(|(_, zero)| **zero) {
+        let index = number * 3 + tim;
+        block.int_clr().write(|w| match tim {
+            0 => w.timer0_tez().clear_bit_by_one(),
+            1 => w.timer1_tez().clear_bit_by_one(),
+            _ => w.timer2_tez().clear_bit_by_one(),
+        });
+
+        let remaining = BURST_REMAINING[index].load(Ordering::Relaxed);
+        if remaining <= 1 {
+            continue;
+        }
+        BURST_REMAINING[index].store(remaining - 1, Ordering::Relaxed);
+
+        if remaining - 1 == 1 {
+            // stop when the counter next equals zero, at the end of the last period
+            block
+                .timer(tim)
+                .cfg1()
+                .modify(|_, w| unsafe { w.start().bits(0) });
+
+            critical_section::with(|_| {
+                block.int_ena().modify(|_, w| match tim {
+                    0 => w.timer0_tez().clear_bit(),
+                    1 => w.timer1_tez().clear_bit(),
+                    _ => w.timer2_tez().clear_bit(),
+                });
+            });
+
+            // the last period already ended before the stop took effect
+            let raw = block.int_raw().read();
+            let late = match tim {
+                0 => raw.timer0_tez().bit_is_set(),
+                1 => raw.timer1_tez().bit_is_set(),
+                _ => raw.timer2_tez().bit_is_set(),
+            };
+            if late {
+                BURST_OVERRUN[index].store(true, Ordering::Relaxed);
+            }
+        }
+    }
+
+    #[cfg(feature = "async")]
+    asynch::handle_interrupt(block, number);
+}
+
+fn set_interrupt_enabled<PWM: PwmPeripheral, const TIM: u8>(event: TimerEvent, enable: bool) {
+    let block = unsafe { &*PWM::block() };
+    critical_section::with(|_| {
+        block.int_ena().modify(|_, w| match (event, TIM) {
+            (TimerEvent::Stop, 0) => w.timer0_stop().bit(enable),
+            (TimerEvent::Stop, 1) => w.timer1_stop().bit(enable),
+            (TimerEvent::Stop, 2) => w.timer2_stop().bit(enable),
+            (TimerEvent::Zero, 0) => w.timer0_tez().bit(enable),
+            (TimerEvent::Zero, 1) => w.timer1_tez().bit(enable),
+            (TimerEvent::Zero, 2) => w.timer2_tez().bit(enable),
+            (TimerEvent::Period, 0) => w.timer0_tep().bit(enable),
+            (TimerEvent::Period, 1) => w.timer1_tep().bit(enable),
+            (TimerEvent::Period, 2) => w.timer2_tep().bit(enable),
+            _ => unreachable!(),
+        });
+    });
+}
+
+fn is_interrupt_raw_set<PWM: PwmPeripheral, const TIM: u8>(event: TimerEvent) -> bool {
+    let raw = unsafe { &*PWM::block() }.int_raw().read();
+    match (event, TIM) {
+        (TimerEvent::Stop, 0) => raw.timer0_stop().bit_is_set(),
+        (TimerEvent::Stop, 1) => raw.timer1_stop().bit_is_set(),
+        (TimerEvent::Stop, 2) => raw.timer2_stop().bit_is_set(),
+        (TimerEvent::Zero, 0) => raw.timer0_tez().bit_is_set(),
+        (TimerEvent::Zero, 1) => raw.timer1_tez().bit_is_set(),
+        (TimerEvent::Zero, 2) => raw.timer2_tez().bit_is_set(),
+        (TimerEvent::Period, 0) => raw.timer0_tep().bit_is_set(),
+        (TimerEvent::Period, 1) => raw.timer1_tep().bit_is_set(),
+        (TimerEvent::Period, 2) => raw.timer2_tep().bit_is_set(),
+        _ => unreachable!(),
+    }
+}
+
+/// Burst errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BurstError {
+    /// The interrupt handler ran too late to stop the timer after the last
+    /// period, so the timer output at least one period too many
+    Overrun,
+}
+
+/// Timer events which can trigger an interrupt
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum TimerEvent {
+    /// The timer stopped, e.g. at the end of a burst
+    Stop,
+    /// The counter equals zero
+    Zero,
+    /// The counter equals the period
+    Period,
 }
 
 /// Clock configuration of a MCPWM timer
@@ -204,8 +504,43 @@ pub enum PwmWorkingMode {
     UpDown   = 3,
 }
 
+/// What reloads the counter of a timer with its phase
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SyncSource {
+    /// Only software syncs, see [`Timer::software_sync`]
+    None      = 0,
+    /// The sync output of timer 0
+    Timer0    = 1,
+    /// The sync output of timer 1
+    Timer1    = 2,
+    /// The sync output of timer 2
+    Timer2    = 3,
+    /// The external sync input 0, see
+    /// [`SyncInput::with_pin`](super::SyncInput::with_pin)
+    External0 = 4,
+    /// The external sync input 1
+    External1 = 5,
+    /// The external sync input 2
+    External2 = 6,
+}
+
+/// What the sync output of a timer forwards
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SyncOutput {
+    /// The sync input of the timer
+    SyncInput = 0,
+    /// The counter equalling zero
+    Zero      = 1,
+    /// The counter equalling the period
+    Period    = 2,
+    /// Software syncs
+    Software  = 3,
+}
+
 /// The direction the timer counter is changing
-#[derive(Debug)]
+#[derive(Copy, Clone, Debug)]
 #[repr(u8)]
 pub enum CounterDirection {
     /// The timer counter is increasing
@@ -222,3 +557,86 @@ impl From<bool> for CounterDirection {
         }
     }
 }
+
+#[cfg(feature = "async")]
+pub(super) mod asynch {
+    use core::{
+        marker::PhantomData,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use embassy_sync::waitqueue::AtomicWaker;
+
+    use super::{is_interrupt_raw_set, set_interrupt_enabled, TimerEvent};
+    use crate::mcpwm::{PwmPeripheral, RegisterBlock};
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: AtomicWaker = AtomicWaker::new();
+    // three timers for each of up to two MCPWM peripherals
+    static WAKERS: [AtomicWaker; 6] = [INIT; 6];
+
+    /// Waits for the timer to stop
+    pub(crate) struct TimerFuture<PWM, const TIM: u8> {
+        phantom: PhantomData<PWM>,
+    }
+
+    impl<PWM: PwmPeripheral, const TIM: u8> TimerFuture<PWM, TIM> {
+        pub fn new() -> Self {
+            set_interrupt_enabled::<PWM, TIM>(TimerEvent::Stop, true);
+            Self {
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<PWM: PwmPeripheral, const TIM: u8> core::future::Future for TimerFuture<PWM, TIM> {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            WAKERS[PWM::NUMBER * 3 + TIM as usize].register(cx.waker());
+
+            if is_interrupt_raw_set::<PWM, TIM>(TimerEvent::Stop) {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl<PWM: PwmPeripheral, const TIM: u8> Drop for TimerFuture<PWM, TIM> {
+        fn drop(&mut self) {
+            set_interrupt_enabled::<PWM, TIM>(TimerEvent::Stop, false);
+        }
+    }
+
+    /// Disable the stop interrupts of the timers which fired and wake their
+    /// tasks
+    pub(crate) fn handle_interrupt(block: &RegisterBlock, number: usize) {
+        let status = block.int_st().read();
+        let fired = [
+            status.timer0_stop().bit_is_set(),
+            status.timer1_stop().bit_is_set(),
+            status.timer2_stop().bit_is_set(),
+        ];
+
+        critical_section::with(|_| {
+            block.int_ena().modify(|_, w| {
+                if fired[0] {
+                    w.timer0_stop().clear_bit();
+                }
+                if fired[1] {
+                    w.timer1_stop().clear_bit();
+                }
+                if fired[2] {
+                    w.timer2_stop().clear_bit();
+                }
+                w
+            });
+        });
+
+        for (tim, _) in fired.iter().enumerate().filter(|(_, fired)| **fired) {
+            WAKERS[number * 3 + tim].wake();
+        }
+    }
+}
@@ -0,0 +1,211 @@
+//! # PCNT - Quadrature encoder
+//!
+//! ## Overview
+//! The `encoder` module decodes the A and B signals of a quadrature encoder
+//! with the two channels of a PCNT unit.
+//!
+//! The hardware counter only has 16 bits. The unit resets it whenever it
+//! reaches one of its limits, and [`Encoder::handle_interrupt`], called from
+//! the PCNT interrupt handler, adds the lost counts to a 64-bit position.
+//!
+//! ## Example
+//! ```no_run
+//! static ENCODER: Mutex<RefCell<Option<Encoder>>> = Mutex::new(RefCell::new(None));
+//!
+//! let pcnt = PCNT::new(peripherals.PCNT, Some(interrupt_handler));
+//! let encoder = Encoder::new(
+//!     pcnt.get_unit(unit::Number::Unit0),
+//!     PcntSource::from_pin(&mut pin_a),
+//!     PcntSource::from_pin(&mut pin_b),
+//!     EncoderMode::X4,
+//!     Some(100),
+//! )
+//! .unwrap();
+//! critical_section::with(|cs| ENCODER.borrow_ref_mut(cs).replace(encoder));
+//!
+//! loop {
+//!     let position =
+//!         critical_section::with(|cs| ENCODER.borrow_ref_mut(cs).as_mut().unwrap().position());
+//!     println!("position: {position}");
+//! }
+//!
+//! #[handler]
+//! fn interrupt_handler() {
+//!     critical_section::with(|cs| {
+//!         ENCODER
+//!             .borrow_ref_mut(cs)
+//!             .as_mut()
+//!             .unwrap()
+//!             .handle_interrupt();
+//!     });
+//! }
+//! ```
+
+use fugit::MicrosDurationU64;
+
+use super::{
+    channel::{self, CtrlMode, EdgeMode, PcntSource},
+    unit::{self, Unit},
+};
+
+/// The counter limits, the counter is reset to zero when reaching them
+const LIMIT: i16 = i16::MAX;
+
+/// Which edges of the encoder signals are counted
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum EncoderMode {
+    /// Count the rising edges of A, one count per encoder cycle
+    X1,
+    /// Count both edges of A, two counts per encoder cycle
+    X2,
+    /// Count both edges of A and B, four counts per encoder cycle
+    #[default]
+    X4,
+}
+
+/// A quadrature encoder counted by a PCNT unit
+///
+/// The position increases when A leads B.
+pub struct Encoder {
+    unit: Unit,
+    overflow: i64,
+    last_position: i64,
+}
+
+impl Encoder {
+    /// Decode the encoder signals `a` and `b` with `unit`.
+    ///
+    /// `filter` ignores pulses shorter than the given number of APB clock
+    /// cycles, see [`unit::Config::filter`]. The unit starts counting right
+    /// away, and its limit interrupts are enabled.
+    pub fn new(
+        mut unit: Unit,
+        a: PcntSource,
+        b: PcntSource,
+        mode: EncoderMode,
+        filter: Option<u16>,
+    ) -> Result<Self, unit::Error> {
+        unit.configure(unit::Config {
+            low_limit: -LIMIT,
+            high_limit: LIMIT,
+            filter,
+            ..Default::default()
+        })?;
+
+        let mut ch0 = unit.get_channel(channel::Number::Channel0);
+        ch0.configure(
+            b,
+            a,
+            channel::Config {
+                lctrl_mode: CtrlMode::Reverse,
+                hctrl_mode: CtrlMode::Keep,
+                pos_edge: EdgeMode::Decrement,
+                neg_edge: match mode {
+                    EncoderMode::X1 => EdgeMode::Hold,
+                    EncoderMode::X2 | EncoderMode::X4 => EdgeMode::Increment,
+                },
+                invert_ctrl: false,
+                invert_sig: false,
+            },
+        );
+
+        let mut ch1 = unit.get_channel(channel::Number::Channel1);
+        if mode == EncoderMode::X4 {
+            ch1.configure(
+                a,
+                b,
+                channel::Config {
+                    lctrl_mode: CtrlMode::Reverse,
+                    hctrl_mode: CtrlMode::Keep,
+                    pos_edge: EdgeMode::Increment,
+                    neg_edge: EdgeMode::Decrement,
+                    invert_ctrl: false,
+                    invert_sig: false,
+                },
+            );
+        } else {
+            ch1.configure(
+                PcntSource::always_low(),
+                PcntSource::always_low(),
+                channel::Config {
+                    pos_edge: EdgeMode::Hold,
+                    neg_edge: EdgeMode::Hold,
+                    ..Default::default()
+                },
+            );
+        }
+
+        unit.events(unit::Events {
+            low_limit: true,
+            high_limit: true,
+            ..Default::default()
+        });
+        unit.reset_interrupt();
+        unit.listen();
+        unit.resume();
+
+        Ok(Encoder {
+            unit,
+            overflow: 0,
+            last_position: 0,
+        })
+    }
+
+    /// Account for the counter reaching its limits.
+    ///
+    /// Call this from the PCNT interrupt handler, a limit missed while the
+    /// counter went around again is lost.
+    pub fn handle_interrupt(&mut self) {
+        if !self.unit.interrupt_set() {
+            return;
+        }
+
+        let events = self.unit.get_events();
+        if events.high_limit {
+            self.overflow += LIMIT as i64;
+        } else if events.low_limit {
+            self.overflow -= LIMIT as i64;
+        }
+        self.unit.reset_interrupt();
+    }
+
+    /// The number of counts since the encoder was created or last reset
+    pub fn position(&mut self) -> i64 {
+        self.handle_interrupt();
+        let mut value = self.unit.get_value();
+
+        // the counter may have reached a limit since the interrupt was handled
+        if self.unit.interrupt_set() {
+            self.handle_interrupt();
+            value = self.unit.get_value();
+        }
+
+        self.overflow + value as i64
+    }
+
+    /// The speed in counts per second, from the position change since the
+    /// last call, which happened `elapsed` ago
+    pub fn velocity(&mut self, elapsed: MicrosDurationU64) -> i64 {
+        let position = self.position();
+        let delta = position - core::mem::replace(&mut self.last_position, position);
+
+        match elapsed.ticks() {
+            0 => 0,
+            micros => delta * 1_000_000 / micros as i64,
+        }
+    }
+
+    /// Set the position to zero
+    pub fn reset(&mut self) {
+        self.unit.clear();
+        self.unit.reset_interrupt();
+        self.overflow = 0;
+        self.last_position = 0;
+    }
+
+    /// The PCNT unit counting the encoder
+    pub fn unit(&self) -> &Unit {
+        &self.unit
+    }
+}
@@ -0,0 +1,209 @@
+//! # PCNT - Frequency meter
+//!
+//! ## Overview
+//! The `frequency_meter` module measures the frequency of a signal, e.g. of a
+//! fan tachometer or a flow sensor, by counting its rising edges with a PCNT
+//! unit for a known gate time.
+//!
+//! The gate is opened with [`FrequencyMeter::open_gate`] and closed with
+//! [`FrequencyMeter::close_gate`], e.g. from the interrupt of a TIMG or
+//! SYSTIMER alarm. With the `embedded-hal-02` feature
+//! [`FrequencyMeter::measure`] times the gate with any of the HAL's count down
+//! timers.
+//!
+//! ## Example
+//! ```no_run
+//! let pcnt = PCNT::new(peripherals.PCNT, None);
+//! let mut meter = FrequencyMeter::new(
+//!     pcnt.get_unit(unit::Number::Unit0),
+//!     PcntSource::from_pin(&mut tacho_pin),
+//!     None,
+//! )
+//! .unwrap();
+//!
+//! let timg0 = TimerGroup::new(peripherals.TIMG0, &clocks, None);
+//! let mut timer0 = timg0.timer0;
+//!
+//! let measurement = meter.measure(&mut timer0, 100_000u64.micros()).unwrap();
+//! // two pulses per fan revolution
+//! println!("{} rpm", measurement.rpm(NonZeroU32::new(2).unwrap()));
+//! ```
+
+use core::num::NonZeroU32;
+
+use fugit::{HertzU32, MicrosDurationU64};
+
+use super::{
+    channel::{self, CtrlMode, EdgeMode, PcntSource},
+    unit::{self, Unit},
+};
+
+/// The most pulses counted in one gate, one less than the high limit of the
+/// unit so a count of exactly `MAX_PULSES` isn't an overflow
+pub const MAX_PULSES: u16 = i16::MAX as u16 - 1;
+
+/// Frequency meter errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// More than [`MAX_PULSES`] pulses arrived while the gate was open, use a
+    /// shorter gate time
+    Overflow,
+    /// The gate time is zero
+    InvalidGate,
+    /// The unit could not be configured
+    Unit(unit::Error),
+}
+
+impl From<unit::Error> for Error {
+    fn from(error: unit::Error) -> Self {
+        Error::Unit(error)
+    }
+}
+
+/// The pulses counted in one gate time
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Measurement {
+    /// The number of rising edges
+    pub pulses: u16,
+    /// How long the gate was open
+    pub gate: MicrosDurationU64,
+}
+
+impl Measurement {
+    /// The measured frequency, rounded down
+    pub fn frequency(&self) -> HertzU32 {
+        HertzU32::from_raw((self.pulses as u64 * 1_000_000 / self.gate.ticks()) as u32)
+    }
+
+    /// The error bound of [`Measurement::frequency`].
+    ///
+    /// A gate can miss one pulse at either end, so the actual frequency is
+    /// within plus or minus one pulse per gate time of the measured one. Any
+    /// error of the gate time itself, e.g. the latency of the interrupt closing
+    /// the gate, adds to this.
+    pub fn error(&self) -> HertzU32 {
+        HertzU32::from_raw(1_000_000u64.div_ceil(self.gate.ticks()) as u32)
+    }
+
+    /// The measured speed in revolutions per minute, for a sensor giving
+    /// `pulses_per_revolution` pulses per revolution
+    pub fn rpm(&self, pulses_per_revolution: NonZeroU32) -> u32 {
+        (self.pulses as u64 * 60_000_000 / (self.gate.ticks() * pulses_per_revolution.get() as u64))
+            as u32
+    }
+}
+
+/// Counts the rising edges of a signal for a gate time
+pub struct FrequencyMeter {
+    unit: Unit,
+}
+
+impl FrequencyMeter {
+    /// Count the rising edges of `signal` with `unit`.
+    ///
+    /// `filter` ignores pulses shorter than the given number of APB clock
+    /// cycles, see [`unit::Config::filter`]. The unit stays paused until the
+    /// gate is opened.
+    pub fn new(mut unit: Unit, signal: PcntSource, filter: Option<u16>) -> Result<Self, Error> {
+        unit.configure(unit::Config {
+            low_limit: -1,
+            high_limit: MAX_PULSES as i16 + 1,
+            filter,
+            ..Default::default()
+        })?;
+
+        let mut ch0 = unit.get_channel(channel::Number::Channel0);
+        ch0.configure(
+            PcntSource::always_high(),
+            signal,
+            channel::Config {
+                lctrl_mode: CtrlMode::Keep,
+                hctrl_mode: CtrlMode::Keep,
+                pos_edge: EdgeMode::Increment,
+                neg_edge: EdgeMode::Hold,
+                invert_ctrl: false,
+                invert_sig: false,
+            },
+        );
+
+        let mut ch1 = unit.get_channel(channel::Number::Channel1);
+        ch1.configure(
+            PcntSource::always_low(),
+            PcntSource::always_low(),
+            channel::Config {
+                pos_edge: EdgeMode::Hold,
+                neg_edge: EdgeMode::Hold,
+                ..Default::default()
+            },
+        );
+
+        // the high limit event flags an overflow, i.e. more than MAX_PULSES
+        // pulses, without an interrupt
+        unit.events(unit::Events {
+            high_limit: true,
+            ..Default::default()
+        });
+
+        Ok(FrequencyMeter { unit })
+    }
+
+    /// Clear the count and start counting
+    pub fn open_gate(&mut self) {
+        self.unit.clear();
+        self.unit.reset_interrupt();
+        self.unit.resume();
+    }
+
+    /// Stop counting, returning the pulses counted since
+    /// [`FrequencyMeter::open_gate`], which happened `gate` ago
+    pub fn close_gate(&mut self, gate: MicrosDurationU64) -> Result<Measurement, Error> {
+        self.unit.pause();
+
+        if gate.ticks() == 0 {
+            return Err(Error::InvalidGate);
+        }
+        if self.overflowed() {
+            return Err(Error::Overflow);
+        }
+
+        Ok(Measurement {
+            pulses: self.unit.get_value() as u16,
+            gate,
+        })
+    }
+
+    /// Count pulses for `gate`, timed with a count down timer, e.g. a TIMG
+    /// timer or a SYSTIMER alarm
+    #[cfg(feature = "embedded-hal-02")]
+    pub fn measure<T>(
+        &mut self,
+        timer: &mut T,
+        gate: MicrosDurationU64,
+    ) -> Result<Measurement, Error>
+    where
+        T: embedded_hal_02::timer::CountDown<Time = MicrosDurationU64>,
+    {
+        if gate.ticks() == 0 {
+            return Err(Error::InvalidGate);
+        }
+
+        timer.start(gate);
+        self.open_gate();
+        nb::block!(timer.wait()).ok();
+        self.close_gate(gate)
+    }
+
+    /// The PCNT unit counting the pulses
+    pub fn unit(&self) -> &Unit {
+        &self.unit
+    }
+
+    fn overflowed(&self) -> bool {
+        let pcnt = unsafe { &*crate::peripherals::PCNT::ptr() };
+        // the raw interrupt is set by the high limit event even when the
+        // interrupt isn't enabled
+        pcnt.int_raw().read().bits() & (1 << self.unit.number() as usize) != 0
+    }
+}
@@ -28,6 +28,10 @@
 //! enables users to pause, resume, and clear the counter, as well as enable or
 //! disable interrupts for specific events associated with the unit.
 //!
+//! The [encoder] module builds a quadrature encoder with a 64-bit position on
+//! top of a unit and its two channels, the [frequency_meter] module measures
+//! the frequency of a signal by counting its pulses for a gate time.
+//!
 //! ## Example
 //! ```no_run
 //! let unit_number = unit::Number::Unit1;
@@ -127,9 +131,51 @@
 //! }
 //! ```
 //!
+//! ## Events
+//! With [`event_handler`] as the interrupt handler, e.g. by creating the
+//! driver with [`PCNT::new_async`], the events enabled by [`Unit::events`] can
+//! be awaited with [`Unit::wait_for_event`] or handled by a callback
+//! registered with [`Unit::set_callback`]:
+//!
+//! ```no_run
+//! let pcnt = PCNT::new_async(peripherals.PCNT);
+//! let mut u0 = pcnt.get_unit(unit::Number::Unit0);
+//! u0.configure(unit::Config {
+//!     low_limit: -100,
+//!     high_limit: 100,
+//!     thresh0: 50,
+//!     ..Default::default()
+//! })
+//! .unwrap();
+//! // ... configure the channels
+//! u0.events(unit::Events {
+//!     thresh0: true,
+//!     ..Default::default()
+//! });
+//! u0.resume();
+//!
+//! let events = u0.wait_for_event().await;
+//! if events.thresh0 {
+//!     println!("counted to 50");
+//! }
+//! ```
+//!
+//! ## ETM
+//! On the supported chips with an Event Task Matrix (ESP32-C6, ESP32-H2) the
+//! PCNT peripheral has neither ETM events nor ETM tasks, so count events can't
+//! trigger other peripherals and units can't be paused or cleared through the
+//! ETM. Use the unit interrupts, see [`event_handler`], instead.
+//!
 //! [channel]: channel/index.html
+//! [encoder]: encoder/index.html
+//! [frequency_meter]: frequency_meter/index.html
 //! [unit]: unit/index.html
 
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use procmacros::handler;
+
 use self::unit::Unit;
 use crate::{
     interrupt::{self, InterruptHandler},
@@ -139,6 +185,8 @@ use crate::{
 };
 
 pub mod channel;
+pub mod encoder;
+pub mod frequency_meter;
 pub mod unit;
 
 pub struct PCNT<'d> {
@@ -165,8 +213,110 @@ impl<'d> PCNT<'d> {
         PCNT { _instance }
     }
 
+    /// Return a new PCNT with [`event_handler`] installed, for awaiting
+    /// events with [`Unit::wait_for_event`]
+    #[cfg(feature = "async")]
+    pub fn new_async(_instance: impl Peripheral<P = peripherals::PCNT> + 'd) -> Self {
+        Self::new(_instance, Some(event_handler))
+    }
+
     /// Return a unit
     pub fn get_unit(&self, number: unit::Number) -> Unit {
         Unit::new(number)
     }
 }
+
+#[cfg(esp32)]
+const NUM_UNITS: usize = 8;
+#[cfg(not(esp32))]
+const NUM_UNITS: usize = 4;
+
+/// Callbacks registered with [`Unit::set_callback`]
+static CALLBACKS: Mutex<Cell<[Option<fn(unit::Events)>; NUM_UNITS]>> =
+    Mutex::new(Cell::new([None; NUM_UNITS]));
+
+/// PCNT interrupt handler which clears the interrupts of the units, calls
+/// their callbacks and wakes the tasks waiting for their events
+///
+/// Pass it to [`PCNT::new`], or use [`PCNT::new_async`].
+#[handler]
+pub fn event_handler() {
+    let pcnt = unsafe { &*crate::peripherals::PCNT::ptr() };
+    // one CNT_THR_EVENT_Ux bit per unit
+    let status = pcnt.int_st().read().bits();
+
+    for unit in (0..NUM_UNITS).filter(|unit| status & (1 << unit) != 0) {
+        let events = unit::Events::read(unit);
+        pcnt.int_clr().write(|w| unsafe { w.bits(1 << unit) });
+
+        if let Some(callback) = critical_section::with(|cs| CALLBACKS.borrow(cs).get()[unit]) {
+            callback(events);
+        }
+
+        #[cfg(feature = "async")]
+        asynch::on_event(unit, events);
+    }
+}
+
+#[cfg(feature = "async")]
+mod asynch {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use embassy_sync::waitqueue::AtomicWaker;
+    use portable_atomic::{AtomicU8, Ordering};
+
+    use super::{unit, NUM_UNITS};
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: AtomicWaker = AtomicWaker::new();
+    static WAKERS: [AtomicWaker; NUM_UNITS] = [INIT; NUM_UNITS];
+
+    // events which happened since the last EventFuture of each unit completed
+    #[allow(clippy::declare_interior_mutable_const)]
+    const NONE: AtomicU8 = AtomicU8::new(0);
+    static PENDING: [AtomicU8; NUM_UNITS] = [NONE; NUM_UNITS];
+    const PENDING_FLAG: u8 = 1 << 7;
+
+    pub(super) fn on_event(unit: usize, events: unit::Events) {
+        PENDING[unit].fetch_or(events.to_bits() | PENDING_FLAG, Ordering::AcqRel);
+        WAKERS[unit].wake();
+    }
+
+    pub(crate) struct EventFuture {
+        unit: usize,
+    }
+
+    impl EventFuture {
+        pub fn new(number: unit::Number) -> Self {
+            let unit = number as usize;
+            PENDING[unit].store(0, Ordering::Release);
+
+            let pcnt = unsafe { &*crate::peripherals::PCNT::ptr() };
+            critical_section::with(|_| {
+                pcnt.int_ena()
+                    .modify(|r, w| unsafe { w.bits(r.bits() | (1 << unit)) });
+            });
+
+            Self { unit }
+        }
+    }
+
+    impl Future for EventFuture {
+        type Output = unit::Events;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            WAKERS[self.unit].register(cx.waker());
+
+            let bits = PENDING[self.unit].swap(0, Ordering::AcqRel);
+            if bits & PENDING_FLAG != 0 {
+                Poll::Ready(unit::Events::from_bits(bits))
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+}
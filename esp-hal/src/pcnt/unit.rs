@@ -13,6 +13,7 @@
 use critical_section::CriticalSection;
 
 use super::channel;
+use crate::clock::Clocks;
 
 /// Unit number
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
@@ -81,6 +82,42 @@ pub struct Events {
     pub zero: bool,
 }
 
+impl Events {
+    /// The latest events of the given unit
+    pub(super) fn read(unit: usize) -> Self {
+        let pcnt = unsafe { &*crate::peripherals::PCNT::ptr() };
+        let status = pcnt.u_status(unit).read();
+
+        Events {
+            low_limit: status.l_lim().bit(),
+            high_limit: status.h_lim().bit(),
+            thresh0: status.thres0().bit(),
+            thresh1: status.thres1().bit(),
+            zero: status.zero().bit(),
+        }
+    }
+
+    #[cfg(feature = "async")]
+    pub(super) fn to_bits(self) -> u8 {
+        (self.low_limit as u8)
+            | (self.high_limit as u8) << 1
+            | (self.thresh0 as u8) << 2
+            | (self.thresh1 as u8) << 3
+            | (self.zero as u8) << 4
+    }
+
+    #[cfg(feature = "async")]
+    pub(super) fn from_bits(bits: u8) -> Self {
+        Events {
+            low_limit: bits & 1 != 0,
+            high_limit: bits & (1 << 1) != 0,
+            thresh0: bits & (1 << 2) != 0,
+            thresh1: bits & (1 << 3) != 0,
+            zero: bits & (1 << 4) != 0,
+        }
+    }
+}
+
 /// Unit configuration
 #[derive(Copy, Clone, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -89,9 +126,34 @@ pub struct Config {
     pub high_limit: i16,
     pub thresh0: i16,
     pub thresh1: i16,
+    /// Ignore pulses shorter than this number of APB clock cycles, at most
+    /// [`MAX_FILTER_THRESHOLD`], see [`filter_threshold_from_nanos`]
     pub filter: Option<u16>,
 }
 
+/// The longest pulse, in APB clock cycles, the glitch filter can ignore
+pub const MAX_FILTER_THRESHOLD: u16 = 1023;
+
+/// The glitch filter threshold ignoring pulses shorter than `nanos`
+/// nanoseconds, rounded up to whole APB clock cycles.
+///
+/// E.g. 1 µs is 80 cycles of an 80 MHz APB clock. Fails if the filter can't
+/// ignore pulses this long, which is about 12.8 µs with an 80 MHz APB clock.
+pub fn filter_threshold_from_nanos(clocks: &Clocks, nanos: u32) -> Result<u16, Error> {
+    let cycles = (clocks.apb_clock.raw() as u64 * nanos as u64).div_ceil(1_000_000_000);
+    if cycles > MAX_FILTER_THRESHOLD as u64 {
+        return Err(Error::InvalidFilterThresh);
+    }
+
+    Ok(cycles as u16)
+}
+
+/// The length in nanoseconds of the pulses the given glitch filter threshold
+/// ignores
+pub fn filter_threshold_to_nanos(clocks: &Clocks, threshold: u16) -> u32 {
+    (threshold as u64 * 1_000_000_000 / clocks.apb_clock.raw() as u64) as u32
+}
+
 pub struct Unit {
     number: Number,
 }
@@ -144,12 +206,8 @@ impl Unit {
         if config.high_limit <= 0 {
             return Err(Error::InvalidHighLimit);
         }
-        let (filter_en, filter) = match config.filter {
-            Some(filter) => (true, filter),
-            None => (false, 0),
-        };
         // filter must be less than 1024
-        if filter > 1023 {
+        if config.filter.unwrap_or(0) > MAX_FILTER_THRESHOLD {
             return Err(Error::InvalidFilterThresh);
         }
 
@@ -180,16 +238,55 @@ impl Unit {
                 .cnt_thres1()
                 .bits(config.thresh1 as u16)
         });
-        conf0.modify(|_, w| unsafe { w.filter_thres().bits(filter).filter_en().bit(filter_en) });
+        self.set_filter(config.filter)?;
         self.pause();
         self.clear();
         Ok(())
     }
 
+    /// Ignore pulses shorter than `filter` APB clock cycles on the inputs of
+    /// this unit, or disable the glitch filter with `None`.
+    ///
+    /// Can be changed while the unit is counting. See
+    /// [`filter_threshold_from_nanos`] to express the threshold as a time.
+    pub fn set_filter(&self, filter: Option<u16>) -> Result<(), Error> {
+        let (filter_en, filter) = match filter {
+            Some(filter) => (true, filter),
+            None => (false, 0),
+        };
+        if filter > MAX_FILTER_THRESHOLD {
+            return Err(Error::InvalidFilterThresh);
+        }
+
+        let pcnt = unsafe { &*crate::peripherals::PCNT::ptr() };
+        let conf0 = match self.number {
+            Number::Unit0 => pcnt.u0_conf0(),
+            Number::Unit1 => pcnt.u1_conf0(),
+            Number::Unit2 => pcnt.u2_conf0(),
+            Number::Unit3 => pcnt.u3_conf0(),
+            #[cfg(esp32)]
+            Number::Unit4 => pcnt.u4_conf0(),
+            #[cfg(esp32)]
+            Number::Unit5 => pcnt.u5_conf0(),
+            #[cfg(esp32)]
+            Number::Unit6 => pcnt.u6_conf0(),
+            #[cfg(esp32)]
+            Number::Unit7 => pcnt.u7_conf0(),
+        };
+        conf0.modify(|_, w| unsafe { w.filter_thres().bits(filter).filter_en().bit(filter_en) });
+        Ok(())
+    }
+
+    /// The number of this unit
+    pub fn number(&self) -> Number {
+        self.number
+    }
+
     pub fn get_channel(&self, number: channel::Number) -> super::channel::Channel {
         super::channel::Channel::new(self.number, number)
     }
 
+    /// Reset the counter to zero. A paused unit stays paused.
     pub fn clear(&self) {
         let pcnt = unsafe { &*crate::peripherals::PCNT::ptr() };
         critical_section::with(|_cs| {
@@ -296,16 +393,31 @@ impl Unit {
 
     /// Get the latest events for this unit.
     pub fn get_events(&self) -> Events {
-        let pcnt = unsafe { &*crate::peripherals::PCNT::ptr() };
-        let status = pcnt.u_status(self.number as usize).read();
+        Events::read(self.number as usize)
+    }
 
-        Events {
-            low_limit: status.l_lim().bit(),
-            high_limit: status.h_lim().bit(),
-            thresh0: status.thres0().bit(),
-            thresh1: status.thres1().bit(),
-            zero: status.zero().bit(),
-        }
+    /// Call `callback` with the decoded events whenever this unit interrupts.
+    ///
+    /// Needs [`super::event_handler`] as the PCNT interrupt handler, which
+    /// clears the interrupts of the units. Enable the events with
+    /// [`Unit::events`] and the interrupt with [`Unit::listen`].
+    pub fn set_callback(&self, callback: Option<fn(Events)>) {
+        critical_section::with(|cs| {
+            let callbacks = super::CALLBACKS.borrow(cs);
+            let mut all = callbacks.get();
+            all[self.number as usize] = callback;
+            callbacks.set(all);
+        });
+    }
+
+    /// Wait for the next event enabled with [`Unit::events`], e.g. the counter
+    /// reaching a threshold
+    ///
+    /// Needs the interrupt handler installed by
+    /// [`PCNT::new_async`](super::PCNT::new_async).
+    #[cfg(feature = "async")]
+    pub async fn wait_for_event(&self) -> Events {
+        super::asynch::EventFuture::new(self.number).await
     }
 
     /// Get the mode of the last zero crossing
@@ -405,4 +517,53 @@ impl Unit {
         let pcnt = unsafe { &*crate::peripherals::PCNT::ptr() };
         pcnt.u_cnt(self.number as usize).read().cnt().bits() as i16
     }
+
+    /// Read the counter value together with the events of the unit.
+    ///
+    /// Reaching a limit resets the counter, so a value read on its own can't
+    /// tell whether the count before the reset was already accounted for. The
+    /// snapshot is retried until no event happened while it was read, and
+    /// [`Snapshot::event_pending`] tells whether an event is still waiting to
+    /// be handled, i.e. whether `value` counts from a reset not yet handled.
+    ///
+    /// For an exact counting window, [`Unit::pause`] the unit before taking
+    /// the snapshot.
+    pub fn snapshot(&self) -> Snapshot {
+        let pcnt = unsafe { &*crate::peripherals::PCNT::ptr() };
+        let unit = self.number as usize;
+        // the raw interrupt is set by every enabled event, even when the
+        // interrupt isn't enabled
+        let event_raw = || pcnt.int_raw().read().bits() & (1 << unit) != 0;
+
+        critical_section::with(|_cs| loop {
+            let before = event_raw();
+            let value = self.get_value();
+            let events = Events::read(unit);
+            let zero_mode = self.get_zero_mode();
+            let after = event_raw();
+
+            if before == after {
+                return Snapshot {
+                    value,
+                    events,
+                    zero_mode,
+                    event_pending: after,
+                };
+            }
+        })
+    }
+}
+
+/// The state of a unit, see [`Unit::snapshot`]
+#[derive(Copy, Clone, Debug)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Snapshot {
+    /// The counter value
+    pub value: i16,
+    /// The latest events
+    pub events: Events,
+    /// The mode of the last zero crossing
+    pub zero_mode: ZeroMode,
+    /// An event happened since the interrupt of the unit was last cleared
+    pub event_pending: bool,
 }
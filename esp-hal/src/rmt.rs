@@ -61,6 +61,24 @@
 //! (on ESP32 and ESP32-S2 you cannot specify a base frequency other than 80
 //! MHz)
 //!
+//! ### Carrier modulation
+//!
+//! IR remote controls usually modulate the high pulses with a 38 kHz
+//! carrier, which the RMT adds in hardware:
+//!
+//! ```no_run
+//! let config = TxChannelConfig {
+//!     clk_divider: 80,
+//!     ..TxChannelConfig::default()
+//! }
+//! .with_carrier(80.MHz(), 38.kHz(), 33, true)
+//! .unwrap();
+//! let mut channel = rmt
+//!     .channel0
+//!     .configure(io.pins.gpio1.into_push_pull_output(), config)
+//!     .unwrap();
+//! ```
+//!
 //! ### Sending a pulse sequence
 //!
 //! ```no_run
@@ -82,6 +100,81 @@
 //! let transaction = channel.transmit(&data);
 //! channel = transaction.wait().unwrap();
 //! ```
+//!
+//! ### Repeating a pulse sequence
+//!
+//! On the ESP32-C6, ESP32-H2 and ESP32-S3 the hardware repeats a sequence a
+//! given number of times and stops by itself, e.g. for bursts of stepper
+//! motor pulses:
+//!
+//! ```no_run
+//! let transaction = channel.transmit_repeated(200, &data).unwrap();
+//! channel = transaction.wait().unwrap();
+//! ```
+//!
+//! ### Synchronized channels
+//!
+//! Except on the ESP32, the [SyncManager] in `Rmt::sync` starts several TX
+//! channels in the same clock cycle. The transmissions only begin once they
+//! are started on all channels of the group:
+//!
+//! ```no_run
+//! let mut sync = rmt.sync;
+//! sync.add(&channel0).unwrap();
+//! sync.add(&channel1).unwrap();
+//!
+//! let transaction0 = channel0.transmit(&data0);
+//! let transaction1 = channel1.transmit(&data1);
+//! channel0 = transaction0.wait().unwrap();
+//! channel1 = transaction1.wait().unwrap();
+//! ```
+//!
+//! ### Async
+//!
+//! Channels created from [Rmt::new_async] transmit and receive with
+//! `async fn`s, which wait for the channel interrupts instead of polling.
+//! Dropping one of these futures stops the transmission or reception.
+//!
+//! ```no_run
+//! let rmt = Rmt::new_async(peripherals.RMT, 80.MHz(), &clocks).unwrap();
+//! let mut channel = rmt.channel2.configure(io.pins.gpio4, rx_config).unwrap();
+//!
+//! let mut data = [PulseCode::default(); 48];
+//! channel.receive(&mut data).await.unwrap();
+//! ```
+//!
+//! ### Receiving a pulse sequence
+//!
+//! ```no_run
+//! let mut channel = rmt
+//!     .channel2
+//!     .configure(
+//!         io.pins.gpio4,
+//!         RxChannelConfig {
+//!             clk_divider: 80,
+//!             filter_threshold: 100,
+//!             idle_threshold: 10000,
+//!             memsize: 2,
+//!             ..RxChannelConfig::default()
+//!         },
+//!     )
+//!     .unwrap();
+//!
+//! let mut data = [PulseCode::default(); 96];
+//! let transaction = channel.receive(&mut data).unwrap();
+//! channel = transaction.wait().unwrap();
+//!
+//! // the received sequence ends with the first zero-length pulse
+//! ```
+//!
+//! ### Sequences longer than the channel RAM
+//!
+//! Blocking transmissions of sequences which don't fit into the RAM of a
+//! channel (e.g. for long addressable LED strips) refill one half of the RAM
+//! while the other half is being sent. Except on the ESP32 and ESP32-S2,
+//! blocking receives into a buffer larger than the channel RAM drain the RAM
+//! the same way. In both cases the CPU has to keep up with the pulse rate, so
+//! `wait` should be called right after starting the transaction.
 
 use core::marker::PhantomData;
 
@@ -97,6 +190,9 @@ use crate::{
     system::PeripheralClockControl,
 };
 
+pub mod ir;
+pub mod one_wire;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum Error {
@@ -182,6 +278,44 @@ pub struct TxChannelConfig {
     pub carrier_level: bool,
 }
 
+impl TxChannelConfig {
+    /// Modulate the output with a carrier of `frequency` and a duty cycle of
+    /// `duty_percent`, e.g. 38 kHz at 33 % for IR remote controls.
+    ///
+    /// `source_frequency` is the frequency the [Rmt] was created with. The
+    /// carrier is added to the pulses of `level`.
+    pub fn with_carrier(
+        mut self,
+        source_frequency: HertzU32,
+        frequency: HertzU32,
+        duty_percent: u8,
+        level: bool,
+    ) -> Result<Self, Error> {
+        if duty_percent > 100 {
+            return Err(Error::InvalidArgument);
+        }
+
+        let period = source_frequency.raw() / frequency.raw();
+        let high = period * duty_percent as u32 / 100;
+        let low = period - high;
+        if high == 0 || low == 0 || high > u16::MAX as u32 || low > u16::MAX as u32 {
+            return Err(Error::UnreachableTargetFrequency);
+        }
+
+        // the ESP32 and ESP32-S2 count the phases in ticks, the later chips in
+        // ticks minus one
+        #[cfg(not(any(esp32, esp32s2)))]
+        let (high, low) = (high - 1, low - 1);
+
+        self.carrier_modulation = true;
+        self.carrier_high = high as u16;
+        self.carrier_low = low as u16;
+        self.carrier_level = level;
+
+        Ok(self)
+    }
+}
+
 /// Channel configuration for RX channels
 #[derive(Debug, Copy, Clone, Default)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -196,12 +330,25 @@ pub struct RxChannelConfig {
     pub carrier_low: u16,
     /// Level of the carrier
     pub carrier_level: bool,
-    /// Filter threshold in ticks
+    /// Filter threshold in ticks of the APB clock, pulses shorter than this
+    /// are ignored. `0` disables the filter
     pub filter_threshold: u8,
-    /// Idle threshold in ticks
+    /// Idle threshold in ticks, reception ends when the input doesn't change
+    /// for this long
     pub idle_threshold: u16,
+    /// Number of RAM blocks used by the channel, `0` is treated as `1`
+    ///
+    /// Using more than one block allows capturing longer pulse trains. The
+    /// blocks of the following channels are used, these channels must not be
+    /// used at the same time.
+    pub memsize: u8,
 }
 
+#[cfg(any(esp32, esp32s3))]
+const NUM_CHANNELS: usize = 8;
+#[cfg(not(any(esp32, esp32s3)))]
+const NUM_CHANNELS: usize = 4;
+
 pub use impl_for_chip::Rmt;
 
 #[cfg(feature = "async")]
@@ -370,10 +517,6 @@ where
     where
         Self: Sized,
     {
-        if config.filter_threshold > 0b111_1111 {
-            return Err(Error::InvalidArgument);
-        }
-
         #[cfg(any(esp32, esp32s2))]
         if config.idle_threshold > 0b111_1111_1111_1111 {
             return Err(Error::InvalidArgument);
@@ -384,9 +527,15 @@ where
             return Err(Error::InvalidArgument);
         }
 
+        let memsize = config.memsize.max(1);
+        if T::CHANNEL as usize + memsize as usize > NUM_CHANNELS {
+            return Err(Error::InvalidArgument);
+        }
+
         crate::into_ref!(pin);
         pin.set_to_input()
             .connect_input_to_peripheral(T::input_signal());
+        T::set_memsize(memsize);
         T::set_divider(config.clk_divider);
         T::set_carrier(
             config.carrier_modulation,
@@ -416,10 +565,6 @@ where
     where
         Self: Sized,
     {
-        if config.filter_threshold > 0b111_1111 {
-            return Err(Error::InvalidArgument);
-        }
-
         #[cfg(any(esp32, esp32s2))]
         if config.idle_threshold > 0b111_1111_1111_1111 {
             return Err(Error::InvalidArgument);
@@ -430,9 +575,15 @@ where
             return Err(Error::InvalidArgument);
         }
 
+        let memsize = config.memsize.max(1);
+        if T::CHANNEL as usize + memsize as usize > NUM_CHANNELS {
+            return Err(Error::InvalidArgument);
+        }
+
         crate::into_ref!(pin);
         pin.set_to_input()
             .connect_input_to_peripheral(T::input_signal());
+        T::set_memsize(memsize);
         T::set_divider(config.clk_divider);
         T::set_carrier(
             config.carrier_modulation,
@@ -469,8 +620,16 @@ where
             }
 
             if self.index < self.data.len() {
-                // wait for TX-THR
+                // wait for TX-THR, the sequence might also end early
                 loop {
+                    if <C as private::TxChannelInternal<crate::Blocking>>::is_error() {
+                        return Err((Error::TransmissionError, self.channel));
+                    }
+
+                    if <C as private::TxChannelInternal<crate::Blocking>>::is_done() {
+                        return Ok(self.channel);
+                    }
+
                     if <C as private::TxChannelInternal<crate::Blocking>>::is_threshold_set() {
                         break;
                     }
@@ -643,6 +802,7 @@ mod impl_for_chip {
         pub channel1: ChannelCreator<M, 1>,
         pub channel2: ChannelCreator<M, 2>,
         pub channel3: ChannelCreator<M, 3>,
+        pub sync: super::SyncManager,
         phantom: PhantomData<M>,
     }
 
@@ -667,6 +827,7 @@ mod impl_for_chip {
                 channel3: ChannelCreator {
                     phantom: PhantomData,
                 },
+                sync: super::SyncManager::new(),
                 phantom: PhantomData,
             }
         }
@@ -815,6 +976,7 @@ mod impl_for_chip {
         pub channel1: ChannelCreator<M, 1>,
         pub channel2: ChannelCreator<M, 2>,
         pub channel3: ChannelCreator<M, 3>,
+        pub sync: super::SyncManager,
         phantom: PhantomData<M>,
     }
 
@@ -839,6 +1001,7 @@ mod impl_for_chip {
                 channel3: ChannelCreator {
                     phantom: PhantomData,
                 },
+                sync: super::SyncManager::new(),
                 phantom: PhantomData,
             }
         }
@@ -893,6 +1056,7 @@ mod impl_for_chip {
         pub channel5: ChannelCreator<M, 5>,
         pub channel6: ChannelCreator<M, 6>,
         pub channel7: ChannelCreator<M, 7>,
+        pub sync: super::SyncManager,
         phantom: PhantomData<M>,
     }
 
@@ -929,6 +1093,7 @@ mod impl_for_chip {
                 channel7: ChannelCreator {
                     phantom: PhantomData,
                 },
+                sync: super::SyncManager::new(),
                 phantom: PhantomData,
             }
         }
@@ -972,11 +1137,79 @@ where
     phantom: PhantomData<M>,
 }
 
+/// Number of channels which can transmit
+#[cfg(not(esp32))]
+const NUM_TX_CHANNELS: u8 = if cfg!(any(esp32s2, esp32s3)) { 4 } else { 2 };
+
+/// Starts a group of TX channels in the same clock cycle.
+///
+/// Once channels are added, starting a transmission on one of them is delayed
+/// until a transmission is started on all of them, e.g. for multi-phase
+/// waveforms or LED strips split into segments. All channels need to use the
+/// same clock divider.
+///
+/// There is only one group, its manager is the `sync` field of [Rmt]. Dropping
+/// the manager stops synchronizing the channels.
+#[cfg(not(esp32))]
+pub struct SyncManager {
+    mask: u8,
+}
+
+#[cfg(not(esp32))]
+impl SyncManager {
+    fn new() -> Self {
+        Self { mask: 0 }
+    }
+
+    /// Add a TX channel to the group
+    pub fn add<M, const CHANNEL: u8>(&mut self, _channel: &Channel<M, CHANNEL>) -> Result<(), Error>
+    where
+        M: crate::Mode,
+    {
+        if CHANNEL >= NUM_TX_CHANNELS {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.mask |= 1 << CHANNEL;
+        Self::write_group(self.mask);
+        self.reset();
+
+        Ok(())
+    }
+
+    /// Reset the clock dividers of the channels in the group, so they run in
+    /// phase. Call this before starting the next synchronized transmission.
+    pub fn reset(&mut self) {
+        let rmt = unsafe { &*crate::peripherals::RMT::PTR };
+        rmt.ref_cnt_rst()
+            .write(|w| unsafe { w.bits(self.mask as u32) });
+    }
+
+    fn write_group(mask: u8) {
+        let rmt = unsafe { &*crate::peripherals::RMT::PTR };
+        let enable = if mask != 0 { 1 << NUM_TX_CHANNELS } else { 0 };
+        rmt.tx_sim()
+            .write(|w| unsafe { w.bits(mask as u32 | enable) });
+    }
+}
+
+#[cfg(not(esp32))]
+impl Drop for SyncManager {
+    fn drop(&mut self) {
+        if self.mask != 0 {
+            Self::write_group(0);
+        }
+    }
+}
+
 pub trait TxChannel: private::TxChannelInternal<crate::Blocking> {
     /// Start transmitting the given pulse code sequence.
     /// This returns a [`SingleShotTxTransaction`] which can be used to wait for
     /// the transaction to complete and get back the channel for further
     /// use.
+    /// Sequences longer than the RMT RAM of the channel are refilled while
+    /// waiting, so [`SingleShotTxTransaction::wait`] needs to be called right
+    /// away.
     fn transmit<T: Into<u32> + Copy>(self, data: &[T]) -> SingleShotTxTransaction<Self, T>
     where
         Self: Sized,
@@ -1018,9 +1251,83 @@ pub trait TxChannel: private::TxChannelInternal<crate::Blocking> {
             return Err(Error::Overflow);
         }
 
+        #[cfg(any(esp32c6, esp32h2, esp32s3))]
+        Self::set_loop_auto_stop(false);
+
         let _index = Self::send_raw(data, true, loopcount);
         Ok(ContinuousTxTransaction { channel: self })
     }
+
+    /// Transmit the given pulse code sequence `count` times and stop
+    /// automatically.
+    /// This returns a [`RepeatedTxTransaction`] which can be used to wait for
+    /// the transmission to complete and get back the channel for further use.
+    /// The length of sequence cannot exceed the size of the allocated RMT RAM
+    /// and `count` cannot exceed [`MAX_TX_LOOPCOUNT`].
+    #[cfg(any(esp32c6, esp32h2, esp32s3))]
+    fn transmit_repeated<T: Into<u32> + Copy>(
+        self,
+        count: u16,
+        data: &[T],
+    ) -> Result<RepeatedTxTransaction<Self>, Error>
+    where
+        Self: Sized,
+    {
+        if data.len() > constants::RMT_CHANNEL_RAM_SIZE {
+            return Err(Error::Overflow);
+        }
+
+        if count == 0 || count > MAX_TX_LOOPCOUNT {
+            return Err(Error::InvalidArgument);
+        }
+
+        Self::set_loop_auto_stop(true);
+        let _index = Self::send_raw(data, true, count);
+        Ok(RepeatedTxTransaction { channel: self })
+    }
+}
+
+/// The largest loop count supported by [`TxChannel::transmit_repeated`]
+#[cfg(any(esp32c6, esp32h2, esp32s3))]
+pub const MAX_TX_LOOPCOUNT: u16 = 1023;
+
+/// An in-progress transaction which repeats a pulse code sequence a given
+/// number of times.
+#[cfg(any(esp32c6, esp32h2, esp32s3))]
+pub struct RepeatedTxTransaction<C>
+where
+    C: TxChannel,
+{
+    channel: C,
+}
+
+#[cfg(any(esp32c6, esp32h2, esp32s3))]
+impl<C> RepeatedTxTransaction<C>
+where
+    C: TxChannel,
+{
+    /// Wait for all repetitions to be transmitted.
+    pub fn wait(self) -> Result<C, (Error, C)> {
+        loop {
+            if <C as private::TxChannelInternal<crate::Blocking>>::is_error() {
+                return Err((Error::TransmissionError, self.channel));
+            }
+
+            if <C as private::TxChannelInternal<crate::Blocking>>::is_loopcount_interrupt_set() {
+                break;
+            }
+        }
+
+        <C as private::TxChannelInternal<crate::Blocking>>::set_continuous(false);
+        <C as private::TxChannelInternal<crate::Blocking>>::update();
+
+        Ok(self.channel)
+    }
+
+    /// Whether all repetitions are transmitted
+    pub fn is_done(&self) -> bool {
+        <C as private::TxChannelInternal<crate::Blocking>>::is_loopcount_interrupt_set()
+    }
 }
 
 /// RX transaction instance
@@ -1038,11 +1345,43 @@ where
 {
     /// Wait for the transaction to complete
     pub fn wait(self) -> Result<C, (Error, C)> {
+        let capacity = constants::RMT_CHANNEL_RAM_SIZE
+            * <C as private::RxChannelInternal<crate::Blocking>>::memsize() as usize;
+        let half = capacity / 2;
+        let ptr = (constants::RMT_RAM_START
+            + C::CHANNEL as usize * constants::RMT_CHANNEL_RAM_SIZE * 4)
+            as *mut u32;
+        #[allow(unused_mut)]
+        let mut index = 0;
+
         loop {
             if <C as private::RxChannelInternal<crate::Blocking>>::is_error() {
                 return Err((Error::TransmissionError, self.channel));
             }
 
+            // drain the half of the RX RAM the hardware just filled
+            #[cfg(not(any(esp32, esp32s2)))]
+            if self.data.len() > capacity
+                && <C as private::RxChannelInternal<crate::Blocking>>::is_threshold_set()
+            {
+                <C as private::RxChannelInternal<crate::Blocking>>::reset_threshold_set();
+
+                if index + half > self.data.len() {
+                    <C as private::RxChannelInternal<crate::Blocking>>::stop();
+                    <C as private::RxChannelInternal<crate::Blocking>>::clear_interrupts();
+                    <C as private::RxChannelInternal<crate::Blocking>>::update();
+                    return Err((Error::Overflow, self.channel));
+                }
+
+                let ram_index = ((index / half) % 2) * half;
+                for (idx, entry) in self.data[index..].iter_mut().take(half).enumerate() {
+                    *entry = unsafe { ptr.add(ram_index + idx).read_volatile().into() };
+                }
+
+                index += half;
+                continue;
+            }
+
             if <C as private::RxChannelInternal<crate::Blocking>>::is_done() {
                 break;
             }
@@ -1052,12 +1391,13 @@ where
         <C as private::RxChannelInternal<crate::Blocking>>::clear_interrupts();
         <C as private::RxChannelInternal<crate::Blocking>>::update();
 
-        let ptr = (constants::RMT_RAM_START
-            + C::CHANNEL as usize * constants::RMT_CHANNEL_RAM_SIZE * 4)
-            as *mut u32;
-        let len = self.data.len();
-        for (idx, entry) in self.data.iter_mut().take(len).enumerate() {
-            *entry = unsafe { ptr.add(idx).read_volatile().into() };
+        let ram_index = ((index / half) % 2) * half;
+        for (idx, entry) in self.data[index..]
+            .iter_mut()
+            .take(capacity - ram_index)
+            .enumerate()
+        {
+            *entry = unsafe { ptr.add(ram_index + idx).read_volatile().into() };
         }
 
         Ok(self.channel)
@@ -1068,16 +1408,26 @@ pub trait RxChannel: private::RxChannelInternal<crate::Blocking> {
     /// Start receiving pulse codes into the given buffer.
     /// This returns a [RxTransaction] which can be used to wait for receive to
     /// complete and get back the channel for further use.
-    /// The length of the received data cannot exceed the allocated RMT RAM.
+    /// The length of the received data cannot exceed the allocated RMT RAM on
+    /// the ESP32 and ESP32-S2. On other chips the RAM is drained into a
+    /// longer buffer while waiting, so [`RxTransaction::wait`] needs to be
+    /// called right away.
     fn receive<T: From<u32> + Copy>(self, data: &mut [T]) -> Result<RxTransaction<Self, T>, Error>
     where
         Self: Sized,
     {
-        if data.len() > constants::RMT_CHANNEL_RAM_SIZE {
+        let capacity = constants::RMT_CHANNEL_RAM_SIZE * Self::memsize() as usize;
+        let wrap = data.len() > capacity;
+
+        #[cfg(any(esp32, esp32s2))]
+        if wrap {
             return Err(Error::InvalidArgument);
         }
 
-        Self::start_receive_raw();
+        #[cfg(not(any(esp32, esp32s2)))]
+        Self::set_threshold((capacity / 2) as u16);
+
+        Self::start_receive_raw(wrap);
 
         Ok(RxTransaction {
             channel: self,
@@ -1098,11 +1448,6 @@ pub mod asynch {
 
     use super::{private::Event, *};
 
-    #[cfg(any(esp32, esp32s3))]
-    const NUM_CHANNELS: usize = 8;
-    #[cfg(not(any(esp32, esp32s3)))]
-    const NUM_CHANNELS: usize = 4;
-
     const INIT: AtomicWaker = AtomicWaker::new();
     static WAKER: [AtomicWaker; NUM_CHANNELS] = [INIT; NUM_CHANNELS];
 
@@ -1133,7 +1478,7 @@ pub mod asynch {
         fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
             WAKER[T::CHANNEL as usize].register(ctx.waker());
 
-            if T::is_error() || T::is_done() {
+            if T::is_error() || T::is_done() || T::is_loopcount_interrupt_set() {
                 Poll::Ready(())
             } else {
                 Poll::Pending
@@ -1141,6 +1486,22 @@ pub mod asynch {
         }
     }
 
+    impl<T> Drop for RmtTxFuture<T>
+    where
+        T: TxChannelAsync,
+    {
+        fn drop(&mut self) {
+            // the future was cancelled, don't leave the channel transmitting
+            if !(T::is_error() || T::is_done() || T::is_loopcount_interrupt_set()) {
+                T::unlisten_interrupt(Event::End);
+                T::unlisten_interrupt(Event::Error);
+                T::unlisten_interrupt(Event::LoopCount);
+                T::set_continuous(false);
+                T::stop();
+            }
+        }
+    }
+
     pub trait TxChannelAsync: private::TxChannelInternal<crate::Async> {
         /// Start transmitting the given pulse code sequence.
         /// The length of sequence cannot exceed the size of the allocated RMT
@@ -1166,6 +1527,45 @@ pub mod asynch {
                 Ok(())
             }
         }
+
+        /// Transmit the given pulse code sequence `count` times and stop
+        /// automatically.
+        /// The length of sequence cannot exceed the size of the allocated RMT
+        /// RAM and `count` cannot exceed [`MAX_TX_LOOPCOUNT`].
+        #[cfg(any(esp32c6, esp32h2, esp32s3))]
+        async fn transmit_repeated<'a, T: Into<u32> + Copy>(
+            &mut self,
+            count: u16,
+            data: &'a [T],
+        ) -> Result<(), Error>
+        where
+            Self: Sized,
+        {
+            if data.len() > constants::RMT_CHANNEL_RAM_SIZE {
+                return Err(Error::InvalidArgument);
+            }
+
+            if count == 0 || count > MAX_TX_LOOPCOUNT {
+                return Err(Error::InvalidArgument);
+            }
+
+            Self::clear_interrupts();
+            Self::listen_interrupt(super::private::Event::LoopCount);
+            Self::listen_interrupt(super::private::Event::Error);
+            Self::set_loop_auto_stop(true);
+            Self::send_raw(data, true, count);
+
+            RmtTxFuture::new(self).await;
+
+            Self::set_continuous(false);
+            Self::update();
+
+            if Self::is_error() {
+                Err(Error::TransmissionError)
+            } else {
+                Ok(())
+            }
+        }
     }
 
     pub(crate) struct RmtRxFuture<T>
@@ -1202,6 +1602,21 @@ pub mod asynch {
         }
     }
 
+    impl<T> Drop for RmtRxFuture<T>
+    where
+        T: RxChannelAsync,
+    {
+        fn drop(&mut self) {
+            // the future was cancelled, don't leave the channel receiving
+            if !(T::is_error() || T::is_done()) {
+                T::unlisten_interrupt(Event::End);
+                T::unlisten_interrupt(Event::Error);
+                T::stop();
+                T::update();
+            }
+        }
+    }
+
     pub trait RxChannelAsync: private::RxChannelInternal<crate::Async> {
         /// Start receiving a pulse code sequence.
         /// The length of sequence cannot exceed the size of the allocated RMT
@@ -1210,14 +1625,14 @@ pub mod asynch {
         where
             Self: Sized,
         {
-            if data.len() > constants::RMT_CHANNEL_RAM_SIZE {
+            if data.len() > constants::RMT_CHANNEL_RAM_SIZE * Self::memsize() as usize {
                 return Err(Error::InvalidArgument);
             }
 
             Self::clear_interrupts();
             Self::listen_interrupt(super::private::Event::End);
             Self::listen_interrupt(super::private::Event::Error);
-            Self::start_receive_raw();
+            Self::start_receive_raw(false);
 
             RmtRxFuture::new(self).await;
 
@@ -1251,39 +1666,47 @@ pub mod asynch {
                 0 => {
                     super::Channel::<crate::Async, 0>::unlisten_interrupt(Event::End);
                     super::Channel::<crate::Async, 0>::unlisten_interrupt(Event::Error);
+                    super::Channel::<crate::Async, 0>::unlisten_interrupt(Event::LoopCount);
                 }
                 1 => {
                     super::Channel::<crate::Async, 1>::unlisten_interrupt(Event::End);
                     super::Channel::<crate::Async, 1>::unlisten_interrupt(Event::Error);
+                    super::Channel::<crate::Async, 1>::unlisten_interrupt(Event::LoopCount);
                 }
                 2 => {
                     super::Channel::<crate::Async, 2>::unlisten_interrupt(Event::End);
                     super::Channel::<crate::Async, 2>::unlisten_interrupt(Event::Error);
+                    super::Channel::<crate::Async, 2>::unlisten_interrupt(Event::LoopCount);
                 }
                 3 => {
                     super::Channel::<crate::Async, 3>::unlisten_interrupt(Event::End);
                     super::Channel::<crate::Async, 3>::unlisten_interrupt(Event::Error);
+                    super::Channel::<crate::Async, 3>::unlisten_interrupt(Event::LoopCount);
                 }
 
                 #[cfg(any(esp32, esp32s3))]
                 4 => {
                     super::Channel::<crate::Async, 4>::unlisten_interrupt(Event::End);
                     super::Channel::<crate::Async, 4>::unlisten_interrupt(Event::Error);
+                    super::Channel::<crate::Async, 4>::unlisten_interrupt(Event::LoopCount);
                 }
                 #[cfg(any(esp32, esp32s3))]
                 5 => {
                     super::Channel::<crate::Async, 5>::unlisten_interrupt(Event::End);
                     super::Channel::<crate::Async, 5>::unlisten_interrupt(Event::Error);
+                    super::Channel::<crate::Async, 5>::unlisten_interrupt(Event::LoopCount);
                 }
                 #[cfg(any(esp32, esp32s3))]
                 6 => {
                     super::Channel::<crate::Async, 6>::unlisten_interrupt(Event::End);
                     super::Channel::<crate::Async, 6>::unlisten_interrupt(Event::Error);
+                    super::Channel::<crate::Async, 6>::unlisten_interrupt(Event::LoopCount);
                 }
                 #[cfg(any(esp32, esp32s3))]
                 7 => {
                     super::Channel::<crate::Async, 7>::unlisten_interrupt(Event::End);
                     super::Channel::<crate::Async, 7>::unlisten_interrupt(Event::Error);
+                    super::Channel::<crate::Async, 7>::unlisten_interrupt(Event::LoopCount);
                 }
 
                 _ => unreachable!(),
@@ -1430,6 +1853,7 @@ mod private {
         Error,
         Threshold,
         End,
+        LoopCount,
     }
 
     pub trait CreateInstance<'d> {
@@ -1452,6 +1876,9 @@ mod private {
 
         fn set_generate_repeat_interrupt(repeats: u16);
 
+        #[cfg(any(esp32c6, esp32h2, esp32s3))]
+        fn set_loop_auto_stop(enable: bool);
+
         fn clear_interrupts();
 
         fn set_continuous(continuous: bool);
@@ -1539,16 +1966,26 @@ mod private {
 
         fn set_memsize(memsize: u8);
 
+        fn memsize() -> u8;
+
         fn start_rx();
 
         fn is_done() -> bool;
 
         fn is_error() -> bool;
 
-        fn start_receive_raw() {
+        #[cfg(not(any(esp32, esp32s2)))]
+        fn is_threshold_set() -> bool;
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        fn reset_threshold_set();
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        fn set_threshold(threshold: u16);
+
+        fn start_receive_raw(wrap: bool) {
             Self::clear_interrupts();
-            Self::set_wrap_mode(false);
-            Self::set_memsize(1);
+            Self::set_wrap_mode(wrap);
             Self::start_rx();
             Self::update();
         }
@@ -1612,9 +2049,9 @@ mod chip_specific {
         let rmt = unsafe { &*crate::peripherals::RMT::PTR };
         let st = rmt.int_st().read();
 
-        if st.ch0_tx_end().bit() || st.ch0_tx_err().bit() {
+        if st.ch0_tx_end().bit() || st.ch0_tx_err().bit() || st.ch0_tx_loop().bit() {
             Some(0)
-        } else if st.ch1_tx_end().bit() || st.ch1_tx_err().bit() {
+        } else if st.ch1_tx_end().bit() || st.ch1_tx_err().bit() || st.ch1_tx_loop().bit() {
             Some(1)
         } else if st.ch2_rx_end().bit() || st.ch2_rx_err().bit() {
             Some(2)
@@ -1631,13 +2068,13 @@ mod chip_specific {
         let rmt = unsafe { &*crate::peripherals::RMT::PTR };
         let st = rmt.int_st().read();
 
-        if st.ch0_tx_end().bit() || st.ch0_tx_err().bit() {
+        if st.ch0_tx_end().bit() || st.ch0_tx_err().bit() || st.ch0_tx_loop().bit() {
             Some(0)
-        } else if st.ch1_tx_end().bit() || st.ch1_tx_err().bit() {
+        } else if st.ch1_tx_end().bit() || st.ch1_tx_err().bit() || st.ch1_tx_loop().bit() {
             Some(1)
-        } else if st.ch2_tx_end().bit() || st.ch2_tx_err().bit() {
+        } else if st.ch2_tx_end().bit() || st.ch2_tx_err().bit() || st.ch2_tx_loop().bit() {
             Some(2)
-        } else if st.ch3_tx_end().bit() || st.ch3_tx_err().bit() {
+        } else if st.ch3_tx_end().bit() || st.ch3_tx_err().bit() || st.ch3_tx_loop().bit() {
             Some(3)
         } else if st.ch4_rx_end().bit() || st.ch4_rx_err().bit() {
             Some(4)
@@ -1680,7 +2117,7 @@ mod chip_specific {
 
                     fn set_generate_repeat_interrupt(repeats: u16) {
                         let rmt = unsafe { &*crate::peripherals::RMT::PTR };
-                        if repeats > 1 {
+                        if repeats > 0 {
                             rmt.ch_tx_lim($ch_num).modify(|_, w| {
                                 w.loop_count_reset()
                                     .set_bit()
@@ -1703,6 +2140,12 @@ mod chip_specific {
                         rmt.ch_tx_lim($ch_num).modify(|_, w| w.loop_count_reset().clear_bit());
                     }
 
+                    #[cfg(any(esp32c6, esp32h2, esp32s3))]
+                    fn set_loop_auto_stop(enable: bool) {
+                        let rmt = unsafe { &*crate::peripherals::RMT::PTR };
+                        rmt.ch_tx_lim($ch_num).modify(|_, w| w.loop_stop_en().bit(enable));
+                    }
+
                     fn clear_interrupts() {
                         let rmt = unsafe { &*crate::peripherals::RMT::PTR };
 
@@ -1823,6 +2266,9 @@ mod chip_specific {
                             $crate::rmt::private::Event::Threshold => {
                                 rmt.int_ena().modify(|_,w| w.[< ch $ch_num _tx_thr_event >]().set_bit());
                             }
+                            $crate::rmt::private::Event::LoopCount => {
+                                rmt.int_ena().modify(|_,w| w.[< ch $ch_num _tx_loop >]().set_bit());
+                            }
                         }
                     }
 
@@ -1838,6 +2284,9 @@ mod chip_specific {
                             $crate::rmt::private::Event::Threshold => {
                                 rmt.int_ena().modify(|_,w| w.[< ch $ch_num _tx_thr_event >]().clear_bit());
                             }
+                            $crate::rmt::private::Event::LoopCount => {
+                                rmt.int_ena().modify(|_,w| w.[< ch $ch_num _tx_loop >]().clear_bit());
+                            }
                         }
                     }
                 }
@@ -1908,6 +2357,11 @@ mod chip_specific {
                         rmt.[< ch $ch_num _rx_conf0 >]().modify(|_, w| w.mem_size().variant(memsize));
                     }
 
+                    fn memsize() -> u8 {
+                        let rmt = unsafe { &*crate::peripherals::RMT::PTR };
+                        rmt.[< ch $ch_num _rx_conf0 >]().read().mem_size().bits()
+                    }
+
                     fn start_rx() {
                         let rmt = unsafe { &*crate::peripherals::RMT::PTR };
                         rmt.[< ch $ch_num _rx_conf1 >]().modify(|_, w| {
@@ -1932,6 +2386,22 @@ mod chip_specific {
                         rmt.int_raw().read().[< ch $ch_num _rx_err >]().bit()
                     }
 
+                    fn is_threshold_set() -> bool {
+                        let rmt = unsafe { &*crate::peripherals::RMT::PTR };
+                        rmt.int_raw().read().[< ch $ch_num _rx_thr_event >]().bit()
+                    }
+
+                    fn reset_threshold_set() {
+                        let rmt = unsafe { &*crate::peripherals::RMT::PTR };
+                        rmt.int_clr()
+                            .write(|w| w.[< ch $ch_num _rx_thr_event >]().set_bit());
+                    }
+
+                    fn set_threshold(threshold: u16) {
+                        let rmt = unsafe { &*crate::peripherals::RMT::PTR };
+                        rmt.ch_rx_lim($ch_index).modify(|_, w| w.rx_lim().variant(threshold));
+                    }
+
                     fn stop() {
                         let rmt = unsafe { &*crate::peripherals::RMT::PTR };
                         rmt.[< ch $ch_num _rx_conf1 >]().modify(|_, w| w.rx_en().clear_bit());
@@ -1966,6 +2436,7 @@ mod chip_specific {
                             $crate::rmt::private::Event::Threshold => {
                                 rmt.int_ena().modify(|_,w| w.[< ch $ch_num _rx_thr_event >]().set_bit());
                             }
+                            $crate::rmt::private::Event::LoopCount => {}
                         }
                     }
 
@@ -1981,6 +2452,7 @@ mod chip_specific {
                             $crate::rmt::private::Event::Threshold => {
                                 rmt.int_ena().modify(|_,w| w.[< ch $ch_num _rx_thr_event >]().clear_bit());
                             }
+                            $crate::rmt::private::Event::LoopCount => {}
                         }
                     }
                 }
@@ -2212,6 +2684,7 @@ mod chip_specific {
                             $crate::rmt::private::Event::Threshold => {
                                 rmt.int_ena().modify(|_,w| w.[< ch $ch_num _tx_thr_event >]().set_bit());
                             }
+                            $crate::rmt::private::Event::LoopCount => {}
                         }
                     }
 
@@ -2227,6 +2700,7 @@ mod chip_specific {
                             $crate::rmt::private::Event::Threshold => {
                                 rmt.int_ena().modify(|_,w| w.[< ch $ch_num _tx_thr_event >]().clear_bit());
                             }
+                            $crate::rmt::private::Event::LoopCount => {}
                         }
                     }
                 }
@@ -2304,6 +2778,12 @@ mod chip_specific {
                         rmt.[< ch $ch_num conf0 >]().modify(|_, w| w.mem_size().variant(memsize));
                     }
 
+                    fn memsize() -> u8 {
+                        let rmt = unsafe { &*crate::peripherals::RMT::PTR };
+
+                        rmt.[< ch $ch_num conf0 >]().read().mem_size().bits()
+                    }
+
                     fn start_rx() {
                         let rmt = unsafe { &*crate::peripherals::RMT::PTR };
 
@@ -2362,6 +2842,7 @@ mod chip_specific {
                             $crate::rmt::private::Event::Threshold => {
                                 rmt.int_ena().modify(|_,w| w.[< ch $ch_num _tx_thr_event >]().set_bit());
                             }
+                            $crate::rmt::private::Event::LoopCount => {}
                         }
                     }
 
@@ -2377,6 +2858,7 @@ mod chip_specific {
                             $crate::rmt::private::Event::Threshold => {
                                 rmt.int_ena().modify(|_,w| w.[< ch $ch_num _tx_thr_event >]().clear_bit());
                             }
+                            $crate::rmt::private::Event::LoopCount => {}
                         }
                     }
                 }
@@ -0,0 +1,401 @@
+//! # Infrared remote control protocols
+//!
+//! ## Overview
+//! Encoders and decoders converting between the frames of common infrared
+//! remote control protocols and RMT pulse codes:
+//!
+//! * **NEC**: pulse distance encoding of an 8-bit address (or a 16-bit extended
+//!   address) and an 8-bit command, plus the repeat code sent while a key is
+//!   held.
+//! * **RC5**: bi-phase (Manchester) encoding of a 5-bit address, a 7-bit
+//!   command (the extended RC5X range included) and a toggle bit.
+//!
+//! The encoders emit marks (carrier bursts) as high levels, so the TX channel
+//! should be configured with a 38kHz (NEC) or 36kHz (RC5) carrier modulating
+//! the high level and an idle output level of low.
+//!
+//! IR receiver modules output the demodulated signal and are usually active
+//! low. The decoders don't depend on the polarity: the first received level
+//! is taken as the mark. Durations are accepted within a tolerance of the
+//! nominal value given in [Config].
+//!
+//! ## Example
+//! ```no_run
+//! let config = ir::Config::default(); // 1 tick per microsecond
+//!
+//! let frame = ir::NecFrame::Command(ir::NecCommand::new(0x04, 0x08));
+//! let mut data = [PulseCode::default(); ir::NEC_MAX_LEN];
+//! let len = ir::encode_nec(&config, frame, &mut data).unwrap();
+//! tx_channel = tx_channel.transmit(&data[..len]).wait().unwrap();
+//!
+//! let mut data = [PulseCode::default(); 48];
+//! rx_channel = rx_channel.receive(&mut data).unwrap().wait().unwrap();
+//! match ir::decode_nec(&config, &data) {
+//!     Ok(ir::NecFrame::Command(command)) => println!("{:?}", command),
+//!     Ok(ir::NecFrame::Repeat) => println!("repeat"),
+//!     Err(err) => println!("{:?}", err),
+//! }
+//! ```
+
+use super::{Error, PulseCode};
+
+/// Number of pulse codes needed for the largest NEC frame
+pub const NEC_MAX_LEN: usize = 34;
+
+/// Number of pulse codes needed for the largest RC5 frame
+pub const RC5_MAX_LEN: usize = 14;
+
+const NEC_LEADING_MARK_NS: u32 = 9_000_000;
+const NEC_LEADING_SPACE_NS: u32 = 4_500_000;
+const NEC_REPEAT_SPACE_NS: u32 = 2_250_000;
+const NEC_MARK_NS: u32 = 562_500;
+const NEC_ZERO_SPACE_NS: u32 = 562_500;
+const NEC_ONE_SPACE_NS: u32 = 1_687_500;
+
+const RC5_HALF_BIT_NS: u32 = 889_000;
+const RC5_HALF_BITS: usize = 28;
+
+/// Timing used to encode and decode frames
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Config {
+    /// Number of RMT ticks per microsecond, i.e. the frequency of the channel
+    /// clock after the divider in MHz
+    pub ticks_per_us: u16,
+    /// Accepted deviation from the nominal durations when decoding, in
+    /// percent
+    pub tolerance_percent: u8,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            ticks_per_us: 1,
+            tolerance_percent: 25,
+        }
+    }
+}
+
+impl Config {
+    fn ticks(&self, ns: u32) -> Result<u16, Error> {
+        let ticks = (ns as u64 * self.ticks_per_us as u64 + 500) / 1000;
+        if ticks == 0 || ticks > 0x7fff {
+            return Err(Error::Overflow);
+        }
+
+        Ok(ticks as u16)
+    }
+
+    fn matches(&self, ticks: u16, ns: u32) -> bool {
+        let expected = ns as u64 * self.ticks_per_us as u64 / 1000;
+        let tolerance = expected * self.tolerance_percent as u64 / 100;
+
+        (ticks as u64).abs_diff(expected) <= tolerance
+    }
+}
+
+/// Reasons for a sequence of pulse codes not to decode into a frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum DecodeError {
+    /// The sequence doesn't start with the header of the protocol
+    InvalidHeader,
+    /// A duration doesn't match the protocol within the tolerance
+    InvalidTiming,
+    /// The sequence ends early or is too long
+    InvalidLength,
+    /// The command doesn't match its inverted copy (NEC)
+    InvalidChecksum,
+}
+
+/// NEC address and command
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct NecCommand {
+    /// The 16 address bits as sent. For standard frames the high byte is the
+    /// inverted low byte.
+    pub address: u16,
+    /// The command
+    pub command: u8,
+}
+
+impl NecCommand {
+    /// A standard frame with an 8-bit address
+    pub fn new(address: u8, command: u8) -> Self {
+        Self {
+            address: u16::from_le_bytes([address, !address]),
+            command,
+        }
+    }
+
+    /// A frame with a 16-bit extended address
+    pub fn new_extended(address: u16, command: u8) -> Self {
+        Self { address, command }
+    }
+
+    /// The 8-bit address of a standard frame, `None` for an extended address
+    pub fn standard_address(&self) -> Option<u8> {
+        let [address, inverted] = self.address.to_le_bytes();
+        if address == !inverted {
+            Some(address)
+        } else {
+            None
+        }
+    }
+}
+
+/// A NEC frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum NecFrame {
+    /// A command frame
+    Command(NecCommand),
+    /// The repeat code sent every 108ms while a key is held
+    Repeat,
+}
+
+/// Encode a NEC frame into `data`, which needs to hold at least
+/// [NEC_MAX_LEN] pulse codes.
+///
+/// Returns the number of pulse codes written, the last one marks the end of
+/// the sequence.
+pub fn encode_nec(
+    config: &Config,
+    frame: NecFrame,
+    data: &mut [PulseCode],
+) -> Result<usize, Error> {
+    let mark = config.ticks(NEC_MARK_NS)?;
+    let leading_mark = config.ticks(NEC_LEADING_MARK_NS)?;
+
+    let command = match frame {
+        NecFrame::Command(command) => command,
+        NecFrame::Repeat => {
+            if data.len() < 2 {
+                return Err(Error::InvalidArgument);
+            }
+
+            data[0] = pulse(leading_mark, config.ticks(NEC_REPEAT_SPACE_NS)?);
+            data[1] = pulse(mark, 0);
+            return Ok(2);
+        }
+    };
+
+    if data.len() < NEC_MAX_LEN {
+        return Err(Error::InvalidArgument);
+    }
+
+    let zero = config.ticks(NEC_ZERO_SPACE_NS)?;
+    let one = config.ticks(NEC_ONE_SPACE_NS)?;
+    let bits =
+        command.address as u32 | (command.command as u32) << 16 | (!command.command as u32) << 24;
+
+    data[0] = pulse(leading_mark, config.ticks(NEC_LEADING_SPACE_NS)?);
+    for (i, entry) in data[1..33].iter_mut().enumerate() {
+        let space = if bits & (1 << i) != 0 { one } else { zero };
+        *entry = pulse(mark, space);
+    }
+    data[33] = pulse(mark, 0);
+
+    Ok(NEC_MAX_LEN)
+}
+
+/// Decode a NEC frame from received pulse codes
+pub fn decode_nec(config: &Config, data: &[PulseCode]) -> Result<NecFrame, DecodeError> {
+    let header = data.first().ok_or(DecodeError::InvalidLength)?;
+    let mark = header.level1;
+
+    if !config.matches(header.length1, NEC_LEADING_MARK_NS) {
+        return Err(DecodeError::InvalidHeader);
+    }
+
+    if config.matches(header.length2, NEC_REPEAT_SPACE_NS) {
+        let stop = data.get(1).ok_or(DecodeError::InvalidLength)?;
+        if stop.level1 != mark || !config.matches(stop.length1, NEC_MARK_NS) {
+            return Err(DecodeError::InvalidTiming);
+        }
+
+        return Ok(NecFrame::Repeat);
+    }
+
+    if !config.matches(header.length2, NEC_LEADING_SPACE_NS) {
+        return Err(DecodeError::InvalidHeader);
+    }
+
+    if data.len() < NEC_MAX_LEN {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut bits = 0u32;
+    for (i, entry) in data[1..33].iter().enumerate() {
+        if entry.level1 != mark || !config.matches(entry.length1, NEC_MARK_NS) {
+            return Err(DecodeError::InvalidTiming);
+        }
+
+        if config.matches(entry.length2, NEC_ONE_SPACE_NS) {
+            bits |= 1 << i;
+        } else if !config.matches(entry.length2, NEC_ZERO_SPACE_NS) {
+            return Err(DecodeError::InvalidTiming);
+        }
+    }
+
+    if data[33].level1 != mark || !config.matches(data[33].length1, NEC_MARK_NS) {
+        return Err(DecodeError::InvalidTiming);
+    }
+
+    let command = (bits >> 16) as u8;
+    if command != !(bits >> 24) as u8 {
+        return Err(DecodeError::InvalidChecksum);
+    }
+
+    Ok(NecFrame::Command(NecCommand {
+        address: bits as u16,
+        command,
+    }))
+}
+
+/// A RC5 frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Rc5Frame {
+    /// The 5-bit address
+    pub address: u8,
+    /// The 7-bit command, commands from 64 on use the RC5X field bit
+    pub command: u8,
+    /// Toggles with every key press, distinguishes a new press from a held key
+    pub toggle: bool,
+}
+
+/// Encode a RC5 frame into `data`, which needs to hold at least
+/// [RC5_MAX_LEN] pulse codes.
+///
+/// Returns the number of pulse codes written, the last one marks the end of
+/// the sequence.
+pub fn encode_rc5(
+    config: &Config,
+    frame: Rc5Frame,
+    data: &mut [PulseCode],
+) -> Result<usize, Error> {
+    if frame.address > 0x1f || frame.command > 0x7f {
+        return Err(Error::InvalidArgument);
+    }
+
+    if data.len() < RC5_MAX_LEN {
+        return Err(Error::InvalidArgument);
+    }
+
+    let half_bit = config.ticks(RC5_HALF_BIT_NS)?;
+    // two half bits of the same level merge into one pulse
+    config.ticks(2 * RC5_HALF_BIT_NS)?;
+    let bits = 1 << 13
+        | ((frame.command & 0x40 == 0) as u16) << 12
+        | (frame.toggle as u16) << 11
+        | (frame.address as u16) << 6
+        | (frame.command & 0x3f) as u16;
+
+    // a one is a space followed by a mark, a zero a mark followed by a space
+    let mut runs = [(false, 0u16); RC5_HALF_BITS];
+    let mut count = 0;
+    for i in (0..14).rev() {
+        let first = bits & (1 << i) == 0;
+        for level in [first, !first] {
+            if count > 0 && runs[count - 1].0 == level {
+                runs[count - 1].1 += half_bit;
+            } else if count > 0 || level {
+                // the leading space of the start bit is part of the idle time
+                runs[count] = (level, half_bit);
+                count += 1;
+            }
+        }
+    }
+
+    // and so is a trailing space
+    if !runs[count - 1].0 {
+        count -= 1;
+    }
+
+    let mut len = 0;
+    for pair in runs[..count].chunks(2) {
+        let (level2, length2) = pair.get(1).copied().unwrap_or((false, 0));
+        data[len] = PulseCode {
+            level1: pair[0].0,
+            length1: pair[0].1,
+            level2,
+            length2,
+        };
+        len += 1;
+    }
+
+    Ok(len)
+}
+
+/// Decode a RC5 frame from received pulse codes
+pub fn decode_rc5(config: &Config, data: &[PulseCode]) -> Result<Rc5Frame, DecodeError> {
+    let mark = data.first().ok_or(DecodeError::InvalidLength)?.level1;
+
+    // the leading space of the start bit is part of the idle time
+    let mut halves = [false; RC5_HALF_BITS];
+    let mut count = 1;
+
+    'codes: for entry in data {
+        for (level, length) in [(entry.level1, entry.length1), (entry.level2, entry.length2)] {
+            if length == 0 {
+                break 'codes;
+            }
+
+            let repeat = if config.matches(length, RC5_HALF_BIT_NS) {
+                1
+            } else if config.matches(length, 2 * RC5_HALF_BIT_NS) {
+                2
+            } else {
+                return Err(DecodeError::InvalidTiming);
+            };
+
+            for _ in 0..repeat {
+                if count == RC5_HALF_BITS {
+                    return Err(DecodeError::InvalidLength);
+                }
+
+                halves[count] = level == mark;
+                count += 1;
+            }
+        }
+    }
+
+    // the trailing space of a frame ending in a zero is part of the idle time
+    if count == RC5_HALF_BITS - 1 {
+        count += 1;
+    }
+
+    if count != RC5_HALF_BITS {
+        return Err(DecodeError::InvalidLength);
+    }
+
+    let mut bits = 0u16;
+    for pair in halves.chunks(2) {
+        bits = match (pair[0], pair[1]) {
+            (false, true) => bits << 1 | 1,
+            (true, false) => bits << 1,
+            _ => return Err(DecodeError::InvalidTiming),
+        };
+    }
+
+    if bits & (1 << 13) == 0 {
+        return Err(DecodeError::InvalidHeader);
+    }
+
+    let field = bits & (1 << 12) == 0;
+    Ok(Rc5Frame {
+        address: ((bits >> 6) & 0x1f) as u8,
+        command: (bits & 0x3f) as u8 | (field as u8) << 6,
+        toggle: bits & (1 << 11) != 0,
+    })
+}
+
+fn pulse(mark: u16, space: u16) -> PulseCode {
+    PulseCode {
+        level1: true,
+        length1: mark,
+        level2: false,
+        length2: space,
+    }
+}
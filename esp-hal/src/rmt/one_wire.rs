@@ -0,0 +1,451 @@
+//! # 1-Wire bus master
+//!
+//! ## Overview
+//! [OneWire] drives a 1-Wire bus (e.g. DS18B20 temperature sensors) with a
+//! pair of RMT channels on the same open drain pin: the TX channel generates
+//! the reset pulse and the time slots, the RX channel captures the bus at the
+//! same time. Unlike [crate::gpio::one_wire], the timing doesn't depend on the
+//! CPU, so interrupts don't need to be disabled during a transfer.
+//!
+//! Besides the reset, bit and byte transfers (standard speed), it implements
+//! the ROM search algorithm to discover the devices on the bus.
+//!
+//! The pin is configured as open drain output with the internal pull-up
+//! enabled. For longer buses, an external pull-up (typically 4.7k) is still
+//! needed.
+//!
+//! ## Example
+//! ```no_run
+//! let rmt = Rmt::new(peripherals.RMT, 80.MHz(), &clocks, None).unwrap();
+//! let mut bus = OneWire::new(rmt.channel0, rmt.channel2, io.pins.gpio4, 80.MHz()).unwrap();
+//!
+//! let mut search = Search::new();
+//! while let Some(address) = bus.search(&mut search).unwrap() {
+//!     println!("found device {:016x}", address);
+//! }
+//!
+//! if bus.reset().unwrap() {
+//!     // skip ROM, convert temperature
+//!     bus.write_bytes(&[0xcc, 0x44]).unwrap();
+//! }
+//! ```
+
+use fugit::HertzU32;
+
+use super::{
+    private::{RxChannelInternal, TxChannelInternal},
+    PulseCode,
+    RxChannel,
+    RxChannelConfig,
+    RxChannelCreator,
+    TxChannel,
+    TxChannelConfig,
+    TxChannelCreator,
+};
+use crate::{
+    gpio::{InputPin, OutputPin},
+    peripheral::Peripheral,
+};
+
+// Standard speed timings in microseconds, see Maxim application note 126.
+const WRITE_1_LOW: u16 = 6;
+const WRITE_1_RELEASE: u16 = 64;
+const WRITE_0_LOW: u16 = 60;
+const WRITE_0_RELEASE: u16 = 10;
+const READ_SAMPLE: u16 = 9;
+const RESET_LOW: u16 = 480;
+const RESET_RELEASE: u16 = 410;
+
+/// A capture ends when the bus doesn't change for this long. The reset pulse
+/// is longer than any time slot, so it needs a longer timeout.
+const SLOT_IDLE: u16 = 100;
+const RESET_IDLE: u16 = 600;
+
+/// Glitches shorter than this many RMT source clock cycles are ignored
+const FILTER_THRESHOLD: u8 = 40;
+
+/// The largest number of time slots sent at once
+const MAX_SLOTS: usize = 8;
+
+/// ROM commands used by [OneWire::search]
+const SEARCH_ROM: u8 = 0xf0;
+const ALARM_SEARCH: u8 = 0xec;
+
+/// 1-Wire bus errors
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Error {
+    /// An RMT channel reported an error
+    Rmt(super::Error),
+    /// The captured bus activity doesn't match the sent time slots, e.g.
+    /// because the bus is held low or noisy
+    InvalidCapture,
+    /// A ROM code found by the search has an invalid CRC
+    Crc,
+}
+
+impl From<super::Error> for Error {
+    fn from(value: super::Error) -> Self {
+        Self::Rmt(value)
+    }
+}
+
+/// State of a ROM search, see [OneWire::search]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Search {
+    command: u8,
+    address: u64,
+    last_discrepancy: u8,
+    done: bool,
+}
+
+impl Search {
+    /// Search for all devices on the bus
+    pub fn new() -> Self {
+        Self::with_command(SEARCH_ROM)
+    }
+
+    /// Search for the devices with an alarm condition
+    pub fn alarms() -> Self {
+        Self::with_command(ALARM_SEARCH)
+    }
+
+    fn with_command(command: u8) -> Self {
+        Self {
+            command,
+            address: 0,
+            last_discrepancy: 0,
+            done: false,
+        }
+    }
+}
+
+impl Default for Search {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A 1-Wire bus master on a pair of RMT channels
+pub struct OneWire<TX, RX>
+where
+    TX: TxChannel,
+    RX: RxChannel,
+{
+    // the transactions take the channels by value and hand them back when done
+    tx: Option<TX>,
+    rx: Option<RX>,
+}
+
+impl<TX, RX> OneWire<TX, RX>
+where
+    TX: TxChannel,
+    RX: RxChannel,
+{
+    /// Create a new bus on `pin` from an unused TX and RX channel.
+    ///
+    /// `source_frequency` is the frequency the [super::Rmt] was created with,
+    /// it has to be a whole number of MHz up to 255MHz.
+    pub fn new<'d, P>(
+        tx: impl TxChannelCreator<'d, TX, P>,
+        rx: impl RxChannelCreator<'d, RX, P>,
+        pin: impl Peripheral<P = P> + 'd,
+        source_frequency: HertzU32,
+    ) -> Result<Self, Error>
+    where
+        P: OutputPin + InputPin + Peripheral<P = P> + 'd,
+    {
+        // count in microseconds
+        let divider = source_frequency.to_MHz();
+        if divider == 0 || divider > u8::MAX as u32 || source_frequency.to_Hz() % 1_000_000 != 0 {
+            return Err(Error::Rmt(super::Error::UnreachableTargetFrequency));
+        }
+
+        crate::into_ref!(pin);
+
+        // safety: both channels are connected to the same pin on purpose, the
+        // pin is configured for both directions below
+        let tx = tx.configure(
+            unsafe { P::clone_unchecked(&mut *pin) },
+            TxChannelConfig {
+                clk_divider: divider as u8,
+                idle_output: true,
+                idle_output_level: true,
+                ..TxChannelConfig::default()
+            },
+        )?;
+        let rx = rx.configure(
+            unsafe { P::clone_unchecked(&mut *pin) },
+            RxChannelConfig {
+                clk_divider: divider as u8,
+                filter_threshold: FILTER_THRESHOLD,
+                idle_threshold: SLOT_IDLE,
+                ..RxChannelConfig::default()
+            },
+        )?;
+
+        pin.set_to_open_drain_output()
+            .enable_input(true)
+            .internal_pull_up(true)
+            .connect_peripheral_to_output(<TX as TxChannelInternal<crate::Blocking>>::output_signal())
+            .connect_input_to_peripheral(<RX as RxChannelInternal<crate::Blocking>>::input_signal());
+
+        Ok(Self {
+            tx: Some(tx),
+            rx: Some(rx),
+        })
+    }
+
+    /// Release the channels
+    pub fn free(self) -> (TX, RX) {
+        (self.tx.unwrap(), self.rx.unwrap())
+    }
+
+    /// Send a reset pulse.
+    ///
+    /// Returns whether any device answered with a presence pulse.
+    pub fn reset(&mut self) -> Result<bool, Error> {
+        let mut lows = [0; 2];
+        let count = self.transfer(
+            &[
+                PulseCode {
+                    level1: false,
+                    length1: RESET_LOW,
+                    level2: true,
+                    length2: RESET_RELEASE,
+                },
+                PulseCode::default(),
+            ],
+            RESET_IDLE,
+            &mut lows,
+        )?;
+
+        match count {
+            0 => Err(Error::InvalidCapture),
+            1 => Ok(false),
+            _ => Ok(true),
+        }
+    }
+
+    /// Write a single bit.
+    pub fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
+        self.write_bits(bit as u8, 1)
+    }
+
+    /// Read a single bit.
+    pub fn read_bit(&mut self) -> Result<bool, Error> {
+        Ok(self.read_bits(1)? != 0)
+    }
+
+    /// Write a byte, least significant bit first.
+    pub fn write_byte(&mut self, byte: u8) -> Result<(), Error> {
+        self.write_bits(byte, 8)
+    }
+
+    /// Read a byte, least significant bit first.
+    pub fn read_byte(&mut self) -> Result<u8, Error> {
+        self.read_bits(8)
+    }
+
+    /// Write all bytes of `bytes`.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        for byte in bytes {
+            self.write_byte(*byte)?;
+        }
+
+        Ok(())
+    }
+
+    /// Fill `buffer` with bytes read from the bus.
+    pub fn read_bytes(&mut self, buffer: &mut [u8]) -> Result<(), Error> {
+        for byte in buffer.iter_mut() {
+            *byte = self.read_byte()?;
+        }
+
+        Ok(())
+    }
+
+    /// Find the next device of a ROM search.
+    ///
+    /// Returns the 64-bit ROM code of the device, with the family code in the
+    /// least significant byte, or `None` once all devices are found.
+    pub fn search(&mut self, search: &mut Search) -> Result<Option<u64>, Error> {
+        if search.done || !self.reset()? {
+            search.done = true;
+            return Ok(None);
+        }
+
+        self.write_byte(search.command)?;
+
+        let mut address = search.address;
+        let mut last_zero = 0;
+        for bit in 1..=64u8 {
+            let mask = 1 << (bit - 1);
+            let direction = match self.read_bits(2)? {
+                // all devices have a one
+                0b01 => true,
+                // all devices have a zero
+                0b10 => false,
+                // devices with both values, take the other branch than last time
+                0b00 => {
+                    let direction = if bit < search.last_discrepancy {
+                        address & mask != 0
+                    } else {
+                        bit == search.last_discrepancy
+                    };
+
+                    if !direction {
+                        last_zero = bit;
+                    }
+
+                    direction
+                }
+                // no device answered
+                _ => {
+                    search.done = true;
+                    return Ok(None);
+                }
+            };
+
+            if direction {
+                address |= mask;
+            } else {
+                address &= !mask;
+            }
+
+            self.write_bit(direction)?;
+        }
+
+        search.address = address;
+        search.last_discrepancy = last_zero;
+        search.done = last_zero == 0;
+
+        let bytes = address.to_le_bytes();
+        if crc8(&bytes[..7]) != bytes[7] {
+            return Err(Error::Crc);
+        }
+
+        Ok(Some(address))
+    }
+
+    fn write_bits(&mut self, bits: u8, count: usize) -> Result<(), Error> {
+        let mut codes = [PulseCode::default(); MAX_SLOTS + 1];
+        for (i, code) in codes.iter_mut().take(count).enumerate() {
+            *code = if bits & (1 << i) != 0 {
+                slot(WRITE_1_LOW, WRITE_1_RELEASE)
+            } else {
+                slot(WRITE_0_LOW, WRITE_0_RELEASE)
+            };
+        }
+
+        let mut lows = [0; MAX_SLOTS];
+        if self.transfer(&codes[..count + 1], SLOT_IDLE, &mut lows)? != count {
+            return Err(Error::InvalidCapture);
+        }
+
+        Ok(())
+    }
+
+    fn read_bits(&mut self, count: usize) -> Result<u8, Error> {
+        let mut codes = [PulseCode::default(); MAX_SLOTS + 1];
+        for code in codes.iter_mut().take(count) {
+            *code = slot(WRITE_1_LOW, WRITE_1_RELEASE);
+        }
+
+        let mut lows = [0; MAX_SLOTS];
+        if self.transfer(&codes[..count + 1], SLOT_IDLE, &mut lows)? != count {
+            return Err(Error::InvalidCapture);
+        }
+
+        // a device sends a zero by holding the bus low past the sample point
+        let mut bits = 0;
+        for (i, low) in lows.iter().take(count).enumerate() {
+            if *low <= WRITE_1_LOW + READ_SAMPLE {
+                bits |= 1 << i;
+            }
+        }
+
+        Ok(bits)
+    }
+
+    /// Send `codes` while capturing the bus, store the lengths of the low
+    /// phases in `lows` and return their number.
+    fn transfer(
+        &mut self,
+        codes: &[PulseCode],
+        idle_threshold: u16,
+        lows: &mut [u16],
+    ) -> Result<usize, Error> {
+        let mut data = [PulseCode::default(); 2 * MAX_SLOTS];
+
+        <RX as RxChannelInternal<crate::Blocking>>::set_idle_threshold(idle_threshold);
+        let rx = self.rx.take().unwrap().receive(&mut data)?;
+        let tx_result = self.tx.take().unwrap().transmit(codes).wait();
+        let rx_result = rx.wait();
+
+        let tx_result = restore(&mut self.tx, tx_result);
+        restore(&mut self.rx, rx_result)?;
+        tx_result?;
+
+        let mut count = 0;
+        'codes: for code in data {
+            for (level, length) in [(code.level1, code.length1), (code.level2, code.length2)] {
+                if length == 0 {
+                    break 'codes;
+                }
+
+                if !level {
+                    if count == lows.len() {
+                        return Err(Error::InvalidCapture);
+                    }
+
+                    lows[count] = length;
+                    count += 1;
+                }
+            }
+        }
+
+        Ok(count)
+    }
+}
+
+/// The Dallas/Maxim CRC-8 used by ROM codes and scratchpads
+pub fn crc8(data: &[u8]) -> u8 {
+    let mut crc = 0;
+    for byte in data {
+        let mut byte = *byte;
+        for _ in 0..8 {
+            let mix = (crc ^ byte) & 1;
+            crc >>= 1;
+            if mix != 0 {
+                crc ^= 0x8c;
+            }
+            byte >>= 1;
+        }
+    }
+
+    crc
+}
+
+fn slot(low: u16, release: u16) -> PulseCode {
+    PulseCode {
+        level1: false,
+        length1: low,
+        level2: true,
+        length2: release,
+    }
+}
+
+fn restore<C>(channel: &mut Option<C>, result: Result<C, (super::Error, C)>) -> Result<(), Error> {
+    match result {
+        Ok(c) => {
+            *channel = Some(c);
+            Ok(())
+        }
+        Err((err, c)) => {
+            *channel = Some(c);
+            Err(Error::Rmt(err))
+        }
+    }
+}
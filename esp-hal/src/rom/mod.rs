@@ -42,6 +42,14 @@ extern "C" {
         reg_add_lsb: u32,
         indata: u32,
     );
+
+    pub(crate) fn rom_i2c_readReg_Mask(
+        block: u32,
+        block_hostid: u32,
+        reg_add: u32,
+        reg_add_msb: u32,
+        reg_add_lsb: u32,
+    ) -> u8;
 }
 
 #[doc(hidden)]
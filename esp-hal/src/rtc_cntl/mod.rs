@@ -68,6 +68,32 @@
 //!     });
 //! }
 //! ```
+//!
+//! ### Diagnostics before an RWDT reset
+//! ```no_run
+//! rtc.rwdt.set_stage(
+//!     RwdtStage::Stage0,
+//!     2000u64.millis(),
+//!     RwdtStageAction::Interrupt,
+//! );
+//! rtc.rwdt.set_stage(
+//!     RwdtStage::Stage1,
+//!     100u64.millis(),
+//!     RwdtStageAction::ResetRtc,
+//! );
+//! rtc.rwdt.set_interrupt_handler(interrupt_handler);
+//! rtc.rwdt.enable();
+//! rtc.rwdt.feed();
+//! ```
+//!
+//! Where the interrupt handler logs whatever is useful while the final stage
+//! is counting down:
+//! ```no_run
+//! #[handler]
+//! fn interrupt_handler() {
+//!     esp_println::println!("RWDT about to reset the chip");
+//! }
+//! ```
 
 #[cfg(not(any(esp32c6, esp32h2)))]
 use fugit::HertzU32;
@@ -239,46 +265,12 @@ impl<'d> Rtc<'d> {
 
     /// Read the current value of the rtc time registers.
     pub fn get_time_raw(&self) -> u64 {
-        #[cfg(not(any(esp32c6, esp32h2)))]
-        let rtc_cntl = unsafe { &*LPWR::ptr() };
-        #[cfg(any(esp32c6, esp32h2))]
-        let rtc_cntl = unsafe { &*LP_TIMER::ptr() };
-
-        #[cfg(esp32)]
-        let (l, h) = {
-            rtc_cntl.time_update().write(|w| w.time_update().set_bit());
-            while rtc_cntl.time_update().read().time_valid().bit_is_clear() {
-                // might take 1 RTC slowclk period, don't flood RTC bus
-                crate::rom::ets_delay_us(1);
-            }
-            let h = rtc_cntl.time1().read().time_hi().bits();
-            let l = rtc_cntl.time0().read().time_lo().bits();
-            (l, h)
-        };
-        #[cfg(any(esp32c2, esp32c3, esp32s3, esp32s2))]
-        let (l, h) = {
-            rtc_cntl.time_update().write(|w| w.time_update().set_bit());
-            let h = rtc_cntl.time_high0().read().timer_value0_high().bits();
-            let l = rtc_cntl.time_low0().read().timer_value0_low().bits();
-            (l, h)
-        };
-        #[cfg(any(esp32c6, esp32h2))]
-        let (l, h) = {
-            rtc_cntl.update().write(|w| w.main_timer_update().set_bit());
-            let h = rtc_cntl
-                .main_buf0_high()
-                .read()
-                .main_timer_buf0_high()
-                .bits();
-            let l = rtc_cntl.main_buf0_low().read().main_timer_buf0_low().bits();
-            (l, h)
-        };
-        ((h as u64) << 32) | (l as u64)
+        get_time_raw()
     }
 
     /// Read the current value of the rtc time registers in microseconds.
     pub fn get_time_us(&self) -> u64 {
-        self.get_time_raw() * 1_000_000 / RtcClock::get_slow_freq().frequency().to_Hz() as u64
+        get_time_us()
     }
 
     /// Read the current value of the rtc time registers in milliseconds.
@@ -327,11 +319,61 @@ impl<'d> Rtc<'d> {
         config.apply();
         delay.delay_millis(100);
 
+        #[cfg(systimer)]
+        let sleep_tracker = crate::time::SleepTracker::start();
+
         config.start_sleep(wakeup_triggers);
         config.finish_sleep();
+
+        #[cfg(systimer)]
+        sleep_tracker.finish();
     }
 }
 
+/// Read the current value of the rtc time registers.
+pub(crate) fn get_time_raw() -> u64 {
+    #[cfg(not(any(esp32c6, esp32h2)))]
+    let rtc_cntl = unsafe { &*LPWR::ptr() };
+    #[cfg(any(esp32c6, esp32h2))]
+    let rtc_cntl = unsafe { &*LP_TIMER::ptr() };
+
+    #[cfg(esp32)]
+    let (l, h) = {
+        rtc_cntl.time_update().write(|w| w.time_update().set_bit());
+        while rtc_cntl.time_update().read().time_valid().bit_is_clear() {
+            // might take 1 RTC slowclk period, don't flood RTC bus
+            crate::rom::ets_delay_us(1);
+        }
+        let h = rtc_cntl.time1().read().time_hi().bits();
+        let l = rtc_cntl.time0().read().time_lo().bits();
+        (l, h)
+    };
+    #[cfg(any(esp32c2, esp32c3, esp32s3, esp32s2))]
+    let (l, h) = {
+        rtc_cntl.time_update().write(|w| w.time_update().set_bit());
+        let h = rtc_cntl.time_high0().read().timer_value0_high().bits();
+        let l = rtc_cntl.time_low0().read().timer_value0_low().bits();
+        (l, h)
+    };
+    #[cfg(any(esp32c6, esp32h2))]
+    let (l, h) = {
+        rtc_cntl.update().write(|w| w.main_timer_update().set_bit());
+        let h = rtc_cntl
+            .main_buf0_high()
+            .read()
+            .main_timer_buf0_high()
+            .bits();
+        let l = rtc_cntl.main_buf0_low().read().main_timer_buf0_low().bits();
+        (l, h)
+    };
+    ((h as u64) << 32) | (l as u64)
+}
+
+/// Read the current value of the rtc time registers in microseconds.
+pub(crate) fn get_time_us() -> u64 {
+    get_time_raw() * 1_000_000 / RtcClock::get_slow_freq().frequency().to_Hz() as u64
+}
+
 #[cfg(not(any(esp32c6, esp32h2)))]
 /// RTC Watchdog Timer.
 pub struct RtcClock;
@@ -674,16 +716,34 @@ impl RtcClock {
 }
 
 /// Behavior of the RWDT stage if it times out.
-#[allow(unused)]
-#[derive(Debug, Clone, Copy)]
-enum RwdtStageAction {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RwdtStageAction {
+    /// No action
     Off         = 0,
+    /// Raise the RWDT interrupt
     Interrupt   = 1,
+    /// Reset the CPU core
     ResetCpu    = 2,
+    /// Reset the main system, but not the RTC
     ResetSystem = 3,
+    /// Reset the main system and the RTC
     ResetRtc    = 4,
 }
 
+/// A stage of the RWDT.
+///
+/// The stages are run in sequence, starting with stage 0 whenever the
+/// watchdog is fed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RwdtStage {
+    Stage0,
+    Stage1,
+    Stage2,
+    Stage3,
+}
+
 /// RTC Watchdog Timer.
 pub struct Rwdt {
     stg0_action: RwdtStageAction,
@@ -757,6 +817,35 @@ impl Rwdt {
         self.set_write_protection(true);
     }
 
+    /// Bind an interrupt handler for the RWDT interrupt and enable it.
+    ///
+    /// Together with a stage configured to [RwdtStageAction::Interrupt],
+    /// followed by a stage resetting the chip, this allows to run code (e.g.
+    /// logging diagnostics) before the watchdog resets the chip.
+    ///
+    /// The RWDT shares its interrupt with other RTC peripherals, so this
+    /// replaces any handler bound via [Rtc::new].
+    pub fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        #[cfg(any(esp32c6, esp32h2))]
+        let interrupt = Interrupt::LP_WDT;
+        #[cfg(not(any(esp32c6, esp32h2)))]
+        let interrupt = Interrupt::RTC_CORE;
+
+        unsafe {
+            interrupt::bind_interrupt(interrupt, handler.handler());
+        }
+        interrupt::enable(interrupt, handler.priority()).unwrap();
+
+        #[cfg(not(any(esp32c6, esp32h2)))]
+        let rtc_cntl = unsafe { &*LPWR::PTR };
+        #[cfg(any(esp32c6, esp32h2))]
+        let rtc_cntl = unsafe { &*LP_WDT::PTR };
+
+        self.set_write_protection(false);
+        rtc_cntl.int_ena().modify(|_, w| w.wdt().set_bit());
+        self.set_write_protection(true);
+    }
+
     /// Clear interrupt.
     pub fn clear_interrupt(&mut self) {
         #[cfg(not(any(esp32c6, esp32h2)))]
@@ -819,34 +908,118 @@ impl Rwdt {
         self.set_write_protection(true);
     }
 
-    /// Configure timeout value in ms.
-    pub fn set_timeout(&mut self, timeout: MicrosDurationU64) {
+    /// Configure the timeout of a single stage.
+    ///
+    /// This takes effect the next time the watchdog is fed.
+    pub fn set_stage_timeout(&mut self, stage: RwdtStage, timeout: MicrosDurationU64) {
+        self.set_write_protection(false);
+        Self::set_stage_hold(stage, timeout);
+        self.set_write_protection(true);
+    }
+
+    /// Configure the action taken when a single stage times out.
+    pub fn set_stage_action(&mut self, stage: RwdtStage, action: RwdtStageAction) {
         #[cfg(not(any(esp32c6, esp32h2)))]
         let rtc_cntl = unsafe { &*LPWR::PTR };
         #[cfg(any(esp32c6, esp32h2))]
         let rtc_cntl = unsafe { &*LP_WDT::PTR };
 
-        let timeout_raw = (timeout.to_millis() * (RtcClock::cycles_to_1ms() as u64)) as u32;
+        match stage {
+            RwdtStage::Stage0 => self.stg0_action = action,
+            RwdtStage::Stage1 => self.stg1_action = action,
+            RwdtStage::Stage2 => self.stg2_action = action,
+            RwdtStage::Stage3 => self.stg3_action = action,
+        }
+
         self.set_write_protection(false);
 
+        rtc_cntl.wdtconfig0().modify(|_, w| unsafe {
+            match stage {
+                RwdtStage::Stage0 => w.wdt_stg0().bits(action as u8),
+                RwdtStage::Stage1 => w.wdt_stg1().bits(action as u8),
+                RwdtStage::Stage2 => w.wdt_stg2().bits(action as u8),
+                RwdtStage::Stage3 => w.wdt_stg3().bits(action as u8),
+            }
+        });
+
+        self.set_write_protection(true);
+    }
+
+    /// Configure timeout and action of a single stage.
+    pub fn set_stage(
+        &mut self,
+        stage: RwdtStage,
+        timeout: MicrosDurationU64,
+        action: RwdtStageAction,
+    ) {
+        self.set_stage_timeout(stage, timeout);
+        self.set_stage_action(stage, action);
+    }
+
+    fn set_stage_hold(stage: RwdtStage, timeout: MicrosDurationU64) {
+        #[cfg(not(any(esp32c6, esp32h2)))]
+        let rtc_cntl = unsafe { &*LPWR::PTR };
+        #[cfg(any(esp32c6, esp32h2))]
+        let rtc_cntl = unsafe { &*LP_WDT::PTR };
+
+        let timeout_raw = (timeout.to_millis() * (RtcClock::cycles_to_1ms() as u64)) as u32;
+
+        // The hold time of stage 0 is scaled by a factor configured in the eFuse
+        #[cfg(not(esp32))]
+        let stg0_raw = timeout_raw >> (1 + Efuse::get_rwdt_multiplier());
+        #[cfg(esp32)]
+        let stg0_raw = timeout_raw;
+
         unsafe {
-            #[cfg(esp32)]
-            rtc_cntl
-                .wdtconfig1()
-                .modify(|_, w| w.wdt_stg0_hold().bits(timeout_raw));
+            #[cfg(not(any(esp32c6, esp32h2)))]
+            match stage {
+                RwdtStage::Stage0 => rtc_cntl
+                    .wdtconfig1()
+                    .modify(|_, w| w.wdt_stg0_hold().bits(stg0_raw)),
+                RwdtStage::Stage1 => rtc_cntl
+                    .wdtconfig2()
+                    .modify(|_, w| w.wdt_stg1_hold().bits(timeout_raw)),
+                RwdtStage::Stage2 => rtc_cntl
+                    .wdtconfig3()
+                    .modify(|_, w| w.wdt_stg2_hold().bits(timeout_raw)),
+                RwdtStage::Stage3 => rtc_cntl
+                    .wdtconfig4()
+                    .modify(|_, w| w.wdt_stg3_hold().bits(timeout_raw)),
+            };
 
             #[cfg(any(esp32c6, esp32h2))]
-            rtc_cntl.config1().modify(|_, w| {
-                w.wdt_stg0_hold()
-                    .bits(timeout_raw >> (1 + Efuse::get_rwdt_multiplier()))
-            });
+            match stage {
+                RwdtStage::Stage0 => rtc_cntl
+                    .config1()
+                    .modify(|_, w| w.wdt_stg0_hold().bits(stg0_raw)),
+                RwdtStage::Stage1 => rtc_cntl
+                    .config2()
+                    .modify(|_, w| w.wdt_stg1_hold().bits(timeout_raw)),
+                RwdtStage::Stage2 => rtc_cntl
+                    .config3()
+                    .modify(|_, w| w.wdt_stg2_hold().bits(timeout_raw)),
+                RwdtStage::Stage3 => rtc_cntl
+                    .config4()
+                    .modify(|_, w| w.wdt_stg3_hold().bits(timeout_raw)),
+            };
+        }
+    }
 
-            #[cfg(not(any(esp32, esp32c6, esp32h2)))]
-            rtc_cntl.wdtconfig1().modify(|_, w| {
-                w.wdt_stg0_hold()
-                    .bits(timeout_raw >> (1 + Efuse::get_rwdt_multiplier()))
-            });
+    /// Configure the timeout of stage 0 and enable the watchdog timer.
+    ///
+    /// The stage actions configured via [Self::set_stage_action] (by default
+    /// stage 0 resets the chip) are applied as well.
+    pub fn set_timeout(&mut self, timeout: MicrosDurationU64) {
+        #[cfg(not(any(esp32c6, esp32h2)))]
+        let rtc_cntl = unsafe { &*LPWR::PTR };
+        #[cfg(any(esp32c6, esp32h2))]
+        let rtc_cntl = unsafe { &*LP_WDT::PTR };
+
+        self.set_write_protection(false);
+
+        Self::set_stage_hold(RwdtStage::Stage0, timeout);
 
+        unsafe {
             rtc_cntl.wdtconfig0().modify(|_, w| {
                 w.wdt_stg0()
                     .bits(self.stg0_action as u8)
@@ -907,11 +1080,60 @@ impl Swd {
         Self
     }
 
+    /// Enable the watchdog timer instance
+    ///
+    /// The super watchdog has a fixed timeout of roughly one second and has to
+    /// be fed via [Self::feed] from then on, otherwise it resets the chip.
+    pub fn enable(&mut self) {
+        self.set_enabled(true);
+    }
+
     /// Disable the watchdog timer instance
+    ///
+    /// The hardware keeps feeding the super watchdog by itself while it is
+    /// disabled, so long running operations (e.g. erasing the flash) can't trip
+    /// it.
     pub fn disable(&mut self) {
         self.set_enabled(false);
     }
 
+    /// Feed the watchdog timer
+    pub fn feed(&mut self) {
+        #[cfg(not(any(esp32c6, esp32h2)))]
+        let rtc_cntl = unsafe { &*LPWR::PTR };
+        #[cfg(any(esp32c6, esp32h2))]
+        let rtc_cntl = unsafe { &*LP_WDT::PTR };
+
+        self.set_write_protection(false);
+        rtc_cntl.swd_conf().modify(|_, w| w.swd_feed().set_bit());
+        self.set_write_protection(true);
+    }
+
+    /// Whether the last reset was caused by the super watchdog
+    pub fn caused_last_reset(&self) -> bool {
+        #[cfg(not(any(esp32c6, esp32h2)))]
+        let rtc_cntl = unsafe { &*LPWR::PTR };
+        #[cfg(any(esp32c6, esp32h2))]
+        let rtc_cntl = unsafe { &*LP_WDT::PTR };
+
+        rtc_cntl.swd_conf().read().swd_reset_flag().bit_is_set()
+            || get_reset_reason(Cpu::ProCpu) == Some(SocResetReason::SysSuperWdt)
+    }
+
+    /// Clear the flag reported by [Self::caused_last_reset]
+    pub fn clear_reset_flag(&mut self) {
+        #[cfg(not(any(esp32c6, esp32h2)))]
+        let rtc_cntl = unsafe { &*LPWR::PTR };
+        #[cfg(any(esp32c6, esp32h2))]
+        let rtc_cntl = unsafe { &*LP_WDT::PTR };
+
+        self.set_write_protection(false);
+        rtc_cntl
+            .swd_conf()
+            .modify(|_, w| w.swd_rst_flag_clr().set_bit());
+        self.set_write_protection(true);
+    }
+
     /// Enable/disable write protection for WDT registers
     fn set_write_protection(&mut self, enable: bool) {
         #[cfg(not(any(esp32c6, esp32h2)))]
@@ -938,7 +1160,7 @@ impl Swd {
         self.set_write_protection(false);
         rtc_cntl
             .swd_conf()
-            .write(|w| w.swd_auto_feed_en().bit(!enable));
+            .modify(|_, w| w.swd_auto_feed_en().bit(!enable).swd_disable().bit(false));
         self.set_write_protection(true);
     }
 }
@@ -960,6 +1182,16 @@ impl embedded_hal_02::watchdog::WatchdogDisable for Swd {
     }
 }
 
+#[cfg(all(
+    any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3),
+    feature = "embedded-hal-02"
+))]
+impl embedded_hal_02::watchdog::Watchdog for Swd {
+    fn feed(&mut self) {
+        self.feed();
+    }
+}
+
 /// Return reset reason.
 pub fn get_reset_reason(cpu: Cpu) -> Option<SocResetReason> {
     let reason = crate::rom::rtc_get_reset_reason(cpu as u32);
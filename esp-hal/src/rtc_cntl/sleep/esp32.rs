@@ -1,4 +1,11 @@
-use super::{Ext0WakeupSource, Ext1WakeupSource, TimerWakeupSource, WakeSource, WakeTriggers};
+use super::{
+    Ext0WakeupSource,
+    Ext1WakeupSource,
+    TimerWakeupSource,
+    UlpWakeupSource,
+    WakeSource,
+    WakeTriggers,
+};
 use crate::{
     gpio::{RTCPin, RtcFunction},
     rtc_cntl::{sleep::WakeupLevel, Clock, Rtc, RtcClock},
@@ -66,6 +73,16 @@ impl WakeSource for TimerWakeupSource {
     }
 }
 
+impl WakeSource for UlpWakeupSource {
+    fn apply(&self, _rtc: &Rtc, triggers: &mut WakeTriggers, sleep_config: &mut RtcSleepConfig) {
+        // the ULP runs from RTC slow memory and samples RTC peripherals
+        sleep_config.set_rtc_slowmem_pd_en(false);
+        sleep_config.set_rtc_peri_pd_en(false);
+
+        triggers.set_ulp(true);
+    }
+}
+
 impl<P: RTCPin> WakeSource for Ext0WakeupSource<'_, P> {
     fn apply(&self, _rtc: &Rtc, triggers: &mut WakeTriggers, sleep_config: &mut RtcSleepConfig) {
         // don't power down RTC peripherals
@@ -536,9 +553,16 @@ impl RtcSleepConfig {
                 .wakeup_state()
                 .modify(|_, w| w.wakeup_ena().bits(wakeup_triggers.0));
 
-            rtc_cntl
-                .state0()
-                .write(|w| w.sleep_en().set_bit().slp_wakeup().set_bit());
+            // keep the ULP timer running, the ULP may be what wakes us up
+            let ulp_timer = rtc_cntl.state0().read().ulp_cp_slp_timer_en().bit();
+            rtc_cntl.state0().write(|w| {
+                w.sleep_en()
+                    .set_bit()
+                    .slp_wakeup()
+                    .set_bit()
+                    .ulp_cp_slp_timer_en()
+                    .bit(ulp_timer)
+            });
         }
     }
 
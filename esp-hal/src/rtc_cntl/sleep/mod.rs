@@ -14,11 +14,29 @@
 //!    * timers
 //!    * `SDIO (Secure Digital Input/Output) - light sleep only`
 //!    * `MAC (Media Access Control)` wake - light sleep only
-//!    * `UART0` - light sleep only
-//!    * `UART1` - light sleep only
+//!    * `UART0` - light sleep only, see [UartWakeupSource]
+//!    * `UART1` - light sleep only, see [UartWakeupSource]
 //!    * `touch`
 //!    * `ULP (Ultra-Low Power)` wake
 //!    * `BT (Bluetooth) wake` - light sleep only
+//!
+//! To keep the level and pull resistors of RTC pins while sleeping, pass a
+//! [PinHold] along with the wakeup sources, and call [release_pin_holds] after
+//! waking up:
+//!
+//! ```no_run
+//! // after a wakeup from deep sleep, the pins are still held
+//! release_pin_holds();
+//!
+//! let mut enable = io.pins.gpio2.into_push_pull_output();
+//! enable.set_high();
+//!
+//! let mut pins: [(&mut dyn RTCPinWithResistors, RtcioPull); 1] = [(&mut enable, RtcioPull::Up)];
+//! let hold = PinHold::new(&mut pins);
+//! let timer = TimerWakeupSource::new(Duration::from_secs(10));
+//!
+//! rtc.sleep_deep(&[&hold, &timer], &mut delay);
+//! ```
 
 use core::cell::RefCell;
 #[cfg(any(esp32, esp32c3, esp32s3, esp32c6))]
@@ -28,7 +46,7 @@ use core::time::Duration;
 use crate::gpio::RTCPin as RtcIoWakeupPinType;
 #[cfg(any(esp32c3, esp32c6))]
 use crate::gpio::RTCPinWithResistors as RtcIoWakeupPinType;
-use crate::rtc_cntl::Rtc;
+use crate::{gpio::RTCPinWithResistors, rtc_cntl::Rtc};
 
 #[cfg_attr(esp32, path = "esp32.rs")]
 #[cfg_attr(esp32s3, path = "esp32s3.rs")]
@@ -112,6 +130,24 @@ impl<'a, 'b> Ext1WakeupSource<'a, 'b> {
     }
 }
 
+/// ULP wakeup source
+///
+/// Wakes up the chip when the ULP coprocessor executes a `WAKE` instruction,
+/// e.g. when a sample of
+/// [`UlpAdcSampler`](crate::analog::adc::ulp::UlpAdcSampler) crosses a
+/// threshold. RTC slow memory and the RTC peripherals stay powered while
+/// sleeping, so the ULP keeps running.
+#[derive(Debug, Default, Clone, Copy)]
+#[cfg(esp32)]
+pub struct UlpWakeupSource {}
+
+#[cfg(esp32)]
+impl UlpWakeupSource {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
 /// RTC_IO wakeup source
 ///
 /// RTC_IO wakeup allows configuring any combination of RTC_IO pins with
@@ -195,3 +231,170 @@ bitfield::bitfield! {
 pub trait WakeSource {
     fn apply(&self, rtc: &Rtc, triggers: &mut WakeTriggers, sleep_config: &mut RtcSleepConfig);
 }
+
+/// UART wakeup source (light sleep only)
+///
+/// Wakes up the chip when the RX line of UART0 or UART1 sees the given number
+/// of rising edges, e.g. when characters are typed into a serial console. The
+/// characters used to wake up the chip are not received.
+pub struct UartWakeupSource {
+    uart_num: usize,
+    register_block: &'static crate::peripherals::uart0::RegisterBlock,
+    threshold: u16,
+}
+
+impl UartWakeupSource {
+    /// The smallest number of edges that can be configured
+    #[cfg(not(esp32c6))]
+    pub const MIN_THRESHOLD: u16 = 2;
+    /// The smallest number of edges that can be configured
+    #[cfg(esp32c6)]
+    pub const MIN_THRESHOLD: u16 = 3;
+    /// The largest number of edges that can be configured
+    pub const MAX_THRESHOLD: u16 = Self::MIN_THRESHOLD + 0x3FF;
+
+    /// Wake up when the RX line of `uart` sees `threshold` rising edges.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `uart` isn't UART0 or UART1, or if `threshold` is outside of
+    /// [Self::MIN_THRESHOLD]..=[Self::MAX_THRESHOLD].
+    pub fn new<T, M>(_uart: &crate::uart::Uart<'_, T, M>, threshold: u16) -> Self
+    where
+        T: crate::uart::Instance,
+    {
+        assert!(
+            T::uart_number() < 2,
+            "Only UART0 and UART1 can wake up the chip"
+        );
+        assert!(
+            (Self::MIN_THRESHOLD..=Self::MAX_THRESHOLD).contains(&threshold),
+            "Invalid UART wakeup threshold"
+        );
+
+        Self {
+            uart_num: T::uart_number(),
+            register_block: T::register_block(),
+            threshold,
+        }
+    }
+}
+
+impl WakeSource for UartWakeupSource {
+    fn apply(&self, _rtc: &Rtc, triggers: &mut WakeTriggers, _sleep_config: &mut RtcSleepConfig) {
+        let threshold = self.threshold - Self::MIN_THRESHOLD;
+
+        #[cfg(not(esp32c6))]
+        self.register_block
+            .sleep_conf()
+            .modify(|_, w| unsafe { w.active_threshold().bits(threshold) });
+
+        #[cfg(esp32c6)]
+        {
+            self.register_block.sleep_conf2().modify(|_, w| unsafe {
+                w.wk_mode_sel().bits(0).active_threshold().bits(threshold)
+            });
+            self.register_block
+                .reg_update()
+                .modify(|_, w| w.reg_update().set_bit());
+            while self
+                .register_block
+                .reg_update()
+                .read()
+                .reg_update()
+                .bit_is_set()
+            {
+                // wait
+            }
+        }
+
+        match self.uart_num {
+            0 => triggers.set_uart0(true),
+            _ => triggers.set_uart1(true),
+        }
+    }
+}
+
+/// Pull resistor configuration of a held RTC pin
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum RtcioPull {
+    /// Neither pull-up nor pull-down
+    #[default]
+    None,
+    /// Pull-up enabled
+    Up,
+    /// Pull-down enabled
+    Down,
+}
+
+/// RTC pins to hold while sleeping
+///
+/// Passed along with the wakeup sources, this enables the RTC pull resistors
+/// as configured and latches the current state of the pins when entering sleep,
+/// so output levels and pulls survive deep sleep. The pins stay held after
+/// waking up, until [release_pin_holds] is called.
+///
+/// Pass it before any wakeup source which reconfigures pins, so their holds are
+/// taken into account.
+pub struct PinHold<'a, 'b> {
+    pins: RefCell<&'a mut [(&'b mut dyn RTCPinWithResistors, RtcioPull)]>,
+}
+
+impl<'a, 'b> PinHold<'a, 'b> {
+    pub fn new(pins: &'a mut [(&'b mut dyn RTCPinWithResistors, RtcioPull)]) -> Self {
+        Self {
+            pins: RefCell::new(pins),
+        }
+    }
+}
+
+impl WakeSource for PinHold<'_, '_> {
+    fn apply(&self, _rtc: &Rtc, _triggers: &mut WakeTriggers, _sleep_config: &mut RtcSleepConfig) {
+        // the RTC IO pulls and holds are lost if the RTC peripherals are powered
+        // down
+        #[cfg(xtensa)]
+        _sleep_config.set_rtc_peri_pd_en(false);
+
+        for (pin, pull) in self.pins.borrow_mut().iter_mut() {
+            pin.rtcio_pullup(*pull == RtcioPull::Up);
+            pin.rtcio_pulldown(*pull == RtcioPull::Down);
+            pin.rtcio_pad_hold(true);
+
+            // keep the pin from being isolated when entering deep sleep
+            #[cfg(esp32c3)]
+            unsafe { &*crate::peripherals::RTC_CNTL::PTR }
+                .dig_pad_hold()
+                .modify(|r, w| unsafe { w.bits(r.bits() | 1 << pin.number()) });
+        }
+    }
+}
+
+/// Release the holds of all RTC pins, e.g. the ones set by a [PinHold] before
+/// entering deep sleep.
+///
+/// Held pins ignore any configuration changes, so this should be called after
+/// waking up, before the pins are used again.
+pub fn release_pin_holds() {
+    #[cfg(esp32)]
+    unsafe { &*crate::peripherals::LPWR::PTR }
+        .hold_force()
+        .write(|w| unsafe { w.bits(0) });
+
+    #[cfg(esp32s3)]
+    unsafe { &*crate::peripherals::LPWR::PTR }
+        .pad_hold()
+        .write(|w| unsafe { w.bits(0) });
+
+    #[cfg(esp32c3)]
+    {
+        let rtc_cntl = unsafe { &*crate::peripherals::RTC_CNTL::PTR };
+        rtc_cntl.pad_hold().write(|w| unsafe { w.bits(0) });
+        rtc_cntl.dig_pad_hold().write(|w| unsafe { w.bits(0) });
+    }
+
+    #[cfg(esp32c6)]
+    unsafe { &*crate::peripherals::LP_AON::PTR }
+        .gpio_hold0()
+        .write(|w| unsafe { w.bits(0) });
+}
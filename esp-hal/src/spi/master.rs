@@ -38,12 +38,41 @@
 //!   from [`embassy-embedded-hal`].
 //!
 //!
+//! ## Half-duplex transfers
+//!
+//! Flash, display and sensor chips often frame their transfers with a command,
+//! an address and a number of dummy cycles before the data phase, each of
+//! which can use 1, 2 or 4 data lines. A [`Spi`] created with
+//! [`Spi::new_half_duplex`] implements [`HalfDuplexReadWrite`] for this, and
+//! [`dma::SpiDma`] provides the same for DMA transfers. On the ESP32-S3, SPI2
+//! can also transfer data on 8 lines, see [`SpiDataMode::Octal`].
+//!
+//! All phases are clocked on a single edge. Dual-edge (DDR/DTR) clocking is
+//! only implemented by the memory SPI controllers (SPI0/SPI1) which run the
+//! external flash and PSRAM, the general purpose SPI peripherals can't clock
+//! data on both edges.
+//!
+//! ```rust
+//! spi.write(
+//!     SpiDataMode::Quad,
+//!     Command::Command8(0x32, SpiDataMode::Single),
+//!     Address::Address24(0x1000, SpiDataMode::Single),
+//!     0,
+//!     &data,
+//! )?;
+//! ```
+//!
 //! ## Shared SPI access
 //!
 //! If you have multiple devices on the same SPI bus that each have their own CS
 //! line, you may want to have a look at the implementations provided by
 //! [`embedded-hal-bus`] and [`embassy-embedded-hal`].
 //!
+//! These wrap a blocking or async `SpiBus`, which doesn't work well with DMA
+//! transfers. With the "async" feature, [`shared::SpiDmaSharedBus`] shares a
+//! DMA capable instance between devices which implement both the blocking and
+//! the async `SpiDevice` traits.
+//!
 //! [`embedded-hal-bus`]: https://docs.rs/embedded-hal-bus/latest/embedded_hal_bus/spi/index.html
 //! [`embassy-embedded-hal`]: https://docs.embassy.dev/embassy-embedded-hal/git/default/shared_bus/index.html
 
@@ -64,6 +93,7 @@ use super::{
     HalfDuplexMode,
     IsFullDuplex,
     IsHalfDuplex,
+    SpiBitOrder,
     SpiDataMode,
     SpiMode,
 };
@@ -106,6 +136,14 @@ const EMPTY_WRITE_PAD: u8 = 0x00u8;
 #[allow(unused)]
 const MAX_DMA_SIZE: usize = 32736;
 
+/// The maximum length of a single transaction in bits
+#[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
+const MAX_TRANSACTION_BITS: u32 = 1 << 18;
+#[cfg(esp32s2)]
+const MAX_TRANSACTION_BITS: u32 = 1 << 23;
+#[cfg(esp32)]
+const MAX_TRANSACTION_BITS: u32 = 1 << 24;
+
 /// SPI command, 1 to 16 bits.
 ///
 /// Can be [Command::None] if command phase should be suppressed.
@@ -360,6 +398,52 @@ impl Address {
     }
 }
 
+/// Configuration of a device on one of the hardware CS lines, see
+/// [Spi::device]
+#[cfg(not(esp32))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DeviceConfig {
+    clock_reg: u32,
+    mode: SpiMode,
+    cs_setup: u8,
+    cs_hold: u8,
+}
+
+#[cfg(not(esp32))]
+impl DeviceConfig {
+    /// Create a configuration for the given SPI clock frequency and mode.
+    pub fn new(frequency: HertzU32, mode: SpiMode, clocks: &Clocks) -> Self {
+        Self {
+            clock_reg: clock_register_value(frequency, clocks),
+            mode,
+            cs_setup: 0,
+            cs_hold: 0,
+        }
+    }
+
+    /// Number of SPI clock cycles between activating CS and the first clock
+    /// edge (0..=32).
+    pub fn with_cs_setup(mut self, cycles: u8) -> Self {
+        self.cs_setup = cycles.min(32);
+        self
+    }
+
+    /// Number of SPI clock cycles between the last clock edge and releasing
+    /// CS (0..=31).
+    pub fn with_cs_hold(mut self, cycles: u8) -> Self {
+        self.cs_hold = cycles.min(31);
+        self
+    }
+}
+
+/// A device on one of the hardware CS lines of the bus, see [Spi::device]
+#[cfg(not(esp32))]
+pub struct SpiBusDevice<'a, 'd, T> {
+    spi: &'a mut Spi<'d, T, FullDuplexMode>,
+    cs_index: usize,
+    config: DeviceConfig,
+}
+
 /// Read and Write in half duplex mode.
 pub trait HalfDuplexReadWrite {
     type Error;
@@ -429,6 +513,96 @@ where
     pub fn transfer<'w>(&mut self, words: &'w mut [u8]) -> Result<&'w [u8], Error> {
         self.spi.transfer(words)
     }
+
+    /// Write words of `word_bits` bits (1 to 32) each, e.g. 9 bit frames.
+    ///
+    /// The words are sent back to back in the configured bit order (see
+    /// [Self::set_bit_order]), in one transaction per FIFO worth of words.
+    pub fn write_words(&mut self, words: &[u32], word_bits: u8) -> Result<(), Error> {
+        assert!((1..=32).contains(&word_bits), "Invalid word size");
+
+        let (_, write_order) = self.spi.bit_order();
+        let mut buffer = [0u8; FIFO_SIZE];
+        for chunk in words.chunks(FIFO_SIZE * 8 / word_bits as usize) {
+            let bits = pack_words(chunk, word_bits, write_order, &mut buffer);
+            self.spi.write_bits(&buffer, bits)?;
+        }
+
+        self.spi.flush()
+    }
+
+    /// Send words of `word_bits` bits (1 to 32) each and replace them with the
+    /// words received from the slave.
+    ///
+    /// The words are sent and received back to back in the configured bit
+    /// orders (see [Self::set_bit_order]), in one transaction per FIFO worth of
+    /// words.
+    pub fn transfer_words(&mut self, words: &mut [u32], word_bits: u8) -> Result<(), Error> {
+        assert!((1..=32).contains(&word_bits), "Invalid word size");
+
+        let (read_order, write_order) = self.spi.bit_order();
+        let mut buffer = [0u8; FIFO_SIZE];
+        for chunk in words.chunks_mut(FIFO_SIZE * 8 / word_bits as usize) {
+            let bits = pack_words(chunk, word_bits, write_order, &mut buffer);
+            self.spi.write_bits(&buffer, bits)?;
+            self.spi.flush()?;
+            self.spi.read_bytes_from_fifo(&mut buffer)?;
+            unpack_words(&buffer, word_bits, read_order, chunk);
+        }
+
+        Ok(())
+    }
+}
+
+/// Position of bit `index` of a bit stream in the FIFO
+fn stream_bit(index: usize, order: SpiBitOrder) -> (usize, u8) {
+    match order {
+        SpiBitOrder::MSBFirst => (index / 8, 7 - (index % 8) as u8),
+        SpiBitOrder::LSBFirst => (index / 8, (index % 8) as u8),
+    }
+}
+
+/// Pack `words` into a bit stream, returns the number of bits
+fn pack_words(words: &[u32], word_bits: u8, order: SpiBitOrder, buffer: &mut [u8]) -> u32 {
+    buffer.fill(0);
+
+    let mut index = 0;
+    for word in words {
+        for bit in 0..word_bits {
+            let word_bit = match order {
+                SpiBitOrder::MSBFirst => word_bits - 1 - bit,
+                SpiBitOrder::LSBFirst => bit,
+            };
+
+            if word & (1 << word_bit) != 0 {
+                let (byte, byte_bit) = stream_bit(index, order);
+                buffer[byte] |= 1 << byte_bit;
+            }
+            index += 1;
+        }
+    }
+
+    index as u32
+}
+
+/// Unpack the bit stream in `buffer` into `words`
+fn unpack_words(buffer: &[u8], word_bits: u8, order: SpiBitOrder, words: &mut [u32]) {
+    let mut index = 0;
+    for word in words {
+        *word = 0;
+        for bit in 0..word_bits {
+            let word_bit = match order {
+                SpiBitOrder::MSBFirst => word_bits - 1 - bit,
+                SpiBitOrder::LSBFirst => bit,
+            };
+
+            let (byte, byte_bit) = stream_bit(index, order);
+            if buffer[byte] & (1 << byte_bit) != 0 {
+                *word |= 1 << word_bit;
+            }
+            index += 1;
+        }
+    }
 }
 
 impl<'d, T> Spi<'d, T, FullDuplexMode>
@@ -481,6 +655,51 @@ where
         self
     }
 
+    /// Connect the hardware CS line `index` to the given pin, see
+    /// [Self::device].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instance doesn't have a CS line with this index.
+    #[cfg(not(esp32))]
+    pub fn with_cs_line<CS: OutputPin>(
+        self,
+        index: usize,
+        cs: impl Peripheral<P = CS> + 'd,
+    ) -> Self {
+        let signal = self.spi.cs_signals()[index];
+
+        crate::into_ref!(cs);
+        cs.set_to_push_pull_output()
+            .connect_peripheral_to_output(signal);
+
+        self
+    }
+
+    /// A handle for the device on the hardware CS line `cs_index`.
+    ///
+    /// Each transaction of the handle applies the clock, mode and CS timing of
+    /// `config` and only drives the given CS line, which is kept active for
+    /// the whole transaction. The bus keeps the configuration of the last
+    /// device afterwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the instance doesn't have a CS line with this index.
+    #[cfg(not(esp32))]
+    pub fn device(&mut self, cs_index: usize, config: DeviceConfig) -> SpiBusDevice<'_, 'd, T> {
+        assert!(
+            cs_index < self.spi.cs_signals().len(),
+            "Invalid CS line {cs_index}"
+        );
+
+        SpiBusDevice {
+            spi: self,
+            cs_index,
+            config,
+        }
+    }
+
     /// Setup pins for this SPI instance.
     ///
     /// All pins are optional. Pass [crate::gpio::NO_PIN] if you don't need the
@@ -541,6 +760,13 @@ where
     pub fn change_bus_frequency(&mut self, frequency: HertzU32, clocks: &Clocks) {
         self.spi.ch_bus_freq(frequency, clocks);
     }
+
+    /// Change the bit order of received and sent data.
+    ///
+    /// Takes effect with the next transfer.
+    pub fn set_bit_order(&mut self, read_order: SpiBitOrder, write_order: SpiBitOrder) {
+        self.spi.set_bit_order(read_order, write_order);
+    }
 }
 
 impl<'d, T> Spi<'d, T, HalfDuplexMode>
@@ -718,6 +944,76 @@ where
     pub fn change_bus_frequency(&mut self, frequency: HertzU32, clocks: &Clocks) {
         self.spi.ch_bus_freq(frequency, clocks);
     }
+
+    /// Change the bit order of received and sent data.
+    ///
+    /// Takes effect with the next transfer.
+    pub fn set_bit_order(&mut self, read_order: SpiBitOrder, write_order: SpiBitOrder) {
+        self.spi.set_bit_order(read_order, write_order);
+    }
+}
+
+/// Octal mode
+///
+/// Data phases using [SpiDataMode::Octal] need the four additional data lines
+/// SIO4 to SIO7, besides MOSI (SIO0), MISO (SIO1), SIO2 and SIO3. DDR transfers
+/// aren't supported by the GP-SPI peripherals.
+#[cfg(esp32s3)]
+impl<'d, T> Spi<'d, T, HalfDuplexMode>
+where
+    T: OctalInstance,
+{
+    pub fn with_sio4<SIO4: OutputPin + InputPin>(
+        self,
+        sio4: impl Peripheral<P = SIO4> + 'd,
+    ) -> Self {
+        crate::into_ref!(sio4);
+        sio4.enable_output(true);
+        sio4.connect_peripheral_to_output(self.spi.sio4_output_signal());
+        sio4.enable_input(true);
+        sio4.connect_input_to_peripheral(self.spi.sio4_input_signal());
+
+        self
+    }
+
+    pub fn with_sio5<SIO5: OutputPin + InputPin>(
+        self,
+        sio5: impl Peripheral<P = SIO5> + 'd,
+    ) -> Self {
+        crate::into_ref!(sio5);
+        sio5.enable_output(true);
+        sio5.connect_peripheral_to_output(self.spi.sio5_output_signal());
+        sio5.enable_input(true);
+        sio5.connect_input_to_peripheral(self.spi.sio5_input_signal());
+
+        self
+    }
+
+    pub fn with_sio6<SIO6: OutputPin + InputPin>(
+        self,
+        sio6: impl Peripheral<P = SIO6> + 'd,
+    ) -> Self {
+        crate::into_ref!(sio6);
+        sio6.enable_output(true);
+        sio6.connect_peripheral_to_output(self.spi.sio6_output_signal());
+        sio6.enable_input(true);
+        sio6.connect_input_to_peripheral(self.spi.sio6_input_signal());
+
+        self
+    }
+
+    pub fn with_sio7<SIO7: OutputPin + InputPin>(
+        self,
+        sio7: impl Peripheral<P = SIO7> + 'd,
+    ) -> Self {
+        crate::into_ref!(sio7);
+        sio7.enable_output(true);
+        sio7.connect_peripheral_to_output(self.spi.sio7_output_signal());
+        sio7.enable_input(true);
+        sio7.connect_input_to_peripheral(self.spi.sio7_input_signal());
+
+        self
+    }
 }
 
 impl<T, M> HalfDuplexReadWrite for Spi<'_, T, M>
@@ -744,7 +1040,7 @@ where
         }
 
         self.spi
-            .init_spi_data_mode(cmd.mode(), address.mode(), data_mode);
+            .init_spi_data_mode(cmd.mode(), address.mode(), data_mode)?;
         self.spi.read_bytes_half_duplex(cmd, address, dummy, buffer)
     }
 
@@ -761,7 +1057,7 @@ where
         }
 
         self.spi
-            .init_spi_data_mode(cmd.mode(), address.mode(), data_mode);
+            .init_spi_data_mode(cmd.mode(), address.mode(), data_mode)?;
         self.spi
             .write_bytes_half_duplex(cmd, address, dummy, buffer)
     }
@@ -810,6 +1106,9 @@ where
     }
 }
 
+#[cfg(feature = "async")]
+pub mod shared;
+
 pub mod dma {
     use embedded_dma::{ReadBuffer, WriteBuffer};
 
@@ -900,6 +1199,38 @@ pub mod dma {
             }
         }
     }
+
+    /// The part of a transfer larger than [MAX_DMA_SIZE] which wasn't sent
+    /// yet, it follows in further transactions while CS is kept active.
+    struct PendingChunks<'t> {
+        write: &'t [u8],
+        read: &'t mut [u8],
+    }
+
+    impl<'t> PendingChunks<'t> {
+        fn new(write: &'t [u8], read: &'t mut [u8]) -> Self {
+            Self { write, read }
+        }
+
+        fn is_empty(&self) -> bool {
+            self.write.is_empty() && self.read.is_empty()
+        }
+
+        /// Split off the data of the next transaction
+        fn next_chunk(&mut self) -> (&'t [u8], &'t mut [u8]) {
+            let (write, rest) = self
+                .write
+                .split_at(usize::min(self.write.len(), MAX_DMA_SIZE));
+            self.write = rest;
+
+            let read = core::mem::take(&mut self.read);
+            let (read, rest) = read.split_at_mut(usize::min(read.len(), MAX_DMA_SIZE));
+            self.read = rest;
+
+            (write, read)
+        }
+    }
+
     /// An in-progress DMA transfer
     #[must_use]
     pub struct SpiDmaTransferRxTx<'t, 'd, T, C, M, DmaMode>
@@ -911,6 +1242,7 @@ pub mod dma {
         DmaMode: Mode,
     {
         spi_dma: &'t mut SpiDma<'d, T, C, M, DmaMode>,
+        pending: PendingChunks<'t>,
     }
 
     impl<'t, 'd, T, C, M, DmaMode> DmaTransferRxTx for SpiDmaTransferRxTx<'t, 'd, T, C, M, DmaMode>
@@ -921,20 +1253,34 @@ pub mod dma {
         M: DuplexMode,
         DmaMode: Mode,
     {
-        /// Wait for the DMA transfer to complete
-        fn wait(self) -> Result<(), DmaError> {
-            // Waiting for the DMA transfer is not enough. We need to wait for the
-            // peripheral to finish flushing its buffers, too.
-            self.spi_dma.spi.flush().ok();
+        /// Wait for the DMA transfer to complete, starting the remaining
+        /// transactions of a transfer larger than 32736 bytes
+        fn wait(mut self) -> Result<(), DmaError> {
+            loop {
+                // Waiting for the DMA transfer is not enough. We need to wait for the
+                // peripheral to finish flushing its buffers, too.
+                self.spi_dma.spi.flush().ok();
 
-            if self.spi_dma.channel.rx.has_error() || self.spi_dma.channel.tx.has_error() {
-                Err(DmaError::DescriptorError)
-            } else {
-                Ok(())
+                if self.spi_dma.channel.rx.has_error() || self.spi_dma.channel.tx.has_error() {
+                    break Err(DmaError::DescriptorError);
+                }
+                if self.pending.is_empty() {
+                    break Ok(());
+                }
+
+                self.spi_dma
+                    .start_chunk(&mut self.pending)
+                    .map_err(|error| match error {
+                        super::Error::DmaError(error) => error,
+                        // the chunks fit into one transaction, only the DMA can fail
+                        _ => unreachable!(),
+                    })?;
             }
         }
 
-        /// Check if the DMA transfer is complete
+        /// Check if the running transaction is complete. For a transfer
+        /// larger than 32736 bytes [DmaTransferRxTx::wait] starts the remaining
+        /// ones.
         fn is_done(&self) -> bool {
             let ch = &self.spi_dma.channel;
             ch.tx.is_done() && ch.rx.is_done()
@@ -951,6 +1297,8 @@ pub mod dma {
     {
         fn drop(&mut self) {
             self.spi_dma.spi.flush().ok();
+            // release CS if the remaining chunks were cancelled
+            self.spi_dma.spi.keep_cs_active(false);
         }
     }
 
@@ -965,6 +1313,7 @@ pub mod dma {
         DmaMode: Mode,
     {
         spi_dma: &'t mut SpiDma<'d, T, C, M, DmaMode>,
+        pending: PendingChunks<'t>,
     }
 
     impl<'t, 'd, T, C, M, DmaMode> DmaTransfer for SpiDmaTransfer<'t, 'd, T, C, M, DmaMode>
@@ -975,20 +1324,34 @@ pub mod dma {
         M: DuplexMode,
         DmaMode: Mode,
     {
-        /// Wait for the DMA transfer to complete
-        fn wait(self) -> Result<(), DmaError> {
-            // Waiting for the DMA transfer is not enough. We need to wait for the
-            // peripheral to finish flushing its buffers, too.
-            self.spi_dma.spi.flush().ok();
+        /// Wait for the DMA transfer to complete, starting the remaining
+        /// transactions of a transfer larger than 32736 bytes
+        fn wait(mut self) -> Result<(), DmaError> {
+            loop {
+                // Waiting for the DMA transfer is not enough. We need to wait for the
+                // peripheral to finish flushing its buffers, too.
+                self.spi_dma.spi.flush().ok();
 
-            if self.spi_dma.channel.rx.has_error() || self.spi_dma.channel.tx.has_error() {
-                Err(DmaError::DescriptorError)
-            } else {
-                Ok(())
+                if self.spi_dma.channel.rx.has_error() || self.spi_dma.channel.tx.has_error() {
+                    break Err(DmaError::DescriptorError);
+                }
+                if self.pending.is_empty() {
+                    break Ok(());
+                }
+
+                self.spi_dma
+                    .start_chunk(&mut self.pending)
+                    .map_err(|error| match error {
+                        super::Error::DmaError(error) => error,
+                        // the chunks fit into one transaction, only the DMA can fail
+                        _ => unreachable!(),
+                    })?;
             }
         }
 
-        /// Check if the DMA transfer is complete
+        /// Check if the running transaction is complete. For a transfer
+        /// larger than 32736 bytes [DmaTransfer::wait] starts the remaining
+        /// ones.
         fn is_done(&self) -> bool {
             let ch = &self.spi_dma.channel;
             ch.tx.is_done() && ch.rx.is_done()
@@ -1005,6 +1368,87 @@ pub mod dma {
     {
         fn drop(&mut self) {
             self.spi_dma.spi.flush().ok();
+            // release CS if the remaining chunks were cancelled
+            self.spi_dma.spi.keep_cs_active(false);
+        }
+    }
+
+    /// An in-progress DMA write transfer using the buffer as a ring buffer.
+    ///
+    /// The DMA keeps sending the buffer over and over again, new data is
+    /// pushed into the part which has already been sent. If no new data is
+    /// pushed in time, old data is sent again.
+    ///
+    /// Only the ESP32 and ESP32-S2 send it as one continuous transaction. The
+    /// other chips send it in transactions of up to 32 KiB, with gaps in
+    /// between, see [SpiDma::dma_write_circular].
+    #[must_use]
+    pub struct SpiDmaCircularTransfer<'t, 'd, T, C, M, DmaMode>
+    where
+        T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+        C: ChannelTypes,
+        C::P: SpiPeripheral,
+        M: DuplexMode,
+        DmaMode: Mode,
+    {
+        spi_dma: &'t mut SpiDma<'d, T, C, M, DmaMode>,
+    }
+
+    impl<'t, 'd, T, C, M, DmaMode> SpiDmaCircularTransfer<'t, 'd, T, C, M, DmaMode>
+    where
+        T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+        C: ChannelTypes,
+        C::P: SpiPeripheral,
+        M: DuplexMode,
+        DmaMode: Mode,
+    {
+        /// Amount of bytes which can be pushed.
+        pub fn available(&mut self) -> usize {
+            self.spi_dma.spi.continue_circular_dma();
+            self.spi_dma.channel.tx.available()
+        }
+
+        /// Push bytes into the DMA buffer.
+        pub fn push(&mut self, data: &[u8]) -> Result<usize, super::Error> {
+            self.spi_dma.spi.continue_circular_dma();
+            Ok(self.spi_dma.channel.tx.push(data)?)
+        }
+
+        /// Push bytes into the DMA buffer via the given closure.
+        /// The closure *must* return the actual number of bytes written.
+        /// The closure *might* get called with a slice which is smaller than
+        /// the total available buffer.
+        pub fn push_with(
+            &mut self,
+            f: impl FnOnce(&mut [u8]) -> usize,
+        ) -> Result<usize, super::Error> {
+            self.spi_dma.spi.continue_circular_dma();
+            Ok(self.spi_dma.channel.tx.push_with(f)?)
+        }
+
+        /// Stop the transfer.
+        pub fn stop(self) -> Result<(), DmaError> {
+            // the transfer is stopped on drop
+            if self.spi_dma.channel.tx.has_error() {
+                Err(DmaError::DescriptorError)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    impl<'t, 'd, T, C, M, DmaMode> Drop for SpiDmaCircularTransfer<'t, 'd, T, C, M, DmaMode>
+    where
+        T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+        C: ChannelTypes,
+        C::P: SpiPeripheral,
+        M: DuplexMode,
+        DmaMode: Mode,
+    {
+        fn drop(&mut self) {
+            self.spi_dma
+                .spi
+                .stop_circular_dma(&mut self.spi_dma.channel.tx);
         }
     }
 
@@ -1085,6 +1529,13 @@ pub mod dma {
         pub fn change_bus_frequency(&mut self, frequency: HertzU32, clocks: &Clocks) {
             self.spi.ch_bus_freq(frequency, clocks);
         }
+
+        /// Change the bit order of received and sent data.
+        ///
+        /// Takes effect with the next transfer.
+        pub fn set_bit_order(&mut self, read_order: SpiBitOrder, write_order: SpiBitOrder) {
+            self.spi.set_bit_order(read_order, write_order);
+        }
     }
 
     impl<'d, T, C, M, DmaMode> SpiDma<'d, T, C, M, DmaMode>
@@ -1095,11 +1546,59 @@ pub mod dma {
         M: IsFullDuplex,
         DmaMode: Mode,
     {
+        /// Write all `segments` in a single transaction, e.g. a command header
+        /// and the payload, without copying them into one buffer.
+        ///
+        /// Each segment is sent from where it is, linked by the DMA
+        /// descriptors, so the channel needs enough descriptors for all of
+        /// them and all segments must be located in RAM, otherwise
+        /// [DmaError::DescriptorError] is returned before anything is sent.
+        pub fn write_segments(&mut self, segments: &[&[u8]]) -> Result<(), Error> {
+            self.spi
+                .start_write_segments_dma(segments, &mut self.channel.tx, false)?;
+
+            while !self.channel.tx.is_done() {}
+            self.spi.flush()
+        }
+
+        /// Continuously write the buffer, using it as a ring buffer.
+        ///
+        /// Returns a [SpiDmaCircularTransfer] to push new data into the
+        /// buffer while it is being sent, e.g. for audio DACs or LED strips.
+        ///
+        /// The output is only gapless on the ESP32 and ESP32-S2, where the
+        /// SPI transaction never ends. The other chips can't do a transaction
+        /// longer than 32 KiB, so the output is not continuous there: every
+        /// 32 KiB the transaction ends, the chip select line is deasserted
+        /// and the clock stops until the next transaction is started. That
+        /// happens when data is pushed or the available space is queried, or
+        /// from the interrupt handler in async mode, see
+        /// [SpiDma::bind_circular_handler]. Use it only for devices which
+        /// tolerate these gaps.
+        ///
+        /// Dropping the transfer stops it, on chips with the 32 KiB limit
+        /// this waits for the running transaction to end.
+        ///
+        /// The buffer must be larger than 2 bytes.
+        pub fn dma_write_circular<'t, TXBUF>(
+            &'t mut self,
+            words: &'t TXBUF,
+        ) -> Result<SpiDmaCircularTransfer<'t, 'd, T, C, M, DmaMode>, super::Error>
+        where
+            TXBUF: ReadBuffer<Word = u8>,
+        {
+            let (ptr, len) = unsafe { words.read_buffer() };
+
+            self.spi
+                .start_write_bytes_dma_circular(ptr, len, &mut self.channel.tx)?;
+            Ok(SpiDmaCircularTransfer { spi_dma: self })
+        }
+
         /// Perform a DMA write.
         ///
         /// This will return a [SpiDmaTransfer] owning the buffer(s) and the SPI
-        /// instance. The maximum amount of data to be sent is 32736
-        /// bytes.
+        /// instance. Buffers larger than 32736 bytes are sent in several
+        /// transactions, with CS kept active in between.
         #[cfg_attr(feature = "place-spi-driver-in-ram", ram)]
         pub fn dma_write<'t, TXBUF>(
             &'t mut self,
@@ -1109,21 +1608,21 @@ pub mod dma {
             TXBUF: ReadBuffer<Word = u8>,
         {
             let (ptr, len) = unsafe { words.read_buffer() };
-
-            if len > MAX_DMA_SIZE {
-                return Err(super::Error::MaxDmaTransferSizeExceeded);
-            }
-
-            self.spi
-                .start_write_bytes_dma(ptr, len, &mut self.channel.tx, false)?;
-            Ok(SpiDmaTransfer { spi_dma: self })
+            let words = unsafe { core::slice::from_raw_parts(ptr, len) };
+
+            let mut pending = PendingChunks::new(words, &mut []);
+            self.start_chunk(&mut pending)?;
+            Ok(SpiDmaTransfer {
+                spi_dma: self,
+                pending,
+            })
         }
 
         /// Perform a DMA read.
         ///
         /// This will return a [SpiDmaTransfer] owning the buffer(s) and the SPI
-        /// instance. The maximum amount of data to be received is 32736
-        /// bytes.
+        /// instance. Buffers larger than 32736 bytes are received in several
+        /// transactions, with CS kept active in between.
         #[cfg_attr(feature = "place-spi-driver-in-ram", ram)]
         pub fn dma_read<'t, RXBUF>(
             &'t mut self,
@@ -1133,21 +1632,21 @@ pub mod dma {
             RXBUF: WriteBuffer<Word = u8>,
         {
             let (ptr, len) = unsafe { words.write_buffer() };
-
-            if len > MAX_DMA_SIZE {
-                return Err(super::Error::MaxDmaTransferSizeExceeded);
-            }
-
-            self.spi
-                .start_read_bytes_dma(ptr, len, &mut self.channel.rx, false)?;
-            Ok(SpiDmaTransfer { spi_dma: self })
+            let words = unsafe { core::slice::from_raw_parts_mut(ptr, len) };
+
+            let mut pending = PendingChunks::new(&[], words);
+            self.start_chunk(&mut pending)?;
+            Ok(SpiDmaTransfer {
+                spi_dma: self,
+                pending,
+            })
         }
 
         /// Perform a DMA transfer.
         ///
         /// This will return a [SpiDmaTransfer] owning the buffer(s) and the SPI
-        /// instance. The maximum amount of data to be sent/received is
-        /// 32736 bytes.
+        /// instance. Buffers larger than 32736 bytes are transferred in
+        /// several transactions, with CS kept active in between.
         pub fn dma_transfer<'t, TXBUF, RXBUF>(
             &'t mut self,
             words: &'t TXBUF,
@@ -1159,21 +1658,59 @@ pub mod dma {
         {
             let (write_ptr, write_len) = unsafe { words.read_buffer() };
             let (read_ptr, read_len) = unsafe { read_buffer.write_buffer() };
+            let write = unsafe { core::slice::from_raw_parts(write_ptr, write_len) };
+            let read = unsafe { core::slice::from_raw_parts_mut(read_ptr, read_len) };
+
+            let mut pending = PendingChunks::new(write, read);
+            self.start_chunk(&mut pending)?;
+            Ok(SpiDmaTransferRxTx {
+                spi_dma: self,
+                pending,
+            })
+        }
+    }
 
-            if write_len > MAX_DMA_SIZE || read_len > MAX_DMA_SIZE {
-                return Err(super::Error::MaxDmaTransferSizeExceeded);
-            }
+    impl<'d, T, C, M, DmaMode> SpiDma<'d, T, C, M, DmaMode>
+    where
+        T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+        C: ChannelTypes,
+        C::P: SpiPeripheral,
+        M: DuplexMode,
+        DmaMode: Mode,
+    {
+        /// Start the next transaction of a transfer, keeping CS active if
+        /// more follow
+        fn start_chunk(&mut self, pending: &mut PendingChunks<'_>) -> Result<(), super::Error> {
+            let (write, read) = pending.next_chunk();
+            self.spi.keep_cs_active(!pending.is_empty());
+
+            let result = if read.is_empty() {
+                self.spi.start_write_bytes_dma(
+                    write.as_ptr(),
+                    write.len(),
+                    &mut self.channel.tx,
+                    false,
+                )
+            } else if write.is_empty() {
+                self.spi.start_read_bytes_dma(
+                    read.as_mut_ptr(),
+                    read.len(),
+                    &mut self.channel.rx,
+                    false,
+                )
+            } else {
+                self.spi.start_transfer_dma(
+                    write.as_ptr(),
+                    write.len(),
+                    read.as_mut_ptr(),
+                    read.len(),
+                    &mut self.channel.tx,
+                    &mut self.channel.rx,
+                    false,
+                )
+            };
 
-            self.spi.start_transfer_dma(
-                write_ptr,
-                write_len,
-                read_ptr,
-                read_len,
-                &mut self.channel.tx,
-                &mut self.channel.rx,
-                false,
-            )?;
-            Ok(SpiDmaTransferRxTx { spi_dma: self })
+            result.inspect_err(|_| self.spi.keep_cs_active(false))
         }
     }
 
@@ -1203,57 +1740,14 @@ pub mod dma {
                 return Err(super::Error::MaxDmaTransferSizeExceeded);
             }
 
-            self.spi.init_half_duplex(
-                false,
-                !cmd.is_none(),
-                !address.is_none(),
-                false,
-                dummy != 0,
-                len == 0,
-            );
-            self.spi
-                .init_spi_data_mode(cmd.mode(), address.mode(), data_mode);
-
-            // set cmd, address, dummy cycles
-            let reg_block = self.spi.register_block();
-            if !cmd.is_none() {
-                reg_block.user2().modify(|_, w| {
-                    w.usr_command_bitlen()
-                        .variant((cmd.width() - 1) as u8)
-                        .usr_command_value()
-                        .variant(cmd.value())
-                });
-            }
-
-            #[cfg(not(esp32))]
-            if !address.is_none() {
-                reg_block
-                    .user1()
-                    .modify(|_, w| w.usr_addr_bitlen().variant((address.width() - 1) as u8));
-
-                let addr = address.value() << (32 - address.width());
-                reg_block.addr().write(|w| w.usr_addr_value().variant(addr));
-            }
-
-            #[cfg(esp32)]
-            if !address.is_none() {
-                reg_block.user1().modify(|r, w| unsafe {
-                    w.bits(r.bits() & !(0x3f << 26) | (((address.width() - 1) as u32) & 0x3f) << 26)
-                });
-
-                let addr = address.value() << (32 - address.width());
-                reg_block.addr().write(|w| unsafe { w.bits(addr) });
-            }
-
-            if dummy > 0 {
-                reg_block
-                    .user1()
-                    .modify(|_, w| w.usr_dummy_cyclelen().variant(dummy - 1));
-            }
+            self.setup_half_duplex(false, data_mode, cmd, address, dummy, len)?;
 
             self.spi
                 .start_read_bytes_dma(ptr, len, &mut self.channel.rx, false)?;
-            Ok(SpiDmaTransfer { spi_dma: self })
+            Ok(SpiDmaTransfer {
+                spi_dma: self,
+                pending: PendingChunks::new(&[], &mut []),
+            })
         }
 
         #[cfg_attr(feature = "place-spi-driver-in-ram", ram)]
@@ -1274,16 +1768,35 @@ pub mod dma {
                 return Err(super::Error::MaxDmaTransferSizeExceeded);
             }
 
+            self.setup_half_duplex(true, data_mode, cmd, address, dummy, len)?;
+
+            self.spi
+                .start_write_bytes_dma(ptr, len, &mut self.channel.tx, false)?;
+            Ok(SpiDmaTransfer {
+                spi_dma: self,
+                pending: PendingChunks::new(&[], &mut []),
+            })
+        }
+
+        fn setup_half_duplex(
+            &mut self,
+            is_write: bool,
+            data_mode: SpiDataMode,
+            cmd: Command,
+            address: Address,
+            dummy: u8,
+            len: usize,
+        ) -> Result<(), super::Error> {
+            self.spi
+                .init_spi_data_mode(cmd.mode(), address.mode(), data_mode)?;
             self.spi.init_half_duplex(
-                true,
+                is_write,
                 !cmd.is_none(),
                 !address.is_none(),
                 false,
                 dummy != 0,
                 len == 0,
             );
-            self.spi
-                .init_spi_data_mode(cmd.mode(), address.mode(), data_mode);
 
             // set cmd, address, dummy cycles
             let reg_block = self.spi.register_block();
@@ -1322,9 +1835,7 @@ pub mod dma {
                     .modify(|_, w| w.usr_dummy_cyclelen().variant(dummy - 1));
             }
 
-            self.spi
-                .start_write_bytes_dma(ptr, len, &mut self.channel.tx, false)?;
-            Ok(SpiDmaTransfer { spi_dma: self })
+            Ok(())
         }
     }
 
@@ -1421,7 +1932,184 @@ pub mod dma {
 
     #[cfg(feature = "async")]
     mod asynch {
+        #[cfg(not(any(esp32, esp32s2)))]
+        use core::{
+            pin::Pin,
+            task::{Context, Poll},
+        };
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        use embassy_futures::select::select;
+        #[cfg(not(any(esp32, esp32s2)))]
+        use embassy_sync::waitqueue::AtomicWaker;
+        #[cfg(not(any(esp32, esp32s2)))]
+        use procmacros::handler;
+
         use super::*;
+        use crate::dma::asynch::DmaTxDoneChFuture;
+
+        #[cfg(all(spi3, not(any(esp32, esp32s2))))]
+        const NUM_SPI: usize = 2;
+        #[cfg(not(any(spi3, esp32, esp32s2)))]
+        const NUM_SPI: usize = 1;
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        #[allow(clippy::declare_interior_mutable_const)]
+        const INIT: AtomicWaker = AtomicWaker::new();
+        #[cfg(not(any(esp32, esp32s2)))]
+        static WAKERS: [AtomicWaker; NUM_SPI] = [INIT; NUM_SPI];
+
+        /// Resolves when the running transaction ended
+        #[cfg(not(any(esp32, esp32s2)))]
+        struct TransDoneFuture<'a, T>
+        where
+            T: Instance,
+        {
+            instance: &'a T,
+        }
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        impl<'a, T> TransDoneFuture<'a, T>
+        where
+            T: Instance,
+        {
+            fn new(instance: &'a T) -> Self {
+                instance
+                    .register_block()
+                    .dma_int_ena()
+                    .modify(|_, w| w.trans_done().set_bit());
+
+                Self { instance }
+            }
+        }
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        impl<'a, T> core::future::Future for TransDoneFuture<'a, T>
+        where
+            T: Instance,
+        {
+            type Output = ();
+
+            fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+                WAKERS[self.instance.spi_num() as usize - 2].register(ctx.waker());
+
+                // the interrupt handler disables the interrupt once it fired
+                if self
+                    .instance
+                    .register_block()
+                    .dma_int_ena()
+                    .read()
+                    .trans_done()
+                    .bit_is_clear()
+                {
+                    Poll::Ready(())
+                } else {
+                    Poll::Pending
+                }
+            }
+        }
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        impl<'a, T> Drop for TransDoneFuture<'a, T>
+        where
+            T: Instance,
+        {
+            fn drop(&mut self) {
+                self.instance
+                    .register_block()
+                    .dma_int_ena()
+                    .modify(|_, w| w.trans_done().clear_bit());
+            }
+        }
+
+        #[cfg(not(any(esp32, esp32s2)))]
+        #[handler]
+        fn spi2_circular_handler() {
+            unsafe { &*crate::peripherals::SPI2::PTR }
+                .dma_int_ena()
+                .modify(|_, w| w.trans_done().clear_bit());
+
+            WAKERS[0].wake();
+        }
+
+        #[cfg(all(spi3, not(any(esp32, esp32s2))))]
+        #[handler]
+        fn spi3_circular_handler() {
+            unsafe { &*crate::peripherals::SPI3::PTR }
+                .dma_int_ena()
+                .modify(|_, w| w.trans_done().clear_bit());
+
+            WAKERS[1].wake();
+        }
+
+        impl<'d, T, C, M> SpiDma<'d, T, C, M, crate::Async>
+        where
+            T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+            C: ChannelTypes,
+            C::P: SpiPeripheral,
+            M: DuplexMode,
+        {
+            /// Bind the driver's interrupt handler needed by
+            /// [SpiDmaCircularTransfer::available_async] and
+            /// [SpiDmaCircularTransfer::push_async].
+            ///
+            /// On chips other than the ESP32 and ESP32-S2 a circular transfer
+            /// is sent in several transactions, the handler signals the end of
+            /// each of them. This replaces a handler set with
+            /// [SpiDma::set_interrupt_handler], call it once before the first
+            /// circular transfer. It does nothing on the ESP32 and ESP32-S2.
+            pub fn bind_circular_handler(&mut self) {
+                #[cfg(not(any(esp32, esp32s2)))]
+                {
+                    let handler = match self.spi.spi_num() {
+                        2 => spi2_circular_handler,
+                        #[cfg(spi3)]
+                        3 => spi3_circular_handler,
+                        _ => panic!("Illegal SPI instance"),
+                    };
+                    self.spi.set_interrupt_handler(handler);
+                }
+            }
+        }
+
+        impl<'t, 'd, T, C, M> SpiDmaCircularTransfer<'t, 'd, T, C, M, crate::Async>
+        where
+            T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+            C: ChannelTypes,
+            C::P: SpiPeripheral,
+            M: DuplexMode,
+        {
+            /// Amount of bytes which can be pushed, waiting until there is
+            /// space for at least one byte.
+            ///
+            /// On chips other than the ESP32 and ESP32-S2 this also starts the
+            /// next transaction once the running one ended, which needs the
+            /// handler bound with [SpiDma::bind_circular_handler].
+            pub async fn available_async(&mut self) -> usize {
+                loop {
+                    let available = self.available();
+                    if available != 0 {
+                        break available;
+                    }
+
+                    let done = DmaTxDoneChFuture::new(&mut self.spi_dma.channel.tx);
+
+                    // the transaction never ends in continuous mode
+                    #[cfg(any(esp32, esp32s2))]
+                    done.await;
+
+                    #[cfg(not(any(esp32, esp32s2)))]
+                    select(done, TransDoneFuture::new(&*self.spi_dma.spi)).await;
+                }
+            }
+
+            /// Push bytes into the DMA buffer, waiting until there is space for
+            /// at least one byte. Returns the amount of bytes pushed.
+            pub async fn push_async(&mut self, data: &[u8]) -> Result<usize, super::Error> {
+                self.available_async().await;
+                self.push(data)
+            }
+        }
 
         impl<'d, T, C, M> embedded_hal_async::spi::SpiBus for SpiDma<'d, T, C, M, crate::Async>
         where
@@ -1431,21 +2119,31 @@ pub mod dma {
             M: IsFullDuplex,
         {
             async fn read(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
-                self.spi.start_read_bytes_dma(
-                    words.as_mut_ptr(),
-                    words.len(),
-                    &mut self.channel.rx,
-                    true,
-                )?;
+                let mut spi = CsGuard(&mut *self.spi);
+                let mut chunks = words.chunks_mut(MAX_DMA_SIZE).peekable();
+                while let Some(chunk) = chunks.next() {
+                    spi.keep_cs_active(chunks.peek().is_some());
+                    spi.start_read_bytes_dma(
+                        chunk.as_mut_ptr(),
+                        chunk.len(),
+                        &mut self.channel.rx,
+                        true,
+                    )?;
 
-                crate::dma::asynch::DmaRxFuture::new(&mut self.channel.rx).await;
+                    crate::dma::asynch::DmaRxFuture::new(&mut self.channel.rx).await;
+
+                    spi.flush()?;
+                }
 
                 Ok(())
             }
 
             async fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
-                for chunk in words.chunks(MAX_DMA_SIZE) {
-                    self.spi.start_write_bytes_dma(
+                let mut spi = CsGuard(&mut *self.spi);
+                let mut chunks = words.chunks(MAX_DMA_SIZE).peekable();
+                while let Some(chunk) = chunks.next() {
+                    spi.keep_cs_active(chunks.peek().is_some());
+                    spi.start_write_bytes_dma(
                         chunk.as_ptr(),
                         chunk.len(),
                         &mut self.channel.tx,
@@ -1456,25 +2154,31 @@ pub mod dma {
 
                     // FIXME: in the future we should use the peripheral DMA status registers to
                     // await on both the dma transfer _and_ the peripherals status
-                    self.spi.flush()?;
+                    spi.flush()?;
                 }
 
                 Ok(())
             }
 
             async fn transfer(&mut self, read: &mut [u8], write: &[u8]) -> Result<(), Self::Error> {
+                let len = usize::max(write.len(), read.len());
+
+                let mut spi = CsGuard(&mut *self.spi);
                 let mut idx = 0;
                 loop {
-                    let write_idx = isize::min(idx, write.len() as isize);
-                    let write_len = usize::min(write.len() - idx as usize, MAX_DMA_SIZE);
+                    let write_idx = usize::min(idx, write.len());
+                    let write_len = usize::min(write.len() - write_idx, MAX_DMA_SIZE);
+
+                    let read_idx = usize::min(idx, read.len());
+                    let read_len = usize::min(read.len() - read_idx, MAX_DMA_SIZE);
 
-                    let read_idx = isize::min(idx, read.len() as isize);
-                    let read_len = usize::min(read.len() - idx as usize, MAX_DMA_SIZE);
+                    idx += MAX_DMA_SIZE;
 
-                    self.spi.start_transfer_dma(
-                        unsafe { write.as_ptr().offset(write_idx) },
+                    spi.keep_cs_active(idx < len);
+                    spi.start_transfer_dma(
+                        unsafe { write.as_ptr().add(write_idx) },
                         write_len,
-                        unsafe { read.as_mut_ptr().offset(read_idx) },
+                        unsafe { read.as_mut_ptr().add(read_idx) },
                         read_len,
                         &mut self.channel.tx,
                         &mut self.channel.rx,
@@ -1489,10 +2193,9 @@ pub mod dma {
 
                     // FIXME: in the future we should use the peripheral DMA status registers to
                     // await on both the dma transfer _and_ the peripherals status
-                    self.spi.flush()?;
+                    spi.flush()?;
 
-                    idx += MAX_DMA_SIZE as isize;
-                    if idx >= write.len() as isize && idx >= read.len() as isize {
+                    if idx >= len {
                         break;
                     }
                 }
@@ -1501,8 +2204,11 @@ pub mod dma {
             }
 
             async fn transfer_in_place(&mut self, words: &mut [u8]) -> Result<(), Self::Error> {
-                for chunk in words.chunks_mut(MAX_DMA_SIZE) {
-                    self.spi.start_transfer_dma(
+                let mut spi = CsGuard(&mut *self.spi);
+                let mut chunks = words.chunks_mut(MAX_DMA_SIZE).peekable();
+                while let Some(chunk) = chunks.next() {
+                    spi.keep_cs_active(chunks.peek().is_some());
+                    spi.start_transfer_dma(
                         chunk.as_ptr(),
                         chunk.len(),
                         chunk.as_mut_ptr(),
@@ -1520,7 +2226,7 @@ pub mod dma {
 
                     // FIXME: in the future we should use the peripheral DMA status registers to
                     // await on both the dma transfer _and_ the peripherals status
-                    self.spi.flush()?;
+                    spi.flush()?;
                 }
 
                 Ok(())
@@ -1532,6 +2238,96 @@ pub mod dma {
             }
         }
 
+        impl<'d, T, C, M> SpiDma<'d, T, C, M, crate::Async>
+        where
+            T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+            C: ChannelTypes,
+            C::P: SpiPeripheral,
+            M: IsFullDuplex,
+        {
+            /// Write all `segments` in a single transaction, see
+            /// [SpiDma::write_segments].
+            pub async fn write_segments_async(
+                &mut self,
+                segments: &[&[u8]],
+            ) -> Result<(), super::Error> {
+                self.spi
+                    .start_write_segments_dma(segments, &mut self.channel.tx, true)?;
+
+                crate::dma::asynch::DmaTxFuture::new(&mut self.channel.tx).await;
+
+                // FIXME: in the future we should use the peripheral DMA status registers to
+                // await on both the dma transfer _and_ the peripherals status
+                self.spi.flush()
+            }
+        }
+
+        impl<'d, T, C, M> SpiDma<'d, T, C, M, crate::Async>
+        where
+            T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+            C: ChannelTypes,
+            C::P: SpiPeripheral,
+            M: IsHalfDuplex,
+        {
+            /// Half-duplex read with command, address and dummy phases.
+            pub async fn read_async(
+                &mut self,
+                data_mode: SpiDataMode,
+                cmd: Command,
+                address: Address,
+                dummy: u8,
+                buffer: &mut [u8],
+            ) -> Result<(), super::Error> {
+                if buffer.len() > MAX_DMA_SIZE {
+                    return Err(super::Error::MaxDmaTransferSizeExceeded);
+                }
+
+                self.setup_half_duplex(false, data_mode, cmd, address, dummy, buffer.len())?;
+
+                self.spi.start_read_bytes_dma(
+                    buffer.as_mut_ptr(),
+                    buffer.len(),
+                    &mut self.channel.rx,
+                    true,
+                )?;
+
+                crate::dma::asynch::DmaRxFuture::new(&mut self.channel.rx).await;
+
+                Ok(())
+            }
+
+            /// Half-duplex write with command, address and dummy phases.
+            pub async fn write_async(
+                &mut self,
+                data_mode: SpiDataMode,
+                cmd: Command,
+                address: Address,
+                dummy: u8,
+                buffer: &[u8],
+            ) -> Result<(), super::Error> {
+                if buffer.len() > MAX_DMA_SIZE {
+                    return Err(super::Error::MaxDmaTransferSizeExceeded);
+                }
+
+                self.setup_half_duplex(true, data_mode, cmd, address, dummy, buffer.len())?;
+
+                self.spi.start_write_bytes_dma(
+                    buffer.as_ptr(),
+                    buffer.len(),
+                    &mut self.channel.tx,
+                    true,
+                )?;
+
+                crate::dma::asynch::DmaTxFuture::new(&mut self.channel.tx).await;
+
+                // FIXME: in the future we should use the peripheral DMA status registers to
+                // await on both the dma transfer _and_ the peripherals status
+                self.spi.flush()?;
+
+                Ok(())
+            }
+        }
+
         impl<T: embedded_hal_async::spi::SpiBus, const SIZE: usize> embedded_hal_async::spi::SpiBus
             for crate::FlashSafeDma<T, SIZE>
         {
@@ -1692,6 +2488,49 @@ mod ehal1 {
         type Error = super::Error;
     }
 
+    #[cfg(not(esp32))]
+    impl<T> embedded_hal::spi::ErrorType for SpiBusDevice<'_, '_, T> {
+        type Error = super::Error;
+    }
+
+    #[cfg(not(esp32))]
+    impl<T> embedded_hal::spi::SpiDevice for SpiBusDevice<'_, '_, T>
+    where
+        T: Instance,
+    {
+        fn transaction(
+            &mut self,
+            operations: &mut [embedded_hal::spi::Operation<'_, u8>],
+        ) -> Result<(), Self::Error> {
+            use embedded_hal::spi::Operation;
+
+            self.spi.spi.flush()?;
+            self.spi.spi.select_device(self.cs_index, &self.config);
+
+            let mut result = Ok(());
+            for operation in operations {
+                result = match operation {
+                    Operation::Read(words) => SpiBus::read(self.spi, words),
+                    Operation::Write(words) => SpiBus::write(self.spi, words),
+                    Operation::Transfer(read, write) => SpiBus::transfer(self.spi, read, write),
+                    Operation::TransferInPlace(words) => SpiBus::transfer_in_place(self.spi, words),
+                    Operation::DelayNs(ns) => self.spi.spi.flush().map(|_| {
+                        crate::rom::ets_delay_us(ns.div_ceil(1000));
+                    }),
+                };
+
+                if result.is_err() {
+                    break;
+                }
+            }
+
+            let flushed = self.spi.spi.flush();
+            self.spi.spi.deselect_device();
+
+            result.and(flushed)
+        }
+    }
+
     impl<T, M> FullDuplex for Spi<'_, T, M>
     where
         T: Instance,
@@ -1780,6 +2619,42 @@ mod ehal1 {
     }
 }
 
+/// Releases the CS line kept active between the transactions of a transfer
+/// larger than [MAX_DMA_SIZE] once the transfer ends, fails or is cancelled.
+struct CsGuard<'a, T>(&'a mut T)
+where
+    T: Instance + ?Sized;
+
+impl<'a, T> core::ops::Deref for CsGuard<'a, T>
+where
+    T: Instance + ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.0
+    }
+}
+
+impl<'a, T> core::ops::DerefMut for CsGuard<'a, T>
+where
+    T: Instance + ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        self.0
+    }
+}
+
+impl<'a, T> Drop for CsGuard<'a, T>
+where
+    T: Instance + ?Sized,
+{
+    fn drop(&mut self) {
+        self.0.flush().ok();
+        self.0.keep_cs_active(false);
+    }
+}
+
 pub trait InstanceDma<TX, RX>: Instance
 where
     TX: Tx,
@@ -1791,8 +2666,11 @@ where
         tx: &mut TX,
         rx: &mut RX,
     ) -> Result<&'w [u8], Error> {
-        for chunk in words.chunks_mut(MAX_DMA_SIZE) {
-            self.start_transfer_dma(
+        let mut spi = CsGuard(self);
+        let mut chunks = words.chunks_mut(MAX_DMA_SIZE).peekable();
+        while let Some(chunk) = chunks.next() {
+            spi.keep_cs_active(chunks.peek().is_some());
+            spi.start_transfer_dma(
                 chunk.as_ptr(),
                 chunk.len(),
                 chunk.as_mut_ptr(),
@@ -1803,7 +2681,7 @@ where
             )?;
 
             while !tx.is_done() && !rx.is_done() {}
-            self.flush().unwrap();
+            spi.flush().unwrap();
         }
 
         Ok(words)
@@ -1816,18 +2694,24 @@ where
         tx: &mut TX,
         rx: &mut RX,
     ) -> Result<&'w [u8], Error> {
+        let len = usize::max(write_buffer.len(), read_buffer.len());
+
+        let mut spi = CsGuard(self);
         let mut idx = 0;
         loop {
-            let write_idx = isize::min(idx, write_buffer.len() as isize);
-            let write_len = usize::min(write_buffer.len() - idx as usize, MAX_DMA_SIZE);
+            let write_idx = usize::min(idx, write_buffer.len());
+            let write_len = usize::min(write_buffer.len() - write_idx, MAX_DMA_SIZE);
 
-            let read_idx = isize::min(idx, read_buffer.len() as isize);
-            let read_len = usize::min(read_buffer.len() - idx as usize, MAX_DMA_SIZE);
+            let read_idx = usize::min(idx, read_buffer.len());
+            let read_len = usize::min(read_buffer.len() - read_idx, MAX_DMA_SIZE);
 
-            self.start_transfer_dma(
-                unsafe { write_buffer.as_ptr().offset(write_idx) },
+            idx += MAX_DMA_SIZE;
+
+            spi.keep_cs_active(idx < len);
+            spi.start_transfer_dma(
+                unsafe { write_buffer.as_ptr().add(write_idx) },
                 write_len,
-                unsafe { read_buffer.as_mut_ptr().offset(read_idx) },
+                unsafe { read_buffer.as_mut_ptr().add(read_idx) },
                 read_len,
                 tx,
                 rx,
@@ -1835,10 +2719,9 @@ where
             )?;
 
             while !tx.is_done() && !rx.is_done() {}
-            self.flush().unwrap();
+            spi.flush().unwrap();
 
-            idx += MAX_DMA_SIZE as isize;
-            if idx >= write_buffer.len() as isize && idx >= read_buffer.len() as isize {
+            if idx >= len {
                 break;
             }
         }
@@ -1861,60 +2744,127 @@ where
         self.configure_datalen(usize::max(read_buffer_len, write_buffer_len) as u32 * 8);
 
         tx.is_done();
-        rx.is_done();
+        rx.is_done();
+
+        self.enable_dma();
+        self.update();
+
+        reset_dma_before_load_dma_dscr(reg_block);
+        tx.prepare_transfer_without_start(
+            self.dma_peripheral(),
+            false,
+            write_buffer_ptr,
+            write_buffer_len,
+        )
+        .and_then(|_| tx.start_transfer())?;
+        rx.prepare_transfer_without_start(
+            false,
+            self.dma_peripheral(),
+            read_buffer_ptr,
+            read_buffer_len,
+        )
+        .and_then(|_| rx.start_transfer())?;
+
+        self.clear_dma_interrupts();
+        reset_dma_before_usr_cmd(reg_block);
+
+        if listen {
+            tx.listen_eof();
+            rx.listen_eof();
+        }
+        reg_block.cmd().modify(|_, w| w.usr().set_bit());
+
+        Ok(())
+    }
+
+    fn write_bytes_dma<'w>(&mut self, words: &'w [u8], tx: &mut TX) -> Result<&'w [u8], Error> {
+        let mut spi = CsGuard(self);
+        let mut chunks = words.chunks(MAX_DMA_SIZE).peekable();
+        while let Some(chunk) = chunks.next() {
+            spi.keep_cs_active(chunks.peek().is_some());
+            spi.start_write_bytes_dma(chunk.as_ptr(), chunk.len(), tx, false)?;
+
+            while !tx.is_done() {}
+            spi.flush().unwrap(); // seems "is_done" doesn't work as intended?
+        }
+
+        Ok(words)
+    }
+
+    #[cfg_attr(feature = "place-spi-driver-in-ram", ram)]
+    fn start_write_bytes_dma(
+        &mut self,
+        ptr: *const u8,
+        len: usize,
+        tx: &mut TX,
+        listen: bool,
+    ) -> Result<(), Error> {
+        let reg_block = self.register_block();
+        self.configure_datalen(len as u32 * 8);
+
+        tx.is_done();
+
+        self.enable_dma();
+        self.update();
+
+        reset_dma_before_load_dma_dscr(reg_block);
+        tx.prepare_transfer_without_start(self.dma_peripheral(), false, ptr, len)
+            .and_then(|_| tx.start_transfer())?;
+
+        self.clear_dma_interrupts();
+        reset_dma_before_usr_cmd(reg_block);
+
+        if listen {
+            tx.listen_eof();
+        }
+        reg_block.cmd().modify(|_, w| w.usr().set_bit());
+
+        Ok(())
+    }
+
+    #[cfg_attr(feature = "place-spi-driver-in-ram", ram)]
+    fn start_write_segments_dma(
+        &mut self,
+        segments: &[&[u8]],
+        tx: &mut TX,
+        listen: bool,
+    ) -> Result<(), Error> {
+        let len: usize = segments.iter().map(|segment| segment.len()).sum();
+        if len as u32 * 8 > MAX_TRANSACTION_BITS {
+            return Err(Error::MaxDmaTransferSizeExceeded);
+        }
+
+        let reg_block = self.register_block();
+        self.configure_datalen(len as u32 * 8);
+
+        tx.is_done();
 
         self.enable_dma();
         self.update();
 
         reset_dma_before_load_dma_dscr(reg_block);
-        tx.prepare_transfer_without_start(
-            self.dma_peripheral(),
-            false,
-            write_buffer_ptr,
-            write_buffer_len,
-        )
-        .and_then(|_| tx.start_transfer())?;
-        rx.prepare_transfer_without_start(
-            false,
-            self.dma_peripheral(),
-            read_buffer_ptr,
-            read_buffer_len,
-        )
-        .and_then(|_| rx.start_transfer())?;
+        tx.prepare_segments_without_start(self.dma_peripheral(), segments)
+            .and_then(|_| tx.start_transfer())?;
 
         self.clear_dma_interrupts();
         reset_dma_before_usr_cmd(reg_block);
 
         if listen {
             tx.listen_eof();
-            rx.listen_eof();
         }
         reg_block.cmd().modify(|_, w| w.usr().set_bit());
 
         Ok(())
     }
 
-    fn write_bytes_dma<'w>(&mut self, words: &'w [u8], tx: &mut TX) -> Result<&'w [u8], Error> {
-        for chunk in words.chunks(MAX_DMA_SIZE) {
-            self.start_write_bytes_dma(chunk.as_ptr(), chunk.len(), tx, false)?;
-
-            while !tx.is_done() {}
-            self.flush().unwrap(); // seems "is_done" doesn't work as intended?
-        }
-
-        Ok(words)
-    }
-
-    #[cfg_attr(feature = "place-spi-driver-in-ram", ram)]
-    fn start_write_bytes_dma(
+    fn start_write_bytes_dma_circular(
         &mut self,
         ptr: *const u8,
         len: usize,
         tx: &mut TX,
-        listen: bool,
     ) -> Result<(), Error> {
         let reg_block = self.register_block();
-        self.configure_datalen(len as u32 * 8);
+        self.configure_datalen(MAX_TRANSACTION_BITS);
 
         tx.is_done();
 
@@ -1922,20 +2872,54 @@ where
         self.update();
 
         reset_dma_before_load_dma_dscr(reg_block);
-        tx.prepare_transfer_without_start(self.dma_peripheral(), false, ptr, len)
+        tx.prepare_transfer_without_start(self.dma_peripheral(), true, ptr, len)
             .and_then(|_| tx.start_transfer())?;
 
         self.clear_dma_interrupts();
         reset_dma_before_usr_cmd(reg_block);
 
-        if listen {
-            tx.listen_eof();
-        }
+        #[cfg(any(esp32, esp32s2))]
+        reg_block
+            .dma_conf()
+            .modify(|_, w| w.dma_continue().set_bit());
+
         reg_block.cmd().modify(|_, w| w.usr().set_bit());
 
         Ok(())
     }
 
+    /// Start the next transaction of a circular transfer if the previous one
+    /// ended.
+    fn continue_circular_dma(&mut self) {
+        // the transaction never ends in continuous mode
+        #[cfg(not(any(esp32, esp32s2)))]
+        if !self.busy() {
+            self.register_block()
+                .dma_int_clr()
+                .write(|w| w.trans_done().clear_bit_by_one());
+            self.update();
+            self.register_block().cmd().modify(|_, w| w.usr().set_bit());
+        }
+    }
+
+    /// Stop a circular transfer, on the chips with a transaction length limit
+    /// this waits for the running transaction to end.
+    fn stop_circular_dma(&mut self, tx: &mut TX) {
+        #[cfg(any(esp32, esp32s2))]
+        self.register_block()
+            .dma_conf()
+            .modify(|_, w| w.dma_continue().clear_bit().dma_tx_stop().set_bit());
+
+        self.flush().ok();
+
+        #[cfg(any(esp32, esp32s2))]
+        self.register_block()
+            .dma_conf()
+            .modify(|_, w| w.dma_tx_stop().clear_bit());
+
+        tx.stop_transfer();
+    }
+
     #[cfg_attr(feature = "place-spi-driver-in-ram", ram)]
     fn start_read_bytes_dma(
         &mut self,
@@ -2089,6 +3073,89 @@ where
 {
 }
 
+/// Calculate the value of the clock register for the given frequency
+fn clock_register_value(frequency: HertzU32, clocks: &Clocks) -> u32 {
+    // FIXME: this might not be always true
+    #[cfg(not(esp32h2))]
+    let apb_clk_freq: HertzU32 = HertzU32::Hz(clocks.apb_clock.to_Hz());
+    // ESP32-H2 is using PLL_48M_CLK source instead of APB_CLK
+    #[cfg(esp32h2)]
+    let apb_clk_freq: HertzU32 = HertzU32::Hz(clocks.pll_48m_clock.to_Hz());
+
+    let reg_val: u32;
+    let duty_cycle = 128;
+
+    // In HW, n, h and l fields range from 1 to 64, pre ranges from 1 to 8K.
+    // The value written to register is one lower than the used value.
+
+    if frequency > ((apb_clk_freq / 4) * 3) {
+        // Using APB frequency directly will give us the best result here.
+        reg_val = 1 << 31;
+    } else {
+        /* For best duty cycle resolution, we want n to be as close to 32 as
+         * possible, but we also need a pre/n combo that gets us as close as
+         * possible to the intended frequency. To do this, we bruteforce n and
+         * calculate the best pre to go along with that. If there's a choice
+         * between pre/n combos that give the same result, use the one with the
+         * higher n.
+         */
+
+        let mut pre: i32;
+        let mut bestn: i32 = -1;
+        let mut bestpre: i32 = -1;
+        let mut besterr: i32 = 0;
+        let mut errval: i32;
+
+        /* Start at n = 2. We need to be able to set h/l so we have at least
+         * one high and one low pulse.
+         */
+
+        for n in 2..64 {
+            /* Effectively, this does:
+             *   pre = round((APB_CLK_FREQ / n) / frequency)
+             */
+
+            pre = ((apb_clk_freq.raw() as i32 / n) + (frequency.raw() as i32 / 2))
+                / frequency.raw() as i32;
+
+            if pre <= 0 {
+                pre = 1;
+            }
+
+            if pre > 16 {
+                pre = 16;
+            }
+
+            errval = (apb_clk_freq.raw() as i32 / (pre * n) - frequency.raw() as i32).abs();
+            if bestn == -1 || errval <= besterr {
+                besterr = errval;
+                bestn = n;
+                bestpre = pre;
+            }
+        }
+
+        let n: i32 = bestn;
+        pre = bestpre;
+        let l: i32 = n;
+
+        /* Effectively, this does:
+         *   h = round((duty_cycle * n) / 256)
+         */
+
+        let mut h: i32 = (duty_cycle * n + 127) / 256;
+        if h <= 0 {
+            h = 1;
+        }
+
+        reg_val = (l as u32 - 1)
+            | ((h as u32 - 1) << 6)
+            | ((n as u32 - 1) << 12)
+            | ((pre as u32 - 1) << 18);
+    }
+
+    reg_val
+}
+
 pub trait ExtendedInstance: Instance {
     fn sio0_input_signal(&self) -> InputSignal;
 
@@ -2103,6 +3170,27 @@ pub trait ExtendedInstance: Instance {
     fn sio3_input_signal(&self) -> InputSignal;
 }
 
+/// An SPI instance with the four additional data lines needed for octal
+/// transfers
+#[cfg(esp32s3)]
+pub trait OctalInstance: ExtendedInstance {
+    fn sio4_output_signal(&self) -> OutputSignal;
+
+    fn sio4_input_signal(&self) -> InputSignal;
+
+    fn sio5_output_signal(&self) -> OutputSignal;
+
+    fn sio5_input_signal(&self) -> InputSignal;
+
+    fn sio6_output_signal(&self) -> OutputSignal;
+
+    fn sio6_input_signal(&self) -> InputSignal;
+
+    fn sio7_output_signal(&self) -> OutputSignal;
+
+    fn sio7_input_signal(&self) -> InputSignal;
+}
+
 pub trait Instance: crate::private::Sealed {
     fn register_block(&self) -> &RegisterBlock;
 
@@ -2114,6 +3202,9 @@ pub trait Instance: crate::private::Sealed {
 
     fn cs_signal(&self) -> OutputSignal;
 
+    /// The hardware CS lines of this instance
+    fn cs_signals(&self) -> &'static [OutputSignal];
+
     fn enable_peripheral(&self);
 
     fn spi_num(&self) -> u8;
@@ -2191,8 +3282,30 @@ pub trait Instance: crate::private::Sealed {
         cmd_mode: SpiDataMode,
         address_mode: SpiDataMode,
         data_mode: SpiDataMode,
-    ) {
+    ) -> Result<(), Error> {
+        // Only SPI2 has the data lines SIO4 to SIO7, SPI3 is limited to quad
+        // transfers
+        #[cfg(esp32s3)]
+        if self.spi_num() != 2 && [cmd_mode, address_mode, data_mode].contains(&SpiDataMode::Octal)
+        {
+            return Err(Error::Unsupported);
+        }
+
         let reg_block = self.register_block();
+
+        #[cfg(esp32s3)]
+        {
+            reg_block.ctrl().modify(|_, w| {
+                w.fcmd_oct()
+                    .clear_bit()
+                    .faddr_oct()
+                    .clear_bit()
+                    .fread_oct()
+                    .clear_bit()
+            });
+            reg_block.user().modify(|_, w| w.fwrite_oct().clear_bit());
+        }
+
         match cmd_mode {
             SpiDataMode::Single => reg_block
                 .ctrl()
@@ -2203,6 +3316,15 @@ pub trait Instance: crate::private::Sealed {
             SpiDataMode::Quad => reg_block
                 .ctrl()
                 .modify(|_, w| w.fcmd_dual().clear_bit().fcmd_quad().set_bit()),
+            #[cfg(esp32s3)]
+            SpiDataMode::Octal => reg_block.ctrl().modify(|_, w| {
+                w.fcmd_dual()
+                    .clear_bit()
+                    .fcmd_quad()
+                    .clear_bit()
+                    .fcmd_oct()
+                    .set_bit()
+            }),
         }
 
         match address_mode {
@@ -2215,6 +3337,15 @@ pub trait Instance: crate::private::Sealed {
             SpiDataMode::Quad => reg_block
                 .ctrl()
                 .modify(|_, w| w.faddr_dual().clear_bit().faddr_quad().set_bit()),
+            #[cfg(esp32s3)]
+            SpiDataMode::Octal => reg_block.ctrl().modify(|_, w| {
+                w.faddr_dual()
+                    .clear_bit()
+                    .faddr_quad()
+                    .clear_bit()
+                    .faddr_oct()
+                    .set_bit()
+            }),
         }
 
         match data_mode {
@@ -2242,7 +3373,28 @@ pub trait Instance: crate::private::Sealed {
                     .user()
                     .modify(|_, w| w.fwrite_quad().set_bit().fwrite_dual().clear_bit());
             }
+            #[cfg(esp32s3)]
+            SpiDataMode::Octal => {
+                reg_block.ctrl().modify(|_, w| {
+                    w.fread_dual()
+                        .clear_bit()
+                        .fread_quad()
+                        .clear_bit()
+                        .fread_oct()
+                        .set_bit()
+                });
+                reg_block.user().modify(|_, w| {
+                    w.fwrite_dual()
+                        .clear_bit()
+                        .fwrite_quad()
+                        .clear_bit()
+                        .fwrite_oct()
+                        .set_bit()
+                });
+            }
         }
+
+        Ok(())
     }
 
     #[cfg(esp32)]
@@ -2251,7 +3403,7 @@ pub trait Instance: crate::private::Sealed {
         cmd_mode: SpiDataMode,
         address_mode: SpiDataMode,
         data_mode: SpiDataMode,
-    ) {
+    ) -> Result<(), Error> {
         let reg_block = self.register_block();
         match cmd_mode {
             SpiDataMode::Single => (),
@@ -2387,87 +3539,13 @@ pub trait Instance: crate::private::Sealed {
                 });
             }
         }
+
+        Ok(())
     }
 
     // taken from https://github.com/apache/incubator-nuttx/blob/8267a7618629838231256edfa666e44b5313348e/arch/risc-v/src/esp32c3/esp32c3_spi.c#L496
     fn setup(&mut self, frequency: HertzU32, clocks: &Clocks) {
-        // FIXME: this might not be always true
-        #[cfg(not(esp32h2))]
-        let apb_clk_freq: HertzU32 = HertzU32::Hz(clocks.apb_clock.to_Hz());
-        // ESP32-H2 is using PLL_48M_CLK source instead of APB_CLK
-        #[cfg(esp32h2)]
-        let apb_clk_freq: HertzU32 = HertzU32::Hz(clocks.pll_48m_clock.to_Hz());
-
-        let reg_val: u32;
-        let duty_cycle = 128;
-
-        // In HW, n, h and l fields range from 1 to 64, pre ranges from 1 to 8K.
-        // The value written to register is one lower than the used value.
-
-        if frequency > ((apb_clk_freq / 4) * 3) {
-            // Using APB frequency directly will give us the best result here.
-            reg_val = 1 << 31;
-        } else {
-            /* For best duty cycle resolution, we want n to be as close to 32 as
-             * possible, but we also need a pre/n combo that gets us as close as
-             * possible to the intended frequency. To do this, we bruteforce n and
-             * calculate the best pre to go along with that. If there's a choice
-             * between pre/n combos that give the same result, use the one with the
-             * higher n.
-             */
-
-            let mut pre: i32;
-            let mut bestn: i32 = -1;
-            let mut bestpre: i32 = -1;
-            let mut besterr: i32 = 0;
-            let mut errval: i32;
-
-            /* Start at n = 2. We need to be able to set h/l so we have at least
-             * one high and one low pulse.
-             */
-
-            for n in 2..64 {
-                /* Effectively, this does:
-                 *   pre = round((APB_CLK_FREQ / n) / frequency)
-                 */
-
-                pre = ((apb_clk_freq.raw() as i32 / n) + (frequency.raw() as i32 / 2))
-                    / frequency.raw() as i32;
-
-                if pre <= 0 {
-                    pre = 1;
-                }
-
-                if pre > 16 {
-                    pre = 16;
-                }
-
-                errval = (apb_clk_freq.raw() as i32 / (pre * n) - frequency.raw() as i32).abs();
-                if bestn == -1 || errval <= besterr {
-                    besterr = errval;
-                    bestn = n;
-                    bestpre = pre;
-                }
-            }
-
-            let n: i32 = bestn;
-            pre = bestpre;
-            let l: i32 = n;
-
-            /* Effectively, this does:
-             *   h = round((duty_cycle * n) / 256)
-             */
-
-            let mut h: i32 = (duty_cycle * n + 127) / 256;
-            if h <= 0 {
-                h = 1;
-            }
-
-            reg_val = (l as u32 - 1)
-                | ((h as u32 - 1) << 6)
-                | ((n as u32 - 1) << 12)
-                | ((pre as u32 - 1) << 18);
-        }
+        let reg_val = clock_register_value(frequency, clocks);
 
         self.register_block()
             .clock()
@@ -2617,6 +3695,108 @@ pub trait Instance: crate::private::Sealed {
         });
     }
 
+    /// Keep CS asserted after the next transaction, which continues in the
+    /// following one.
+    fn keep_cs_active(&self, keep: bool) {
+        #[cfg(esp32)]
+        self.register_block()
+            .pin()
+            .modify(|_, w| w.cs_keep_active().bit(keep));
+
+        #[cfg(not(esp32))]
+        self.register_block()
+            .misc()
+            .modify(|_, w| w.cs_keep_active().bit(keep));
+
+        self.update();
+    }
+
+    /// Apply the configuration of a device and activate its CS line for the
+    /// following transactions.
+    #[cfg(not(esp32))]
+    fn select_device(&mut self, cs_index: usize, config: &DeviceConfig) {
+        let reg_block = self.register_block();
+
+        #[cfg(not(esp32s2))]
+        reg_block.clk_gate().modify(|_, w| {
+            w.clk_en()
+                .clear_bit()
+                .mst_clk_active()
+                .clear_bit()
+                .mst_clk_sel()
+                .clear_bit()
+        });
+
+        reg_block
+            .clock()
+            .write(|w| unsafe { w.bits(config.clock_reg) });
+
+        #[cfg(not(esp32s2))]
+        reg_block.clk_gate().modify(|_, w| {
+            w.clk_en()
+                .set_bit()
+                .mst_clk_active()
+                .set_bit()
+                .mst_clk_sel()
+                .set_bit()
+        });
+
+        self.set_data_mode(config.mode);
+
+        reg_block
+            .user()
+            .modify(|_, w| w.cs_setup().bit(config.cs_setup > 0).cs_hold().set_bit());
+        reg_block.user1().modify(|_, w| unsafe {
+            w.cs_setup_time()
+                .bits(config.cs_setup.saturating_sub(1))
+                .cs_hold_time()
+                .bits(config.cs_hold)
+        });
+
+        reg_block.misc().modify(|_, w| {
+            w.cs0_dis()
+                .bit(cs_index != 0)
+                .cs1_dis()
+                .bit(cs_index != 1)
+                .cs2_dis()
+                .bit(cs_index != 2)
+                .cs3_dis()
+                .bit(cs_index != 3)
+                .cs4_dis()
+                .bit(cs_index != 4)
+                .cs5_dis()
+                .bit(cs_index != 5)
+                .cs_keep_active()
+                .set_bit()
+        });
+
+        self.update();
+    }
+
+    /// Release the CS line of the selected device and enable all CS lines
+    /// again.
+    #[cfg(not(esp32))]
+    fn deselect_device(&mut self) {
+        self.register_block().misc().modify(|_, w| {
+            w.cs0_dis()
+                .clear_bit()
+                .cs1_dis()
+                .clear_bit()
+                .cs2_dis()
+                .clear_bit()
+                .cs3_dis()
+                .clear_bit()
+                .cs4_dis()
+                .clear_bit()
+                .cs5_dis()
+                .clear_bit()
+                .cs_keep_active()
+                .clear_bit()
+        });
+
+        self.update();
+    }
+
     fn read_byte(&mut self) -> nb::Result<u8, Error> {
         if self.busy() {
             return Err(nb::Error::WouldBlock);
@@ -3011,6 +4191,82 @@ pub trait Instance: crate::private::Sealed {
                 .write(|w| unsafe { w.usr_miso_dbitlen().bits(len) });
         }
     }
+
+    #[cfg(not(any(esp32, esp32c3, esp32s2)))]
+    fn set_bit_order(&mut self, read_order: SpiBitOrder, write_order: SpiBitOrder) {
+        let read_value = match read_order {
+            SpiBitOrder::MSBFirst => 0,
+            SpiBitOrder::LSBFirst => 1,
+        };
+        let write_value = match write_order {
+            SpiBitOrder::MSBFirst => 0,
+            SpiBitOrder::LSBFirst => 1,
+        };
+        self.register_block().ctrl().modify(|_, w| unsafe {
+            w.rd_bit_order()
+                .bits(read_value)
+                .wr_bit_order()
+                .bits(write_value)
+        });
+    }
+
+    #[cfg(any(esp32, esp32c3, esp32s2))]
+    fn set_bit_order(&mut self, read_order: SpiBitOrder, write_order: SpiBitOrder) {
+        self.register_block().ctrl().modify(|_, w| {
+            w.rd_bit_order()
+                .bit(read_order == SpiBitOrder::LSBFirst)
+                .wr_bit_order()
+                .bit(write_order == SpiBitOrder::LSBFirst)
+        });
+    }
+
+    /// The configured bit orders of received and sent data
+    fn bit_order(&self) -> (SpiBitOrder, SpiBitOrder) {
+        let ctrl = self.register_block().ctrl().read();
+
+        #[cfg(not(any(esp32, esp32c3, esp32s2)))]
+        let (lsb_read, lsb_write) = (
+            ctrl.rd_bit_order().bits() != 0,
+            ctrl.wr_bit_order().bits() != 0,
+        );
+        #[cfg(any(esp32, esp32c3, esp32s2))]
+        let (lsb_read, lsb_write) = (
+            ctrl.rd_bit_order().bit_is_set(),
+            ctrl.wr_bit_order().bit_is_set(),
+        );
+
+        let order = |lsb_first: bool| {
+            if lsb_first {
+                SpiBitOrder::LSBFirst
+            } else {
+                SpiBitOrder::MSBFirst
+            }
+        };
+
+        (order(lsb_read), order(lsb_write))
+    }
+
+    /// Start a transaction of `bits` bits, sending the bit stream in `data`
+    /// which must fit into the FIFO.
+    fn write_bits(&mut self, data: &[u8], bits: u32) -> Result<(), Error> {
+        self.flush()?;
+        self.configure_datalen(bits);
+
+        let fifo_ptr = self.register_block().w0().as_ptr();
+        for (i, chunk) in data[..(bits as usize).div_ceil(8)].chunks(4).enumerate() {
+            let mut word = [0u8; 4];
+            word[..chunk.len()].copy_from_slice(chunk);
+
+            unsafe {
+                fifo_ptr.add(i).write_volatile(u32::from_le_bytes(word));
+            }
+        }
+
+        self.update();
+        self.register_block().cmd().modify(|_, w| w.usr().set_bit());
+
+        Ok(())
+    }
 }
 
 #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2))]
@@ -3046,6 +4302,18 @@ impl Instance for crate::peripherals::SPI2 {
         OutputSignal::FSPICS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::FSPICS0,
+            OutputSignal::FSPICS1,
+            OutputSignal::FSPICS2,
+            OutputSignal::FSPICS3,
+            OutputSignal::FSPICS4,
+            OutputSignal::FSPICS5,
+        ]
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self) {
         PeripheralClockControl::enable(crate::system::Peripheral::Spi2);
@@ -3123,6 +4391,15 @@ impl Instance for crate::peripherals::SPI2 {
         OutputSignal::HSPICS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::HSPICS0,
+            OutputSignal::HSPICS1,
+            OutputSignal::HSPICS2,
+        ]
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self) {
         PeripheralClockControl::enable(crate::system::Peripheral::Spi2);
@@ -3194,6 +4471,15 @@ impl Instance for crate::peripherals::SPI3 {
         OutputSignal::VSPICS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::VSPICS0,
+            OutputSignal::VSPICS1,
+            OutputSignal::VSPICS2,
+        ]
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self) {
         PeripheralClockControl::enable(crate::system::Peripheral::Spi3)
@@ -3238,6 +4524,18 @@ impl Instance for crate::peripherals::SPI2 {
         OutputSignal::FSPICS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::FSPICS0,
+            OutputSignal::FSPICS1,
+            OutputSignal::FSPICS2,
+            OutputSignal::FSPICS3,
+            OutputSignal::FSPICS4,
+            OutputSignal::FSPICS5,
+        ]
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self) {
         PeripheralClockControl::enable(crate::system::Peripheral::Spi2)
@@ -3282,6 +4580,49 @@ impl ExtendedInstance for crate::peripherals::SPI2 {
     }
 }
 
+#[cfg(esp32s3)]
+impl OctalInstance for crate::peripherals::SPI2 {
+    #[inline(always)]
+    fn sio4_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIIO4
+    }
+
+    #[inline(always)]
+    fn sio4_input_signal(&self) -> InputSignal {
+        InputSignal::FSPIIO4
+    }
+
+    #[inline(always)]
+    fn sio5_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIIO5
+    }
+
+    #[inline(always)]
+    fn sio5_input_signal(&self) -> InputSignal {
+        InputSignal::FSPIIO5
+    }
+
+    #[inline(always)]
+    fn sio6_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIIO6
+    }
+
+    #[inline(always)]
+    fn sio6_input_signal(&self) -> InputSignal {
+        InputSignal::FSPIIO6
+    }
+
+    #[inline(always)]
+    fn sio7_output_signal(&self) -> OutputSignal {
+        OutputSignal::FSPIIO7
+    }
+
+    #[inline(always)]
+    fn sio7_input_signal(&self) -> InputSignal {
+        InputSignal::FSPIIO7
+    }
+}
+
 #[cfg(any(esp32s2, esp32s3))]
 impl Instance for crate::peripherals::SPI3 {
     #[inline(always)]
@@ -3315,6 +4656,15 @@ impl Instance for crate::peripherals::SPI3 {
         OutputSignal::SPI3_CS0
     }
 
+    #[inline(always)]
+    fn cs_signals(&self) -> &'static [OutputSignal] {
+        &[
+            OutputSignal::SPI3_CS0,
+            OutputSignal::SPI3_CS1,
+            OutputSignal::SPI3_CS2,
+        ]
+    }
+
     #[inline(always)]
     fn enable_peripheral(&self) {
         PeripheralClockControl::enable(crate::system::Peripheral::Spi3)
@@ -0,0 +1,204 @@
+//! # Shared SPI bus with DMA
+//!
+//! ## Overview
+//!
+//! A [SpiDmaSharedBus] owns a DMA capable SPI instance and hands out
+//! [SpiDmaDevice]s, one per device on the bus. Each device has its own CS pin
+//! and implements both [embedded_hal::spi::SpiDevice] and
+//! [embedded_hal_async::spi::SpiDevice].
+//!
+//! A transaction locks the bus, asserts CS, runs all its operations as DMA
+//! transfers and deasserts CS again, so devices can be used concurrently from
+//! different tasks. Async transactions wait for the bus without blocking,
+//! blocking transactions spin until the bus is available. Don't mix blocking
+//! and async transactions on the same bus from code running on the same core,
+//! a blocking transaction waiting for an async one which can't make progress
+//! never returns.
+//!
+//! `DelayNs` operations are busy waiting while the bus is locked.
+//!
+//! ## Example
+//! ```no_run
+//! static BUS: StaticCell<SpiDmaSharedBus<'static, SPI2, ChannelCreator0, FullDuplexMode>> =
+//!     StaticCell::new();
+//!
+//! let spi = Spi::new(peripherals.SPI2, 10.MHz(), SpiMode::Mode0, &clocks)
+//!     .with_pins(Some(sclk), Some(mosi), Some(miso), NO_PIN)
+//!     .with_dma(dma_channel.configure_for_async(
+//!         false,
+//!         &mut descriptors,
+//!         &mut rx_descriptors,
+//!         DmaPriority::Priority0,
+//!     ));
+//!
+//! let bus = BUS.init(SpiDmaSharedBus::new(spi));
+//! let display = bus.device(io.pins.gpio10.into_push_pull_output());
+//! let sensor = bus.device(io.pins.gpio11.into_push_pull_output());
+//! ```
+
+use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, mutex::Mutex};
+use embedded_hal::{digital::OutputPin, spi::Operation};
+
+use super::{dma::SpiDma, Error, InstanceDma, IsFullDuplex};
+use crate::{
+    dma::{ChannelTypes, SpiPeripheral},
+    Async,
+};
+
+/// A DMA capable SPI bus shared by multiple [SpiDmaDevice]s
+pub struct SpiDmaSharedBus<'d, T, C, M>
+where
+    C: ChannelTypes,
+    C::P: SpiPeripheral,
+    M: IsFullDuplex,
+{
+    spi: Mutex<CriticalSectionRawMutex, SpiDma<'d, T, C, M, Async>>,
+}
+
+impl<'d, T, C, M> SpiDmaSharedBus<'d, T, C, M>
+where
+    T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+    C: ChannelTypes,
+    C::P: SpiPeripheral,
+    M: IsFullDuplex,
+{
+    /// Share the given SPI instance.
+    pub fn new(spi: SpiDma<'d, T, C, M, Async>) -> Self {
+        Self {
+            spi: Mutex::new(spi),
+        }
+    }
+
+    /// A device on the bus which is selected with the given CS pin.
+    ///
+    /// The CS pin is deasserted (set high) here.
+    pub fn device<CS: OutputPin>(&self, mut cs: CS) -> SpiDmaDevice<'_, 'd, T, C, M, CS> {
+        cs.set_high().ok();
+
+        SpiDmaDevice { bus: self, cs }
+    }
+
+    /// Return the SPI instance.
+    pub fn free(self) -> SpiDma<'d, T, C, M, Async> {
+        self.spi.into_inner()
+    }
+}
+
+/// A device on a [SpiDmaSharedBus]
+pub struct SpiDmaDevice<'a, 'd, T, C, M, CS>
+where
+    C: ChannelTypes,
+    C::P: SpiPeripheral,
+    M: IsFullDuplex,
+{
+    bus: &'a SpiDmaSharedBus<'d, T, C, M>,
+    cs: CS,
+}
+
+impl<'a, 'd, T, C, M, CS> SpiDmaDevice<'a, 'd, T, C, M, CS>
+where
+    C: ChannelTypes,
+    C::P: SpiPeripheral,
+    M: IsFullDuplex,
+{
+    /// Return the CS pin.
+    pub fn free(self) -> CS {
+        self.cs
+    }
+}
+
+impl<'a, 'd, T, C, M, CS> embedded_hal::spi::ErrorType for SpiDmaDevice<'a, 'd, T, C, M, CS>
+where
+    C: ChannelTypes,
+    C::P: SpiPeripheral,
+    M: IsFullDuplex,
+{
+    type Error = Error;
+}
+
+impl<'a, 'd, T, C, M, CS> embedded_hal::spi::SpiDevice for SpiDmaDevice<'a, 'd, T, C, M, CS>
+where
+    T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+    C: ChannelTypes,
+    C::P: SpiPeripheral,
+    M: IsFullDuplex,
+    CS: OutputPin,
+{
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Self::Error> {
+        use embedded_hal::spi::SpiBus;
+
+        let mut spi = loop {
+            if let Ok(spi) = self.bus.spi.try_lock() {
+                break spi;
+            }
+        };
+
+        self.cs.set_low().map_err(|_| Error::Unknown)?;
+
+        let mut result = Ok(());
+        for operation in operations {
+            result = match operation {
+                Operation::Read(words) => SpiBus::read(&mut *spi, words),
+                Operation::Write(words) => SpiBus::write(&mut *spi, words),
+                Operation::Transfer(read, write) => SpiBus::transfer(&mut *spi, read, write),
+                Operation::TransferInPlace(words) => SpiBus::transfer_in_place(&mut *spi, words),
+                Operation::DelayNs(ns) => SpiBus::flush(&mut *spi).map(|_| {
+                    crate::rom::ets_delay_us(ns.div_ceil(1000));
+                }),
+            };
+
+            if result.is_err() {
+                break;
+            }
+        }
+
+        let flushed = SpiBus::flush(&mut *spi);
+        let deselected = self.cs.set_high().map_err(|_| Error::Unknown);
+
+        result.and(flushed).and(deselected)
+    }
+}
+
+impl<'a, 'd, T, C, M, CS> embedded_hal_async::spi::SpiDevice for SpiDmaDevice<'a, 'd, T, C, M, CS>
+where
+    T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+    C: ChannelTypes,
+    C::P: SpiPeripheral,
+    M: IsFullDuplex,
+    CS: OutputPin,
+{
+    async fn transaction(
+        &mut self,
+        operations: &mut [Operation<'_, u8>],
+    ) -> Result<(), Self::Error> {
+        use embedded_hal_async::spi::SpiBus;
+
+        let mut spi = self.bus.spi.lock().await;
+
+        self.cs.set_low().map_err(|_| Error::Unknown)?;
+
+        let mut result = Ok(());
+        for operation in operations {
+            result = match operation {
+                Operation::Read(words) => SpiBus::read(&mut *spi, words).await,
+                Operation::Write(words) => SpiBus::write(&mut *spi, words).await,
+                Operation::Transfer(read, write) => SpiBus::transfer(&mut *spi, read, write).await,
+                Operation::TransferInPlace(words) => {
+                    SpiBus::transfer_in_place(&mut *spi, words).await
+                }
+                Operation::DelayNs(ns) => SpiBus::flush(&mut *spi).await.map(|_| {
+                    crate::rom::ets_delay_us(ns.div_ceil(1000));
+                }),
+            };
+
+            if result.is_err() {
+                break;
+            }
+        }
+
+        let flushed = SpiBus::flush(&mut *spi).await;
+        let deselected = self.cs.set_high().map_err(|_| Error::Unknown);
+
+        result.and(flushed).and(deselected)
+    }
+}
@@ -44,6 +44,14 @@ pub enum SpiMode {
     Mode3,
 }
 
+/// SPI bit order
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SpiBitOrder {
+    MSBFirst,
+    LSBFirst,
+}
+
 pub trait DuplexMode {}
 pub trait IsFullDuplex: DuplexMode {}
 pub trait IsHalfDuplex: DuplexMode {}
@@ -53,12 +61,16 @@ pub trait IsHalfDuplex: DuplexMode {}
 /// Single = 1 bit, 2 wires
 /// Dual = 2 bit, 2 wires
 /// Quad = 4 bit, 4 wires
+/// Octal = 8 bit, 8 wires (SPI2 of the ESP32-S3 in half-duplex mode only,
+/// transfers on SPI3 fail with [Error::Unsupported])
 #[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "defmt", derive(defmt::Format))]
 pub enum SpiDataMode {
     Single,
     Dual,
     Quad,
+    #[cfg(esp32s3)]
+    Octal,
 }
 
 /// Full-duplex operation
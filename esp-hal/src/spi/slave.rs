@@ -21,10 +21,12 @@
 //! There are several options for working with the SPI peripheral in slave mode,
 //! but the code currently only supports single transfers (not segmented
 //! transfers), full duplex, single bit (not dual or quad SPI), and DMA mode
-//! (not CPU mode). It also does not support blocking operations, as the actual
-//! transfer is controlled by the SPI master; if these are necessary,
-//! then the DmaTransfer trait instance can be wait()ed on or polled for
-//! is_done().
+//! (not CPU mode). As the actual transfer is controlled by the SPI master, the
+//! DmaTransfer trait instance can be wait()ed on or polled for is_done().
+//! Alternatively, [dma::SpiDma::transfer] blocks until the master ended the
+//! transaction and, with the `async` feature, [dma::SpiDma::transfer_async]
+//! waits for it asynchronously, woken by the interrupt raised when the master
+//! deasserts CS (not on the ESP32 and ESP32-S2).
 //!
 //! ```rust
 //! let dma = Gdma::new(peripherals.DMA);
@@ -49,20 +51,24 @@
 //! TODO:
 //! - Notify the Spi user when the master wants to send or receive data, if
 //!   possible
-//! - Blocking transfers
 //! - Half duplex
 //! - Segmented transfers
-//! - Interrupt support
 //! - Custom interrupts from segmented transfer commands
 //! - Dual and quad SPI
 //! - CPU mode
 
 use core::marker::PhantomData;
 
+#[cfg(not(any(esp32, esp32s2)))]
+use enumset::EnumSet;
+
+#[cfg(not(any(esp32, esp32s2)))]
+pub use super::master::SpiInterrupt;
 use super::{Error, FullDuplexMode, SpiMode};
 use crate::{
     dma::{DmaPeripheral, Rx, Tx},
     gpio::{InputPin, InputSignal, OutputPin, OutputSignal},
+    interrupt::InterruptHandler,
     peripheral::{Peripheral, PeripheralRef},
     peripherals::spi2::RegisterBlock,
     system::PeripheralClockControl,
@@ -508,9 +514,239 @@ pub mod dma {
                     read_len,
                     &mut self.channel.tx,
                     &mut self.channel.rx,
+                    false,
                 )
                 .map(move |_| SpiDmaTransferRxTx { spi_dma: self })
         }
+
+        /// Sets the interrupt handler, enables it with
+        /// [crate::interrupt::Priority::min()]
+        ///
+        /// Interrupts are not enabled at the peripheral level here.
+        pub fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+            self.spi.set_interrupt_handler(handler);
+        }
+
+        /// Listen for the given interrupts
+        ///
+        /// [SpiInterrupt::TransDone] is raised when the master ends a
+        /// transaction by releasing the CS line.
+        #[cfg(not(any(esp32, esp32s2)))]
+        pub fn listen(&mut self, interrupts: EnumSet<SpiInterrupt>) {
+            self.spi.listen(interrupts);
+        }
+
+        /// Unlisten the given interrupts
+        #[cfg(not(any(esp32, esp32s2)))]
+        pub fn unlisten(&mut self, interrupts: EnumSet<SpiInterrupt>) {
+            self.spi.unlisten(interrupts);
+        }
+
+        /// Gets asserted interrupts
+        #[cfg(not(any(esp32, esp32s2)))]
+        pub fn interrupts(&mut self) -> EnumSet<SpiInterrupt> {
+            self.spi.interrupts()
+        }
+
+        /// Resets asserted interrupts
+        #[cfg(not(any(esp32, esp32s2)))]
+        pub fn clear_interrupts(&mut self, interrupts: EnumSet<SpiInterrupt>) {
+            self.spi.clear_interrupts(interrupts);
+        }
+    }
+
+    impl<'d, T, C> SpiDma<'d, T, C, crate::Blocking>
+    where
+        T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+        C: ChannelTypes,
+        C::P: SpiPeripheral,
+    {
+        /// Send `write` and receive into `read` in a single transaction,
+        /// blocking until the master ended it.
+        ///
+        /// The maximum amount of data to be sent/received is 32736 bytes. If
+        /// the master ends the transaction early, the remaining bytes of
+        /// `read` are left untouched and the rest of `write` isn't sent.
+        pub fn transfer(&mut self, write: &[u8], read: &mut [u8]) -> Result<(), Error> {
+            if write.len() > MAX_DMA_SIZE || read.len() > MAX_DMA_SIZE {
+                return Err(Error::MaxDmaTransferSizeExceeded);
+            }
+
+            self.spi.start_transfer_dma(
+                write.as_ptr(),
+                write.len(),
+                read.as_mut_ptr(),
+                read.len(),
+                &mut self.channel.tx,
+                &mut self.channel.rx,
+                false,
+            )?;
+
+            // the DMA channels may never finish if the master clocks fewer bytes,
+            // so only wait for the master to end the transaction
+            while self.spi.is_bus_busy() {}
+
+            if self.channel.rx.has_error() || self.channel.tx.has_error() {
+                Err(Error::DmaError(DmaError::DescriptorError))
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    // The ESP32 and ESP32-S2 signal the end of a transaction in `slave` instead
+    // of the DMA interrupt registers
+    #[cfg(all(feature = "async", not(any(esp32, esp32s2))))]
+    mod asynch {
+        use core::{
+            pin::Pin,
+            task::{Context, Poll},
+        };
+
+        use embassy_sync::waitqueue::AtomicWaker;
+        use procmacros::handler;
+
+        use super::*;
+
+        #[cfg(spi3)]
+        const NUM_SPI: usize = 2;
+        #[cfg(not(spi3))]
+        const NUM_SPI: usize = 1;
+
+        #[allow(clippy::declare_interior_mutable_const)]
+        const INIT: AtomicWaker = AtomicWaker::new();
+        static WAKERS: [AtomicWaker; NUM_SPI] = [INIT; NUM_SPI];
+
+        /// Resolves when the master ended the transaction by deasserting CS
+        struct TransDoneFuture<'a, T>
+        where
+            T: Instance,
+        {
+            instance: &'a T,
+        }
+
+        impl<'a, T> TransDoneFuture<'a, T>
+        where
+            T: Instance,
+        {
+            fn new(instance: &'a T) -> Self {
+                instance
+                    .register_block()
+                    .dma_int_ena()
+                    .modify(|_, w| w.trans_done().set_bit());
+
+                Self { instance }
+            }
+        }
+
+        impl<'a, T> core::future::Future for TransDoneFuture<'a, T>
+        where
+            T: Instance,
+        {
+            type Output = ();
+
+            fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+                WAKERS[self.instance.spi_num() as usize - 2].register(ctx.waker());
+
+                // the interrupt handler disables the interrupt once it fired
+                if self
+                    .instance
+                    .register_block()
+                    .dma_int_ena()
+                    .read()
+                    .trans_done()
+                    .bit_is_clear()
+                {
+                    Poll::Ready(())
+                } else {
+                    Poll::Pending
+                }
+            }
+        }
+
+        impl<'a, T> Drop for TransDoneFuture<'a, T>
+        where
+            T: Instance,
+        {
+            fn drop(&mut self) {
+                self.instance
+                    .register_block()
+                    .dma_int_ena()
+                    .modify(|_, w| w.trans_done().clear_bit());
+            }
+        }
+
+        impl<'d, T, C> SpiDma<'d, T, C, crate::Async>
+        where
+            T: InstanceDma<C::Tx<'d>, C::Rx<'d>>,
+            C: ChannelTypes,
+            C::P: SpiPeripheral,
+        {
+            /// Send `write` and receive into `read` in a single transaction,
+            /// waiting until the master ended it by deasserting CS.
+            ///
+            /// The maximum amount of data to be sent/received is 32736 bytes.
+            /// If the master ends the transaction early, the remaining bytes
+            /// of `read` are left untouched and the rest of `write` isn't
+            /// sent.
+            ///
+            /// This binds the driver's own handler to the SPI interrupt,
+            /// replacing a handler set with [SpiDma::set_interrupt_handler].
+            pub async fn transfer_async(
+                &mut self,
+                write: &[u8],
+                read: &mut [u8],
+            ) -> Result<(), Error> {
+                if write.len() > MAX_DMA_SIZE || read.len() > MAX_DMA_SIZE {
+                    return Err(Error::MaxDmaTransferSizeExceeded);
+                }
+
+                let handler = match self.spi.spi_num() {
+                    2 => spi2_slave_handler,
+                    #[cfg(spi3)]
+                    3 => spi3_slave_handler,
+                    _ => panic!("Illegal SPI instance"),
+                };
+                self.spi.set_interrupt_handler(handler);
+
+                self.spi.start_transfer_dma(
+                    write.as_ptr(),
+                    write.len(),
+                    read.as_mut_ptr(),
+                    read.len(),
+                    &mut self.channel.tx,
+                    &mut self.channel.rx,
+                    false,
+                )?;
+
+                TransDoneFuture::new(&*self.spi).await;
+
+                if self.channel.rx.has_error() || self.channel.tx.has_error() {
+                    Err(Error::DmaError(DmaError::DescriptorError))
+                } else {
+                    Ok(())
+                }
+            }
+        }
+
+        #[handler]
+        fn spi2_slave_handler() {
+            unsafe { &*crate::peripherals::SPI2::PTR }
+                .dma_int_ena()
+                .modify(|_, w| w.trans_done().clear_bit());
+
+            WAKERS[0].wake();
+        }
+
+        #[cfg(spi3)]
+        #[handler]
+        fn spi3_slave_handler() {
+            unsafe { &*crate::peripherals::SPI3::PTR }
+                .dma_int_ena()
+                .modify(|_, w| w.trans_done().clear_bit());
+
+            WAKERS[1].wake();
+        }
     }
 }
 
@@ -527,6 +763,7 @@ where
         read_buffer_len: usize,
         tx: &mut TX,
         rx: &mut RX,
+        listen: bool,
     ) -> Result<(), Error> {
         let reg_block = self.register_block();
 
@@ -560,6 +797,11 @@ where
             .dma_conf()
             .modify(|_, w| w.dma_slv_seg_trans_en().clear_bit());
 
+        if listen {
+            tx.listen_eof();
+            rx.listen_eof();
+        }
+
         tx.start_transfer()?;
         Ok(rx.start_transfer()?)
     }
@@ -749,6 +991,72 @@ pub trait Instance: crate::private::Sealed {
 
     fn spi_num(&self) -> u8;
 
+    /// Set the interrupt handler
+    fn set_interrupt_handler(&mut self, handler: InterruptHandler);
+
+    /// Listen for the given interrupts
+    #[cfg(not(any(esp32, esp32s2)))]
+    fn listen(&mut self, interrupts: EnumSet<SpiInterrupt>) {
+        let reg_block = self.register_block();
+
+        for interrupt in interrupts {
+            match interrupt {
+                SpiInterrupt::TransDone => {
+                    reg_block
+                        .dma_int_ena()
+                        .modify(|_, w| w.trans_done().set_bit());
+                }
+            }
+        }
+    }
+
+    /// Unlisten the given interrupts
+    #[cfg(not(any(esp32, esp32s2)))]
+    fn unlisten(&mut self, interrupts: EnumSet<SpiInterrupt>) {
+        let reg_block = self.register_block();
+
+        for interrupt in interrupts {
+            match interrupt {
+                SpiInterrupt::TransDone => {
+                    reg_block
+                        .dma_int_ena()
+                        .modify(|_, w| w.trans_done().clear_bit());
+                }
+            }
+        }
+    }
+
+    /// Gets asserted interrupts
+    #[cfg(not(any(esp32, esp32s2)))]
+    fn interrupts(&mut self) -> EnumSet<SpiInterrupt> {
+        let mut res = EnumSet::new();
+        let reg_block = self.register_block();
+
+        let ints = reg_block.dma_int_st().read();
+
+        if ints.trans_done().bit() {
+            res.insert(SpiInterrupt::TransDone);
+        }
+
+        res
+    }
+
+    /// Resets asserted interrupts
+    #[cfg(not(any(esp32, esp32s2)))]
+    fn clear_interrupts(&mut self, interrupts: EnumSet<SpiInterrupt>) {
+        let reg_block = self.register_block();
+
+        for interrupt in interrupts {
+            match interrupt {
+                SpiInterrupt::TransDone => {
+                    reg_block
+                        .dma_int_clr()
+                        .write(|w| w.trans_done().clear_bit_by_one());
+                }
+            }
+        }
+    }
+
     /// Initialize for full-duplex 1 bit mode
     fn init(&mut self) {
         let reg_block = self.register_block();
@@ -929,6 +1237,12 @@ impl Instance for crate::peripherals::SPI2 {
         self
     }
 
+    #[inline(always)]
+    fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        self.bind_spi2_interrupt(handler.handler());
+        crate::interrupt::enable(crate::peripherals::Interrupt::SPI2, handler.priority()).unwrap();
+    }
+
     #[inline(always)]
     fn sclk_signal(&self) -> InputSignal {
         InputSignal::FSPICLK
@@ -967,6 +1281,12 @@ impl Instance for crate::peripherals::SPI2 {
         self
     }
 
+    #[inline(always)]
+    fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        self.bind_spi2_interrupt(handler.handler());
+        crate::interrupt::enable(crate::peripherals::Interrupt::SPI2, handler.priority()).unwrap();
+    }
+
     #[inline(always)]
     fn sclk_signal(&self) -> InputSignal {
         InputSignal::HSPICLK
@@ -1005,6 +1325,12 @@ impl Instance for crate::peripherals::SPI3 {
         self
     }
 
+    #[inline(always)]
+    fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        self.bind_spi3_interrupt(handler.handler());
+        crate::interrupt::enable(crate::peripherals::Interrupt::SPI3, handler.priority()).unwrap();
+    }
+
     #[inline(always)]
     fn sclk_signal(&self) -> InputSignal {
         InputSignal::VSPICLK
@@ -1043,6 +1369,12 @@ impl Instance for crate::peripherals::SPI2 {
         self
     }
 
+    #[inline(always)]
+    fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        self.bind_spi2_interrupt(handler.handler());
+        crate::interrupt::enable(crate::peripherals::Interrupt::SPI2, handler.priority()).unwrap();
+    }
+
     #[inline(always)]
     fn sclk_signal(&self) -> InputSignal {
         InputSignal::FSPICLK
@@ -1081,6 +1413,12 @@ impl Instance for crate::peripherals::SPI3 {
         self
     }
 
+    #[inline(always)]
+    fn set_interrupt_handler(&mut self, handler: InterruptHandler) {
+        self.bind_spi3_interrupt(handler.handler());
+        crate::interrupt::enable(crate::peripherals::Interrupt::SPI3, handler.priority()).unwrap();
+    }
+
     #[inline(always)]
     fn sclk_signal(&self) -> InputSignal {
         InputSignal::SPI3_CLK
@@ -83,6 +83,8 @@ pub enum Peripheral {
     Uart1,
     #[cfg(uart2)]
     Uart2,
+    #[cfg(uhci0)]
+    Uhci0,
     #[cfg(rsa)]
     Rsa,
     #[cfg(parl_io)]
@@ -293,6 +295,11 @@ impl PeripheralClockControl {
                 perip_clk_en0.modify(|_, w| w.rmt_clk_en().set_bit());
                 perip_rst_en0.modify(|_, w| w.rmt_rst().clear_bit());
             }
+            #[cfg(uhci0)]
+            Peripheral::Uhci0 => {
+                perip_clk_en0.modify(|_, w| w.uhci0_clk_en().set_bit());
+                perip_rst_en0.modify(|_, w| w.uhci0_rst().clear_bit());
+            }
             #[cfg(ledc)]
             Peripheral::Ledc => {
                 perip_clk_en0.modify(|_, w| w.ledc_clk_en().set_bit());
@@ -502,6 +509,11 @@ impl PeripheralClockControl {
                 perip_rst_en0.modify(|_, w| w.rmt_rst().set_bit());
                 perip_rst_en0.modify(|_, w| w.rmt_rst().clear_bit());
             }
+            #[cfg(uhci0)]
+            Peripheral::Uhci0 => {
+                perip_rst_en0.modify(|_, w| w.uhci0_rst().set_bit());
+                perip_rst_en0.modify(|_, w| w.uhci0_rst().clear_bit());
+            }
             #[cfg(ledc)]
             Peripheral::Ledc => {
                 perip_rst_en0.modify(|_, w| w.ledc_rst().set_bit());
@@ -703,6 +715,11 @@ impl PeripheralClockControl {
                 system.rmt_conf().modify(|_, w| w.rmt_clk_en().set_bit());
                 system.rmt_conf().modify(|_, w| w.rmt_rst_en().clear_bit());
             }
+            #[cfg(uhci0)]
+            Peripheral::Uhci0 => {
+                system.uhci_conf().modify(|_, w| w.uhci_clk_en().set_bit());
+                system.uhci_conf().modify(|_, w| w.uhci_rst_en().clear_bit());
+            }
             #[cfg(ledc)]
             Peripheral::Ledc => {
                 system.ledc_conf().modify(|_, w| w.ledc_clk_en().set_bit());
@@ -918,6 +935,11 @@ impl PeripheralClockControl {
                 system.rmt_conf().modify(|_, w| w.rmt_rst_en().set_bit());
                 system.rmt_conf().modify(|_, w| w.rmt_rst_en().clear_bit());
             }
+            #[cfg(uhci0)]
+            Peripheral::Uhci0 => {
+                system.uhci_conf().modify(|_, w| w.uhci_rst_en().set_bit());
+                system.uhci_conf().modify(|_, w| w.uhci_rst_en().clear_bit());
+            }
             #[cfg(ledc)]
             Peripheral::Ledc => {
                 system.ledc_conf().modify(|_, w| w.ledc_rst_en().set_bit());
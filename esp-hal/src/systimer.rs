@@ -22,15 +22,24 @@
 //! ```no_run
 //! let peripherals = Peripherals::take();
 //!
-//! let syst = SystemTimer::new(peripherals.SYSTIMER);
+//! let syst = SystemTimer::new(peripherals.SYSTIMER, &clocks);
 //! println!("SYSTIMER Current value = {}", SystemTimer::now());
+//!
+//! let start = SystemTimer::now_instant();
+//! // ...
+//! let elapsed = SystemTimer::now_instant() - start;
+//! println!("Took {} µs", elapsed.to_micros());
 //! ```
 
 use core::{marker::PhantomData, mem::transmute};
 
-use fugit::MicrosDurationU32;
+use fugit::{MicrosDurationU32, MicrosDurationU64};
+use portable_atomic::{AtomicU32, Ordering};
 
+#[cfg(not(esp32s2))]
+use crate::Cpu;
 use crate::{
+    clock::Clocks,
     interrupt::InterruptHandler,
     peripheral::Peripheral,
     peripherals::{
@@ -44,13 +53,14 @@ use crate::{
     },
 };
 
-// TODO this only handles unit0 of the systimer
-
 /// The SystemTimer
 pub struct SystemTimer<'d, DM: crate::Mode> {
     pub alarm0: Alarm<Target, DM, 0>,
     pub alarm1: Alarm<Target, DM, 1>,
     pub alarm2: Alarm<Target, DM, 2>,
+    pub unit0: Unit<0>,
+    #[cfg(not(esp32s2))]
+    pub unit1: Unit<1>,
     _phantom: &'d PhantomData<()>,
 }
 
@@ -61,72 +71,262 @@ impl<'d> SystemTimer<'d, crate::Blocking> {
     #[cfg(not(esp32s2))]
     pub const BIT_MASK: u64 = 0xF_FFFF_FFFF_FFFF;
 
-    /// The ticks per second the underlying peripheral uses
+    /// The nominal ticks per second the underlying peripheral uses, assuming
+    /// a 40 MHz (32 MHz on the ESP32-H2) XTAL. See [Self::ticks_per_second]
+    /// for the rate derived from the actually configured clocks.
     #[cfg(esp32s2)]
-    pub const TICKS_PER_SECOND: u64 = 80_000_000; // TODO this can change when we have support for changing APB frequency
+    pub const TICKS_PER_SECOND: u64 = 80_000_000;
     #[cfg(not(esp32s2))]
     pub const TICKS_PER_SECOND: u64 = 16_000_000;
 
     /// Create a new instance in [crate::Blocking] mode.
-    pub fn new(_p: impl Peripheral<P = SYSTIMER> + 'd) -> Self {
-        #[cfg(soc_etm)]
-        etm::enable_etm();
+    pub fn new(_p: impl Peripheral<P = SYSTIMER> + 'd, clocks: &Clocks) -> Self {
+        init(clocks);
 
         Self {
             alarm0: Alarm::new(),
             alarm1: Alarm::new(),
             alarm2: Alarm::new(),
+            unit0: Unit::new(),
+            #[cfg(not(esp32s2))]
+            unit1: Unit::new(),
             _phantom: &PhantomData,
         }
     }
 
-    // TODO use fugit types
     /// Get the current count of the system-timer.
     pub fn now() -> u64 {
-        // This should be safe to access from multiple contexts
-        // worst case scenario the second accessor ends up reading
-        // an older time stamp
-        let systimer = unsafe { &*SYSTIMER::ptr() };
-        systimer
-            .unit0_op()
-            .modify(|_, w| w.timer_unit0_update().set_bit());
+        Unit::<0>::read_value()
+    }
 
-        while !systimer
-            .unit0_op()
-            .read()
-            .timer_unit0_value_valid()
-            .bit_is_set()
-        {}
+    /// Get the current time of the system-timer as an [Instant].
+    pub fn now_instant() -> Instant {
+        Instant::from_ticks(Self::ticks_to_duration(Self::now()).ticks())
+    }
 
-        let value_lo = systimer.unit0_value_lo().read().bits();
-        let value_hi = systimer.unit0_value_hi().read().bits();
+    /// The ticks per second of the counters, as derived from the clocks
+    /// passed to [Self::new].
+    ///
+    /// Before the driver has been created this is [Self::TICKS_PER_SECOND].
+    pub fn ticks_per_second() -> u64 {
+        TICK_RATE.load(Ordering::Relaxed) as u64
+    }
 
-        ((value_hi as u64) << 32) | value_lo as u64
+    /// Convert a number of system-timer ticks into a duration.
+    pub fn ticks_to_duration(ticks: u64) -> MicrosDurationU64 {
+        let rate = Self::ticks_per_second();
+        // split the calculation to avoid overflowing for large tick counts
+        MicrosDurationU64::from_ticks(ticks / rate * 1_000_000 + (ticks % rate) * 1_000_000 / rate)
+    }
+
+    /// Convert a duration into the number of system-timer ticks it spans.
+    pub fn duration_to_ticks(duration: MicrosDurationU64) -> u64 {
+        let rate = Self::ticks_per_second();
+        let us = duration.ticks();
+        us / 1_000_000 * rate + (us % 1_000_000) * rate / 1_000_000
     }
 }
 
+/// The tick rate of the counters, see [SystemTimer::ticks_per_second].
+static TICK_RATE: AtomicU32 = AtomicU32::new(SystemTimer::TICKS_PER_SECOND as u32);
+
+/// The tick rate of the counters for the given clock configuration.
+pub(crate) fn ticks_per_second(clocks: &Clocks) -> u64 {
+    cfg_if::cfg_if! {
+        if #[cfg(esp32s2)] {
+            // The counter is stepped to always run at 80 MHz, see `init`.
+            let _ = clocks;
+            80_000_000
+        } else if #[cfg(esp32h2)] {
+            // The counters and comparators are driven using `XTAL_CLK`.
+            // The average clock frequency is fXTAL_CLK/2, which is 16 MHz.
+            clocks.xtal_clock.to_Hz() as u64 / 2
+        } else {
+            // The counters and comparators are driven using `XTAL_CLK`.
+            // The average clock frequency is fXTAL_CLK/2.5, which is 16 MHz.
+            clocks.xtal_clock.to_Hz() as u64 * 10 / 25
+        }
+    }
+}
+
+fn init(clocks: &Clocks) {
+    #[cfg(soc_etm)]
+    etm::enable_etm();
+
+    // The counter is clocked by APB, which is either derived from the PLL
+    // (80 MHz) or XTAL. Step the counter accordingly so it runs at 80 MHz
+    // regardless of the APB clock source.
+    #[cfg(esp32s2)]
+    unsafe { &*SYSTIMER::ptr() }.step().write(|w| {
+        w.timer_xtal_step()
+            .bits((80_000_000 / clocks.xtal_clock.to_Hz()) as u16)
+            .timer_pll_step()
+            .bits(1)
+    });
+
+    // Keep unit 0 counting while the CPUs are stalled, this can be changed via
+    // `Unit::set_stall`.
+    #[cfg(not(esp32s2))]
+    {
+        let unit0 = Unit::<0>::new();
+        unit0.set_stall(Cpu::ProCpu, false);
+        #[cfg(multi_core)]
+        unit0.set_stall(Cpu::AppCpu, false);
+    }
+
+    TICK_RATE.store(ticks_per_second(clocks) as u32, Ordering::Relaxed);
+}
+
 impl<'d> SystemTimer<'d, crate::Async> {
     /// Create a new instance in [crate::Async] mode.
-    pub fn new_async(_p: impl Peripheral<P = SYSTIMER> + 'd) -> Self {
-        #[cfg(soc_etm)]
-        etm::enable_etm();
+    pub fn new_async(_p: impl Peripheral<P = SYSTIMER> + 'd, clocks: &Clocks) -> Self {
+        init(clocks);
 
         Self {
             alarm0: Alarm::new(),
             alarm1: Alarm::new(),
             alarm2: Alarm::new(),
+            unit0: Unit::new(),
+            #[cfg(not(esp32s2))]
+            unit1: Unit::new(),
             _phantom: &PhantomData,
         }
     }
 }
 
+/// A point in time as measured by the system timer, with microsecond
+/// resolution.
+pub type Instant = fugit::TimerInstantU64<1_000_000>;
+
+/// A counter unit of the system timer.
+///
+/// Unit 0 is running out of reset and is the unit read by [SystemTimer::now].
+/// On all chips but the ESP32-S2 there is a second unit, which is stopped out
+/// of reset and has to be enabled via [Unit::enable] before use. Each [Alarm]
+/// can select either unit as the source of its comparator, see
+/// [Alarm::set_unit].
+#[derive(Debug)]
+pub struct Unit<const UNIT: u8> {
+    _private: (),
+}
+
+impl<const UNIT: u8> Unit<UNIT> {
+    // private constructor
+    fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Get the current count of this unit.
+    pub fn now(&self) -> u64 {
+        Self::read_value()
+    }
+
+    /// Get the current time of this unit as an [Instant].
+    pub fn now_instant(&self) -> Instant {
+        Instant::from_ticks(SystemTimer::ticks_to_duration(Self::read_value()).ticks())
+    }
+
+    fn read_value() -> u64 {
+        // This should be safe to access from multiple contexts
+        // worst case scenario the second accessor ends up reading
+        // an older time stamp
+        let systimer = unsafe { &*SYSTIMER::ptr() };
+
+        let (value_lo, value_hi) = match UNIT {
+            0 => {
+                systimer
+                    .unit0_op()
+                    .modify(|_, w| w.timer_unit0_update().set_bit());
+
+                while !systimer
+                    .unit0_op()
+                    .read()
+                    .timer_unit0_value_valid()
+                    .bit_is_set()
+                {}
+
+                (
+                    systimer.unit0_value_lo().read().bits(),
+                    systimer.unit0_value_hi().read().bits(),
+                )
+            }
+            #[cfg(not(esp32s2))]
+            1 => {
+                systimer
+                    .unit1_op()
+                    .modify(|_, w| w.timer_unit1_update().set_bit());
+
+                while !systimer
+                    .unit1_op()
+                    .read()
+                    .timer_unit1_value_valid()
+                    .bit_is_set()
+                {}
+
+                (
+                    systimer.unit1_value_lo().read().bits(),
+                    systimer.unit1_value_hi().read().bits(),
+                )
+            }
+            _ => unreachable!(),
+        };
+
+        ((value_hi as u64) << 32) | value_lo as u64
+    }
+
+    /// Start or stop the counter of this unit.
+    #[cfg(not(esp32s2))]
+    pub fn enable(&self, enable: bool) {
+        let systimer = unsafe { &*SYSTIMER::ptr() };
+        systimer.conf().modify(|_, w| match UNIT {
+            0 => w.timer_unit0_work_en().bit(enable),
+            1 => w.timer_unit1_work_en().bit(enable),
+            _ => unreachable!(),
+        });
+    }
+
+    /// Configure whether the counter of this unit stalls while the given CPU
+    /// is stalled, e.g. when it is halted by the debugger.
+    ///
+    /// [SystemTimer::new] configures unit 0 to keep counting while the CPUs
+    /// are stalled, unit 1 keeps its reset configuration.
+    #[cfg(not(esp32s2))]
+    pub fn set_stall(&self, cpu: Cpu, stall: bool) {
+        let systimer = unsafe { &*SYSTIMER::ptr() };
+        systimer.conf().modify(|_, w| match (UNIT, cpu) {
+            (0, Cpu::ProCpu) => w.timer_unit0_core0_stall_en().bit(stall),
+            (1, Cpu::ProCpu) => w.timer_unit1_core0_stall_en().bit(stall),
+            #[cfg(multi_core)]
+            (0, Cpu::AppCpu) => w.timer_unit0_core1_stall_en().bit(stall),
+            #[cfg(multi_core)]
+            (1, Cpu::AppCpu) => w.timer_unit1_core1_stall_en().bit(stall),
+            _ => unreachable!(),
+        });
+    }
+
+    /// Whether the counter of this unit stalls while the given CPU is stalled.
+    #[cfg(not(esp32s2))]
+    pub fn stalls(&self, cpu: Cpu) -> bool {
+        let conf = unsafe { &*SYSTIMER::ptr() }.conf().read();
+        match (UNIT, cpu) {
+            (0, Cpu::ProCpu) => conf.timer_unit0_core0_stall_en().bit_is_set(),
+            (1, Cpu::ProCpu) => conf.timer_unit1_core0_stall_en().bit_is_set(),
+            #[cfg(multi_core)]
+            (0, Cpu::AppCpu) => conf.timer_unit0_core1_stall_en().bit_is_set(),
+            #[cfg(multi_core)]
+            (1, Cpu::AppCpu) => conf.timer_unit1_core1_stall_en().bit_is_set(),
+            _ => unreachable!(),
+        }
+    }
+}
+
 /// A marker for a [Alarm] in target mode.
 #[derive(Debug)]
 pub struct Target;
 
 /// A marker for a [Alarm] in periodic mode.
 #[derive(Debug)]
-pub struct Periodic; // TODO, also impl e-h timer traits
+pub struct Periodic;
 
 /// A single alarm.
 #[derive(Debug)]
@@ -169,19 +369,15 @@ impl<T, DM: crate::Mode, const CHANNEL: u8> Alarm<T, DM, CHANNEL> {
                 _ => unreachable!(),
             };
 
-            #[cfg(esp32s2)]
-            systimer.step().write(|w| w.timer_xtal_step().bits(0x1)); // run at XTAL freq, not 80 * XTAL freq
-
+            // the unit selected via `set_unit`, preserved across reconfiguration
             #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
-            {
-                tconf.write(|w| w.target0_timer_unit_sel().clear_bit()); // default, use unit 0
-                systimer
-                    .conf()
-                    .modify(|_, w| w.timer_unit0_core0_stall_en().clear_bit());
-            }
+            let unit_sel = tconf.read().target0_timer_unit_sel().bit();
 
             conf(tconf, hi, lo);
 
+            #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
+            tconf.modify(|_, w| w.target0_timer_unit_sel().bit(unit_sel));
+
             #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s3))]
             {
                 match CHANNEL {
@@ -215,6 +411,41 @@ impl<T, DM: crate::Mode, const CHANNEL: u8> Alarm<T, DM, CHANNEL> {
         }
     }
 
+    /// Select the counter unit the comparator of this alarm is driven by.
+    ///
+    /// The selection takes effect the next time the alarm is configured via
+    /// `set_target` or `set_period`.
+    #[cfg(not(esp32s2))]
+    pub fn set_unit<const UNIT: u8>(&self, _unit: &Unit<UNIT>) {
+        Self::target_conf().modify(|_, w| w.target0_timer_unit_sel().bit(UNIT == 1));
+    }
+
+    #[cfg(not(esp32s2))]
+    fn target_conf() -> &'static Reg<TARGET0_CONF_SPEC> {
+        let systimer = unsafe { &*SYSTIMER::ptr() };
+        match CHANNEL {
+            0 => systimer.target0_conf(),
+            1 => unsafe { transmute(systimer.target1_conf()) },
+            2 => unsafe { transmute(systimer.target2_conf()) },
+            _ => unreachable!(),
+        }
+    }
+
+    /// The current value of the unit the comparator of this alarm is driven
+    /// by.
+    pub(crate) fn unit_value(&self) -> u64 {
+        #[cfg(not(esp32s2))]
+        if Self::target_conf()
+            .read()
+            .target0_timer_unit_sel()
+            .bit_is_set()
+        {
+            return Unit::<1>::read_value();
+        }
+
+        Unit::<0>::read_value()
+    }
+
     pub(crate) fn enable_interrupt_internal(&self, val: bool) {
         let systimer = unsafe { &*SYSTIMER::ptr() };
         match CHANNEL {
@@ -234,6 +465,17 @@ impl<T, DM: crate::Mode, const CHANNEL: u8> Alarm<T, DM, CHANNEL> {
             _ => unreachable!(),
         }
     }
+
+    pub(crate) fn is_interrupt_set_internal(&self) -> bool {
+        let systimer = unsafe { &*SYSTIMER::ptr() };
+        let r = systimer.int_raw().read();
+        match CHANNEL {
+            0 => r.target0().bit_is_set(),
+            1 => r.target1().bit_is_set(),
+            2 => r.target2().bit_is_set(),
+            _ => unreachable!(),
+        }
+    }
 }
 
 impl<T, const CHANNEL: u8> Alarm<T, crate::Blocking, CHANNEL> {
@@ -284,6 +526,140 @@ impl<T, const CHANNEL: u8> Alarm<T, crate::Blocking, CHANNEL> {
         self.clear_interrupt_internal();
     }
 }
+
+impl<const CHANNEL: u8> Alarm<Target, crate::Blocking, CHANNEL> {
+    /// Start a one-shot countdown which elapses after `timeout`.
+    pub fn start(&mut self, timeout: MicrosDurationU64) {
+        self.start_ticks(SystemTimer::duration_to_ticks(timeout));
+    }
+
+    fn start_ticks(&mut self, ticks: u64) {
+        self.clear_interrupt_internal();
+        self.set_target(self.unit_value() + ticks);
+    }
+
+    /// Check if the countdown has elapsed.
+    ///
+    /// Once elapsed, this keeps returning `true` until the countdown is
+    /// restarted with [Self::start].
+    pub fn has_elapsed(&mut self) -> bool {
+        // The alarm might not fire for targets which have already passed when
+        // they were set, so compare against the counter as well.
+        self.is_interrupt_set_internal() || self.unit_value() >= self.target()
+    }
+
+    /// Block until the countdown has elapsed.
+    pub fn wait(&mut self) {
+        while !self.has_elapsed() {}
+    }
+
+    fn target(&self) -> u64 {
+        let systimer = unsafe { &*SYSTIMER::ptr() };
+        let (hi, lo) = match CHANNEL {
+            0 => (
+                systimer.target0_hi().read().timer_target0_hi().bits(),
+                systimer.target0_lo().read().timer_target0_lo().bits(),
+            ),
+            1 => (
+                systimer.target1_hi().read().timer_target1_hi().bits(),
+                systimer.target1_lo().read().timer_target1_lo().bits(),
+            ),
+            2 => (
+                systimer.target2_hi().read().timer_target2_hi().bits(),
+                systimer.target2_lo().read().timer_target2_lo().bits(),
+            ),
+            _ => unreachable!(),
+        };
+
+        ((hi as u64) << 32) | lo as u64
+    }
+}
+
+impl<const CHANNEL: u8> Alarm<Periodic, crate::Blocking, CHANNEL> {
+    /// Start a periodic countdown which elapses every `period`.
+    pub fn start(&mut self, period: MicrosDurationU32) {
+        self.set_period(period);
+        self.clear_interrupt_internal();
+    }
+
+    /// Check if the current period has elapsed.
+    ///
+    /// This acknowledges the elapsed period, so it returns `true` once per
+    /// period.
+    pub fn has_elapsed(&mut self) -> bool {
+        if self.is_interrupt_set_internal() {
+            self.clear_interrupt_internal();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Block until the current period has elapsed.
+    pub fn wait(&mut self) {
+        while !self.has_elapsed() {}
+    }
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<const CHANNEL: u8> embedded_hal::delay::DelayNs for Alarm<Target, crate::Blocking, CHANNEL> {
+    fn delay_ns(&mut self, ns: u32) {
+        let ticks = ns as u64 * SystemTimer::ticks_per_second() / 1_000_000_000;
+        self.start_ticks(ticks);
+        self.wait();
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+impl<const CHANNEL: u8> embedded_hal_02::timer::CountDown
+    for Alarm<Target, crate::Blocking, CHANNEL>
+{
+    type Time = MicrosDurationU64;
+
+    fn start<Time>(&mut self, timeout: Time)
+    where
+        Time: Into<Self::Time>,
+    {
+        (*self).start(timeout.into())
+    }
+
+    fn wait(&mut self) -> nb::Result<(), void::Void> {
+        if self.has_elapsed() {
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+impl<const CHANNEL: u8> embedded_hal_02::timer::CountDown
+    for Alarm<Periodic, crate::Blocking, CHANNEL>
+{
+    type Time = MicrosDurationU32;
+
+    fn start<Time>(&mut self, period: Time)
+    where
+        Time: Into<Self::Time>,
+    {
+        (*self).start(period.into())
+    }
+
+    fn wait(&mut self) -> nb::Result<(), void::Void> {
+        if self.has_elapsed() {
+            Ok(())
+        } else {
+            Err(nb::Error::WouldBlock)
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-02")]
+impl<const CHANNEL: u8> embedded_hal_02::timer::Periodic
+    for Alarm<Periodic, crate::Blocking, CHANNEL>
+{
+}
 impl<DM: crate::Mode, const CHANNEL: u8> Alarm<Target, DM, CHANNEL> {
     /// Set the target value of this [Alarm]
     pub fn set_target(&self, timestamp: u64) {
@@ -303,8 +679,7 @@ impl<DM: crate::Mode, const CHANNEL: u8> Alarm<Target, DM, CHANNEL> {
 impl<DM: crate::Mode, const CHANNEL: u8> Alarm<Periodic, DM, CHANNEL> {
     /// Set the period of this [Alarm]
     pub fn set_period(&self, period: MicrosDurationU32) {
-        let us = period.ticks();
-        let ticks = us * (SystemTimer::TICKS_PER_SECOND / 1_000_000) as u32;
+        let ticks = SystemTimer::duration_to_ticks(period.into()) as u32;
 
         self.configure(|tconf, hi, lo| unsafe {
             tconf.write(|w| {
@@ -324,6 +699,248 @@ impl<DM: crate::Mode, const CHANNEL: u8> Alarm<Periodic, DM, CHANNEL> {
     }
 }
 
+/// Calls `$body` with `$alarm` bound to the [Alarm] for the runtime channel
+/// `$channel`.
+macro_rules! with_alarm {
+    ($channel:expr, $mode:ty, $dm:ty, $alarm:ident => $body:expr) => {
+        match $channel {
+            0 => {
+                let $alarm = Alarm::<$mode, $dm, 0>::new();
+                $body
+            }
+            1 => {
+                let $alarm = Alarm::<$mode, $dm, 1>::new();
+                $body
+            }
+            2 => {
+                let $alarm = Alarm::<$mode, $dm, 2>::new();
+                $body
+            }
+            _ => unreachable!(),
+        }
+    };
+}
+
+/// A type-erased [Alarm].
+///
+/// The channel and the mode of the alarm are only known at runtime, which
+/// makes it possible to e.g. store alarms in an array. Setting a target puts
+/// the alarm into [Target] mode, setting a period puts it into [Periodic]
+/// mode.
+#[derive(Debug)]
+pub struct AnyAlarm<DM: crate::Mode> {
+    channel: u8,
+    _pd: PhantomData<DM>,
+}
+
+impl<MODE, DM: crate::Mode, const CHANNEL: u8> Alarm<MODE, DM, CHANNEL> {
+    /// Erases the channel and the mode of this [Alarm].
+    pub fn degrade(self) -> AnyAlarm<DM> {
+        AnyAlarm {
+            channel: CHANNEL,
+            _pd: PhantomData,
+        }
+    }
+}
+
+impl<MODE, DM: crate::Mode, const CHANNEL: u8> From<Alarm<MODE, DM, CHANNEL>> for AnyAlarm<DM> {
+    fn from(alarm: Alarm<MODE, DM, CHANNEL>) -> Self {
+        alarm.degrade()
+    }
+}
+
+impl<DM: crate::Mode> AnyAlarm<DM> {
+    /// The channel of the underlying [Alarm].
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+
+    /// Set the target value of this alarm, putting it into [Target] mode.
+    pub fn set_target(&self, timestamp: u64) {
+        with_alarm!(self.channel, Target, DM, alarm => alarm.set_target(timestamp))
+    }
+
+    /// Set the period of this alarm, putting it into [Periodic] mode.
+    pub fn set_period(&self, period: MicrosDurationU32) {
+        with_alarm!(self.channel, Periodic, DM, alarm => alarm.set_period(period))
+    }
+
+    /// Select the counter unit the comparator of this alarm is driven by.
+    ///
+    /// See [Alarm::set_unit].
+    #[cfg(not(esp32s2))]
+    pub fn set_unit<const UNIT: u8>(&self, unit: &Unit<UNIT>) {
+        with_alarm!(self.channel, Target, DM, alarm => alarm.set_unit(unit))
+    }
+
+    /// Whether the interrupt of this alarm is pending.
+    pub fn is_interrupt_set(&self) -> bool {
+        with_alarm!(self.channel, Target, DM, alarm => alarm.is_interrupt_set_internal())
+    }
+
+    pub(crate) fn enable_interrupt_internal(&self, val: bool) {
+        with_alarm!(self.channel, Target, DM, alarm => alarm.enable_interrupt_internal(val))
+    }
+
+    pub(crate) fn clear_interrupt_internal(&self) {
+        with_alarm!(self.channel, Target, DM, alarm => alarm.clear_interrupt_internal())
+    }
+}
+
+impl AnyAlarm<crate::Blocking> {
+    /// Set the interrupt handler for this alarm.
+    pub fn set_interrupt_handler(&self, handler: InterruptHandler) {
+        with_alarm!(self.channel, Target, crate::Blocking, alarm => {
+            alarm.set_interrupt_handler(handler)
+        })
+    }
+
+    /// Enable the interrupt for this alarm.
+    pub fn enable_interrupt(&self, val: bool) {
+        self.enable_interrupt_internal(val);
+    }
+
+    /// Clear the interrupt pending status for this alarm.
+    pub fn clear_interrupt(&self) {
+        self.clear_interrupt_internal();
+    }
+}
+
+impl<const CHANNEL: u8> crate::timer::GenericTimer for Alarm<Target, crate::Blocking, CHANNEL> {
+    fn start(&mut self, timeout: MicrosDurationU64) {
+        Alarm::start(self, timeout);
+    }
+
+    fn has_elapsed(&mut self) -> bool {
+        Alarm::has_elapsed(self)
+    }
+
+    fn enable_interrupt(&mut self, enable: bool) {
+        self.enable_interrupt_internal(enable);
+    }
+
+    fn clear_interrupt(&mut self) {
+        self.clear_interrupt_internal();
+    }
+
+    fn is_interrupt_set(&self) -> bool {
+        self.is_interrupt_set_internal()
+    }
+}
+
+/// An [AnyAlarm] used as a [GenericTimer](crate::timer::GenericTimer) is put
+/// into [Target] mode.
+impl crate::timer::GenericTimer for AnyAlarm<crate::Blocking> {
+    fn start(&mut self, timeout: MicrosDurationU64) {
+        with_alarm!(self.channel, Target, crate::Blocking, alarm => {
+            let mut alarm = alarm;
+            alarm.start(timeout)
+        })
+    }
+
+    fn has_elapsed(&mut self) -> bool {
+        with_alarm!(self.channel, Target, crate::Blocking, alarm => {
+            let mut alarm = alarm;
+            alarm.has_elapsed()
+        })
+    }
+
+    fn enable_interrupt(&mut self, enable: bool) {
+        self.enable_interrupt_internal(enable);
+    }
+
+    fn clear_interrupt(&mut self) {
+        self.clear_interrupt_internal();
+    }
+
+    fn is_interrupt_set(&self) -> bool {
+        AnyAlarm::is_interrupt_set(self)
+    }
+}
+
+/// A drift-free periodic alarm.
+///
+/// In contrast to an [Alarm] in [Periodic] mode, whose period restarts every
+/// time the hardware reloads the comparator, a ticker is built on an [Alarm]
+/// in [Target] mode and computes every target from the previous one. Ticks
+/// therefore stay aligned to an absolute schedule, no matter how late the
+/// previous tick has been handled. Ticks which have been missed entirely are
+/// skipped.
+#[derive(Debug)]
+pub struct Ticker<DM: crate::Mode, const CHANNEL: u8> {
+    alarm: Alarm<Target, DM, CHANNEL>,
+    period: u64,
+    next: u64,
+}
+
+impl<DM: crate::Mode, const CHANNEL: u8> Alarm<Target, DM, CHANNEL> {
+    /// Converts this [Alarm] into a [Ticker] firing every `period`, starting
+    /// one period from now.
+    pub fn into_ticker(self, period: MicrosDurationU64) -> Ticker<DM, CHANNEL> {
+        let period = SystemTimer::duration_to_ticks(period).max(1);
+        let next = self.unit_value() + period;
+
+        self.clear_interrupt_internal();
+        self.set_target(next);
+
+        Ticker {
+            alarm: self,
+            period,
+            next,
+        }
+    }
+}
+
+impl<DM: crate::Mode, const CHANNEL: u8> Ticker<DM, CHANNEL> {
+    /// The timestamp of the next tick, in ticks of the unit the underlying
+    /// alarm is driven by.
+    pub fn next_tick(&self) -> u64 {
+        self.next
+    }
+
+    /// Converts this [Ticker] back into the underlying [Alarm].
+    pub fn into_alarm(self) -> Alarm<Target, DM, CHANNEL> {
+        self.alarm
+    }
+
+    fn advance(&mut self) {
+        self.next += self.period;
+
+        let now = self.alarm.unit_value();
+        if self.next <= now {
+            let missed = (now - self.next) / self.period + 1;
+            self.next += missed * self.period;
+        }
+    }
+}
+
+impl<const CHANNEL: u8> Ticker<crate::Blocking, CHANNEL> {
+    /// Set the interrupt handler for the underlying alarm.
+    pub fn set_interrupt_handler(&self, handler: InterruptHandler) {
+        self.alarm.set_interrupt_handler(handler);
+    }
+
+    /// Enable the interrupt for the underlying alarm.
+    pub fn enable_interrupt(&self, val: bool) {
+        self.alarm.enable_interrupt(val);
+    }
+
+    /// Acknowledge the current tick and arm the alarm for the next one.
+    ///
+    /// This is meant to be called from the interrupt handler.
+    pub fn rearm(&mut self) {
+        self.alarm.clear_interrupt_internal();
+        self.advance();
+        self.alarm.set_target(self.next);
+    }
+
+    /// Block until the next tick, then arm the alarm for the following one.
+    pub fn wait(&mut self) {
+        while !self.alarm.has_elapsed() {}
+        self.rearm();
+    }
+}
+
 impl<T, DM: crate::Mode> Alarm<T, DM, 0> {
     /// Conjure an alarm out of thin air.
     ///
@@ -378,14 +995,14 @@ mod asynch {
     const INIT: AtomicWaker = AtomicWaker::new();
     static WAKERS: [AtomicWaker; NUM_ALARMS] = [INIT; NUM_ALARMS];
 
-    pub(crate) struct AlarmFuture<'a, const N: u8> {
-        phantom: PhantomData<&'a Alarm<Periodic, crate::Async, N>>,
+    pub(crate) struct AlarmFuture<'a, MODE, const N: u8> {
+        phantom: PhantomData<&'a Alarm<MODE, crate::Async, N>>,
     }
 
-    impl<'a, const N: u8> AlarmFuture<'a, N> {
-        pub(crate) fn new(alarm: &'a Alarm<Periodic, crate::Async, N>) -> Self {
-            alarm.clear_interrupt_internal();
-
+    impl<'a, MODE, const N: u8> AlarmFuture<'a, MODE, N> {
+        /// Enables the interrupt of an already configured alarm. Any pending
+        /// interrupt must have been cleared before configuring the alarm.
+        pub(crate) fn new(alarm: &'a Alarm<MODE, crate::Async, N>) -> Self {
             let (interrupt, handler) = match N {
                 0 => (
                     crate::peripherals::Interrupt::SYSTIMER_TARGET0,
@@ -427,7 +1044,7 @@ mod asynch {
         }
     }
 
-    impl<'a, const N: u8> core::future::Future for AlarmFuture<'a, N> {
+    impl<'a, MODE, const N: u8> core::future::Future for AlarmFuture<'a, MODE, N> {
         type Output = ();
 
         fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
@@ -441,12 +1058,69 @@ mod asynch {
         }
     }
 
+    impl<const CHANNEL: u8> Alarm<Target, crate::Async, CHANNEL> {
+        /// Wait until the counter reaches `timestamp`.
+        ///
+        /// The timestamp is in ticks of the unit this alarm is driven by. If
+        /// the timestamp has already passed, this returns immediately.
+        pub async fn wait_until(&mut self, timestamp: u64) {
+            self.clear_interrupt_internal();
+            self.set_target(timestamp);
+
+            // The hardware fires the alarm even if the timestamp already passed,
+            // but in practice the interrupt is missed for very short timeouts, so
+            // catch this case here instead of waiting forever.
+            if self.unit_value() >= timestamp {
+                return;
+            }
+
+            AlarmFuture::new(self).await;
+        }
+
+        /// Wait for the given duration to elapse.
+        pub async fn wait_for(&mut self, duration: MicrosDurationU64) {
+            let ticks = SystemTimer::duration_to_ticks(duration);
+            self.wait_until(self.unit_value() + ticks).await;
+        }
+    }
+
+    impl AnyAlarm<crate::Async> {
+        /// Wait until the counter reaches `timestamp`, putting the alarm into
+        /// [Target] mode.
+        ///
+        /// See [Alarm::wait_until].
+        pub async fn wait_until(&mut self, timestamp: u64) {
+            with_alarm!(self.channel, Target, crate::Async, alarm => {
+                let mut alarm = alarm;
+                alarm.wait_until(timestamp).await
+            })
+        }
+
+        /// Wait for the given duration to elapse, putting the alarm into
+        /// [Target] mode.
+        pub async fn wait_for(&mut self, duration: MicrosDurationU64) {
+            with_alarm!(self.channel, Target, crate::Async, alarm => {
+                let mut alarm = alarm;
+                alarm.wait_for(duration).await
+            })
+        }
+    }
+
+    impl<const CHANNEL: u8> Ticker<crate::Async, CHANNEL> {
+        /// Wait for the next tick.
+        pub async fn tick(&mut self) {
+            self.alarm.wait_until(self.next).await;
+            self.advance();
+        }
+    }
+
     impl<const CHANNEL: u8> embedded_hal_async::delay::DelayNs
         for Alarm<Periodic, crate::Async, CHANNEL>
     {
         async fn delay_ns(&mut self, ns: u32) {
             let period = MicrosDurationU32::from_ticks(ns / 1000);
             self.set_period(period);
+            self.clear_interrupt_internal();
 
             AlarmFuture::new(self).await;
         }
@@ -500,9 +1174,14 @@ pub mod etm {
     //!    - SYSTIMER_EVT_CNT_CMPx: Indicates the alarm pulses generated by
     //!      COMPx
     //!
+    //! The system timer does not provide any ETM tasks on the supported chips,
+    //! so it can't be controlled by other peripherals' events. Use the tasks of
+    //! the general purpose timers instead, see
+    //! [TimerEtmTasks](crate::timer::etm::TimerEtmTasks).
+    //!
     //! ## Example
     //! ```no_run
-    //! let syst = SystemTimer::new(peripherals.SYSTIMER);
+    //! let syst = SystemTimer::new(peripherals.SYSTIMER, &clocks);
     //! let mut alarm0 = syst.alarm0.into_periodic();
     //! alarm0.set_period(1.secs());
     //!
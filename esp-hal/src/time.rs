@@ -0,0 +1,118 @@
+//! # Monotonic time keeping
+//!
+//! ## Overview
+//! This module provides a 64-bit microsecond clock which keeps advancing
+//! across light sleep and deep sleep.
+//!
+//! The high resolution time base is the `SYSTIMER` peripheral. It is anchored
+//! to the RTC timer, which keeps running in all sleep modes. The time spent in
+//! light sleep entered via [Rtc::sleep] is measured using the RTC timer and
+//! added to the clock, and after waking up from deep sleep the clock is
+//! re-synchronized to the RTC timer.
+//!
+//! The accuracy of the time spent in sleep and of the anchor depends on the
+//! accuracy of the RTC slow clock.
+//!
+//! ## Example
+//! ```no_run
+//! let boot = time::boot_time();
+//! let uptime = time::uptime();
+//!
+//! println!(
+//!     "Booted at {} µs, up for {} µs",
+//!     boot.ticks(),
+//!     uptime.to_micros()
+//! );
+//! println!("Now: {} µs", time::now().ticks());
+//! ```
+//!
+//! [Rtc::sleep]: crate::rtc_cntl::Rtc::sleep
+
+use core::cell::Cell;
+
+use critical_section::Mutex;
+use fugit::MicrosDurationU64;
+
+use crate::{rtc_cntl, systimer::SystemTimer};
+
+/// A point in time with microsecond resolution, measured from the moment the
+/// RTC timer started counting (usually power-on).
+pub type Instant = fugit::TimerInstantU64<1_000_000>;
+
+/// The time spent in light sleep during the current boot, in microseconds.
+static SLEEP_TIME: Mutex<Cell<u64>> = Mutex::new(Cell::new(0));
+
+/// The RTC time at which the current boot started, in microseconds.
+static BOOT_TIME: Mutex<Cell<Option<u64>>> = Mutex::new(Cell::new(None));
+
+/// The current time.
+///
+/// This keeps advancing across light sleep and deep sleep.
+pub fn now() -> Instant {
+    boot_time() + uptime()
+}
+
+/// The time passed since the current boot, including the time spent in light
+/// sleep.
+///
+/// This starts from zero again after waking up from deep sleep.
+pub fn uptime() -> MicrosDurationU64 {
+    let sleep_time = critical_section::with(|cs| SLEEP_TIME.borrow(cs).get());
+
+    MicrosDurationU64::from_ticks(systimer_us() + sleep_time)
+}
+
+/// The point in time at which the current boot started.
+pub fn boot_time() -> Instant {
+    let boot_time = critical_section::with(|cs| {
+        let boot_time = BOOT_TIME.borrow(cs);
+
+        match boot_time.get() {
+            Some(time) => time,
+            None => {
+                let sleep_time = SLEEP_TIME.borrow(cs).get();
+                let time = rtc_cntl::get_time_us().saturating_sub(systimer_us() + sleep_time);
+                boot_time.set(Some(time));
+                time
+            }
+        }
+    });
+
+    Instant::from_ticks(boot_time)
+}
+
+fn systimer_us() -> u64 {
+    SystemTimer::ticks_to_duration(SystemTimer::now()).ticks()
+}
+
+/// Measures the time spent in light sleep.
+pub(crate) struct SleepTracker {
+    rtc_us: u64,
+    systimer_us: u64,
+}
+
+impl SleepTracker {
+    /// Start measuring, right before entering sleep.
+    pub(crate) fn start() -> Self {
+        // make sure the anchor is taken before the clocks diverge
+        boot_time();
+
+        Self {
+            rtc_us: rtc_cntl::get_time_us(),
+            systimer_us: systimer_us(),
+        }
+    }
+
+    /// Stop measuring, right after waking up.
+    pub(crate) fn finish(self) {
+        let rtc_elapsed = rtc_cntl::get_time_us().saturating_sub(self.rtc_us);
+        let systimer_elapsed = systimer_us().saturating_sub(self.systimer_us);
+
+        // Only account for the time the SYSTIMER didn't see, it might or might not
+        // have been running while sleeping.
+        critical_section::with(|cs| {
+            let sleep_time = SLEEP_TIME.borrow(cs);
+            sleep_time.set(sleep_time.get() + rtc_elapsed.saturating_sub(systimer_elapsed));
+        });
+    }
+}
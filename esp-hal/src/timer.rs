@@ -39,7 +39,7 @@ use core::{
     ops::{Deref, DerefMut},
 };
 
-use fugit::{HertzU32, MicrosDurationU64};
+use fugit::{ExtU64, HertzU32, MicrosDurationU64};
 
 #[cfg(timg1)]
 use crate::peripherals::TIMG1;
@@ -60,6 +60,8 @@ pub enum Error {
     TimerActive,
     TimerInactive,
     AlarmInactive,
+    /// The window of a windowed watchdog doesn't close after it opens
+    InvalidWindow,
 }
 
 /// Interrupts which can be registered in [crate::Blocking] mode
@@ -368,10 +370,89 @@ where
     }
 }
 
+/// Clock source of a general purpose timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum ClockSource {
+    /// The APB clock, which follows changes of the CPU clock
+    #[cfg(not(any(esp32c6, esp32h2)))]
+    Apb,
+    /// The crystal clock, which is independent of the CPU clock
+    #[cfg(not(esp32))]
+    Xtal,
+    /// A fixed frequency PLL clock (80 MHz on the ESP32-C6, 48 MHz on the
+    /// ESP32-H2)
+    #[cfg(any(esp32c6, esp32h2))]
+    Pll,
+}
+
+impl ClockSource {
+    fn frequency(&self, clocks: &Clocks) -> HertzU32 {
+        match self {
+            #[cfg(not(any(esp32c6, esp32h2)))]
+            ClockSource::Apb => clocks.apb_clock,
+            #[cfg(not(esp32))]
+            ClockSource::Xtal => clocks.xtal_clock,
+            #[cfg(esp32c6)]
+            ClockSource::Pll => clocks.apb_clock,
+            #[cfg(esp32h2)]
+            ClockSource::Pll => clocks.pll_48m_clock,
+        }
+    }
+}
+
+/// Counting direction of a general purpose timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CountDirection {
+    Up,
+    Down,
+}
+
+/// Configuration of a timer which reloads its counter in hardware on every
+/// alarm, see [Timer::start_auto_reload].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct AutoReloadConfig {
+    load_value: u64,
+    alarm_value: u64,
+    direction: CountDirection,
+}
+
+impl AutoReloadConfig {
+    /// Raise an alarm whenever the counter reaches `alarm_value`, counting up
+    /// from zero.
+    pub const fn new(alarm_value: u64) -> Self {
+        Self {
+            load_value: 0,
+            alarm_value,
+            direction: CountDirection::Up,
+        }
+    }
+
+    /// The value the counter starts at, and is reloaded with on every alarm.
+    pub const fn with_load_value(mut self, load_value: u64) -> Self {
+        self.load_value = load_value;
+        self
+    }
+
+    /// The counter value which raises the alarm.
+    pub const fn with_alarm_value(mut self, alarm_value: u64) -> Self {
+        self.alarm_value = alarm_value;
+        self
+    }
+
+    /// The direction the counter counts in.
+    pub const fn with_direction(mut self, direction: CountDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+}
+
 /// General-purpose Timer driver
 pub struct Timer<T, DM: crate::Mode> {
     timg: T,
-    apb_clk_freq: HertzU32,
+    clk_freq: HertzU32,
     phantom: PhantomData<DM>,
 }
 
@@ -379,31 +460,59 @@ impl<T, DM: crate::Mode> Timer<T, DM>
 where
     T: Instance,
 {
-    /// Create a new timer instance.
-    pub fn new(timg: T, apb_clk_freq: HertzU32) -> Self {
-        // TODO: this currently assumes APB_CLK is being used, as we don't yet have a
-        //       way to select the XTAL_CLK.
-
+    /// Create a new timer instance, clocked by a source of frequency
+    /// `clk_freq`.
+    pub fn new(timg: T, clk_freq: HertzU32) -> Self {
         timg.enable_peripheral();
 
         Self {
             timg,
-            apb_clk_freq,
+            clk_freq,
             phantom: PhantomData,
         }
     }
 
+    /// Select the clock source of the timer.
+    ///
+    /// The prescaler is adjusted to keep the counting rate as close as possible
+    /// to the current one. The timer is paused while switching the source.
+    ///
+    /// Note that on the ESP32-C6 and ESP32-H2 the clock source is shared by
+    /// all timers of a timer group.
+    pub fn set_clock_source(&mut self, source: ClockSource, clocks: &Clocks) {
+        let new_freq = source.frequency(clocks).to_Hz() as u64;
+        let old_freq = self.clk_freq.to_Hz() as u64;
+        let divider = self.timg.divider() as u64;
+
+        // Keep `freq / divider` constant, within the range of the prescaler
+        let divider = ((divider * new_freq + old_freq / 2) / old_freq).clamp(2, 65536);
+
+        let active = self.timg.is_counter_active();
+        self.timg.set_counter_active(false);
+
+        self.timg.set_clock_source(source);
+        // A value of 0 selects a divider of 65536
+        self.timg.set_divider(divider as u16);
+        self.clk_freq = source.frequency(clocks);
+
+        self.timg.set_counter_active(active);
+    }
+
+    /// The frequency of the clock driving the timer, before the prescaler.
+    pub fn clock_frequency(&self) -> HertzU32 {
+        self.clk_freq
+    }
+
     /// Start the timer with the given time period.
     pub fn start(&mut self, timeout: MicrosDurationU64) {
         self.timg.set_counter_active(false);
         self.timg.set_alarm_active(false);
+        self.timg.clear_interrupt();
 
         self.timg.reset_counter();
 
-        // TODO: this currently assumes APB_CLK is being used, as we don't yet have a
-        //       way to select the XTAL_CLK.
         // TODO: can we cache the divider (only get it on initialization)?
-        let ticks = timeout_to_ticks(timeout, self.apb_clk_freq, self.timg.divider());
+        let ticks = timeout_to_ticks(timeout, self.clk_freq, self.timg.divider());
         self.timg.load_alarm_value(ticks);
 
         self.timg.set_counter_decrementing(false);
@@ -412,6 +521,36 @@ where
         self.timg.set_alarm_active(true);
     }
 
+    /// Start the timer with counter values given in ticks of the prescaled
+    /// clock.
+    ///
+    /// The counter is reloaded with the load value by the hardware whenever
+    /// the alarm fires, so the period doesn't depend on how quickly the alarm
+    /// is handled. The alarm itself still has to be re-enabled after each
+    /// alarm, see [Self::rearm].
+    pub fn start_auto_reload(&mut self, config: AutoReloadConfig) {
+        self.timg.set_counter_active(false);
+        self.timg.set_alarm_active(false);
+        self.timg.clear_interrupt();
+
+        self.timg.load_counter_value(config.load_value);
+        self.timg.load_alarm_value(config.alarm_value);
+
+        self.timg
+            .set_counter_decrementing(config.direction == CountDirection::Down);
+        self.timg.set_auto_reload(true);
+        self.timg.set_counter_active(true);
+        self.timg.set_alarm_active(true);
+    }
+
+    /// Clear the interrupt and re-enable the alarm of a timer started via
+    /// [Self::start] or [Self::start_auto_reload], e.g. from its interrupt
+    /// handler.
+    pub fn rearm(&mut self) {
+        self.timg.clear_interrupt();
+        self.timg.set_alarm_active(true);
+    }
+
     /// Check if the timer has elapsed
     pub fn has_elapsed(&mut self) -> bool {
         if !self.timg.is_counter_active() {
@@ -454,10 +593,286 @@ where
     }
 }
 
+/// Functionality shared by the general purpose timers and the `SYSTIMER`
+/// alarms.
+///
+/// This allows code which only needs some source of timeouts to accept any of
+/// them, either generically or as an [AnyTimer].
+pub trait GenericTimer {
+    /// Start a countdown which elapses after `timeout`.
+    fn start(&mut self, timeout: MicrosDurationU64);
+
+    /// Check if the countdown has elapsed.
+    ///
+    /// This returns `true` at least once after the countdown elapsed.
+    fn has_elapsed(&mut self) -> bool;
+
+    /// Block until the countdown has elapsed.
+    fn wait(&mut self) {
+        while !self.has_elapsed() {}
+    }
+
+    /// Enable or disable the interrupt raised when the countdown elapses.
+    fn enable_interrupt(&mut self, enable: bool);
+
+    /// Clear the pending interrupt.
+    fn clear_interrupt(&mut self);
+
+    /// Whether the interrupt is pending.
+    fn is_interrupt_set(&self) -> bool;
+}
+
+impl<T> GenericTimer for Timer<T, crate::Blocking>
+where
+    T: Instance,
+{
+    fn start(&mut self, timeout: MicrosDurationU64) {
+        Timer::start(self, timeout);
+    }
+
+    fn has_elapsed(&mut self) -> bool {
+        Timer::has_elapsed(self)
+    }
+
+    fn enable_interrupt(&mut self, enable: bool) {
+        if enable {
+            self.timg.listen();
+        } else {
+            self.timg.unlisten();
+        }
+    }
+
+    fn clear_interrupt(&mut self) {
+        self.timg.clear_interrupt();
+    }
+
+    fn is_interrupt_set(&self) -> bool {
+        self.timg.is_interrupt_set()
+    }
+}
+
+enum AnyTimerInner {
+    Timg0Timer0(Timer<Timer0<TIMG0>, crate::Blocking>),
+    #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+    Timg0Timer1(Timer<Timer1<TIMG0>, crate::Blocking>),
+    #[cfg(timg1)]
+    Timg1Timer0(Timer<Timer0<TIMG1>, crate::Blocking>),
+    #[cfg(all(timg1, not(any(esp32c2, esp32c3, esp32c6, esp32h2))))]
+    Timg1Timer1(Timer<Timer1<TIMG1>, crate::Blocking>),
+    #[cfg(systimer)]
+    SystemTimer(crate::systimer::AnyAlarm<crate::Blocking>),
+}
+
+/// A type-erased timer, which is either a general purpose timer or a
+/// `SYSTIMER` alarm.
+///
+/// All timers convert into an [AnyTimer] via [From]. An alarm of the system
+/// timer is used in target mode.
+pub struct AnyTimer(AnyTimerInner);
+
+/// Calls `$body` with `$timer` bound to the [GenericTimer] wrapped by the
+/// [AnyTimer] `$any`.
+macro_rules! with_timer {
+    ($any:expr, $timer:ident => $body:expr) => {
+        match $any {
+            AnyTimerInner::Timg0Timer0($timer) => $body,
+            #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+            AnyTimerInner::Timg0Timer1($timer) => $body,
+            #[cfg(timg1)]
+            AnyTimerInner::Timg1Timer0($timer) => $body,
+            #[cfg(all(timg1, not(any(esp32c2, esp32c3, esp32c6, esp32h2))))]
+            AnyTimerInner::Timg1Timer1($timer) => $body,
+            #[cfg(systimer)]
+            AnyTimerInner::SystemTimer($timer) => $body,
+        }
+    };
+}
+
+impl GenericTimer for AnyTimer {
+    fn start(&mut self, timeout: MicrosDurationU64) {
+        with_timer!(&mut self.0, timer => timer.start(timeout))
+    }
+
+    fn has_elapsed(&mut self) -> bool {
+        with_timer!(&mut self.0, timer => timer.has_elapsed())
+    }
+
+    fn enable_interrupt(&mut self, enable: bool) {
+        with_timer!(&mut self.0, timer => timer.enable_interrupt(enable))
+    }
+
+    fn clear_interrupt(&mut self) {
+        with_timer!(&mut self.0, timer => timer.clear_interrupt())
+    }
+
+    fn is_interrupt_set(&self) -> bool {
+        with_timer!(&self.0, timer => timer.is_interrupt_set())
+    }
+}
+
+impl From<Timer<Timer0<TIMG0>, crate::Blocking>> for AnyTimer {
+    fn from(timer: Timer<Timer0<TIMG0>, crate::Blocking>) -> Self {
+        Self(AnyTimerInner::Timg0Timer0(timer))
+    }
+}
+
+#[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+impl From<Timer<Timer1<TIMG0>, crate::Blocking>> for AnyTimer {
+    fn from(timer: Timer<Timer1<TIMG0>, crate::Blocking>) -> Self {
+        Self(AnyTimerInner::Timg0Timer1(timer))
+    }
+}
+
+#[cfg(timg1)]
+impl From<Timer<Timer0<TIMG1>, crate::Blocking>> for AnyTimer {
+    fn from(timer: Timer<Timer0<TIMG1>, crate::Blocking>) -> Self {
+        Self(AnyTimerInner::Timg1Timer0(timer))
+    }
+}
+
+#[cfg(all(timg1, not(any(esp32c2, esp32c3, esp32c6, esp32h2))))]
+impl From<Timer<Timer1<TIMG1>, crate::Blocking>> for AnyTimer {
+    fn from(timer: Timer<Timer1<TIMG1>, crate::Blocking>) -> Self {
+        Self(AnyTimerInner::Timg1Timer1(timer))
+    }
+}
+
+#[cfg(systimer)]
+impl From<crate::systimer::AnyAlarm<crate::Blocking>> for AnyTimer {
+    fn from(alarm: crate::systimer::AnyAlarm<crate::Blocking>) -> Self {
+        Self(AnyTimerInner::SystemTimer(alarm))
+    }
+}
+
+#[cfg(systimer)]
+impl<MODE, const CHANNEL: u8> From<crate::systimer::Alarm<MODE, crate::Blocking, CHANNEL>>
+    for AnyTimer
+{
+    fn from(alarm: crate::systimer::Alarm<MODE, crate::Blocking, CHANNEL>) -> Self {
+        Self(AnyTimerInner::SystemTimer(alarm.degrade()))
+    }
+}
+
+/// A counter running freely at 1 MHz, with an alarm at an absolute value of
+/// it. This is what the embassy time driver needs from an [AnyTimer].
+#[cfg(feature = "embassy-time-any-timer")]
+pub(crate) trait FreeRunning {
+    /// Start counting from zero, with the alarm disabled.
+    fn start_free_running(&mut self);
+
+    /// The counter value, in microseconds.
+    fn now_micros(&self) -> u64;
+
+    /// Raise the interrupt once the counter reaches `micros`.
+    fn arm_at(&mut self, micros: u64);
+}
+
+#[cfg(feature = "embassy-time-any-timer")]
+impl<T> FreeRunning for Timer<T, crate::Blocking>
+where
+    T: Instance,
+{
+    fn start_free_running(&mut self) {
+        self.timg.set_counter_active(false);
+        self.timg.set_alarm_active(false);
+        self.timg.unlisten();
+        self.timg.clear_interrupt();
+
+        let divider = self.clk_freq.to_MHz().clamp(2, u16::MAX as u32);
+        self.timg.set_divider(divider as u16);
+        self.timg.reset_counter();
+        self.timg.set_counter_decrementing(false);
+        self.timg.set_auto_reload(false);
+        self.timg.set_counter_active(true);
+    }
+
+    fn now_micros(&self) -> u64 {
+        self.timg.now()
+    }
+
+    fn arm_at(&mut self, micros: u64) {
+        self.timg.load_alarm_value(micros);
+        self.timg.listen();
+        self.timg.set_alarm_active(true);
+    }
+}
+
+/// The system timer counts in [Target] mode on unit 0, which is always
+/// running.
+///
+/// [Target]: crate::systimer::Target
+#[cfg(all(feature = "embassy-time-any-timer", systimer))]
+impl FreeRunning for crate::systimer::AnyAlarm<crate::Blocking> {
+    fn start_free_running(&mut self) {
+        self.enable_interrupt_internal(false);
+        self.clear_interrupt_internal();
+    }
+
+    fn now_micros(&self) -> u64 {
+        use crate::systimer::SystemTimer;
+
+        SystemTimer::ticks_to_duration(SystemTimer::now()).ticks()
+    }
+
+    fn arm_at(&mut self, micros: u64) {
+        use crate::systimer::SystemTimer;
+
+        // Rounded up, so the alarm doesn't fire before `micros`
+        let rate = SystemTimer::ticks_per_second();
+        let ticks = (micros / 1_000_000)
+            .saturating_mul(rate)
+            .saturating_add(((micros % 1_000_000) * rate).div_ceil(1_000_000));
+
+        self.set_target(ticks.min(SystemTimer::BIT_MASK));
+        self.enable_interrupt_internal(true);
+    }
+}
+
+#[cfg(feature = "embassy-time-any-timer")]
+impl FreeRunning for AnyTimer {
+    fn start_free_running(&mut self) {
+        with_timer!(&mut self.0, timer => timer.start_free_running())
+    }
+
+    fn now_micros(&self) -> u64 {
+        with_timer!(&self.0, timer => timer.now_micros())
+    }
+
+    fn arm_at(&mut self, micros: u64) {
+        with_timer!(&mut self.0, timer => timer.arm_at(micros))
+    }
+}
+
+#[cfg(feature = "embassy-time-any-timer")]
+impl AnyTimer {
+    /// The interrupt raised by the alarm of this timer.
+    pub(crate) fn interrupt(&self) -> crate::peripherals::Interrupt {
+        use crate::peripherals::Interrupt;
+
+        match &self.0 {
+            AnyTimerInner::Timg0Timer0(_) => Interrupt::TG0_T0_LEVEL,
+            #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+            AnyTimerInner::Timg0Timer1(_) => Interrupt::TG0_T1_LEVEL,
+            #[cfg(timg1)]
+            AnyTimerInner::Timg1Timer0(_) => Interrupt::TG1_T0_LEVEL,
+            #[cfg(all(timg1, not(any(esp32c2, esp32c3, esp32c6, esp32h2))))]
+            AnyTimerInner::Timg1Timer1(_) => Interrupt::TG1_T1_LEVEL,
+            #[cfg(systimer)]
+            AnyTimerInner::SystemTimer(alarm) => match alarm.channel() {
+                0 => Interrupt::SYSTIMER_TARGET0,
+                1 => Interrupt::SYSTIMER_TARGET1,
+                _ => Interrupt::SYSTIMER_TARGET2,
+            },
+        }
+    }
+}
+
 /// Timer peripheral instance
 pub trait Instance: crate::private::Sealed + Enable {
     fn reset_counter(&mut self);
 
+    fn load_counter_value(&mut self, value: u64);
+
     fn set_counter_active(&mut self, state: bool);
 
     fn is_counter_active(&self) -> bool;
@@ -484,6 +899,8 @@ pub trait Instance: crate::private::Sealed + Enable {
 
     fn set_divider(&mut self, divider: u16);
 
+    fn set_clock_source(&mut self, source: ClockSource);
+
     fn is_interrupt_set(&self) -> bool;
 }
 
@@ -534,6 +951,20 @@ where
         t.load().write(|w| unsafe { w.load().bits(1) });
     }
 
+    fn load_counter_value(&mut self, value: u64) {
+        let value = value & 0x3F_FFFF_FFFF_FFFF;
+        let high = (value >> 32) as u32;
+        let low = (value & 0xFFFF_FFFF) as u32;
+
+        let t = unsafe { Self::t() };
+
+        t.loadlo().write(|w| unsafe { w.load_lo().bits(low) });
+
+        t.loadhi().write(|w| unsafe { w.load_hi().bits(high) });
+
+        t.load().write(|w| unsafe { w.load().bits(1) });
+    }
+
     fn set_counter_active(&mut self, state: bool) {
         unsafe { Self::t() }
             .config()
@@ -643,6 +1074,35 @@ where
             .config()
             .modify(|_, w| unsafe { w.divider().bits(divider) })
     }
+
+    #[cfg(esp32)]
+    fn set_clock_source(&mut self, _source: ClockSource) {
+        // ESP32 has only APB clock source, do nothing
+    }
+
+    #[cfg(any(esp32c2, esp32c3, esp32s2, esp32s3))]
+    fn set_clock_source(&mut self, source: ClockSource) {
+        unsafe { Self::t() }
+            .config()
+            .modify(|_, w| w.use_xtal().bit(source == ClockSource::Xtal));
+    }
+
+    #[cfg(any(esp32c6, esp32h2))]
+    fn set_clock_source(&mut self, source: ClockSource) {
+        let sel = match source {
+            ClockSource::Xtal => 0,
+            ClockSource::Pll => TIMG_DEFAULT_CLK_SRC,
+        };
+
+        let pcr = unsafe { &*crate::peripherals::PCR::PTR };
+        if TG::id() == 0 {
+            pcr.timergroup0_timer_clk_conf()
+                .modify(|_, w| unsafe { w.tg0_timer_clk_sel().bits(sel) });
+        } else {
+            pcr.timergroup1_timer_clk_conf()
+                .modify(|_, w| unsafe { w.tg1_timer_clk_sel().bits(sel) });
+        }
+    }
 }
 
 impl<TG> Enable for Timer0<TG>
@@ -732,8 +1192,33 @@ where
 {
 }
 
+/// A stage of the watchdog timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MwdtStage {
+    Stage0,
+    Stage1,
+    Stage2,
+    Stage3,
+}
+
+/// The action taken when a stage of the watchdog timer times out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum MwdtStageAction {
+    /// No action
+    Off         = 0,
+    /// Raise the watchdog interrupt
+    Interrupt   = 1,
+    /// Reset the CPU core
+    ResetCpu    = 2,
+    /// Reset the main system, including the peripherals but not the RTC
+    ResetSystem = 3,
+}
+
 /// Watchdog timer
 pub struct Wdt<TG, DM> {
+    windowed: bool,
     phantom: PhantomData<(TG, DM)>,
 }
 
@@ -751,6 +1236,7 @@ where
         TG::configure_wdt_src_clk();
 
         Self {
+            windowed: false,
             phantom: PhantomData,
         }
     }
@@ -794,22 +1280,193 @@ where
             .write(|w| unsafe { w.wdt_wkey().bits(0u32) });
     }
 
+    /// Feed the watchdog timer.
+    ///
+    /// In windowed mode (see [Self::set_window]), feeding the watchdog before
+    /// the window opened resets the system.
     pub fn feed(&mut self) {
         let reg_block = unsafe { &*TG::register_block() };
 
+        if self.windowed {
+            // The window opens when stage 0 times out, which raises the (not
+            // necessarily enabled) watchdog interrupt.
+            if reg_block.int_raw_timers().read().wdt().bit_is_clear() {
+                Self::force_reset();
+            }
+
+            reg_block
+                .int_clr_timers()
+                .write(|w| w.wdt().clear_bit_by_one());
+        }
+
+        reg_block
+            .wdtwprotect()
+            .write(|w| unsafe { w.wdt_wkey().bits(0x50D8_3AA1u32) });
+
+        reg_block.wdtfeed().write(|w| unsafe { w.bits(1) });
+
+        reg_block
+            .wdtwprotect()
+            .write(|w| unsafe { w.wdt_wkey().bits(0u32) });
+    }
+
+    /// Configure the timeout and the action of a single stage.
+    ///
+    /// The stages are run in sequence, starting with stage 0 whenever the
+    /// watchdog is fed. This does not enable the watchdog timer.
+    pub fn set_stage(
+        &mut self,
+        stage: MwdtStage,
+        timeout: MicrosDurationU64,
+        action: MwdtStageAction,
+    ) {
+        let reg_block = unsafe { &*TG::register_block() };
+        let timeout_raw = timeout_to_wdt_ticks(timeout);
+
+        reg_block
+            .wdtwprotect()
+            .write(|w| unsafe { w.wdt_wkey().bits(0x50D8_3AA1u32) });
+
+        reg_block
+            .wdtconfig1()
+            .write(|w| unsafe { w.wdt_clk_prescale().bits(1) });
+
+        let action = action as u8;
+
+        #[cfg_attr(esp32, allow(unused_unsafe))]
+        match stage {
+            MwdtStage::Stage0 => {
+                reg_block
+                    .wdtconfig2()
+                    .write(|w| unsafe { w.wdt_stg0_hold().bits(timeout_raw) });
+                reg_block
+                    .wdtconfig0()
+                    .modify(|_, w| unsafe { w.wdt_stg0().bits(action) });
+            }
+            MwdtStage::Stage1 => {
+                reg_block
+                    .wdtconfig3()
+                    .write(|w| unsafe { w.wdt_stg1_hold().bits(timeout_raw) });
+                reg_block
+                    .wdtconfig0()
+                    .modify(|_, w| unsafe { w.wdt_stg1().bits(action) });
+            }
+            MwdtStage::Stage2 => {
+                reg_block
+                    .wdtconfig4()
+                    .write(|w| unsafe { w.wdt_stg2_hold().bits(timeout_raw) });
+                reg_block
+                    .wdtconfig0()
+                    .modify(|_, w| unsafe { w.wdt_stg2().bits(action) });
+            }
+            MwdtStage::Stage3 => {
+                reg_block
+                    .wdtconfig5()
+                    .write(|w| unsafe { w.wdt_stg3_hold().bits(timeout_raw) });
+                reg_block
+                    .wdtconfig0()
+                    .modify(|_, w| unsafe { w.wdt_stg3().bits(action) });
+            }
+        }
+
+        #[cfg(any(esp32c2, esp32c3, esp32c6))]
+        reg_block
+            .wdtconfig0()
+            .modify(|_, w| w.wdt_conf_update_en().set_bit());
+
+        reg_block
+            .wdtwprotect()
+            .write(|w| unsafe { w.wdt_wkey().bits(0u32) });
+    }
+
+    /// Enable the watchdog timer in windowed mode.
+    ///
+    /// The watchdog has to be fed no earlier than `min` and no later than
+    /// `max` after it was previously fed, otherwise the system is reset. This
+    /// uses stage 0 and stage 1, and the watchdog interrupt to detect that the
+    /// window has opened.
+    ///
+    /// Returns [Error::InvalidWindow] without changing the watchdog if `max`
+    /// isn't later than `min`.
+    pub fn set_window(
+        &mut self,
+        min: MicrosDurationU64,
+        max: MicrosDurationU64,
+    ) -> Result<(), Error> {
+        if max <= min {
+            return Err(Error::InvalidWindow);
+        }
+
+        self.set_stage(MwdtStage::Stage0, min, MwdtStageAction::Interrupt);
+        self.set_stage(MwdtStage::Stage1, max - min, MwdtStageAction::ResetSystem);
+        self.set_stage(MwdtStage::Stage2, 0u64.micros(), MwdtStageAction::Off);
+        self.set_stage(MwdtStage::Stage3, 0u64.micros(), MwdtStageAction::Off);
+
+        let reg_block = unsafe { &*TG::register_block() };
+
         reg_block
             .wdtwprotect()
             .write(|w| unsafe { w.wdt_wkey().bits(0x50D8_3AA1u32) });
 
+        reg_block.wdtconfig0().modify(|_, w| w.wdt_en().set_bit());
+
+        #[cfg(any(esp32c2, esp32c3, esp32c6))]
+        reg_block
+            .wdtconfig0()
+            .modify(|_, w| w.wdt_conf_update_en().set_bit());
+
         reg_block.wdtfeed().write(|w| unsafe { w.bits(1) });
 
         reg_block
             .wdtwprotect()
             .write(|w| unsafe { w.wdt_wkey().bits(0u32) });
+
+        reg_block
+            .int_clr_timers()
+            .write(|w| w.wdt().clear_bit_by_one());
+
+        self.windowed = true;
+
+        Ok(())
     }
 
+    fn force_reset() -> ! {
+        let reg_block = unsafe { &*TG::register_block() };
+
+        reg_block
+            .wdtwprotect()
+            .write(|w| unsafe { w.wdt_wkey().bits(0x50D8_3AA1u32) });
+
+        reg_block
+            .wdtconfig2()
+            .write(|w| unsafe { w.wdt_stg0_hold().bits(1) });
+
+        #[cfg_attr(esp32, allow(unused_unsafe))]
+        reg_block.wdtconfig0().write(|w| unsafe {
+            w.wdt_en()
+                .bit(true)
+                .wdt_stg0()
+                .bits(MwdtStageAction::ResetSystem as u8)
+        });
+
+        #[cfg(any(esp32c2, esp32c3, esp32c6))]
+        reg_block
+            .wdtconfig0()
+            .modify(|_, w| w.wdt_conf_update_en().set_bit());
+
+        reg_block.wdtfeed().write(|w| unsafe { w.bits(1) });
+
+        loop {}
+    }
+
+    /// Enable the watchdog timer with a single stage resetting the system
+    /// after `timeout`.
+    ///
+    /// This leaves windowed mode.
     pub fn set_timeout(&mut self, timeout: MicrosDurationU64) {
-        let timeout_raw = (timeout.to_nanos() * 10 / 125) as u32;
+        self.windowed = false;
+
+        let timeout_raw = timeout_to_wdt_ticks(timeout);
 
         let reg_block = unsafe { &*TG::register_block() };
 
@@ -854,6 +1511,10 @@ where
     }
 }
 
+fn timeout_to_wdt_ticks(timeout: MicrosDurationU64) -> u32 {
+    (timeout.to_nanos() * 10 / 125) as u32
+}
+
 impl<TG, DM> Default for Wdt<TG, DM>
 where
     TG: TimerGroupInstance,
@@ -903,14 +1564,228 @@ where
     }
 }
 
+// Async functionality of the general purpose timers.
+#[cfg(feature = "async")]
+mod asynch {
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use embassy_sync::waitqueue::AtomicWaker;
+    use procmacros::handler;
+
+    use super::*;
+
+    const NUM_TIMERS: usize = 4;
+
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: AtomicWaker = AtomicWaker::new();
+    static WAKERS: [AtomicWaker; NUM_TIMERS] = [INIT; NUM_TIMERS];
+
+    pub(crate) struct TimerFuture<'a, TG, const N: u8> {
+        phantom: PhantomData<&'a Timer<TimerX<TG, N>, crate::Async>>,
+    }
+
+    impl<'a, TG, const N: u8> TimerFuture<'a, TG, N>
+    where
+        TG: TimerGroupInstance,
+        TimerX<TG, N>: Instance,
+    {
+        /// Enables the interrupt of an already started timer. Any stale
+        /// interrupt must have been cleared before starting the timer.
+        pub(crate) fn new(timer: &'a mut Timer<TimerX<TG, N>, crate::Async>) -> Self {
+            use crate::peripherals::Interrupt;
+
+            let (interrupt, handler) = match (TG::id(), N) {
+                (0, 0) => (Interrupt::TG0_T0_LEVEL, tg0_t0_handler),
+                #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+                (0, _) => (Interrupt::TG0_T1_LEVEL, tg0_t1_handler),
+                #[cfg(timg1)]
+                (_, 0) => (Interrupt::TG1_T0_LEVEL, tg1_t0_handler),
+                #[cfg(all(timg1, not(any(esp32c2, esp32c3, esp32c6, esp32h2))))]
+                (_, _) => (Interrupt::TG1_T1_LEVEL, tg1_t1_handler),
+                #[cfg(not(all(timg1, not(any(esp32c2, esp32c3, esp32c6, esp32h2)))))]
+                _ => unreachable!(),
+            };
+
+            unsafe {
+                crate::interrupt::bind_interrupt(interrupt, handler.handler());
+                crate::interrupt::enable(interrupt, handler.priority()).unwrap();
+            }
+
+            timer.listen();
+
+            Self {
+                phantom: PhantomData,
+            }
+        }
+
+        fn event_bit_is_clear(&self) -> bool {
+            unsafe { &*TG::register_block() }
+                .int_ena_timers()
+                .read()
+                .t(N)
+                .bit_is_clear()
+        }
+    }
+
+    impl<'a, TG, const N: u8> core::future::Future for TimerFuture<'a, TG, N>
+    where
+        TG: TimerGroupInstance,
+        TimerX<TG, N>: Instance,
+    {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, ctx: &mut Context<'_>) -> Poll<Self::Output> {
+            WAKERS[waker_index(TG::id(), N)].register(ctx.waker());
+
+            if self.event_bit_is_clear() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    impl<TG, const N: u8> Timer<TimerX<TG, N>, crate::Async>
+    where
+        TG: TimerGroupInstance,
+        TimerX<TG, N>: Instance,
+    {
+        /// Wait for the given duration to elapse.
+        ///
+        /// The timer is stopped afterwards.
+        pub async fn wait_for(&mut self, duration: MicrosDurationU64) {
+            // An alarm value of zero matches the counter right away, which the
+            // hardware doesn't reliably report.
+            if duration.ticks() == 0 {
+                return;
+            }
+
+            self.start(duration);
+
+            TimerFuture::new(self).await;
+
+            self.timg.clear_interrupt();
+            self.timg.set_counter_active(false);
+        }
+
+        /// Wait for the next period of a timer started via [Timer::start].
+        ///
+        /// The timer reloads in hardware on every alarm, so the ticks don't
+        /// drift even if a tick is awaited late. Ticks which were missed
+        /// entirely are not made up for.
+        pub async fn tick(&mut self) {
+            if !self.timg.is_counter_active() {
+                panic!("Called tick on an inactive timer!")
+            }
+
+            TimerFuture::new(self).await;
+
+            self.timg.clear_interrupt();
+            self.timg.set_alarm_active(true);
+        }
+    }
+
+    impl<TG, const N: u8> embedded_hal_async::delay::DelayNs for Timer<TimerX<TG, N>, crate::Async>
+    where
+        TG: TimerGroupInstance,
+        TimerX<TG, N>: Instance,
+    {
+        async fn delay_ns(&mut self, ns: u32) {
+            self.wait_for(MicrosDurationU64::micros((ns as u64).div_ceil(1000)))
+                .await;
+        }
+    }
+
+    fn waker_index(group: u8, timer: u8) -> usize {
+        group as usize * 2 + timer as usize
+    }
+
+    fn on_interrupt<TG: TimerGroupInstance>(group: u8, timer: u8) {
+        unsafe { &*TG::register_block() }
+            .int_ena_timers()
+            .modify(|_, w| w.t(timer).clear_bit());
+
+        WAKERS[waker_index(group, timer)].wake();
+    }
+
+    #[handler]
+    fn tg0_t0_handler() {
+        on_interrupt::<TIMG0>(0, 0);
+    }
+
+    #[cfg(not(any(esp32c2, esp32c3, esp32c6, esp32h2)))]
+    #[handler]
+    fn tg0_t1_handler() {
+        on_interrupt::<TIMG0>(0, 1);
+    }
+
+    #[cfg(timg1)]
+    #[handler]
+    fn tg1_t0_handler() {
+        on_interrupt::<TIMG1>(1, 0);
+    }
+
+    #[cfg(all(timg1, not(any(esp32c2, esp32c3, esp32c6, esp32h2))))]
+    #[handler]
+    fn tg1_t1_handler() {
+        on_interrupt::<TIMG1>(1, 1);
+    }
+}
+
 #[cfg(soc_etm)]
 pub mod etm {
+    //! # Event Task Matrix Function
+    //!
+    //! ## Overview
+    //!
+    //! The general purpose timers can generate an ETM event when the alarm
+    //! fires, and provide ETM tasks to control the counter and the alarm.
+    //!
+    //! The `cnt_cap` task latches the current counter value, which can then be
+    //! read via [Timer::capture]. Triggered by e.g. a GPIO event, this
+    //! timestamps the event without any interrupt latency.
+    //!
+    //! ## Example
+    //! ```no_run
+    //! let timg0 = TimerGroup::new(peripherals.TIMG0, &clocks, None);
+    //! let mut timer0 = timg0.timer0;
+    //! timer0.start(10u64.secs());
+    //!
+    //! let gpio_ext = GpioEtmChannels::new(peripherals.GPIO_SD);
+    //! let button_event = gpio_ext.channel0_event.falling_edge(button);
+    //! let capture_task = timer0.cnt_cap();
+    //!
+    //! let etm = Etm::new(peripherals.SOC_ETM);
+    //! let _channel = etm.channel0.setup(&button_event, &capture_task);
+    //!
+    //! // later on
+    //! let timestamp = timer0.capture();
+    //! ```
+
     use super::*;
     use crate::{
-        etm::{EtmEvent, EtmTask},
+        etm::{EtmChannel, EtmEvent, EtmTask},
+        gpio::{
+            etm::{GpioEtmTaskChannel, GpioEtmTaskClear},
+            Bank0GpioRegisterAccess,
+            BankGpioRegisterAccess,
+            OutputPin,
+        },
         private::Sealed,
     };
 
+    // ETM event and task ids of the timer in TIMG0, the ones of TIMG1 follow
+    // right after
+    const TIMER0_EVT_CNT_CMP_TIMER0: u8 = 48;
+    const TIMER0_TASK_CNT_START_TIMER0: u8 = 88;
+    const TIMER0_TASK_ALARM_START_TIMER0: u8 = 90;
+    const TIMER0_TASK_CNT_STOP_TIMER0: u8 = 92;
+    const TIMER0_TASK_CNT_RELOAD_TIMER0: u8 = 94;
+    const TIMER0_TASK_CNT_CAP_TIMER0: u8 = 96;
+
     pub struct TimerEtmEvent {
         id: u8,
     }
@@ -955,7 +1830,9 @@ pub mod etm {
     {
         /// ETM event triggered on alarm
         fn on_alarm(&self) -> TimerEtmEvent {
-            TimerEtmEvent { id: 48 + TG::id() }
+            TimerEtmEvent {
+                id: TIMER0_EVT_CNT_CMP_TIMER0 + TG::id(),
+            }
         }
     }
 
@@ -965,28 +1842,160 @@ pub mod etm {
     {
         /// ETM task to start the counter
         fn cnt_start(&self) -> TimerEtmTask {
-            TimerEtmTask { id: 88 + TG::id() }
+            TimerEtmTask {
+                id: TIMER0_TASK_CNT_START_TIMER0 + TG::id(),
+            }
         }
 
         /// ETM task to start the alarm
         fn alarm_start(&self) -> TimerEtmTask {
-            TimerEtmTask { id: 90 + TG::id() }
+            TimerEtmTask {
+                id: TIMER0_TASK_ALARM_START_TIMER0 + TG::id(),
+            }
         }
 
         /// ETM task to stop the counter
         fn cnt_stop(&self) -> TimerEtmTask {
-            TimerEtmTask { id: 92 + TG::id() }
+            TimerEtmTask {
+                id: TIMER0_TASK_CNT_STOP_TIMER0 + TG::id(),
+            }
         }
 
         /// ETM task to reload the counter
         fn cnt_reload(&self) -> TimerEtmTask {
-            TimerEtmTask { id: 94 + TG::id() }
+            TimerEtmTask {
+                id: TIMER0_TASK_CNT_RELOAD_TIMER0 + TG::id(),
+            }
         }
 
-        /// ETM task to load the counter with the value stored when the last
-        /// `now()` was called
+        /// ETM task to capture the current counter value, see
+        /// [Timer::capture]
         fn cnt_cap(&self) -> TimerEtmTask {
-            TimerEtmTask { id: 96 + TG::id() }
+            TimerEtmTask {
+                id: TIMER0_TASK_CNT_CAP_TIMER0 + TG::id(),
+            }
+        }
+    }
+
+    /// A one-shot pulse generator.
+    ///
+    /// The alarm of the timer is connected to the clear task of a GPIO ETM
+    /// task channel, so the pin is driven low by hardware when the pulse
+    /// ends. The pin is driven high right before the timer is started, with
+    /// interrupts disabled in between, so the width of the pulse doesn't
+    /// depend on interrupt or code latency.
+    ///
+    /// The chips with an ETM have a single timer in each timer group, the
+    /// `timer0` of both TIMG0 and TIMG1 can be used.
+    ///
+    /// Dropping the pulse generator disconnects the ETM channel and the GPIO
+    /// task channel again.
+    ///
+    /// ## Example
+    /// ```no_run
+    /// let timg0 = TimerGroup::new(peripherals.TIMG0, &clocks, None);
+    /// let gpio_ext = GpioEtmChannels::new(peripherals.GPIO_SD);
+    /// let etm = Etm::new(peripherals.SOC_ETM);
+    ///
+    /// let mut pulse = OneShotPulse::new(
+    ///     timg0.timer0,
+    ///     io.pins.gpio1,
+    ///     gpio_ext.channel0_task,
+    ///     etm.channel0,
+    /// );
+    ///
+    /// pulse.trigger(250u64.micros());
+    /// pulse.wait();
+    /// ```
+    pub struct OneShotPulse<'d, TG, PIN, const GPIO_CH: u8, const ETM_CH: u8>
+    where
+        TG: TimerGroupInstance,
+        PIN: OutputPin,
+    {
+        timer: Timer<Timer0<TG>, crate::Blocking>,
+        pin: u8,
+        _clear_task: GpioEtmTaskClear<'d, PIN, GPIO_CH>,
+        _etm_channel: EtmChannel<ETM_CH>,
+    }
+
+    impl<'d, TG, PIN, const GPIO_CH: u8, const ETM_CH: u8> OneShotPulse<'d, TG, PIN, GPIO_CH, ETM_CH>
+    where
+        TG: TimerGroupInstance,
+        PIN: OutputPin,
+    {
+        /// Create a pulse generator driving `pin`, idling low.
+        pub fn new(
+            timer: Timer<Timer0<TG>, crate::Blocking>,
+            pin: impl Peripheral<P = PIN> + 'd,
+            task_channel: GpioEtmTaskChannel<GPIO_CH>,
+            etm_channel: EtmChannel<ETM_CH>,
+        ) -> Self {
+            crate::into_ref!(pin);
+
+            pin.set_to_push_pull_output().set_output_high(false);
+            let number = pin.number();
+
+            let clear_task = task_channel.clear(pin);
+            let alarm = timer.on_alarm();
+            crate::etm::enable_channel(ETM_CH, alarm.id(), clear_task.id());
+
+            Self {
+                timer,
+                pin: number,
+                _clear_task: clear_task,
+                _etm_channel: etm_channel,
+            }
+        }
+
+        /// Drive the pin high for `width`.
+        pub fn trigger(&mut self, width: MicrosDurationU64) {
+            critical_section::with(|_| {
+                // all pins of the chips with an ETM are in the first bank
+                Bank0GpioRegisterAccess::write_output_set(1 << self.pin);
+                self.timer.start(width);
+            });
+        }
+
+        /// Whether the pulse triggered last has ended.
+        pub fn is_done(&self) -> bool {
+            self.timer.is_interrupt_set()
+        }
+
+        /// Block until the pulse triggered last has ended.
+        pub fn wait(&self) {
+            while !self.is_done() {}
+        }
+    }
+
+    impl<'d, TG, PIN, const GPIO_CH: u8, const ETM_CH: u8> Drop
+        for OneShotPulse<'d, TG, PIN, GPIO_CH, ETM_CH>
+    where
+        TG: TimerGroupInstance,
+        PIN: OutputPin,
+    {
+        fn drop(&mut self) {
+            crate::etm::disable_channel(ETM_CH);
+            crate::gpio::etm::disable_task_channel(self.pin);
+        }
+    }
+
+    impl<TG, DM> Timer<Timer0<TG>, DM>
+    where
+        TG: TimerGroupInstance,
+        DM: crate::Mode,
+    {
+        /// The counter value latched by the last `cnt_cap` ETM task, in ticks
+        /// of the prescaled timer clock.
+        ///
+        /// Note that [Instance::now] latches the counter value as well, so
+        /// this reads the most recent of both.
+        pub fn capture(&self) -> u64 {
+            let t = unsafe { Timer0::<TG>::t() };
+
+            let value_lo = t.lo().read().bits() as u64;
+            let value_hi = (t.hi().read().bits() as u64) << 32;
+
+            value_lo | value_hi
         }
     }
 }
@@ -24,6 +24,7 @@
 //!     data_bits: DataBits::DataBits8,
 //!     parity: Parity::ParityNone,
 //!     stop_bits: StopBits::STOP1,
+//!     flow_control: FlowControl::default(),
 //! };
 //!
 //! let io = IO::new(peripherals.GPIO, peripherals.IO_MUX);
@@ -35,6 +36,50 @@
 //! let mut uart1 = Uart::new_with_config(peripherals.UART1, config, Some(pins), &clocks);
 //! ```
 //!
+//! ### Hardware Flow Control
+//!
+//! To stop dropping bytes on high throughput links, e.g. to modems, the UART
+//! can pause transmitting while CTS is high and pull RTS high while its RX
+//! FIFO fills up. The CTS and RTS pins are connected via the GPIO matrix.
+//!
+//! ```no_run
+//! let config = Config::default()
+//!     .baudrate(921_600)
+//!     .flow_control(FlowControl {
+//!         cts: true,
+//!         rts_threshold: Some(64),
+//!     });
+//!
+//! let pins = AllPins::new(
+//!     io.pins.gpio1.into_push_pull_output(),
+//!     io.pins.gpio2.into_floating_input(),
+//!     io.pins.gpio3.into_floating_input(),
+//!     io.pins.gpio4.into_push_pull_output(),
+//! );
+//!
+//! let mut uart1 = Uart::new_with_config(peripherals.UART1, config, Some(pins), &clocks, None);
+//! ```
+//!
+//! ### IrDA
+//!
+//! With [Uart::enable_irda] the UART encodes the transmitted bits as short
+//! pulses and decodes received pulses, so an IrDA SIR transceiver can be
+//! connected to the TX and RX pins directly.
+//!
+//! ```no_run
+//! uart1.enable_irda(IrdaConfig {
+//!     rx_inverted: true,
+//!     ..IrdaConfig::default()
+//! });
+//! ```
+//!
+//! ### 9-bit Frames
+//!
+//! The UART controllers support at most 8 data bits and have no address
+//! matching, so the 9-bit multiprocessor mode used on RS-485 multidrop
+//! networks can't be filtered in hardware. Every received byte has to be
+//! handled by the CPU.
+//!
 //! ## Usage
 //!
 //! The UART driver implements a number of third-party traits, with the
@@ -74,9 +119,10 @@
 
 use core::marker::PhantomData;
 
-use self::config::Config;
+use self::config::{Config, FlowControl, IrdaConfig, Rs485Config};
 use crate::{
     clock::Clocks,
+    delay::Delay,
     gpio::{InputPin, InputSignal, NoPinType, OutputPin, OutputSignal},
     interrupt::InterruptHandler,
     peripheral::{Peripheral, PeripheralRef},
@@ -98,9 +144,14 @@ const UART_FIFO_SIZE: u16 = 128;
 pub enum Error {
     /// An invalid configuration argument was provided
     InvalidArgument,
+    /// A collision was detected on the RS-485 bus
+    Rs485Collision,
     /// The RX FIFO overflowed
     #[cfg(feature = "async")]
     RxFifoOvf,
+    /// The receive buffer was filled before the RX line went idle
+    #[cfg(feature = "async")]
+    RxBufferFull,
 }
 
 #[cfg(feature = "embedded-hal")]
@@ -117,6 +168,10 @@ impl embedded_io::Error for Error {
     }
 }
 
+pub mod buffered;
+#[cfg(all(uhci0, gdma))]
+pub mod uhci;
+
 /// UART Configuration
 pub mod config {
     /// Number of data bits
@@ -150,6 +205,18 @@ pub mod config {
         STOP2   = 3,
     }
 
+    /// Hardware flow control
+    #[derive(PartialEq, Eq, Copy, Clone, Debug, Default)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    pub struct FlowControl {
+        /// Only transmit while the CTS input is low
+        pub cts: bool,
+        /// Pull the RTS output high while the RX FIFO holds more than this
+        /// number of bytes, `None` disables RTS flow control. The FIFO holds
+        /// 128 bytes, larger values are limited to 127.
+        pub rts_threshold: Option<u8>,
+    }
+
     /// UART Configuration
     #[derive(Debug, Copy, Clone)]
     #[cfg_attr(feature = "defmt", derive(defmt::Format))]
@@ -158,6 +225,7 @@ pub mod config {
         pub data_bits: DataBits,
         pub parity: Parity,
         pub stop_bits: StopBits,
+        pub flow_control: FlowControl,
     }
 
     impl Config {
@@ -191,6 +259,11 @@ pub mod config {
             self
         }
 
+        pub fn flow_control(mut self, flow_control: FlowControl) -> Self {
+            self.flow_control = flow_control;
+            self
+        }
+
         pub fn symbol_length(&self) -> u8 {
             let mut length: u8 = 1; // start bit
             length += match self.data_bits {
@@ -218,6 +291,7 @@ pub mod config {
                 data_bits: DataBits::DataBits8,
                 parity: Parity::ParityNone,
                 stop_bits: StopBits::STOP1,
+                flow_control: FlowControl::default(),
             }
         }
     }
@@ -248,6 +322,51 @@ pub mod config {
             }
         }
     }
+
+    /// Configuration for the RS-485 half duplex mode
+    #[derive(Debug, Copy, Clone)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    pub struct Rs485Config {
+        /// Delay between enabling the driver and sending the first bit, in bit
+        /// times (0..=15)
+        pub tx_delay: u8,
+        /// Time to keep the driver enabled after the last bit was sent, in
+        /// microseconds
+        pub turnaround_us: u32,
+        /// Receive the transmitted data and compare it to the sent data to
+        /// detect collisions
+        pub collision_detection: bool,
+    }
+
+    impl Default for Rs485Config {
+        fn default() -> Rs485Config {
+            Rs485Config {
+                tx_delay: 0,
+                turnaround_us: 0,
+                collision_detection: false,
+            }
+        }
+    }
+
+    /// Configuration for the IrDA mode
+    ///
+    /// The hardware encodes each zero bit as a pulse of 3/16 bit time (IrDA
+    /// SIR), the pulse width can't be changed.
+    #[derive(Debug, Copy, Clone, Default)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
+    pub struct IrdaConfig {
+        /// Invert the pulses sent to the transmitter, for transmitters with an
+        /// active low input
+        pub tx_inverted: bool,
+        /// Invert the pulses received from the receiver, for receivers with an
+        /// active low output (the usual case)
+        pub rx_inverted: bool,
+        /// Make the 11th bit sent by the IrDA transmitter equal to the 10th bit
+        /// instead of 0
+        pub wctl: bool,
+        /// Loop the transmitted pulses back to the receiver
+        pub loopback: bool,
+    }
 }
 
 /// Pins used by the UART interface
@@ -360,6 +479,13 @@ pub struct Uart<'d, T, M> {
     symbol_len: u8,
     tx: UartTx<'d, T, M>,
     rx: UartRx<'d, T, M>,
+    rs485: Option<Rs485Turnaround>,
+    baudrate: u32,
+}
+
+struct Rs485Turnaround {
+    delay: Delay,
+    turnaround_us: u32,
 }
 
 /// UART (Transmit)
@@ -371,7 +497,7 @@ pub struct UartTx<'d, T, M> {
 pub struct UartRx<'d, T, M> {
     phantom: PhantomData<(&'d mut T, M)>,
     at_cmd_config: Option<config::AtCmdConfig>,
-    rx_timeout_config: Option<u8>,
+    rx_timeout_config: Option<u16>,
 }
 
 impl<'d, T, M> UartTx<'d, T, M>
@@ -530,12 +656,15 @@ where
             rx: UartRx::new_inner(),
             #[cfg(not(esp32))]
             symbol_len: config.symbol_length(),
+            rs485: None,
+            baudrate: config.baudrate,
         };
 
         serial.change_baud_internal(config.baudrate, clocks);
         serial.change_data_bits(config.data_bits);
         serial.change_parity(config.parity);
         serial.change_stop_bits(config.stop_bits);
+        serial.change_flow_control(config.flow_control);
 
         if let Some(interrupt) = interrupt {
             unsafe {
@@ -630,8 +759,42 @@ where
     /// - `esp32c2`, `esp32c3`, `esp32c6`, `esp32h2`, esp32s2`, esp32s3`: The
     ///   value you pass times the symbol size must be <= **0x3FF**
     pub fn set_rx_timeout(&mut self, timeout: Option<u8>) -> Result<(), Error> {
+        // the esp32 counts directly in number of symbols (symbol len fixed to 8)
         #[cfg(esp32)]
-        const MAX_THRHD: u8 = 0x7F; // 7 bits
+        let timeout_reg = timeout.map(|timeout| timeout as u16);
+        // all other count in bits, so we need to multiply by the symbol len.
+        #[cfg(not(esp32))]
+        let timeout_reg = timeout.map(|timeout| timeout as u16 * self.symbol_len as u16);
+
+        self.set_rx_timeout_reg(timeout_reg)
+    }
+
+    /// Configures the Receive Timeout detection setting in bit times
+    ///
+    /// Like [Self::set_rx_timeout], but the time the RX line has to be idle
+    /// is given in bit times. This allows matching the inter-frame gap of
+    /// packet oriented protocols, e.g. 3.5 characters of 11 bits for Modbus
+    /// RTU. On the `esp32` the timeout is counted in symbols of 8 bits, so
+    /// `bit_times` is rounded up to a multiple of 8.
+    ///
+    ///  # Errors
+    /// `Err(Error::InvalidArgument)` if the provided value exceeds the maximum
+    /// value for SOC :
+    /// - `esp32`: **0x3F8**
+    /// - `esp32c2`, `esp32c3`, `esp32c6`, `esp32h2`, esp32s2`, esp32s3`:
+    ///   **0x3FF**
+    pub fn set_rx_idle_timeout(&mut self, bit_times: Option<u16>) -> Result<(), Error> {
+        #[cfg(esp32)]
+        let timeout_reg = bit_times.map(|bit_times| bit_times / 8 + (bit_times % 8 != 0) as u16);
+        #[cfg(not(esp32))]
+        let timeout_reg = bit_times;
+
+        self.set_rx_timeout_reg(timeout_reg)
+    }
+
+    fn set_rx_timeout_reg(&mut self, timeout_reg: Option<u16>) -> Result<(), Error> {
+        #[cfg(esp32)]
+        const MAX_THRHD: u16 = 0x7F; // 7 bits
         #[cfg(any(esp32c2, esp32c3, esp32c6, esp32h2, esp32s2, esp32s3))]
         const MAX_THRHD: u16 = 0x3FF; // 10 bits
 
@@ -647,28 +810,24 @@ where
         #[cfg(any(esp32, esp32c2, esp32c3, esp32s2, esp32s3))]
         let reg_en = &T::register_block().conf1();
 
-        match timeout {
+        match timeout_reg {
             None => {
                 reg_en.modify(|_, w| w.rx_tout_en().clear_bit());
             }
-            Some(timeout) => {
-                // the esp32 counts directly in number of symbols (symbol len fixed to 8)
-                #[cfg(esp32)]
-                let timeout_reg = timeout;
-                // all other count in bits, so we need to multiply by the symbol len.
-                #[cfg(not(esp32))]
-                let timeout_reg = timeout as u16 * self.symbol_len as u16;
-
+            Some(timeout_reg) => {
                 if timeout_reg > MAX_THRHD {
                     return Err(Error::InvalidArgument);
                 }
 
+                #[cfg(esp32)]
+                let timeout_reg = timeout_reg as u8;
+
                 reg_thrhd.modify(|_, w| unsafe { w.rx_tout_thrhd().bits(timeout_reg) });
                 reg_en.modify(|_, w| w.rx_tout_en().set_bit());
             }
         }
 
-        self.rx.rx_timeout_config = timeout;
+        self.rx.rx_timeout_config = timeout_reg;
 
         self.sync_regs();
         Ok(())
@@ -735,6 +894,232 @@ where
             .modify(|_, w| w.tx_done().clear_bit());
     }
 
+    /// Enable the RS-485 half duplex mode.
+    ///
+    /// The RTS pin (see [AllPins]) is used to control the driver of the
+    /// transceiver: it's high while [Self::write_rs485] transmits and low
+    /// otherwise, so the transceiver receives while the UART doesn't transmit.
+    ///
+    /// # Errors
+    /// `Err(Error::InvalidArgument)` if `tx_delay` exceeds 15 bit times
+    pub fn enable_rs485(&mut self, config: Rs485Config, clocks: &Clocks) -> Result<(), Error> {
+        if config.tx_delay > 0xF {
+            return Err(Error::InvalidArgument);
+        }
+
+        // release the driver, `sw_rts` set means RTS is low
+        T::register_block()
+            .conf0()
+            .modify(|_, w| w.irda_en().clear_bit().sw_rts().set_bit());
+
+        T::register_block().rs485_conf().modify(|_, w| unsafe {
+            w.rs485_tx_dly_num()
+                .bits(config.tx_delay)
+                .rs485tx_rx_en()
+                .bit(config.collision_detection)
+                .rs485rxby_tx_en()
+                .bit(config.collision_detection)
+                .rs485_en()
+                .set_bit()
+        });
+
+        self.sync_regs();
+
+        self.rs485 = Some(Rs485Turnaround {
+            delay: Delay::new(clocks),
+            turnaround_us: config.turnaround_us,
+        });
+
+        Ok(())
+    }
+
+    /// Disable the RS-485 half duplex mode.
+    pub fn disable_rs485(&mut self) {
+        T::register_block().rs485_conf().modify(|_, w| {
+            w.rs485tx_rx_en()
+                .clear_bit()
+                .rs485rxby_tx_en()
+                .clear_bit()
+                .rs485_en()
+                .clear_bit()
+        });
+        T::register_block()
+            .conf0()
+            .modify(|_, w| w.sw_rts().clear_bit());
+
+        self.sync_regs();
+
+        self.rs485 = None;
+    }
+
+    /// Write bytes in RS-485 half duplex mode.
+    ///
+    /// Enables the driver, writes all bytes and waits until the last bit left
+    /// the TX shift register and the turnaround time has passed before
+    /// releasing the bus again.
+    ///
+    /// # Errors
+    /// - `Err(Error::InvalidArgument)` if the RS-485 mode isn't enabled
+    /// - `Err(Error::Rs485Collision)` if collision detection is enabled and the
+    ///   received data didn't match the transmitted data. All bytes are sent
+    ///   anyway.
+    pub fn write_rs485(&mut self, data: &[u8]) -> Result<usize, Error> {
+        if self.rs485.is_none() {
+            return Err(Error::InvalidArgument);
+        }
+
+        self.reset_rs485_collision_interrupt();
+
+        T::register_block()
+            .conf0()
+            .modify(|_, w| w.sw_rts().clear_bit());
+        self.sync_regs();
+
+        let count = self.tx.write_bytes(data)?;
+
+        while T::get_tx_fifo_count() > 0 || !T::is_tx_idle() {
+            // wait until the last stop bit was sent
+        }
+
+        if let Some(rs485) = &self.rs485 {
+            rs485.delay.delay_micros(rs485.turnaround_us);
+        }
+
+        T::register_block()
+            .conf0()
+            .modify(|_, w| w.sw_rts().set_bit());
+        self.sync_regs();
+
+        if self.rs485_collision_interrupt_set() {
+            return Err(Error::Rs485Collision);
+        }
+
+        Ok(count)
+    }
+
+    /// Enable the IrDA mode.
+    ///
+    /// The TX and RX pins are connected to an infrared transceiver, which
+    /// sends and receives the pulse encoded bits. The RS-485 mode is disabled,
+    /// the two modes are mutually exclusive.
+    pub fn enable_irda(&mut self, config: IrdaConfig) {
+        if self.rs485.is_some() {
+            self.disable_rs485();
+        }
+
+        T::register_block().conf0().modify(|_, w| {
+            w.irda_tx_inv()
+                .bit(config.tx_inverted)
+                .irda_rx_inv()
+                .bit(config.rx_inverted)
+                .irda_wctl()
+                .bit(config.wctl)
+                .irda_dplx()
+                .bit(config.loopback)
+                .irda_tx_en()
+                .set_bit()
+                .irda_en()
+                .set_bit()
+        });
+
+        self.sync_regs();
+    }
+
+    /// Disable the IrDA mode.
+    pub fn disable_irda(&mut self) {
+        T::register_block().conf0().modify(|_, w| {
+            w.irda_tx_en()
+                .clear_bit()
+                .irda_dplx()
+                .clear_bit()
+                .irda_en()
+                .clear_bit()
+        });
+
+        self.sync_regs();
+    }
+
+    /// Send a break, i.e. hold the TX line low for the given number of bit
+    /// times.
+    ///
+    /// Waits until all data in the TX FIFO has been sent before sending the
+    /// break, and until the break is over before returning.
+    pub fn send_break(&mut self, bits: u32) {
+        while T::get_tx_fifo_count() > 0 || !T::is_tx_idle() {
+            // wait until the last stop bit was sent
+        }
+
+        let inverted = T::register_block().conf0().read().txd_inv().bit_is_set();
+        T::register_block()
+            .conf0()
+            .modify(|_, w| w.txd_inv().bit(!inverted));
+        self.sync_regs();
+
+        let us = (bits as u64 * 1_000_000).div_ceil(self.baudrate as u64);
+        crate::rom::ets_delay_us(us.max(1) as u32);
+
+        T::register_block()
+            .conf0()
+            .modify(|_, w| w.txd_inv().bit(inverted));
+        self.sync_regs();
+    }
+
+    /// Listen for break detected interrupts
+    pub fn listen_break_detected(&mut self) {
+        T::register_block()
+            .int_ena()
+            .modify(|_, w| w.brk_det().set_bit());
+    }
+
+    /// Stop listening for break detected interrupts
+    pub fn unlisten_break_detected(&mut self) {
+        T::register_block()
+            .int_ena()
+            .modify(|_, w| w.brk_det().clear_bit());
+    }
+
+    /// Checks if break detected interrupt is set
+    pub fn break_detected_interrupt_set(&self) -> bool {
+        T::register_block().int_raw().read().brk_det().bit_is_set()
+    }
+
+    /// Reset break detected interrupt
+    pub fn reset_break_detected_interrupt(&self) {
+        T::register_block()
+            .int_clr()
+            .write(|w| w.brk_det().clear_bit_by_one());
+    }
+
+    /// Listen for RS-485 collision interrupts
+    pub fn listen_rs485_collision(&mut self) {
+        T::register_block()
+            .int_ena()
+            .modify(|_, w| w.rs485_clash().set_bit());
+    }
+
+    /// Stop listening for RS-485 collision interrupts
+    pub fn unlisten_rs485_collision(&mut self) {
+        T::register_block()
+            .int_ena()
+            .modify(|_, w| w.rs485_clash().clear_bit());
+    }
+
+    /// Checks if RS-485 collision interrupt is set
+    pub fn rs485_collision_interrupt_set(&self) -> bool {
+        T::register_block()
+            .int_raw()
+            .read()
+            .rs485_clash()
+            .bit_is_set()
+    }
+
+    /// Reset RS-485 collision interrupt
+    pub fn reset_rs485_collision_interrupt(&self) {
+        T::register_block()
+            .int_clr()
+            .write(|w| w.rs485_clash().clear_bit_by_one());
+    }
+
     /// Listen for RX-FIFO-FULL interrupts
     pub fn listen_rx_fifo_full(&mut self) {
         T::register_block()
@@ -838,6 +1223,48 @@ where
         self
     }
 
+    /// Change the hardware flow control
+    ///
+    /// The CTS and RTS pins have to be connected, see [AllPins].
+    pub fn change_flow_control(&mut self, flow_control: FlowControl) -> &mut Self {
+        T::register_block()
+            .conf0()
+            .modify(|_, w| w.tx_flow_en().bit(flow_control.cts));
+
+        let enable_rts = flow_control.rts_threshold.is_some();
+        let threshold = flow_control.rts_threshold.unwrap_or(0).min(0x7F);
+
+        #[cfg(esp32)]
+        T::register_block().conf1().modify(|_, w| unsafe {
+            w.rx_flow_thrhd()
+                .bits(threshold)
+                .rx_flow_en()
+                .bit(enable_rts)
+        });
+
+        #[cfg(any(esp32c6, esp32h2))]
+        T::register_block().hwfc_conf().modify(|_, w| unsafe {
+            w.rx_flow_thrhd()
+                .bits(threshold)
+                .rx_flow_en()
+                .bit(enable_rts)
+        });
+
+        #[cfg(any(esp32c2, esp32c3, esp32s2, esp32s3))]
+        {
+            T::register_block()
+                .mem_conf()
+                .modify(|_, w| unsafe { w.rx_flow_thrhd().bits(threshold as u16) });
+            T::register_block()
+                .conf1()
+                .modify(|_, w| w.rx_flow_en().bit(enable_rts));
+        }
+
+        self.sync_regs();
+
+        self
+    }
+
     fn change_data_bits(&mut self, data_bits: config::DataBits) -> &mut Self {
         T::register_block()
             .conf0()
@@ -984,6 +1411,7 @@ where
     /// Modify UART baud rate and reset TX/RX fifo.
     pub fn change_baud(&mut self, baudrate: u32, clocks: &Clocks) {
         self.change_baud_internal(baudrate, clocks);
+        self.baudrate = baudrate;
         self.txfifo_reset();
         self.rxfifo_reset();
     }
@@ -1542,6 +1970,50 @@ where
     }
 }
 
+#[cfg(feature = "embedded-io")]
+impl<T, M> embedded_io::ReadReady for Uart<'_, T, M>
+where
+    T: Instance,
+    M: Mode,
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        embedded_io::ReadReady::read_ready(&mut self.rx)
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<T, M> embedded_io::ReadReady for UartRx<'_, T, M>
+where
+    T: Instance,
+    M: Mode,
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(T::get_rx_fifo_count() > 0)
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<T, M> embedded_io::WriteReady for Uart<'_, T, M>
+where
+    T: Instance,
+    M: Mode,
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        embedded_io::WriteReady::write_ready(&mut self.tx)
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<T, M> embedded_io::WriteReady for UartTx<'_, T, M>
+where
+    T: Instance,
+    M: Mode,
+{
+    fn write_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(T::get_tx_fifo_count() < UART_FIFO_SIZE)
+    }
+}
+
 #[cfg(feature = "async")]
 mod asynch {
     use core::task::Poll;
@@ -1579,13 +2051,14 @@ mod asynch {
         RxCmdCharDetected,
         RxFifoOvf,
         RxFifoTout,
+        BreakDetected,
     }
 
     /// A future that resolves when the passed interrupt is triggered,
     /// or has been triggered in the meantime (flag set in INT_RAW).
     /// Upon construction the future enables the passed interrupt and when it
-    /// is dropped it disables the interrupt again. The future returns the event
-    /// that was initially passed, when it resolves.
+    /// is dropped it disables the interrupt again. The RX future returns the
+    /// events that triggered, when it resolves.
     pub(crate) struct UartRxFuture<'d, T: Instance> {
         events: EnumSet<RxEvent>,
         phantom: PhantomData<&'d mut T>,
@@ -1606,11 +2079,11 @@ mod asynch {
             }
         }
 
-        fn event_bit_is_clear(&self) -> bool {
+        fn triggered_events(&self) -> EnumSet<RxEvent> {
             let interrupts_enabled = T::register_block().int_ena().read();
-            let mut event_triggered = false;
+            let mut events_triggered = EnumSet::new();
             for event in self.events {
-                event_triggered |= match event {
+                let event_triggered = match event {
                     RxEvent::RxFifoFull => interrupts_enabled.rxfifo_full().bit_is_clear(),
                     RxEvent::RxCmdCharDetected => {
                         interrupts_enabled.at_cmd_char_det().bit_is_clear()
@@ -1618,14 +2091,19 @@ mod asynch {
 
                     RxEvent::RxFifoOvf => interrupts_enabled.rxfifo_ovf().bit_is_clear(),
                     RxEvent::RxFifoTout => interrupts_enabled.rxfifo_tout().bit_is_clear(),
+                    RxEvent::BreakDetected => interrupts_enabled.brk_det().bit_is_clear(),
+                };
+                if event_triggered {
+                    events_triggered |= event;
                 }
             }
-            event_triggered
+            events_triggered
         }
     }
 
     impl<'d, T: Instance> core::future::Future for UartRxFuture<'d, T> {
-        type Output = ();
+        /// The events that triggered
+        type Output = EnumSet<RxEvent>;
 
         fn poll(
             mut self: core::pin::Pin<&mut Self>,
@@ -1640,14 +2118,16 @@ mod asynch {
                             RxEvent::RxCmdCharDetected => w.at_cmd_char_det().set_bit(),
                             RxEvent::RxFifoOvf => w.rxfifo_ovf().set_bit(),
                             RxEvent::RxFifoTout => w.rxfifo_tout().set_bit(),
+                            RxEvent::BreakDetected => w.brk_det().set_bit(),
                         };
                     }
                     w
                 });
                 self.registered = true;
             }
-            if self.event_bit_is_clear() {
-                Poll::Ready(())
+            let events = self.triggered_events();
+            if !events.is_empty() {
+                Poll::Ready(events)
             } else {
                 Poll::Pending
             }
@@ -1668,6 +2148,7 @@ mod asynch {
                     }
                     RxEvent::RxFifoOvf => int_ena.modify(|_, w| w.rxfifo_ovf().clear_bit()),
                     RxEvent::RxFifoTout => int_ena.modify(|_, w| w.rxfifo_tout().clear_bit()),
+                    RxEvent::BreakDetected => int_ena.modify(|_, w| w.brk_det().clear_bit()),
                 }
             }
         }
@@ -1791,6 +2272,16 @@ mod asynch {
             self.rx.read_async(buf).await
         }
 
+        /// See [`UartRx::read_until_idle`]
+        pub async fn read_until_idle(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+            self.rx.read_until_idle(buf).await
+        }
+
+        /// See [`UartRx::wait_for_break_async`]
+        pub async fn wait_for_break_async(&mut self) {
+            self.rx.wait_for_break_async().await
+        }
+
         pub async fn write_async(&mut self, words: &[u8]) -> Result<usize, Error> {
             self.tx.write_async(words).await
         }
@@ -1894,6 +2385,83 @@ mod asynch {
                 }
             }
         }
+
+        /// Wait for a break on the RX line.
+        ///
+        /// Breaks which were detected before calling this method are ignored.
+        /// The NUL byte the UART receives for the break stays in the RX
+        /// FIFO.
+        pub async fn wait_for_break_async(&mut self) {
+            T::register_block()
+                .int_clr()
+                .write(|w| w.brk_det().clear_bit_by_one());
+
+            UartRxFuture::<T>::new(RxEvent::BreakDetected.into()).await;
+        }
+
+        /// Read async to buffer slice `buf` until the RX line goes idle.
+        ///
+        /// Waits until data is received and keeps reading until the RX line
+        /// has been idle for the receive timeout configured by
+        /// [`Uart::set_rx_idle_timeout`] or [`Uart::set_rx_timeout`]. This
+        /// makes it possible to receive the frames of packet oriented
+        /// protocols like Modbus RTU, which are delimited by a pause.
+        ///
+        /// # Ok
+        /// When successful, returns the number of bytes received before the
+        /// RX line went idle.
+        ///
+        /// # Errors
+        /// - `Err(Error::InvalidArgument)` if no receive timeout is configured
+        /// - `Err(Error::RxFifoOvf)` if the RX FIFO overflowed and data was
+        ///   lost
+        /// - `Err(Error::RxBufferFull)` if `buf` was filled before the RX line
+        ///   went idle
+        pub async fn read_until_idle(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+            if self.rx_timeout_config.is_none() {
+                return Err(Error::InvalidArgument);
+            }
+
+            let mut count = 0;
+            loop {
+                let events = UartRxFuture::<T>::new(
+                    RxEvent::RxFifoFull | RxEvent::RxFifoOvf | RxEvent::RxFifoTout,
+                )
+                .await;
+
+                if events.contains(RxEvent::RxFifoOvf) {
+                    return Err(Error::RxFifoOvf);
+                }
+
+                let idle = events.contains(RxEvent::RxFifoTout);
+                if idle {
+                    // The handler disabled the idle interrupt, clear its status
+                    // as well before draining the FIFO, so it's only armed for
+                    // the next wait and doesn't fire again for the same idle
+                    // period. An idle period after this drain is still caught.
+                    T::register_block()
+                        .int_clr()
+                        .write(|w| w.rxfifo_tout().clear_bit_by_one());
+                }
+
+                // The timeout only triggers while there is data in the FIFO, so
+                // leave a byte in there until the line went idle.
+                let mut available = T::get_rx_fifo_count() as usize;
+                if !idle {
+                    available = available.saturating_sub(1);
+                }
+                let end = usize::min(count + available, buf.len());
+                count += self.drain_fifo(&mut buf[count..end]);
+
+                if idle && T::get_rx_fifo_count() == 0 {
+                    return Ok(count);
+                }
+
+                if count == buf.len() {
+                    return Err(Error::RxBufferFull);
+                }
+            }
+        }
     }
 
     impl<T> embedded_io_async::Read for Uart<'_, T, Async>
@@ -1959,7 +2527,8 @@ mod asynch {
         let rx_wake = interrupts.rxfifo_full().bit_is_set()
             || interrupts.rxfifo_ovf().bit_is_set()
             || interrupts.rxfifo_tout().bit_is_set()
-            || interrupts.at_cmd_char_det().bit_is_set();
+            || interrupts.at_cmd_char_det().bit_is_set()
+            || interrupts.brk_det().bit_is_set();
         let tx_wake = interrupts.tx_done().bit_is_set() || interrupts.txfifo_empty().bit_is_set();
         uart.int_clr().write(|w| unsafe { w.bits(interrupt_bits) });
         uart.int_ena()
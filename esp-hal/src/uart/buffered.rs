@@ -0,0 +1,330 @@
+//! # Interrupt driven buffered UART receiver
+//!
+//! ## Overview
+//!
+//! The RX FIFO of the UART only holds 128 bytes, so it easily overflows when
+//! the application doesn't read it often enough. A [BufferedUartRx] moves
+//! received bytes from the FIFO into a user provided ring buffer in the UART
+//! interrupt, and the application reads them from there.
+//!
+//! Bytes which don't fit into the ring buffer are dropped and counted, as are
+//! RX FIFO overflows, see [BufferedUartRx::dropped_bytes] and
+//! [BufferedUartRx::fifo_overflows].
+//!
+//! The interrupt triggers when the RX FIFO reaches its threshold (see
+//! [super::Uart::set_rx_fifo_full_threshold]) or, if configured, on an RX
+//! timeout (see [super::Uart::set_rx_timeout]). Bytes still waiting in the FIFO
+//! are picked up whenever the ring buffer is read, too.
+//!
+//! The driver binds its own handler to the UART interrupt, so the buffered
+//! receiver is only available in blocking mode.
+//!
+//! ## Example
+//! ```no_run
+//! static mut RX_BUFFER: [u8; 1024] = [0; 1024];
+//!
+//! let mut uart = Uart::new_with_config(peripherals.UART1, config, Some(pins), &clocks, None);
+//! uart.set_rx_timeout(Some(10)).unwrap();
+//!
+//! let (tx, rx) = uart.split();
+//! let mut rx = rx.into_buffered(unsafe { &mut RX_BUFFER }, Priority::Priority1);
+//!
+//! let mut buf = [0u8; 64];
+//! loop {
+//!     let count = rx.read(&mut buf);
+//!     process(&buf[..count]);
+//!
+//!     if rx.dropped_bytes() > 0 {
+//!         println!("lost {} bytes", rx.dropped_bytes());
+//!         rx.clear_overflow_counts();
+//!     }
+//! }
+//! ```
+
+use core::cell::RefCell;
+
+use cfg_if::cfg_if;
+use critical_section::Mutex;
+
+use super::{Instance, UartRx};
+use crate::{
+    interrupt::{InterruptHandler, Priority},
+    peripherals::uart0::fifo::FIFO_SPEC,
+    Blocking,
+};
+
+cfg_if! {
+    if #[cfg(all(uart0, uart1, uart2))] {
+        const NUM_UART: usize = 3;
+    } else if #[cfg(all(uart0, uart1))] {
+        const NUM_UART: usize = 2;
+    } else if #[cfg(uart0)] {
+        const NUM_UART: usize = 1;
+    }
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const INIT: Mutex<RefCell<Option<RxRing>>> = Mutex::new(RefCell::new(None));
+static RX_RINGS: [Mutex<RefCell<Option<RxRing>>>; NUM_UART] = [INIT; NUM_UART];
+
+struct RxRing {
+    buffer: &'static mut [u8],
+    read: usize,
+    len: usize,
+    dropped_bytes: u32,
+    fifo_overflows: u32,
+}
+
+impl RxRing {
+    fn push(&mut self, byte: u8) {
+        if self.len == self.buffer.len() {
+            self.dropped_bytes = self.dropped_bytes.saturating_add(1);
+            return;
+        }
+
+        let write = (self.read + self.len) % self.buffer.len();
+        self.buffer[write] = byte;
+        self.len += 1;
+    }
+
+    fn pop_into(&mut self, buf: &mut [u8]) -> usize {
+        let count = usize::min(self.len, buf.len());
+        for byte in buf[..count].iter_mut() {
+            *byte = self.buffer[self.read];
+            self.read = (self.read + 1) % self.buffer.len();
+        }
+        self.len -= count;
+
+        count
+    }
+
+    fn fill_from_fifo<T: Instance>(&mut self) {
+        #[allow(unused_variables)]
+        let offset = 0;
+
+        // on ESP32-S2 we need to use PeriBus2 to read the FIFO
+        #[cfg(esp32s2)]
+        let offset = 0x20c00000;
+
+        while T::get_rx_fifo_count() > 0 {
+            let byte = unsafe {
+                let fifo = (T::register_block().fifo().as_ptr() as *mut u8).offset(offset)
+                    as *mut crate::peripherals::generic::Reg<FIFO_SPEC>;
+                (*fifo).read().rxfifo_rd_byte().bits()
+            };
+            self.push(byte);
+        }
+    }
+}
+
+/// A UART receiver buffering received data in a ring buffer
+pub struct BufferedUartRx<'d, T> {
+    rx: UartRx<'d, T, Blocking>,
+}
+
+impl<'d, T> UartRx<'d, T, Blocking>
+where
+    T: Instance + 'd,
+{
+    /// Receive into `buffer` from the UART interrupt, which is enabled with
+    /// the given priority.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `buffer` is empty.
+    pub fn into_buffered(
+        self,
+        buffer: &'static mut [u8],
+        priority: Priority,
+    ) -> BufferedUartRx<'d, T> {
+        assert!(!buffer.is_empty(), "The RX buffer must not be empty");
+
+        critical_section::with(|cs| {
+            RX_RINGS[T::uart_number()]
+                .borrow_ref_mut(cs)
+                .replace(RxRing {
+                    buffer,
+                    read: 0,
+                    len: 0,
+                    dropped_bytes: 0,
+                    fifo_overflows: 0,
+                });
+        });
+
+        let handler: extern "C" fn() = match T::uart_number() {
+            #[cfg(uart0)]
+            0 => uart0_rx,
+            #[cfg(uart1)]
+            1 => uart1_rx,
+            #[cfg(uart2)]
+            2 => uart2_rx,
+            _ => unreachable!(),
+        };
+        let handler = InterruptHandler::new(handler, priority);
+
+        unsafe {
+            crate::interrupt::bind_interrupt(T::interrupt(), handler.handler());
+            crate::interrupt::enable(T::interrupt(), handler.priority()).unwrap();
+        }
+
+        T::register_block().int_clr().write(|w| {
+            w.rxfifo_full()
+                .clear_bit_by_one()
+                .rxfifo_tout()
+                .clear_bit_by_one()
+                .rxfifo_ovf()
+                .clear_bit_by_one()
+        });
+        T::register_block().int_ena().modify(|_, w| {
+            w.rxfifo_full()
+                .set_bit()
+                .rxfifo_tout()
+                .set_bit()
+                .rxfifo_ovf()
+                .set_bit()
+        });
+
+        BufferedUartRx { rx: self }
+    }
+}
+
+impl<'d, T> BufferedUartRx<'d, T>
+where
+    T: Instance + 'd,
+{
+    /// Stop buffering and return the receiver and the buffer.
+    ///
+    /// Data left in the buffer is lost.
+    pub fn free(self) -> (UartRx<'d, T, Blocking>, &'static mut [u8]) {
+        T::register_block().int_ena().modify(|_, w| {
+            w.rxfifo_full()
+                .clear_bit()
+                .rxfifo_tout()
+                .clear_bit()
+                .rxfifo_ovf()
+                .clear_bit()
+        });
+
+        let ring =
+            critical_section::with(|cs| RX_RINGS[T::uart_number()].borrow_ref_mut(cs).take());
+
+        (self.rx, ring.unwrap().buffer)
+    }
+
+    /// The number of bytes available to read.
+    pub fn available(&self) -> usize {
+        self.with_ring(|ring| ring.len)
+    }
+
+    /// Read the available bytes into `buf`, without blocking.
+    ///
+    /// Returns the number of bytes read.
+    pub fn read(&mut self, buf: &mut [u8]) -> usize {
+        self.with_ring(|ring| ring.pop_into(buf))
+    }
+
+    /// The number of bytes dropped because the buffer was full.
+    pub fn dropped_bytes(&self) -> u32 {
+        self.with_ring(|ring| ring.dropped_bytes)
+    }
+
+    /// The number of RX FIFO overflows, each of which lost an unknown number
+    /// of bytes.
+    pub fn fifo_overflows(&self) -> u32 {
+        self.with_ring(|ring| ring.fifo_overflows)
+    }
+
+    /// Reset the counts of dropped bytes and RX FIFO overflows.
+    pub fn clear_overflow_counts(&mut self) {
+        self.with_ring(|ring| {
+            ring.dropped_bytes = 0;
+            ring.fifo_overflows = 0;
+        })
+    }
+
+    fn with_ring<R>(&self, f: impl FnOnce(&mut RxRing) -> R) -> R {
+        critical_section::with(|cs| {
+            let mut ring = RX_RINGS[T::uart_number()].borrow_ref_mut(cs);
+            let ring = ring.as_mut().unwrap();
+
+            // pick up bytes which didn't trigger an interrupt yet
+            ring.fill_from_fifo::<T>();
+
+            f(ring)
+        })
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<T> embedded_io::ErrorType for BufferedUartRx<'_, T> {
+    type Error = super::Error;
+}
+
+#[cfg(feature = "embedded-io")]
+impl<T> embedded_io::Read for BufferedUartRx<'_, T>
+where
+    T: Instance,
+{
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        loop {
+            let count = BufferedUartRx::read(self, buf);
+            if count > 0 {
+                return Ok(count);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "embedded-io")]
+impl<T> embedded_io::ReadReady for BufferedUartRx<'_, T>
+where
+    T: Instance,
+{
+    fn read_ready(&mut self) -> Result<bool, Self::Error> {
+        Ok(self.available() > 0)
+    }
+}
+
+fn on_interrupt<T: Instance>() {
+    let status = T::register_block().int_st().read();
+
+    critical_section::with(|cs| {
+        if let Some(ring) = RX_RINGS[T::uart_number()].borrow_ref_mut(cs).as_mut() {
+            ring.fill_from_fifo::<T>();
+
+            if status.rxfifo_ovf().bit_is_set() {
+                ring.fifo_overflows = ring.fifo_overflows.saturating_add(1);
+            }
+        }
+    });
+
+    // clear after draining the FIFO, the RX timeout counts while there is data
+    // in it
+    T::register_block().int_clr().write(|w| {
+        w.rxfifo_full()
+            .clear_bit_by_one()
+            .rxfifo_tout()
+            .clear_bit_by_one()
+            .rxfifo_ovf()
+            .clear_bit_by_one()
+    });
+}
+
+#[cfg(uart0)]
+extern "C" fn uart0_rx() {
+    on_interrupt::<crate::peripherals::UART0>();
+}
+
+#[cfg(uart1)]
+extern "C" fn uart1_rx() {
+    on_interrupt::<crate::peripherals::UART1>();
+}
+
+#[cfg(uart2)]
+extern "C" fn uart2_rx() {
+    on_interrupt::<crate::peripherals::UART2>();
+}
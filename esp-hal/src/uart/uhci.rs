@@ -0,0 +1,433 @@
+//! # UART DMA transfers via UHCI
+//!
+//! ## Overview
+//!
+//! The UHCI peripheral connects a UART to a GDMA channel, so data is moved
+//! between memory and the UART FIFOs without an interrupt per byte. This is
+//! useful for large or continuous transfers, e.g. GPS NMEA streams or modem
+//! AT traffic.
+//!
+//! A receive transfer ends when the UART line goes idle after receiving data
+//! (see [Uart::set_rx_timeout] to configure the idle time), when the chunk
+//! limit (see [UartDma::set_rx_chunk_limit]) is reached or when the buffer is
+//! full, whichever comes first. [UartDma::received_len] returns the number of
+//! bytes received into the buffer, so a large buffer can be filled chunk by
+//! chunk by passing the remaining part of it to the next transfer.
+//!
+//! With the `async` feature, [UartDma::write_dma_async] and
+//! [UartDma::read_dma_async] wait for the transfer using the DMA interrupt.
+//!
+//! ## Example
+//! ```no_run
+//! let dma = Gdma::new(peripherals.DMA);
+//! let (mut tx_descriptors, mut rx_descriptors) = dma_descriptors!(4096);
+//!
+//! let uart = Uart::new_with_config(peripherals.UART1, config, Some(pins), &clocks, None);
+//! let mut uart = uart.with_dma(
+//!     peripherals.UHCI0,
+//!     dma.channel0.configure(
+//!         false,
+//!         &mut tx_descriptors,
+//!         &mut rx_descriptors,
+//!         DmaPriority::Priority0,
+//!     ),
+//! );
+//!
+//! let transfer = uart.write_dma(&buffer)?;
+//! transfer.wait()?;
+//!
+//! let transfer = uart.read_dma(&mut buffer)?;
+//! transfer.wait()?;
+//! let received = &buffer[..uart.received_len()];
+//! ```
+
+use embedded_dma::{ReadBuffer, WriteBuffer};
+
+use super::{Instance, Uart};
+use crate::{
+    dma::{
+        Channel,
+        ChannelTypes,
+        DmaError,
+        DmaPeripheral,
+        DmaTransfer,
+        RxPrivate,
+        TxPrivate,
+        UhciPeripheral,
+    },
+    peripheral::{Peripheral, PeripheralRef},
+    peripherals::UHCI0,
+    system::PeripheralClockControl,
+    Mode,
+};
+
+/// The maximum number of bytes received by a single transfer
+pub const MAX_CHUNK_LIMIT: u16 = 4095;
+
+/// A UART using DMA transfers via UHCI.
+pub struct UartDma<'d, T, C, M>
+where
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    pub uart: Uart<'d, T, M>,
+    uhci: PeripheralRef<'d, UHCI0>,
+    pub(crate) channel: Channel<'d, C, M>,
+    rx_chunk_limit: u16,
+}
+
+pub trait WithDmaUart<'d, T, C, M>
+where
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    fn with_dma(
+        self,
+        uhci: impl Peripheral<P = UHCI0> + 'd,
+        channel: Channel<'d, C, M>,
+    ) -> UartDma<'d, T, C, M>;
+}
+
+impl<'d, T, C, M> WithDmaUart<'d, T, C, M> for Uart<'d, T, M>
+where
+    T: Instance + 'd,
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    fn with_dma(
+        self,
+        uhci: impl Peripheral<P = UHCI0> + 'd,
+        mut channel: Channel<'d, C, M>,
+    ) -> UartDma<'d, T, C, M> {
+        crate::into_ref!(uhci);
+
+        PeripheralClockControl::enable(crate::system::Peripheral::Uhci0);
+
+        channel.tx.init_channel(); // no need to call this for both, TX and RX
+
+        let mut uart_dma = UartDma {
+            uart: self,
+            uhci,
+            channel,
+            rx_chunk_limit: MAX_CHUNK_LIMIT,
+        };
+        uart_dma.init();
+
+        uart_dma
+    }
+}
+
+impl<'d, T, C, M> core::fmt::Debug for UartDma<'d, T, C, M>
+where
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UartDma").finish()
+    }
+}
+
+impl<'d, T, C, M> UartDma<'d, T, C, M>
+where
+    T: Instance + 'd,
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    fn init(&mut self) {
+        // SLIP framing, CRC and packet headers are enabled after reset, we want
+        // the raw UART data
+        self.uhci.conf0().write(|w| unsafe { w.bits(0) });
+        self.uhci.conf0().modify(|_, w| w.clk_en().set_bit());
+        self.uhci.conf1().write(|w| unsafe { w.bits(0) });
+        self.uhci.escape_conf().write(|w| unsafe { w.bits(0) });
+
+        self.uhci
+            .conf0()
+            .modify(|_, w| w.rx_rst().set_bit().tx_rst().set_bit());
+        self.uhci
+            .conf0()
+            .modify(|_, w| w.rx_rst().clear_bit().tx_rst().clear_bit());
+
+        self.uhci.conf0().modify(|_, w| match T::uart_number() {
+            0 => w.uart0_ce().set_bit(),
+            1 => w.uart1_ce().set_bit(),
+            #[cfg(uart2)]
+            2 => w.uart2_ce().set_bit(),
+            _ => unreachable!(),
+        });
+
+        // end receive transfers when the line goes idle or the chunk limit is
+        // reached
+        self.uhci
+            .conf0()
+            .modify(|_, w| w.uart_idle_eof_en().set_bit().len_eof_en().set_bit());
+    }
+
+    /// Set the maximum number of bytes received by a single transfer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `limit` is zero or larger than [MAX_CHUNK_LIMIT].
+    pub fn set_rx_chunk_limit(&mut self, limit: u16) {
+        assert!(
+            limit > 0 && limit <= MAX_CHUNK_LIMIT,
+            "The chunk limit has to be in 1..=4095"
+        );
+
+        self.rx_chunk_limit = limit;
+    }
+
+    /// Return the UART, the UHCI peripheral and the DMA channel.
+    pub fn free(self) -> (Uart<'d, T, M>, PeripheralRef<'d, UHCI0>, Channel<'d, C, M>) {
+        (self.uart, self.uhci, self.channel)
+    }
+
+    /// The number of bytes received by the last receive transfer.
+    pub fn received_len(&self) -> usize {
+        self.channel.rx.received_len()
+    }
+
+    /// Send the contents of `words` using DMA.
+    ///
+    /// This will return a [UartDmaTxTransfer] which finishes once all data
+    /// has been sent.
+    pub fn write_dma<'t, TXBUF>(
+        &'t mut self,
+        words: &'t TXBUF,
+    ) -> Result<UartDmaTxTransfer<'t, 'd, T, C, M>, DmaError>
+    where
+        TXBUF: ReadBuffer<Word = u8>,
+    {
+        let (ptr, len) = unsafe { words.read_buffer() };
+
+        self.start_write_dma(ptr, len, false)?;
+
+        Ok(UartDmaTxTransfer { uart_dma: self })
+    }
+
+    /// Receive into `words` using DMA.
+    ///
+    /// This will return a [UartDmaRxTransfer] which finishes when the line
+    /// goes idle, the chunk limit is reached or the buffer is full. Use
+    /// [Self::received_len] to get the number of bytes received afterwards.
+    pub fn read_dma<'t, RXBUF>(
+        &'t mut self,
+        words: &'t mut RXBUF,
+    ) -> Result<UartDmaRxTransfer<'t, 'd, T, C, M>, DmaError>
+    where
+        RXBUF: WriteBuffer<Word = u8>,
+    {
+        let (ptr, len) = unsafe { words.write_buffer() };
+
+        self.start_read_dma(ptr, len, false)?;
+
+        Ok(UartDmaRxTransfer { uart_dma: self })
+    }
+
+    fn start_write_dma(
+        &mut self,
+        ptr: *const u8,
+        len: usize,
+        listen: bool,
+    ) -> Result<(), DmaError> {
+        self.channel.tx.is_done();
+
+        self.channel
+            .tx
+            .prepare_transfer_without_start(DmaPeripheral::Uhci0, false, ptr, len)?;
+
+        if listen {
+            self.channel.tx.listen_eof();
+        }
+
+        self.channel.tx.start_transfer()
+    }
+
+    fn start_read_dma(&mut self, ptr: *mut u8, len: usize, listen: bool) -> Result<(), DmaError> {
+        self.channel.rx.is_done();
+
+        // the transfer has to end before the buffer is full, otherwise there's
+        // no EOF
+        let limit = usize::min(self.rx_chunk_limit as usize, len);
+        self.uhci
+            .pkt_thres()
+            .write(|w| unsafe { w.bits(limit as u32) });
+
+        self.channel
+            .rx
+            .prepare_transfer_without_start(false, DmaPeripheral::Uhci0, ptr, len)?;
+
+        if listen {
+            self.channel.rx.listen_eof();
+        }
+
+        self.channel.rx.start_transfer()
+    }
+
+    fn flush_tx(&self) {
+        while self.uart.flush_tx().is_err() {
+            // wait until the UART sent all data
+        }
+    }
+}
+
+/// An in-progress DMA transfer sending data.
+#[must_use]
+pub struct UartDmaTxTransfer<'t, 'd, T, C, M>
+where
+    T: Instance + 'd,
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    uart_dma: &'t mut UartDma<'d, T, C, M>,
+}
+
+impl<'t, 'd, T, C, M> DmaTransfer for UartDmaTxTransfer<'t, 'd, T, C, M>
+where
+    T: Instance + 'd,
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    /// Wait for the DMA transfer to complete
+    fn wait(self) -> Result<(), DmaError> {
+        while !self.uart_dma.channel.tx.is_done() {
+            // wait until the DMA moved all data to the UART
+        }
+
+        // Waiting for the DMA transfer is not enough. We need to wait for the
+        // UART to send the data left in its FIFO, too.
+        self.uart_dma.flush_tx();
+
+        if self.uart_dma.channel.tx.has_error() {
+            Err(DmaError::DescriptorError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check if the DMA transfer is complete
+    fn is_done(&self) -> bool {
+        self.uart_dma.channel.tx.is_done()
+    }
+}
+
+impl<'t, 'd, T, C, M> Drop for UartDmaTxTransfer<'t, 'd, T, C, M>
+where
+    T: Instance + 'd,
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    fn drop(&mut self) {
+        while !self.uart_dma.channel.tx.is_done() {}
+        self.uart_dma.flush_tx();
+    }
+}
+
+/// An in-progress DMA transfer receiving data.
+#[must_use]
+pub struct UartDmaRxTransfer<'t, 'd, T, C, M>
+where
+    T: Instance + 'd,
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    uart_dma: &'t mut UartDma<'d, T, C, M>,
+}
+
+impl<'t, 'd, T, C, M> DmaTransfer for UartDmaRxTransfer<'t, 'd, T, C, M>
+where
+    T: Instance + 'd,
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    /// Wait for the DMA transfer to complete
+    fn wait(self) -> Result<(), DmaError> {
+        while !self.uart_dma.channel.rx.is_done() {
+            // wait for the line to go idle or the chunk to be complete
+        }
+
+        if self.uart_dma.channel.rx.has_error() {
+            Err(DmaError::DescriptorError)
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Check if the DMA transfer is complete
+    fn is_done(&self) -> bool {
+        self.uart_dma.channel.rx.is_done()
+    }
+}
+
+impl<'t, 'd, T, C, M> Drop for UartDmaRxTransfer<'t, 'd, T, C, M>
+where
+    T: Instance + 'd,
+    C: ChannelTypes,
+    C::P: UhciPeripheral,
+    M: Mode,
+{
+    fn drop(&mut self) {
+        while !self.uart_dma.channel.rx.is_done() {}
+    }
+}
+
+#[cfg(feature = "async")]
+mod asynch {
+    use super::*;
+    use crate::{
+        dma::asynch::{DmaRxFuture, DmaTxFuture},
+        Async,
+    };
+
+    impl<'d, T, C> UartDma<'d, T, C, Async>
+    where
+        T: Instance + 'd,
+        C: ChannelTypes,
+        C::P: UhciPeripheral,
+    {
+        /// Send all of `words` using DMA, waiting for the transfer using the
+        /// DMA interrupt.
+        pub async fn write_dma_async(&mut self, words: &[u8]) -> Result<(), DmaError> {
+            self.start_write_dma(words.as_ptr(), words.len(), true)?;
+
+            DmaTxFuture::new(&mut self.channel.tx).await;
+
+            // the UART FIFO holds at most 128 bytes, so this doesn't take long
+            self.flush_tx();
+
+            if self.channel.tx.has_error() {
+                Err(DmaError::DescriptorError)
+            } else {
+                Ok(())
+            }
+        }
+
+        /// Receive into `words` using DMA, waiting for the transfer using the
+        /// DMA interrupt.
+        ///
+        /// The transfer ends when the line goes idle, the chunk limit is
+        /// reached or the buffer is full. Returns the number of bytes
+        /// received.
+        pub async fn read_dma_async(&mut self, words: &mut [u8]) -> Result<usize, DmaError> {
+            self.start_read_dma(words.as_mut_ptr(), words.len(), true)?;
+
+            DmaRxFuture::new(&mut self.channel.rx).await;
+
+            if self.channel.rx.has_error() {
+                Err(DmaError::DescriptorError)
+            } else {
+                Ok(self.received_len())
+            }
+        }
+    }
+}
@@ -1,14 +1,23 @@
 //! Low-power I2C driver
+//!
+//! Drives the LP I2C master configured by the HP core with
+//! `esp_hal::i2c::lp_i2c::LpI2c`. Transfers of any length are split into
+//! chunks of the 16 byte FIFO, pausing the bus in between, and `write_read` and
+//! `transaction` run as a single transaction with repeated starts.
 
 use esp32c6_lp::LP_I2C0;
 
 const LP_I2C_TRANS_COMPLETE_INT_ST_S: u32 = 7;
 const LP_I2C_END_DETECT_INT_ST_S: u32 = 3;
 const LP_I2C_NACK_INT_ST_S: u32 = 10;
+const LP_I2C_TIME_OUT_INT_ST_S: u32 = 8;
+const LP_I2C_ARBITRATION_LOST_INT_ST_S: u32 = 5;
 
 const I2C_LL_INTR_MASK: u32 = (1 << LP_I2C_TRANS_COMPLETE_INT_ST_S)
     | (1 << LP_I2C_END_DETECT_INT_ST_S)
-    | (1 << LP_I2C_NACK_INT_ST_S);
+    | (1 << LP_I2C_NACK_INT_ST_S)
+    | (1 << LP_I2C_TIME_OUT_INT_ST_S)
+    | (1 << LP_I2C_ARBITRATION_LOST_INT_ST_S);
 
 const LP_I2C_FIFO_LEN: u32 = 16;
 
@@ -227,155 +236,234 @@ impl CommandRegister {
 // Configure LP_EXT_I2C_CK_EN high to enable the clock source of I2C_SCLK.
 // Adjust the timing registers accordingly when the clock frequency changes.
 
+/// LP I2C master, handed over by the HP core
 pub struct LpI2c {
     i2c: LP_I2C0,
 }
 
 impl LpI2c {
-    /// Send data bytes from the `bytes` array to a target slave with the
-    /// address `addr`
-    fn master_write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Error> {
-        let mut cmd_iterator = CommandRegister::COMD0;
+    /// Reads enough bytes from slave with `address` to fill `buffer`
+    pub fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Error> {
+        self.reset_bus_if_busy();
+        self.read_operation(address, buffer, true, true, true)
+    }
 
-        // If SCL is busy, reset the Master FSM
-        if self.i2c.sr().read().bus_busy().bit_is_set() {
-            self.i2c.ctr().modify(|_, w| w.fsm_rst().set_bit());
+    /// Writes bytes to slave with address `address`
+    pub fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Error> {
+        self.reset_bus_if_busy();
+        self.write_operation(address, bytes, true, true)
+    }
+
+    /// Writes bytes to slave with address `address` and then reads enough bytes
+    /// to fill `buffer` *in a single transaction*
+    pub fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Error> {
+        if buffer.is_empty() {
+            return self.write(address, bytes);
         }
 
-        if bytes.len() > 255 {
-            return Err(Error::ExceedingFifo);
+        self.reset_bus_if_busy();
+        self.write_operation(address, bytes, true, false)?;
+        self.read_operation(address, buffer, true, true, true)
+    }
+
+    /// Execute the `operations` as a single transaction with the slave with
+    /// address `address`. Only a change of the direction starts with a
+    /// repeated START and the address.
+    #[cfg(feature = "embedded-hal-1")]
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal_1::i2c::Operation<'_>],
+    ) -> Result<(), Error> {
+        use embedded_hal_1::i2c::Operation;
+
+        fn is_read(operation: &Operation<'_>) -> bool {
+            matches!(operation, Operation::Read(_))
         }
 
-        // Reset FIFO and command list
-        self.reset_fifo();
+        // a read has to end with a NACKed byte, empty reads can't be put on the bus
+        fn is_empty_read(operation: &Operation<'_>) -> bool {
+            matches!(operation, Operation::Read(buffer) if buffer.is_empty())
+        }
 
-        self.add_cmd_lp(&mut cmd_iterator, Command::Start)?;
+        self.reset_bus_if_busy();
 
-        // Load device address and R/W bit into FIFO
-        self.write_fifo(addr << 1 | OperationType::Write as u8);
+        for index in 0..operations.len() {
+            if is_empty_read(&operations[index]) {
+                continue;
+            }
 
-        self.add_cmd_lp(
-            &mut cmd_iterator,
-            Command::Write {
-                ack_exp: Ack::Ack,
-                ack_check_en: true,
-                length: 1_u8,
-            },
-        )?;
+            let read = is_read(&operations[index]);
+            let previous = operations[..index]
+                .iter()
+                .rfind(|operation| !is_empty_read(operation));
+            let next = operations[index + 1..]
+                .iter()
+                .find(|operation| !is_empty_read(operation));
+
+            let start = previous.map_or(true, |previous| is_read(previous) != read);
+            let stop = next.is_none();
+            let nack = !next.is_some_and(is_read);
+
+            match &mut operations[index] {
+                Operation::Write(bytes) if bytes.is_empty() && !start && !stop => {}
+                Operation::Write(bytes) => self.write_operation(address, bytes, start, stop)?,
+                Operation::Read(buffer) => {
+                    self.read_operation(address, buffer, start, stop, nack)?
+                }
+            }
+        }
 
-        self.enable_interrupts(I2C_LL_INTR_MASK);
+        Ok(())
+    }
+
+    /// If SCL is busy, reset the Master FSM
+    fn reset_bus_if_busy(&self) {
+        if self.i2c.sr().read().bus_busy().bit_is_set() {
+            self.i2c.ctr().modify(|_, w| w.fsm_rst().set_bit());
+        }
+    }
+
+    /// Send data bytes from the `bytes` array to a target slave with the
+    /// address `addr`, in chunks which fit into the FIFO.
+    ///
+    /// The address is sent first if `start`. The transaction ends with a STOP
+    /// if `stop`, otherwise the bus is paused until the next commands are
+    /// started.
+    fn write_operation(
+        &mut self,
+        addr: u8,
+        bytes: &[u8],
+        start: bool,
+        stop: bool,
+    ) -> Result<(), Error> {
+        let mut start = start;
+        let mut remaining = bytes;
 
-        let mut data_idx = 0;
-        let mut remaining_bytes = bytes.len() as u32;
+        loop {
+            // every chunk starts from the first command register again
+            let mut cmd_iterator = CommandRegister::COMD0;
 
-        let mut fifo_available = LP_I2C_FIFO_LEN - 1;
+            // Reset FIFO and command list
+            self.reset_fifo();
 
-        while remaining_bytes > 0 {
-            let fifo_size = if remaining_bytes < fifo_available {
-                remaining_bytes
-            } else {
-                fifo_available
-            };
-            remaining_bytes -= fifo_size;
+            if start {
+                self.add_cmd_lp(&mut cmd_iterator, Command::Start)?;
 
-            // Write data to the FIFO
-            for &byte in &bytes[data_idx as usize..(data_idx as usize) + fifo_size as usize] {
-                self.write_fifo(byte);
+                // Load device address and R/W bit into FIFO
+                self.write_fifo(addr << 1 | OperationType::Write as u8);
+
+                self.add_cmd_lp(
+                    &mut cmd_iterator,
+                    Command::Write {
+                        ack_exp: Ack::Ack,
+                        ack_check_en: true,
+                        length: 1_u8,
+                    },
+                )?;
             }
 
-            // Add a Write command with the specified length
-            self.add_cmd_lp(
-                &mut cmd_iterator,
-                Command::Write {
-                    ack_exp: Ack::Ack,
-                    ack_check_en: true,
-                    length: fifo_size as u8,
-                },
-            )?;
-
-            // Check if this is the last chunk
-            let cmd = if remaining_bytes == 0 {
+            let fifo_available = LP_I2C_FIFO_LEN as usize - start as usize;
+            let (chunk, rest) = remaining.split_at(usize::min(fifo_available, remaining.len()));
+
+            if !chunk.is_empty() {
+                // Write data to the FIFO
+                for &byte in chunk {
+                    self.write_fifo(byte);
+                }
+
+                // Add a Write command with the specified length
+                self.add_cmd_lp(
+                    &mut cmd_iterator,
+                    Command::Write {
+                        ack_exp: Ack::Ack,
+                        ack_check_en: true,
+                        length: chunk.len() as u8,
+                    },
+                )?;
+            }
+
+            // Add the Stop/End command
+            let last = rest.is_empty();
+            let cmd = if last && stop {
                 Command::Stop
             } else {
                 Command::End
             };
-
-            // Add the Stop/End command
             self.add_cmd_lp(&mut cmd_iterator, cmd)?;
 
-            // Start the I2C transaction
-            self.lp_i2c_update();
-            self.i2c.ctr().modify(|_, w| w.trans_start().set_bit());
-
-            // Wait for the transaction to complete
+            self.start_transmission();
             self.wait_for_completion()?;
 
-            // Update the index for the next data chunk
-            data_idx += fifo_size;
+            if last {
+                break;
+            }
 
-            fifo_available = LP_I2C_FIFO_LEN;
+            remaining = rest;
+            start = false;
         }
 
         Ok(())
     }
 
-    fn master_read(&mut self, addr: u8, buffer: &mut [u8]) -> Result<(), Error> {
-        // Check size constraints
-        if buffer.len() > 254 {
-            return Err(Error::ExceedingFifo);
-        }
-
-        let mut cmd_iterator = CommandRegister::COMD0;
-
-        self.add_cmd_lp(&mut cmd_iterator, Command::Start)?;
+    /// Read bytes from a target slave with the address `addr` into `buffer`,
+    /// in chunks which fit into the FIFO.
+    ///
+    /// The address is sent first if `start`. The last byte is NACKed if
+    /// `nack`. The transaction ends with a STOP if `stop`, otherwise the bus is
+    /// paused until the next commands are started.
+    fn read_operation(
+        &mut self,
+        addr: u8,
+        buffer: &mut [u8],
+        start: bool,
+        stop: bool,
+        nack: bool,
+    ) -> Result<(), Error> {
+        let count = buffer.len().div_ceil(LP_I2C_FIFO_LEN as usize);
 
-        // Load device address
-        self.write_fifo(addr << 1 | OperationType::Read as u8);
+        for (index, chunk) in buffer.chunks_mut(LP_I2C_FIFO_LEN as usize).enumerate() {
+            let last = index == count - 1;
 
-        self.add_cmd_lp(
-            &mut cmd_iterator,
-            Command::Write {
-                ack_exp: Ack::Ack,
-                ack_check_en: true,
-                length: 1_u8,
-            },
-        )?;
+            // every chunk starts from the first command register again
+            let mut cmd_iterator = CommandRegister::COMD0;
 
-        self.enable_interrupts(
-            1 << LP_I2C_TRANS_COMPLETE_INT_ST_S | 1 << LP_I2C_END_DETECT_INT_ST_S,
-        );
+            // Reset FIFO and command list
+            self.reset_fifo();
 
-        let mut remaining_bytes = buffer.len();
+            if start && index == 0 {
+                self.add_cmd_lp(&mut cmd_iterator, Command::Start)?;
 
-        while remaining_bytes > 0 {
-            let fifo_size = if remaining_bytes < LP_I2C_FIFO_LEN as usize {
-                remaining_bytes
-            } else {
-                LP_I2C_FIFO_LEN as usize
-            };
-            remaining_bytes -= fifo_size;
+                // Load device address
+                self.write_fifo(addr << 1 | OperationType::Read as u8);
 
-            if fifo_size == 1 {
-                // Read one byte and send NACK
-                self.add_cmd_lp(
-                    &mut cmd_iterator,
-                    Command::Read {
-                        ack_value: Ack::Nack,
-                        length: 1, // which is `fifo_size`
-                    },
-                )?;
-                // Send STOP command after reading
-                self.add_cmd_lp(&mut cmd_iterator, Command::Stop)?;
-            } else if fifo_size > 1 && remaining_bytes == 0 {
-                // This means it is the last transaction
-                // Read all but the last byte and send ACKs
                 self.add_cmd_lp(
                     &mut cmd_iterator,
-                    Command::Read {
-                        ack_value: Ack::Ack,
-                        length: (fifo_size - 1) as u8,
+                    Command::Write {
+                        ack_exp: Ack::Ack,
+                        ack_check_en: true,
+                        length: 1_u8,
                     },
                 )?;
+            }
+
+            if last && nack {
+                if chunk.len() > 1 {
+                    // Read all but the last byte and send ACKs
+                    self.add_cmd_lp(
+                        &mut cmd_iterator,
+                        Command::Read {
+                            ack_value: Ack::Ack,
+                            length: (chunk.len() - 1) as u8,
+                        },
+                    )?;
+                }
+
                 // Read the last byte and send NACK
                 self.add_cmd_lp(
                     &mut cmd_iterator,
@@ -384,52 +472,44 @@ impl LpI2c {
                         length: 1,
                     },
                 )?;
-                // Send STOP command after reading
-                self.add_cmd_lp(&mut cmd_iterator, Command::Stop)?;
             } else {
-                // This means we have to read data more than we can fit into the Rx FIFO
-                // Read fifo_size bytes and send ACKs
+                // More data is to come, read the whole chunk and send ACKs
                 self.add_cmd_lp(
                     &mut cmd_iterator,
                     Command::Read {
                         ack_value: Ack::Ack,
-                        length: fifo_size as u8,
+                        length: chunk.len() as u8,
                     },
                 )?;
-                // Send END command signaling more data to come
-                self.add_cmd_lp(&mut cmd_iterator, Command::End)?;
             }
 
-            self.lp_i2c_update();
-
-            // Initiate I2C transfer
-            self.i2c.ctr().modify(|_, w| w.trans_start().set_bit());
+            // Send STOP command after the last byte, END command signaling more data to
+            // come otherwise
+            let cmd = if last && stop {
+                Command::Stop
+            } else {
+                Command::End
+            };
+            self.add_cmd_lp(&mut cmd_iterator, cmd)?;
 
-            // Await for completion (This function or mechanism should handle waiting for
-            // the I2C transfer to complete)
+            self.start_transmission();
             self.wait_for_completion()?;
 
             // Read from FIFO into the current chunk
-            for byte in buffer.iter_mut() {
+            for byte in chunk.iter_mut() {
                 *byte = self.read_fifo();
             }
         }
+
         Ok(())
     }
 
-    fn master_write_read(
-        &mut self,
-        addr: u8,
-        bytes: &[u8],
-        buffer: &mut [u8],
-    ) -> Result<(), Error> {
-        // it would be possible to combine the write and read
-        // in one transaction but filling the tx fifo with
-        // the current code is somewhat slow even in release mode
-        // which can cause issues
-        self.master_write(addr, bytes)?;
-        self.master_read(addr, buffer)?;
-        Ok(())
+    fn start_transmission(&self) {
+        self.enable_interrupts(I2C_LL_INTR_MASK);
+        self.lp_i2c_update();
+
+        // Initiate I2C transfer
+        self.i2c.ctr().modify(|_, w| w.trans_start().set_bit());
     }
 
     /// Update I2C configuration
@@ -466,6 +546,12 @@ impl LpI2c {
                     .int_clr()
                     .write(|w| unsafe { w.bits(I2C_LL_INTR_MASK) });
                 return Err(Error::InvalidResponse);
+            } else if interrupts.time_out().bit_is_set() {
+                self.reset_after_error();
+                return Err(Error::TimeOut);
+            } else if interrupts.arbitration_lost().bit_is_set() {
+                self.reset_after_error();
+                return Err(Error::ArbitrationLost);
             } else if interrupts.trans_complete().bit_is_set() {
                 self.disable_interrupts();
 
@@ -484,6 +570,16 @@ impl LpI2c {
         Ok(())
     }
 
+    /// Clear the interrupts and reset the master FSM, which is stuck after a
+    /// timeout or lost arbitration
+    fn reset_after_error(&self) {
+        self.i2c
+            .int_clr()
+            .write(|w| unsafe { w.bits(I2C_LL_INTR_MASK) });
+        self.disable_interrupts();
+        self.i2c.ctr().modify(|_, w| w.fsm_rst().set_bit());
+    }
+
     fn enable_interrupts(&self, mask: u32) {
         self.i2c.int_ena().write(|w| unsafe { w.bits(mask) });
     }
@@ -559,7 +655,7 @@ impl embedded_hal_02::blocking::i2c::Read for LpI2c {
     type Error = Error;
 
     fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
-        self.master_read(address, buffer)
+        self.read(address, buffer)
     }
 }
 
@@ -568,7 +664,7 @@ impl embedded_hal_02::blocking::i2c::Write for LpI2c {
     type Error = Error;
 
     fn write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Self::Error> {
-        self.master_write(addr, bytes)
+        self.write(addr, bytes)
     }
 }
 
@@ -582,6 +678,52 @@ impl embedded_hal_02::blocking::i2c::WriteRead for LpI2c {
         bytes: &[u8],
         buffer: &mut [u8],
     ) -> Result<(), Self::Error> {
-        self.master_write_read(address, bytes, buffer)
+        self.write_read(address, bytes, buffer)
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl embedded_hal_1::i2c::Error for Error {
+    fn kind(&self) -> embedded_hal_1::i2c::ErrorKind {
+        use embedded_hal_1::i2c::ErrorKind;
+
+        match self {
+            Self::ExceedingFifo => ErrorKind::Overrun,
+            Self::ArbitrationLost => ErrorKind::ArbitrationLoss,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl embedded_hal_1::i2c::ErrorType for LpI2c {
+    type Error = Error;
+}
+
+#[cfg(feature = "embedded-hal-1")]
+impl embedded_hal_1::i2c::I2c for LpI2c {
+    fn read(&mut self, address: u8, buffer: &mut [u8]) -> Result<(), Self::Error> {
+        self.read(address, buffer)
+    }
+
+    fn write(&mut self, address: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+        self.write(address, bytes)
+    }
+
+    fn write_read(
+        &mut self,
+        address: u8,
+        bytes: &[u8],
+        buffer: &mut [u8],
+    ) -> Result<(), Self::Error> {
+        self.write_read(address, bytes, buffer)
+    }
+
+    fn transaction(
+        &mut self,
+        address: u8,
+        operations: &mut [embedded_hal_1::i2c::Operation<'_>],
+    ) -> Result<(), Self::Error> {
+        self.transaction(address, operations)
     }
 }
@@ -20,7 +20,7 @@ use esp_hal::{
     peripherals::Peripherals,
     prelude::*,
     uart::{
-        config::{Config, DataBits, Parity, StopBits},
+        config::{Config, DataBits, FlowControl, Parity, StopBits},
         TxRxPins,
         Uart,
     },
@@ -39,6 +39,7 @@ fn main() -> ! {
         data_bits: DataBits::DataBits8,
         parity: Parity::ParityNone,
         stop_bits: StopBits::STOP1,
+        flow_control: FlowControl::default(),
     };
 
     let io = IO::new(peripherals.GPIO, peripherals.IO_MUX);
@@ -7,6 +7,7 @@
 
 use esp_backtrace as _;
 use esp_hal::{
+    clock::ClockControl,
     etm::Etm,
     gpio::{etm::GpioEtmChannels, IO},
     peripherals::Peripherals,
@@ -18,8 +19,10 @@ use fugit::ExtU32;
 #[entry]
 fn main() -> ! {
     let peripherals = Peripherals::take();
+    let system = peripherals.SYSTEM.split();
+    let clocks = ClockControl::boot_defaults(system.clock_control).freeze();
 
-    let syst = SystemTimer::new(peripherals.SYSTIMER);
+    let syst = SystemTimer::new(peripherals.SYSTIMER, &clocks);
     let mut alarm0 = syst.alarm0.into_periodic();
     alarm0.set_period(1u32.secs());
 
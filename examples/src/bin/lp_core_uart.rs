@@ -18,7 +18,7 @@ use esp_hal::{
     peripherals::Peripherals,
     prelude::*,
     uart::{
-        config::{Config, DataBits, Parity, StopBits},
+        config::{Config, DataBits, FlowControl, Parity, StopBits},
         lp_uart::LpUart,
         TxRxPins,
         Uart,
@@ -41,6 +41,7 @@ fn main() -> ! {
         data_bits: DataBits::DataBits8,
         parity: Parity::ParityNone,
         stop_bits: StopBits::STOP1,
+        flow_control: FlowControl::default(),
     };
 
     let pins = TxRxPins::new_tx_rx(
@@ -34,7 +34,7 @@ fn main() -> ! {
     let system = peripherals.SYSTEM.split();
     let clocks = ClockControl::boot_defaults(system.clock_control).freeze();
 
-    let systimer = SystemTimer::new(peripherals.SYSTIMER);
+    let systimer = SystemTimer::new(peripherals.SYSTIMER, &clocks);
 
     println!("SYSTIMER Current value = {}", SystemTimer::now());
 
@@ -46,12 +46,12 @@ fn main() -> ! {
 
         let alarm1 = systimer.alarm1;
         alarm1.set_interrupt_handler(systimer_target1);
-        alarm1.set_target(SystemTimer::now() + (SystemTimer::TICKS_PER_SECOND * 2));
+        alarm1.set_target(SystemTimer::now() + (SystemTimer::ticks_per_second() * 2));
         alarm1.enable_interrupt(true);
 
         let alarm2 = systimer.alarm2;
         alarm2.set_interrupt_handler(systimer_target2);
-        alarm2.set_target(SystemTimer::now() + (SystemTimer::TICKS_PER_SECOND * 3));
+        alarm2.set_target(SystemTimer::now() + (SystemTimer::ticks_per_second() * 3));
         alarm2.enable_interrupt(true);
 
         ALARM0.borrow_ref_mut(cs).replace(alarm0);
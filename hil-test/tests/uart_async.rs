@@ -17,7 +17,7 @@ use esp_hal::{
     peripherals::{Peripherals, UART0},
     prelude::*,
     uart::{
-        config::{Config, DataBits, Parity, StopBits},
+        config::{Config, DataBits, FlowControl, Parity, StopBits},
         TxRxPins,
         Uart,
         UartRx,
@@ -46,6 +46,7 @@ impl Context {
             data_bits: DataBits::DataBits8,
             parity: Parity::ParityNone,
             stop_bits: StopBits::STOP1,
+            flow_control: FlowControl::default(),
         };
 
         let uart = Uart::new_async_with_config(peripherals.UART0, config, Some(pins), &clocks);